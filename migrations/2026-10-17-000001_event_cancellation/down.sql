@@ -0,0 +1,5 @@
+-- Rollback event cancellation
+
+DROP INDEX IF EXISTS idx_events_cancelled;
+
+ALTER TABLE events DROP COLUMN IF EXISTS cancelled_at;
@@ -0,0 +1,6 @@
+-- Event cancellation
+-- Cancelled events keep their visibility so existing links still resolve
+
+ALTER TABLE events ADD COLUMN cancelled_at TIMESTAMPTZ;
+
+CREATE INDEX idx_events_cancelled ON events(cancelled_at) WHERE cancelled_at IS NOT NULL;
@@ -0,0 +1,5 @@
+-- Rollback event signup waitlist
+
+UPDATE event_signups SET status = 'cancelled', cancelled_at = NOW() WHERE status = 'waitlisted';
+
+DROP INDEX IF EXISTS idx_event_signups_waitlist;
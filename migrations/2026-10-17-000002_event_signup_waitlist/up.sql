@@ -0,0 +1,5 @@
+-- Event signup waitlist
+-- Waitlisted signups are promoted in signed_up_at order when a seat opens
+
+CREATE INDEX idx_event_signups_waitlist ON event_signups(event_shift_id, signed_up_at)
+    WHERE status = 'waitlisted';
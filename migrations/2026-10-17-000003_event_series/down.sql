@@ -0,0 +1,10 @@
+-- Rollback recurring event series
+
+DROP INDEX IF EXISTS idx_event_shifts_series_occurrence;
+
+ALTER TABLE event_shifts
+    DROP COLUMN IF EXISTS series_occurrence_date,
+    DROP COLUMN IF EXISTS series_id;
+
+DROP TRIGGER IF EXISTS update_event_series_updated_at ON event_series;
+DROP TABLE IF EXISTS event_series;
@@ -0,0 +1,51 @@
+-- Recurring event series
+-- A series holds a recurrence rule and materializes event_shifts in its local timezone
+
+-------------------------------------------------------------------------------
+-- EVENT SERIES
+-------------------------------------------------------------------------------
+CREATE TABLE event_series (
+    id SERIAL PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    
+    -- Recurrence Rule
+    frequency VARCHAR(20) NOT NULL,
+    repeat_interval INTEGER NOT NULL DEFAULT 1 CHECK (repeat_interval >= 1),
+    by_weekday TEXT[] NOT NULL DEFAULT '{}',
+    starts_on DATE NOT NULL,
+    until_date DATE,
+    occurrence_count INTEGER CHECK (occurrence_count >= 1),
+    excluded_dates DATE[] NOT NULL DEFAULT '{}',
+    
+    -- Shift Template (local wall-clock time in the series timezone)
+    start_time_local TIME NOT NULL,
+    duration_minutes INTEGER NOT NULL CHECK (duration_minutes >= 1),
+    timezone VARCHAR(50) NOT NULL DEFAULT 'America/New_York',
+    capacity INTEGER,
+    notes TEXT,
+    
+    -- Metadata
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    
+    -- A series must be bounded by an end date or an occurrence count
+    CHECK (until_date IS NOT NULL OR occurrence_count IS NOT NULL)
+);
+
+CREATE INDEX idx_event_series_event ON event_series(event_id);
+
+CREATE TRIGGER update_event_series_updated_at
+    BEFORE UPDATE ON event_series
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-------------------------------------------------------------------------------
+-- EVENT SHIFTS
+-------------------------------------------------------------------------------
+-- Shifts detached from their series (edited individually) keep series_id NULL
+ALTER TABLE event_shifts
+    ADD COLUMN series_id INTEGER REFERENCES event_series(id) ON DELETE SET NULL,
+    ADD COLUMN series_occurrence_date DATE;
+
+CREATE UNIQUE INDEX idx_event_shifts_series_occurrence
+    ON event_shifts(series_id, series_occurrence_date)
+    WHERE series_id IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Rollback calendar feed tokens
+
+DROP TABLE IF EXISTS calendar_feed_tokens;
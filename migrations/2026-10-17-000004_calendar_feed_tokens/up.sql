@@ -0,0 +1,14 @@
+-- Calendar feed tokens
+-- Each user gets one rotatable token for their private signup calendar feed
+
+-------------------------------------------------------------------------------
+-- CALENDAR FEED TOKENS
+-------------------------------------------------------------------------------
+CREATE TABLE calendar_feed_tokens (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER UNIQUE NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token UUID UNIQUE NOT NULL DEFAULT uuid_generate_v4(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_calendar_feed_token ON calendar_feed_tokens(token);
@@ -0,0 +1,3 @@
+-- Rollback exports
+
+DROP TABLE IF EXISTS exports;
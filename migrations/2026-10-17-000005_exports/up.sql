@@ -0,0 +1,24 @@
+-- Exports
+-- Records each generated export file stored in the exports bucket
+
+-------------------------------------------------------------------------------
+-- EXPORTS
+-------------------------------------------------------------------------------
+CREATE TABLE exports (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    requested_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    export_type VARCHAR(50) NOT NULL,
+    format VARCHAR(10) NOT NULL,
+    event_id INTEGER REFERENCES events(id) ON DELETE SET NULL,
+    range_start TIMESTAMPTZ,
+    range_end TIMESTAMPTZ,
+    filename VARCHAR(255) NOT NULL,
+    mime_type VARCHAR(100) NOT NULL,
+    file_size_bytes BIGINT NOT NULL,
+    row_count INTEGER NOT NULL,
+    storage_key TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_exports_organization ON exports(organization_id, created_at DESC);
@@ -0,0 +1,4 @@
+-- Rollback event location ZIP code
+
+DROP INDEX IF EXISTS idx_events_location_zip_code;
+ALTER TABLE events DROP COLUMN IF EXISTS location_zip_code;
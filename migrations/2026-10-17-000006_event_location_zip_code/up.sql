@@ -0,0 +1,10 @@
+-- Event location ZIP code
+-- Lets public event discovery filter in-person events by distance
+
+-------------------------------------------------------------------------------
+-- EVENTS
+-------------------------------------------------------------------------------
+ALTER TABLE events ADD COLUMN location_zip_code VARCHAR(5);
+
+CREATE INDEX idx_events_location_zip_code ON events(location_zip_code)
+    WHERE location_zip_code IS NOT NULL;
@@ -0,0 +1,12 @@
+-- Rollback guest signups
+
+-- signups that were never attached to an account can't survive the NOT NULL
+DELETE FROM event_signups WHERE user_id IS NULL;
+
+DROP INDEX IF EXISTS idx_event_signups_contact;
+DROP INDEX IF EXISTS idx_event_signups_shift_contact;
+ALTER TABLE event_signups DROP CONSTRAINT IF EXISTS event_signups_owner_check;
+ALTER TABLE event_signups DROP COLUMN IF EXISTS contact_id;
+ALTER TABLE event_signups ALTER COLUMN user_id SET NOT NULL;
+
+DROP TABLE IF EXISTS contacts;
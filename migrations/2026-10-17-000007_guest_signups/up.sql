@@ -0,0 +1,34 @@
+-- Guest signups
+-- Volunteers can RSVP without an account, their details are kept on a contact
+-- record that is linked to the user when they register with the same email
+
+-------------------------------------------------------------------------------
+-- CONTACTS
+-------------------------------------------------------------------------------
+CREATE TABLE contacts (
+    id SERIAL PRIMARY KEY,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    first_name VARCHAR(100) NOT NULL,
+    last_name VARCHAR(100) NOT NULL,
+    phone_number VARCHAR(20),
+    zip_code VARCHAR(5),
+    user_id INTEGER UNIQUE REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_contacts_updated_at
+    BEFORE UPDATE ON contacts
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-------------------------------------------------------------------------------
+-- EVENT SIGNUPS
+-------------------------------------------------------------------------------
+ALTER TABLE event_signups ALTER COLUMN user_id DROP NOT NULL;
+ALTER TABLE event_signups ADD COLUMN contact_id INTEGER REFERENCES contacts(id) ON DELETE CASCADE;
+ALTER TABLE event_signups ADD CONSTRAINT event_signups_owner_check
+    CHECK (user_id IS NOT NULL OR contact_id IS NOT NULL);
+
+CREATE UNIQUE INDEX idx_event_signups_shift_contact ON event_signups(event_shift_id, contact_id)
+    WHERE contact_id IS NOT NULL;
+CREATE INDEX idx_event_signups_contact ON event_signups(contact_id);
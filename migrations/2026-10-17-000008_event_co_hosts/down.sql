@@ -0,0 +1,24 @@
+-- Rollback event co-hosts
+
+ALTER TABLE events ADD COLUMN co_hosts TEXT[] NOT NULL DEFAULT '{}';
+
+-- keep the names of accepted and pending co-hosts, and the names that were never
+-- matched to an organization, as free text
+UPDATE events SET co_hosts = ARRAY(
+    SELECT name FROM (
+        SELECT organizations.name, event_co_hosts.id AS position
+        FROM event_co_hosts
+        JOIN organizations ON organizations.id = event_co_hosts.organization_id
+        WHERE event_co_hosts.event_id = events.id
+          AND event_co_hosts.status IN ('accepted', 'pending')
+        UNION ALL
+        SELECT legacy_event_co_hosts.name, legacy_event_co_hosts.id AS position
+        FROM legacy_event_co_hosts
+        WHERE legacy_event_co_hosts.event_id = events.id
+    ) AS names
+    ORDER BY position
+);
+
+DROP TABLE IF EXISTS legacy_event_co_hosts;
+
+DROP TABLE IF EXISTS event_co_hosts;
@@ -0,0 +1,75 @@
+-- Event co-hosts
+-- Replaces the free-text co_hosts names with invitations between organizations,
+-- an accepted co-host sees the event and, if shared, its signups
+
+-------------------------------------------------------------------------------
+-- EVENT CO-HOSTS
+-------------------------------------------------------------------------------
+CREATE TABLE event_co_hosts (
+    id SERIAL PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    data_sharing VARCHAR(20) NOT NULL DEFAULT 'summary',
+    invited_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    responded_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    responded_at TIMESTAMPTZ,
+    UNIQUE(event_id, organization_id)
+);
+
+CREATE INDEX idx_event_co_hosts_organization ON event_co_hosts(organization_id, status);
+
+-------------------------------------------------------------------------------
+-- EXISTING CO-HOST NAMES
+-------------------------------------------------------------------------------
+-- a name that matches exactly one other organization's slug or name becomes a
+-- pending invitation from the event's creator, so nothing is shared until that
+-- organization accepts. names that match no organization, or several, are kept
+-- here as they were written
+CREATE TABLE legacy_event_co_hosts (
+    id SERIAL PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name TEXT NOT NULL
+);
+
+CREATE INDEX idx_legacy_event_co_hosts_event ON legacy_event_co_hosts(event_id);
+
+CREATE TEMPORARY TABLE migrated_co_host_names AS
+SELECT
+    named.event_id,
+    named.created_by,
+    named.name,
+    MIN(organizations.id) AS organization_id,
+    COUNT(organizations.id) AS matches
+FROM (
+    SELECT DISTINCT
+        events.id AS event_id,
+        events.organization_id AS host_organization_id,
+        events.created_by,
+        btrim(co_host) AS name
+    FROM events, unnest(events.co_hosts) AS co_host
+    WHERE btrim(co_host) <> ''
+) AS named
+LEFT JOIN organizations
+    ON organizations.id <> named.host_organization_id
+    AND (
+        organizations.slug = lower(named.name)
+        OR lower(organizations.name) = lower(named.name)
+    )
+GROUP BY named.event_id, named.created_by, named.name;
+
+INSERT INTO event_co_hosts (event_id, organization_id, status, invited_by)
+SELECT event_id, organization_id, 'pending', created_by
+FROM migrated_co_host_names
+WHERE matches = 1
+ON CONFLICT (event_id, organization_id) DO NOTHING;
+
+INSERT INTO legacy_event_co_hosts (event_id, name)
+SELECT event_id, name
+FROM migrated_co_host_names
+WHERE matches <> 1;
+
+DROP TABLE migrated_co_host_names;
+
+ALTER TABLE events DROP COLUMN co_hosts;
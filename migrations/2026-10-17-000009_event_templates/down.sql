@@ -0,0 +1,3 @@
+-- Rollback event templates
+
+DROP TABLE IF EXISTS event_templates;
@@ -0,0 +1,42 @@
+-- Event templates
+-- Saved per organization to pre-fill new events and their shift layout
+
+-------------------------------------------------------------------------------
+-- EVENT TEMPLATES
+-------------------------------------------------------------------------------
+CREATE TABLE event_templates (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+
+    -- Event Fields
+    event_name VARCHAR(255) NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    description TEXT,
+    attendee_message TEXT,
+    image_url TEXT,
+    location_in_person TEXT,
+    location_online TEXT,
+    location_zip_code VARCHAR(5),
+    communication_bring_a_friend BOOLEAN NOT NULL DEFAULT false,
+    communication_other_events BOOLEAN NOT NULL DEFAULT false,
+    communication_confirmation BOOLEAN NOT NULL DEFAULT true,
+    communication_check_in BOOLEAN NOT NULL DEFAULT true,
+    contact_name VARCHAR(255) NOT NULL,
+    contact_email VARCHAR(255),
+    contact_phone VARCHAR(20),
+    invite_groups TEXT[] NOT NULL DEFAULT '{}',
+
+    -- Shift layout relative to the first day of the event
+    shifts JSONB NOT NULL DEFAULT '[]',
+
+    -- Metadata
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(organization_id, name)
+);
+
+CREATE TRIGGER update_event_templates_updated_at
+    BEFORE UPDATE ON event_templates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
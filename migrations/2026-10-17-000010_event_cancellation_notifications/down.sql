@@ -0,0 +1,5 @@
+-- Rollback event cancellation notifications
+
+DROP TABLE IF EXISTS outbound_messages;
+
+ALTER TABLE events DROP COLUMN IF EXISTS cancellation_reason;
@@ -0,0 +1,32 @@
+-- Event cancellation notifications
+-- Records why an event was cancelled and queues the emails and texts sent to
+-- attendees, the queue is drained by the mail and SMS senders
+
+ALTER TABLE events ADD COLUMN cancellation_reason TEXT;
+
+-------------------------------------------------------------------------------
+-- OUTBOUND MESSAGES
+-------------------------------------------------------------------------------
+CREATE TABLE outbound_messages (
+    id SERIAL PRIMARY KEY,
+    channel VARCHAR(10) NOT NULL,
+    recipient VARCHAR(255) NOT NULL,
+    subject VARCHAR(255),
+    body TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    contact_id INTEGER REFERENCES contacts(id) ON DELETE SET NULL,
+    event_id INTEGER REFERENCES events(id) ON DELETE SET NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    claimed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_outbound_messages_pending ON outbound_messages(next_attempt_at)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_outbound_messages_sending ON outbound_messages(claimed_at)
+    WHERE status = 'sending';
@@ -0,0 +1,3 @@
+-- Rollback volunteer hours
+
+DROP TABLE IF EXISTS volunteer_hour_adjustments;
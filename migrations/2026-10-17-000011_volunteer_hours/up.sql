@@ -0,0 +1,19 @@
+-- Volunteer hours
+-- Hours are derived from checked-in signups, manual adjustments are append-only so
+-- every correction keeps who made it and why
+
+-------------------------------------------------------------------------------
+-- VOLUNTEER HOUR ADJUSTMENTS
+-------------------------------------------------------------------------------
+CREATE TABLE volunteer_hour_adjustments (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_signup_id INTEGER REFERENCES event_signups(id) ON DELETE SET NULL,
+    minutes INTEGER NOT NULL CHECK (minutes <> 0),
+    reason TEXT NOT NULL,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_volunteer_hour_adjustments_user ON volunteer_hour_adjustments(organization_id, user_id, created_at DESC);
@@ -0,0 +1,3 @@
+-- Rollback shift conflict policy
+
+ALTER TABLE organizations DROP COLUMN IF EXISTS shift_conflict_policy;
@@ -0,0 +1,8 @@
+-- Shift conflict policy
+-- Whether signing up for a shift that overlaps another of the volunteer's shifts is
+-- allowed with a warning or blocked outright
+
+-------------------------------------------------------------------------------
+-- ORGANIZATIONS
+-------------------------------------------------------------------------------
+ALTER TABLE organizations ADD COLUMN shift_conflict_policy VARCHAR(10) NOT NULL DEFAULT 'warn';
@@ -0,0 +1,5 @@
+-- Rollback event images
+
+ALTER TABLE event_templates DROP COLUMN IF EXISTS image_variants;
+ALTER TABLE events DROP COLUMN IF EXISTS image_variants;
+ALTER TABLE events DROP COLUMN IF EXISTS image_storage_prefix;
@@ -0,0 +1,14 @@
+-- Event images
+-- Uploaded cover images live in the events bucket under a per-upload prefix, with
+-- resized variants for responsive layouts
+
+-------------------------------------------------------------------------------
+-- EVENTS
+-------------------------------------------------------------------------------
+ALTER TABLE events ADD COLUMN image_storage_prefix VARCHAR(255);
+ALTER TABLE events ADD COLUMN image_variants JSONB NOT NULL DEFAULT '[]';
+
+-------------------------------------------------------------------------------
+-- EVENT TEMPLATES
+-------------------------------------------------------------------------------
+ALTER TABLE event_templates ADD COLUMN image_variants JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,5 @@
+-- Rollback notification preferences
+
+ALTER TABLE outbound_messages DROP COLUMN IF EXISTS unsubscribe_token;
+
+DROP TABLE IF EXISTS notification_preferences;
@@ -0,0 +1,31 @@
+-- Notification preferences
+-- Per-user switches for each notification type and delivery channel. A row without
+-- an organization is the account default, a row with one overrides the default for
+-- that organization, and anything without a row is delivered. Queued emails carry
+-- a signed one-click unsubscribe token for the mailer to link to
+
+-------------------------------------------------------------------------------
+-- NOTIFICATION PREFERENCES
+-------------------------------------------------------------------------------
+CREATE TABLE notification_preferences (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id INTEGER REFERENCES organizations(id) ON DELETE CASCADE,
+    notification_type VARCHAR(50) NOT NULL,
+    channel VARCHAR(20) NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- account defaults have no organization, so they're folded to 0 to stay unique
+CREATE UNIQUE INDEX idx_notification_preferences_unique ON notification_preferences(user_id, COALESCE(organization_id, 0), notification_type, channel);
+
+CREATE TRIGGER update_notification_preferences_updated_at
+    BEFORE UPDATE ON notification_preferences
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-------------------------------------------------------------------------------
+-- OUTBOUND MESSAGES
+-------------------------------------------------------------------------------
+ALTER TABLE outbound_messages ADD COLUMN unsubscribe_token TEXT;
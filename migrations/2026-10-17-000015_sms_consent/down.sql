@@ -0,0 +1,3 @@
+-- Rollback SMS consent
+
+DROP TABLE IF EXISTS sms_consents;
@@ -0,0 +1,27 @@
+-- SMS consent
+-- An append-only ledger of every opt-in and opt-out for a phone number, recording
+-- how it was given (account settings, an RSVP form or a keyword text), the
+-- disclosure that was shown and where the request came from. The latest entry for
+-- a number decides whether it can be texted, so nothing is ever updated or deleted.
+-- Outbound texts to numbers without a standing opt-in are marked blocked
+
+-------------------------------------------------------------------------------
+-- SMS CONSENTS
+-------------------------------------------------------------------------------
+CREATE TABLE sms_consents (
+    id SERIAL PRIMARY KEY,
+    phone_number VARCHAR(20) NOT NULL,
+    action VARCHAR(20) NOT NULL,
+    source VARCHAR(20) NOT NULL,
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    contact_id INTEGER REFERENCES contacts(id) ON DELETE SET NULL,
+    organization_id INTEGER REFERENCES organizations(id) ON DELETE SET NULL,
+    consent_text TEXT,
+    ip_address INET,
+    provider_message_id VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_sms_consents_phone_number ON sms_consents(phone_number, created_at DESC, id DESC);
+CREATE INDEX idx_sms_consents_user_id ON sms_consents(user_id);
+CREATE INDEX idx_sms_consents_contact_id ON sms_consents(contact_id);
@@ -0,0 +1,4 @@
+-- Rollback ownership transfers
+
+DROP TABLE IF EXISTS audit_logs;
+DROP TABLE IF EXISTS ownership_transfers;
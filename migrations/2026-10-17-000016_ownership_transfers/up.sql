@@ -0,0 +1,38 @@
+-- Ownership transfers
+-- An owner nominates an existing member to take over the organization and the
+-- nominee confirms before anything changes. On acceptance the two roles swap in a
+-- single transaction, the previous owner staying on as an admin. Only one transfer
+-- can be waiting per organization, and unanswered nominations lapse.
+-- Sensitive organization changes are recorded in an append-only audit log
+
+-------------------------------------------------------------------------------
+-- OWNERSHIP TRANSFERS
+-------------------------------------------------------------------------------
+CREATE TABLE ownership_transfers (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    from_user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    to_user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    responded_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX idx_ownership_transfers_pending ON ownership_transfers(organization_id) WHERE status = 'pending';
+CREATE INDEX idx_ownership_transfers_to_user_id ON ownership_transfers(to_user_id, status);
+
+-------------------------------------------------------------------------------
+-- AUDIT LOGS
+-------------------------------------------------------------------------------
+CREATE TABLE audit_logs (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER REFERENCES organizations(id) ON DELETE SET NULL,
+    actor_user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(50) NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_logs_organization_id ON audit_logs(organization_id, created_at DESC);
+CREATE INDEX idx_audit_logs_actor_user_id ON audit_logs(actor_user_id);
@@ -0,0 +1,9 @@
+-- Rollback organization archival
+
+DROP INDEX IF EXISTS idx_organizations_purge_scheduled_at;
+
+ALTER TABLE organizations
+    DROP COLUMN IF EXISTS purged_at,
+    DROP COLUMN IF EXISTS purge_scheduled_at,
+    DROP COLUMN IF EXISTS archived_by,
+    DROP COLUMN IF EXISTS archived_at;
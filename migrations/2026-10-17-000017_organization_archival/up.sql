@@ -0,0 +1,16 @@
+-- Organization archival
+-- Owners can archive an organization, which hides it from its members straight
+-- away and schedules its events, signups and notifications for permanent deletion
+-- once the grace period is over. The organization row itself is kept, marked as
+-- purged, so its audit entries still point somewhere
+
+-------------------------------------------------------------------------------
+-- ORGANIZATIONS
+-------------------------------------------------------------------------------
+ALTER TABLE organizations
+    ADD COLUMN archived_at TIMESTAMPTZ,
+    ADD COLUMN archived_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN purge_scheduled_at TIMESTAMPTZ,
+    ADD COLUMN purged_at TIMESTAMPTZ;
+
+CREATE INDEX idx_organizations_purge_scheduled_at ON organizations(purge_scheduled_at) WHERE purge_scheduled_at IS NOT NULL AND purged_at IS NULL;
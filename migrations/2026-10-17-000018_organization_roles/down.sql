@@ -0,0 +1,5 @@
+-- Rollback organization roles
+
+ALTER TABLE organization_members DROP COLUMN IF EXISTS custom_role_id;
+
+DROP TABLE IF EXISTS organization_roles;
@@ -0,0 +1,31 @@
+-- Organization roles
+-- Permissions are named capabilities (events.publish, team.invite, ...). Every
+-- member role has a default set, and an organization can define its own roles
+-- with any set of capabilities and assign them to members, replacing the defaults
+-- of their role. The owner always has every capability
+
+-------------------------------------------------------------------------------
+-- ORGANIZATION ROLES
+-------------------------------------------------------------------------------
+CREATE TABLE organization_roles (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    capabilities TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_organization_roles_name ON organization_roles(organization_id, LOWER(name));
+
+CREATE TRIGGER update_organization_roles_updated_at
+    BEFORE UPDATE ON organization_roles
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-------------------------------------------------------------------------------
+-- ORGANIZATION MEMBERS
+-------------------------------------------------------------------------------
+ALTER TABLE organization_members
+    ADD COLUMN custom_role_id INTEGER REFERENCES organization_roles(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_organization_members_custom_role_id ON organization_members(custom_role_id);
@@ -0,0 +1,11 @@
+-- Rollback subscription plans
+
+ALTER TABLE events DROP COLUMN IF EXISTS image_storage_bytes;
+
+ALTER TABLE outbound_messages DROP COLUMN IF EXISTS organization_id;
+
+ALTER TABLE organizations ADD COLUMN subscriptions TEXT[] NOT NULL DEFAULT '{}';
+
+UPDATE organizations SET subscriptions = '{events}';
+
+DROP TABLE IF EXISTS organization_plans;
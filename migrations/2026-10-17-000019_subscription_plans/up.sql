@@ -0,0 +1,46 @@
+-- Subscription plans
+-- An organization's features and limits (member seats, monthly email and SMS
+-- quotas, image storage) come from its plan. Plan changes are kept as an
+-- effective-dated history, the current plan is the latest row that has taken
+-- effect, so staff can schedule a change ahead of time. Replaces the
+-- subscriptions array on organizations
+
+-------------------------------------------------------------------------------
+-- OUTBOUND MESSAGES
+-------------------------------------------------------------------------------
+-- messages sent on an organization's behalf count against its monthly quota
+ALTER TABLE outbound_messages
+    ADD COLUMN organization_id INTEGER REFERENCES organizations(id) ON DELETE SET NULL;
+
+UPDATE outbound_messages
+SET organization_id = events.organization_id
+FROM events
+WHERE events.id = outbound_messages.event_id;
+
+CREATE INDEX idx_outbound_messages_organization_id ON outbound_messages(organization_id, channel, created_at);
+
+-------------------------------------------------------------------------------
+-- ORGANIZATION PLANS
+-------------------------------------------------------------------------------
+CREATE TABLE organization_plans (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    plan VARCHAR(50) NOT NULL,
+    effective_from TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    changed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_organization_plans_effective_from ON organization_plans(organization_id, effective_from DESC);
+
+-- existing organizations are put on the smallest plan that covers what they already
+-- use by assign_missing_organization_plans when the server starts, so the plan
+-- limits are only defined in SubscriptionPlan::entitlements
+ALTER TABLE organizations DROP COLUMN subscriptions;
+
+-------------------------------------------------------------------------------
+-- EVENTS
+-------------------------------------------------------------------------------
+-- size of the stored image variants, counted against the storage quota
+ALTER TABLE events ADD COLUMN image_storage_bytes BIGINT NOT NULL DEFAULT 0;
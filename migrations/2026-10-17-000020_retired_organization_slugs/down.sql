@@ -0,0 +1,3 @@
+-- Rollback retired organization slugs
+
+DROP TABLE IF EXISTS retired_organization_slugs;
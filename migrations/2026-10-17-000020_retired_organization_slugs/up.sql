@@ -0,0 +1,19 @@
+-- Retired organization slugs
+-- When an organization changes its slug the old one is kept here so public links
+-- (event pages, calendar feeds, surveys) still resolve to the organization. A
+-- retired slug can't be taken by another organization until its reservation
+-- runs out, after that claiming it ends the redirect
+
+-------------------------------------------------------------------------------
+-- RETIRED ORGANIZATION SLUGS
+-------------------------------------------------------------------------------
+CREATE TABLE retired_organization_slugs (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    slug VARCHAR(100) NOT NULL UNIQUE,
+    changed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    retired_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reserved_until TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_retired_organization_slugs_organization_id ON retired_organization_slugs(organization_id);
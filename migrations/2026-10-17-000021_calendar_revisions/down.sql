@@ -0,0 +1,8 @@
+-- Rollback calendar revisions
+
+DROP TRIGGER IF EXISTS increment_event_shifts_revision ON event_shifts;
+DROP TRIGGER IF EXISTS increment_events_revision ON events;
+DROP FUNCTION IF EXISTS increment_revision_column();
+
+ALTER TABLE event_shifts DROP COLUMN IF EXISTS revision;
+ALTER TABLE events DROP COLUMN IF EXISTS revision;
@@ -0,0 +1,26 @@
+-- Calendar revisions
+-- Every update to an event or shift bumps its revision, calendar feeds publish the
+-- two added together as the SEQUENCE of each shift so subscribed clients apply
+-- the change
+
+ALTER TABLE events ADD COLUMN revision INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE event_shifts ADD COLUMN revision INTEGER NOT NULL DEFAULT 0;
+
+-------------------------------------------------------------------------------
+-- TRIGGER: Increment revision
+-------------------------------------------------------------------------------
+CREATE OR REPLACE FUNCTION increment_revision_column()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.revision = OLD.revision + 1;
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER increment_events_revision
+    BEFORE UPDATE ON events
+    FOR EACH ROW EXECUTE FUNCTION increment_revision_column();
+
+CREATE TRIGGER increment_event_shifts_revision
+    BEFORE UPDATE ON event_shifts
+    FOR EACH ROW EXECUTE FUNCTION increment_revision_column();
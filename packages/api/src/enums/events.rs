@@ -0,0 +1,8 @@
+use crate::define_enum;
+
+define_enum! {
+    pub enum EventLocationType {
+        InPerson => ("in_person", "In Person"),
+        Online => ("online", "Online"),
+    }
+}
@@ -1,4 +1,6 @@
 mod article;
+mod audit_log;
+mod capability;
 mod event;
 mod member;
 mod organization;
@@ -6,6 +8,8 @@ mod session;
 mod subscription;
 
 pub use article::*;
+pub use audit_log::*;
+pub use capability::*;
 pub use event::*;
 pub use member::*;
 pub use organization::*;
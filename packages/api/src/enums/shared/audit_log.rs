@@ -0,0 +1,11 @@
+use crate::define_enum;
+
+define_enum! {
+    pub enum AuditAction {
+        OwnershipTransferred => ("ownership_transferred", "Ownership Transferred"),
+        OrganizationArchived => ("organization_archived", "Organization Archived"),
+        OrganizationPurged => ("organization_purged", "Organization Purged"),
+        PlanChanged => ("plan_changed", "Plan Changed"),
+        SlugChanged => ("slug_changed", "Slug Changed"),
+    }
+}
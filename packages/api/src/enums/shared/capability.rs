@@ -0,0 +1,158 @@
+use crate::define_enum;
+use crate::enums::MemberRole;
+
+pub const MAX_ORGANIZATION_ROLE_NAME_LENGTH: usize = 100;
+
+define_enum! {
+    pub enum Capability {
+        EventsView => ("events.view", "View events"),
+        EventsManage => ("events.manage", "Create and edit events"),
+        EventsPublish => ("events.publish", "Publish and cancel events"),
+        EventsCheckIn => ("events.check_in", "Check in attendees"),
+        SignupsManage => ("signups.manage", "Manage signups"),
+        CoHostsRespond => ("co_hosts.respond", "Respond to co-host invitations"),
+        VolunteerHoursView => ("volunteer_hours.view", "View volunteer hours"),
+        VolunteerHoursManage => ("volunteer_hours.manage", "Adjust volunteer hours"),
+        ExportsDownload => ("exports.download", "Download exports"),
+        NotificationsSend => ("notifications.send", "Send notifications"),
+        TeamView => ("team.view", "View the team"),
+        TeamInvite => ("team.invite", "Invite members"),
+        TeamManage => ("team.manage", "Remove members"),
+        RolesManage => ("roles.manage", "Manage roles"),
+        OrganizationManage => ("organization.manage", "Edit organization settings"),
+        BillingManage => ("billing.manage", "Manage billing"),
+    }
+}
+
+pub const ALL_CAPABILITIES: [Capability; 16] = [
+    Capability::EventsView,
+    Capability::EventsManage,
+    Capability::EventsPublish,
+    Capability::EventsCheckIn,
+    Capability::SignupsManage,
+    Capability::CoHostsRespond,
+    Capability::VolunteerHoursView,
+    Capability::VolunteerHoursManage,
+    Capability::ExportsDownload,
+    Capability::NotificationsSend,
+    Capability::TeamView,
+    Capability::TeamInvite,
+    Capability::TeamManage,
+    Capability::RolesManage,
+    Capability::OrganizationManage,
+    Capability::BillingManage,
+];
+
+const MEMBER_CAPABILITIES: [Capability; 4] = [
+    Capability::EventsView,
+    Capability::EventsCheckIn,
+    Capability::VolunteerHoursView,
+    Capability::TeamView,
+];
+
+const MANAGER_CAPABILITIES: [Capability; 5] = [
+    Capability::EventsManage,
+    Capability::EventsPublish,
+    Capability::SignupsManage,
+    Capability::VolunteerHoursManage,
+    Capability::ExportsDownload,
+];
+
+const ADMIN_CAPABILITIES: [Capability; 5] = [
+    Capability::CoHostsRespond,
+    Capability::NotificationsSend,
+    Capability::TeamInvite,
+    Capability::TeamManage,
+    Capability::OrganizationManage,
+];
+
+impl MemberRole {
+    // each role builds on the one below it, only the owner manages roles and billing
+    pub fn default_capabilities(&self) -> Vec<Capability> {
+        match self {
+            MemberRole::Owner => ALL_CAPABILITIES.to_vec(),
+            MemberRole::Admin => [
+                &MEMBER_CAPABILITIES[..],
+                &MANAGER_CAPABILITIES[..],
+                &ADMIN_CAPABILITIES[..],
+            ]
+            .concat(),
+            MemberRole::Manager => [&MEMBER_CAPABILITIES[..], &MANAGER_CAPABILITIES[..]].concat(),
+            MemberRole::Member => MEMBER_CAPABILITIES.to_vec(),
+        }
+    }
+}
+
+// the one place a member's capabilities are worked out, server functions authorize
+// against it and the web app is sent the same list for its menus and gates, the
+// owner can always do everything and a custom role replaces the role's defaults
+pub fn resolve_capabilities(
+    role: MemberRole,
+    custom_role_capabilities: Option<Vec<Capability>>,
+) -> Vec<Capability> {
+    match (role, custom_role_capabilities) {
+        (MemberRole::Owner, _) | (_, None) => role.default_capabilities(),
+        (_, Some(capabilities)) => capabilities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CUSTOM_CAPABILITIES: [Capability; 2] =
+        [Capability::TeamInvite, Capability::NotificationsSend];
+
+    #[test]
+    fn roles_without_a_custom_role_get_their_defaults() {
+        for role in [
+            MemberRole::Owner,
+            MemberRole::Admin,
+            MemberRole::Manager,
+            MemberRole::Member,
+        ] {
+            assert!(resolve_capabilities(role, None) == role.default_capabilities());
+        }
+    }
+
+    #[test]
+    fn custom_role_replaces_the_defaults() {
+        let capabilities =
+            resolve_capabilities(MemberRole::Manager, Some(CUSTOM_CAPABILITIES.to_vec()));
+
+        assert!(capabilities == CUSTOM_CAPABILITIES.to_vec());
+        assert!(!capabilities.contains(&Capability::EventsManage));
+    }
+
+    #[test]
+    fn custom_role_can_take_everything_away() {
+        assert!(resolve_capabilities(MemberRole::Admin, Some(vec![])).is_empty());
+    }
+
+    #[test]
+    fn owner_keeps_every_capability_whatever_the_custom_role() {
+        let capabilities =
+            resolve_capabilities(MemberRole::Owner, Some(CUSTOM_CAPABILITIES.to_vec()));
+
+        assert!(capabilities == ALL_CAPABILITIES.to_vec());
+    }
+
+    #[test]
+    fn each_role_includes_the_one_below_it() {
+        let roles = [
+            MemberRole::Member,
+            MemberRole::Manager,
+            MemberRole::Admin,
+            MemberRole::Owner,
+        ];
+
+        for pair in roles.windows(2) {
+            let (lower, higher) = (
+                pair[0].default_capabilities(),
+                pair[1].default_capabilities(),
+            );
+            assert!(lower.iter().all(|capability| higher.contains(capability)));
+            assert!(higher.len() > lower.len());
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use crate::define_enum;
 
+// used by redis for public event discovery cache expiry (5 minutes in seconds),
+// kept short since results depend on which shifts are still upcoming
+pub const PUBLIC_EVENT_CACHE_EXPIRY_SECONDS: u64 = 300;
+
 define_enum! {
     pub enum EventType {
         Canvassing => ("canvassing", "Canvassing"),
@@ -30,8 +34,94 @@ define_enum! {
 define_enum! {
     pub enum SignupStatus {
         SignedUp => ("signed_up", "Signed Up"),
+        Waitlisted => ("waitlisted", "Waitlisted"),
         CheckedIn => ("checked_in", "Checked In"),
         NoShow => ("no_show", "No Show"),
         Cancelled => ("cancelled", "Cancelled"),
     }
 }
+
+impl SignupStatus {
+    // statuses that hold one of the shift's capacity seats
+    pub const SEATED: [SignupStatus; 3] = [
+        SignupStatus::SignedUp,
+        SignupStatus::CheckedIn,
+        SignupStatus::NoShow,
+    ];
+
+    pub fn is_seated(&self) -> bool {
+        Self::SEATED.contains(self)
+    }
+}
+
+define_enum! {
+    pub enum CoHostStatus {
+        Pending => ("pending", "Pending"),
+        Accepted => ("accepted", "Accepted"),
+        Declined => ("declined", "Declined"),
+    }
+}
+
+// how much of the event's signup data an accepted co-host organization can see
+define_enum! {
+    pub enum CoHostDataSharing {
+        Summary => ("summary", "Signup Counts Only"),
+        Signups => ("signups", "Full Signup List"),
+    }
+}
+
+define_enum! {
+    pub enum RecurrenceFrequency {
+        Daily => ("daily", "Daily"),
+        Weekly => ("weekly", "Weekly"),
+        Monthly => ("monthly", "Monthly"),
+    }
+}
+
+define_enum! {
+    pub enum RecurrenceWeekday {
+        Monday => ("monday", "Monday"),
+        Tuesday => ("tuesday", "Tuesday"),
+        Wednesday => ("wednesday", "Wednesday"),
+        Thursday => ("thursday", "Thursday"),
+        Friday => ("friday", "Friday"),
+        Saturday => ("saturday", "Saturday"),
+        Sunday => ("sunday", "Sunday"),
+    }
+}
+
+impl From<RecurrenceWeekday> for chrono::Weekday {
+    fn from(weekday: RecurrenceWeekday) -> Self {
+        match weekday {
+            RecurrenceWeekday::Monday => chrono::Weekday::Mon,
+            RecurrenceWeekday::Tuesday => chrono::Weekday::Tue,
+            RecurrenceWeekday::Wednesday => chrono::Weekday::Wed,
+            RecurrenceWeekday::Thursday => chrono::Weekday::Thu,
+            RecurrenceWeekday::Friday => chrono::Weekday::Fri,
+            RecurrenceWeekday::Saturday => chrono::Weekday::Sat,
+            RecurrenceWeekday::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+impl From<chrono::Weekday> for RecurrenceWeekday {
+    fn from(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => RecurrenceWeekday::Monday,
+            chrono::Weekday::Tue => RecurrenceWeekday::Tuesday,
+            chrono::Weekday::Wed => RecurrenceWeekday::Wednesday,
+            chrono::Weekday::Thu => RecurrenceWeekday::Thursday,
+            chrono::Weekday::Fri => RecurrenceWeekday::Friday,
+            chrono::Weekday::Sat => RecurrenceWeekday::Saturday,
+            chrono::Weekday::Sun => RecurrenceWeekday::Sunday,
+        }
+    }
+}
+
+define_enum! {
+    pub enum SeriesEditScope {
+        This => ("this", "This Shift"),
+        ThisAndFollowing => ("this_and_following", "This and Following Shifts"),
+        All => ("all", "All Shifts"),
+    }
+}
@@ -1,6 +1,11 @@
 use crate::define_enum;
 
 pub const DEFAULT_INVITATION_EXPIRY_DAYS: i64 = 7;
+pub const DEFAULT_OWNERSHIP_TRANSFER_EXPIRY_DAYS: i64 = 7;
+// how long an archived organization's data is kept before it's deleted for good
+pub const ARCHIVED_ORGANIZATION_PURGE_DAYS: i64 = 30;
+// how long a slug an organization moved away from stays out of reach of others
+pub const RETIRED_ORGANIZATION_SLUG_RESERVATION_DAYS: i64 = 180;
 
 define_enum! {
     pub enum OrganizationType {
@@ -14,5 +19,22 @@ define_enum! {
         Pending => ("pending", "Pending"),
         Accepted => ("accepted", "Accepted"),
         Expired => ("expired", "Expired"),
+        Revoked => ("revoked", "Revoked"),
+    }
+}
+
+define_enum! {
+    pub enum OwnershipTransferStatus {
+        Pending => ("pending", "Pending"),
+        Accepted => ("accepted", "Accepted"),
+        Declined => ("declined", "Declined"),
+        Cancelled => ("cancelled", "Cancelled"),
+    }
+}
+
+define_enum! {
+    pub enum ShiftConflictPolicy {
+        Warn => ("warn", "Warn"),
+        Block => ("block", "Block"),
     }
 }
@@ -1,4 +1,8 @@
 use crate::define_enum;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SUBSCRIPTION_PLAN: SubscriptionPlan = SubscriptionPlan::Free;
+pub const MAX_PLAN_CHANGE_NOTE_LENGTH: usize = 1000;
 
 define_enum! {
     #[derive(Eq, Hash)]
@@ -6,3 +10,83 @@ define_enum! {
         Events => ("events", "Events"),
     }
 }
+
+define_enum! {
+    pub enum SubscriptionPlan {
+        Free => ("free", "Free"),
+        Starter => ("starter", "Starter"),
+        Growth => ("growth", "Growth"),
+        Enterprise => ("enterprise", "Enterprise"),
+    }
+}
+
+// what a plan includes, a limit of None is unlimited, member seats count pending
+// invitations as well as members
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct PlanEntitlements {
+    pub features: Vec<SubscriptionType>,
+    pub max_members: Option<i64>,
+    pub monthly_email_quota: Option<i64>,
+    pub monthly_sms_quota: Option<i64>,
+    pub storage_quota_bytes: Option<i64>,
+}
+
+const MEGABYTE: i64 = 1024 * 1024;
+
+impl SubscriptionPlan {
+    // smallest first
+    pub const ALL: [SubscriptionPlan; 4] = [
+        SubscriptionPlan::Free,
+        SubscriptionPlan::Starter,
+        SubscriptionPlan::Growth,
+        SubscriptionPlan::Enterprise,
+    ];
+
+    // the smallest plan whose limits cover the usage, storage isn't considered
+    pub fn smallest_covering(member_seats: i64, monthly_emails: i64, monthly_sms: i64) -> Self {
+        let covers = |limit: Option<i64>, usage: i64| limit.is_none_or(|limit| usage <= limit);
+
+        SubscriptionPlan::ALL
+            .into_iter()
+            .find(|plan| {
+                let entitlements = plan.entitlements();
+                covers(entitlements.max_members, member_seats)
+                    && covers(entitlements.monthly_email_quota, monthly_emails)
+                    && covers(entitlements.monthly_sms_quota, monthly_sms)
+            })
+            .unwrap_or(SubscriptionPlan::Enterprise)
+    }
+
+    pub fn entitlements(&self) -> PlanEntitlements {
+        match self {
+            SubscriptionPlan::Free => PlanEntitlements {
+                features: vec![SubscriptionType::Events],
+                max_members: Some(5),
+                monthly_email_quota: Some(500),
+                monthly_sms_quota: Some(0),
+                storage_quota_bytes: Some(250 * MEGABYTE),
+            },
+            SubscriptionPlan::Starter => PlanEntitlements {
+                features: vec![SubscriptionType::Events],
+                max_members: Some(25),
+                monthly_email_quota: Some(5_000),
+                monthly_sms_quota: Some(500),
+                storage_quota_bytes: Some(5 * 1024 * MEGABYTE),
+            },
+            SubscriptionPlan::Growth => PlanEntitlements {
+                features: vec![SubscriptionType::Events],
+                max_members: Some(100),
+                monthly_email_quota: Some(25_000),
+                monthly_sms_quota: Some(5_000),
+                storage_quota_bytes: Some(25 * 1024 * MEGABYTE),
+            },
+            SubscriptionPlan::Enterprise => PlanEntitlements {
+                features: vec![SubscriptionType::Events],
+                max_members: None,
+                monthly_email_quota: None,
+                monthly_sms_quota: None,
+                storage_quota_bytes: None,
+            },
+        }
+    }
+}
@@ -1,3 +1,9 @@
+mod export;
 mod notification;
+mod outbound_message;
+mod sms;
 
+pub use export::*;
 pub use notification::*;
+pub use outbound_message::*;
+pub use sms::*;
@@ -0,0 +1,29 @@
+use crate::define_enum;
+
+define_enum! {
+    pub enum ExportType {
+        EventAttendees => ("event_attendees", "Event Attendees"),
+    }
+}
+
+define_enum! {
+    pub enum ExportFormat {
+        Csv => ("csv", "CSV"),
+        Xlsx => ("xlsx", "Excel"),
+    }
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        self.as_str()
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+}
@@ -6,5 +6,19 @@ define_enum! {
         EventReminder => ("event_reminder", "Event Reminder"),
         TeamInvite => ("team_invite", "Team Invite"),
         MemberJoined => ("member_joined", "Member Joined"),
+        SignupConfirmed => ("signup_confirmed", "Signup Confirmed"),
+        WaitlistPromoted => ("waitlist_promoted", "Waitlist Promoted"),
+        CoHostInvite => ("co_host_invite", "Co-Host Invite"),
+        EventCancelled => ("event_cancelled", "Event Cancelled"),
+        OwnershipTransfer => ("ownership_transfer", "Ownership Transfer"),
+    }
+}
+
+define_enum! {
+    pub enum NotificationChannel {
+        InApp => ("in_app", "In-App"),
+        Email => ("email", "Email"),
+        Sms => ("sms", "SMS"),
+        Push => ("push", "Push"),
     }
 }
@@ -0,0 +1,18 @@
+use crate::define_enum;
+
+define_enum! {
+    pub enum MessageChannel {
+        Email => ("email", "Email"),
+        Sms => ("sms", "SMS"),
+    }
+}
+
+define_enum! {
+    pub enum OutboundMessageStatus {
+        Pending => ("pending", "Pending"),
+        Sending => ("sending", "Sending"),
+        Sent => ("sent", "Sent"),
+        Failed => ("failed", "Failed"),
+        Blocked => ("blocked", "Blocked"),
+    }
+}
@@ -0,0 +1,20 @@
+use crate::define_enum;
+
+// shown next to every opt-in checkbox and stored in the consent ledger with each
+// opt-in, so changing the wording never rewrites what someone agreed to
+pub const SMS_CONSENT_DISCLOSURE: &str = "By checking this box you agree to receive recurring text messages about your events and organizations at the number provided. Consent is not a condition of any purchase. Msg frequency varies. Msg & data rates may apply. Reply HELP for help and STOP to cancel.";
+
+define_enum! {
+    pub enum SmsConsentAction {
+        OptIn => ("opt_in", "Opted In"),
+        OptOut => ("opt_out", "Opted Out"),
+    }
+}
+
+define_enum! {
+    pub enum SmsConsentSource {
+        Account => ("account", "Account Settings"),
+        GuestSignup => ("guest_signup", "Event RSVP"),
+        Keyword => ("keyword", "Text Message"),
+    }
+}
@@ -107,6 +107,22 @@ pub fn minio_error(error: impl std::fmt::Display) -> AppError {
     }
 }
 
+#[cfg(feature = "server")]
+pub fn mail_error(error: impl std::fmt::Display) -> AppError {
+    AppError::ExternalServiceError {
+        service: "Mail".to_string(),
+        message: error.to_string(),
+    }
+}
+
+#[cfg(feature = "server")]
+pub fn sms_error(error: impl std::fmt::Display) -> AppError {
+    AppError::ExternalServiceError {
+        service: "SMS".to_string(),
+        message: error.to_string(),
+    }
+}
+
 #[cfg(feature = "server")]
 impl From<AppError> for DioxusServerFnError {
     fn from(err: AppError) -> Self {
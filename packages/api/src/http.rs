@@ -1,5 +1,13 @@
 mod middleware;
+#[cfg(all(feature = "server", feature = "web_app"))]
+mod sms_webhook;
 mod token;
+#[cfg(all(feature = "server", feature = "web_app"))]
+mod unsubscribe;
 
 pub use middleware::*;
+#[cfg(all(feature = "server", feature = "web_app"))]
+pub use sms_webhook::*;
 pub use token::*;
+#[cfg(all(feature = "server", feature = "web_app"))]
+pub use unsubscribe::*;
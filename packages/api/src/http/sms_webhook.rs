@@ -0,0 +1,74 @@
+use crate::services::get_app_link;
+use crate::services::web_app::handle_inbound_sms;
+use crate::sms::verify_sms_webhook_signature;
+use axum::{
+    extract::Form,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+// registered as a plain route, providers post form encoded bodies rather than the
+// json server functions expect
+pub const INBOUND_SMS_WEBHOOK_PATH: &str = "/api/public/sms/inbound";
+
+const SMS_SIGNATURE_HEADER: &str = "x-twilio-signature";
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// replies are returned as TwiML so the provider texts them back without another call
+fn messaging_response(reply: Option<String>) -> Response {
+    let body = match reply {
+        Some(reply) => format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Response><Message>{}</Message></Response>",
+            escape_xml(&reply)
+        ),
+        None => "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Response></Response>".to_string(),
+    };
+
+    ([(CONTENT_TYPE, "text/xml")], body).into_response()
+}
+
+pub async fn inbound_sms_webhook(
+    headers: HeaderMap,
+    Form(params): Form<Vec<(String, String)>>,
+) -> Response {
+    let signature = headers
+        .get(SMS_SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    let verified = get_app_link(INBOUND_SMS_WEBHOOK_PATH)
+        .and_then(|url| verify_sms_webhook_signature(&url, &params, signature));
+    match verified {
+        Ok(true) => {}
+        Ok(false) => return StatusCode::FORBIDDEN.into_response(),
+        Err(error) => {
+            tracing::error!("failed to verify inbound text: {}", error);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+    let (Some(from), Some(body)) = (param("From"), param("Body")) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    match handle_inbound_sms(&from, &body, param("MessageSid")).await {
+        Ok(reply) => messaging_response(reply),
+        Err(error) => {
+            tracing::error!("failed to handle inbound text from {}: {}", from, error);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
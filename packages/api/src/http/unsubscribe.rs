@@ -0,0 +1,25 @@
+use crate::services::unsubscribe_from_notifications;
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+// the List-Unsubscribe header points here rather than at the unsubscribe page, mail
+// clients post a form encoded body for one-click unsubscribes (RFC 8058) while a GET
+// of the page from a link scanner mustn't change anything
+pub const ONE_CLICK_UNSUBSCRIBE_PATH: &str = "/api/public/list-unsubscribe/{token}";
+
+pub fn one_click_unsubscribe_path(token: &str) -> String {
+    ONE_CLICK_UNSUBSCRIBE_PATH.replace("{token}", token)
+}
+
+pub async fn one_click_unsubscribe(Path(token): Path<String>) -> Response {
+    match unsubscribe_from_notifications(&token).await {
+        Ok(_) => StatusCode::OK.into_response(),
+        Err(error) => {
+            tracing::warn!("failed to unsubscribe from notifications: {}", error);
+            StatusCode::BAD_REQUEST.into_response()
+        }
+    }
+}
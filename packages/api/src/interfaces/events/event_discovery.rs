@@ -0,0 +1,66 @@
+use crate::enums::{EventLocationType, EventType};
+use crate::interfaces::EventImageVariant;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ListPublicEventsRequest {
+    pub event_type: Option<EventType>,
+    pub organization_slug: Option<String>,
+    pub location_type: Option<EventLocationType>,
+    // only shifts starting inside the range count, defaults to every upcoming shift
+    pub starts_after: Option<DateTime<Utc>>,
+    pub starts_before: Option<DateTime<Utc>>,
+    // limits results to in-person events near this ZIP code
+    pub zip_code: Option<String>,
+    pub radius_miles: Option<f64>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PublicEventOrganizationInfo {
+    pub name: String,
+    pub slug: String,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PublicEventShiftResponse {
+    pub id: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    // none when the shift has no capacity limit
+    pub spots_remaining: Option<i32>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PublicEventResponse {
+    pub id: i32,
+    pub name: String,
+    pub event_type: EventType,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub image_variants: Vec<EventImageVariant>,
+    pub location_in_person: Option<String>,
+    pub location_online: Option<String>,
+    pub location_zip_code: Option<String>,
+    pub contact_name: String,
+    pub organization: PublicEventOrganizationInfo,
+    pub shifts: Vec<PublicEventShiftResponse>,
+    // only set for searches filtered by ZIP code
+    pub distance_miles: Option<f64>,
+    // cancelled events stay reachable by id so shared links can show why
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub cancellation_reason: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PublicEventListResponse {
+    pub events: Vec<PublicEventResponse>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
@@ -1,10 +1,20 @@
 mod article;
 mod auth;
+mod email_verification;
+mod event_image;
+mod notification;
+mod notification_preference;
 mod pagination;
 mod password_reset;
+mod subscription;
 
 pub use article::*;
 pub use auth::*;
+pub use email_verification::*;
+pub use event_image::*;
+pub use notification::*;
+pub use notification_preference::*;
 pub use pagination::*;
 pub use password_reset::*;
+pub use subscription::*;
 
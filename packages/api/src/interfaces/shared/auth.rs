@@ -1,4 +1,5 @@
-use crate::enums::{MemberRole, Platform, SubscriptionType};
+use crate::enums::{Capability, MemberRole, Platform, SubscriptionType};
+use crate::interfaces::NotificationResponse;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -9,6 +10,9 @@ pub struct RegisterRequest {
     pub first_name: String,
     pub last_name: String,
     pub platform: Platform,
+    // set when registering from an invitation link
+    #[serde(default)]
+    pub invitation_token: Option<String>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -52,6 +56,7 @@ pub struct OrganizationMembershipInfo {
     pub organization: OrganizationInfo,
     pub user_role: MemberRole,
     pub permissions: HashMap<SubscriptionType, bool>,
+    pub capabilities: Vec<Capability>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -63,4 +68,6 @@ pub struct UserAccountResponse {
     pub avatar_url: Option<String>,
     pub active_organization_membership_id: Option<i32>,
     pub organization_memberships: HashMap<i32, OrganizationMembershipInfo>,
+    // the most recent unread notifications
+    pub notifications: Vec<NotificationResponse>,
 }
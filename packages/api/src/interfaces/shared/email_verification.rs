@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct VerifyEmailResponse {
+    pub email: String,
+    // organizations joined from invitations that were waiting on the address
+    pub organizations_joined: usize,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ResendEmailVerificationResponse {
+    pub success: bool,
+}
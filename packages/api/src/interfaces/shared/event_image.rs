@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use serde_json::Value as JsonValue;
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventImageVariant {
+    pub width: u32,
+    pub height: u32,
+    pub url: String,
+}
+
+#[cfg(feature = "server")]
+impl EventImageVariant {
+    // events without an uploaded image store an empty list
+    pub fn list_from_json(value: &JsonValue) -> Vec<Self> {
+        serde_json::from_value(value.clone()).unwrap_or_default()
+    }
+}
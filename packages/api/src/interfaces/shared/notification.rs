@@ -0,0 +1,66 @@
+use crate::enums::NotificationType;
+#[cfg(feature = "server")]
+use crate::models::Notification;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct ListNotificationsRequest {
+    pub unread_only: Option<bool>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+// notifications can only be sent to members of the sender's active organization
+#[derive(Deserialize, Serialize)]
+pub struct CreateNotificationRequest {
+    pub user_id: i32,
+    pub notification_type: Option<NotificationType>,
+    pub title: String,
+    pub message: String,
+    pub link: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct NotificationResponse {
+    pub id: i32,
+    pub organization_id: Option<i32>,
+    pub notification_type: NotificationType,
+    pub title: String,
+    pub message: String,
+    pub link: Option<String>,
+    pub read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<Notification> for NotificationResponse {
+    fn from(notification: Notification) -> Self {
+        Self {
+            id: notification.id,
+            organization_id: notification.organization_id,
+            notification_type: notification.get_notification_type(),
+            title: notification.title,
+            message: notification.message,
+            link: notification.link,
+            read: notification.read,
+            read_at: notification.read_at,
+            created_at: notification.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<NotificationResponse>,
+    pub total: i64,
+    pub unread_count: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct MarkAllNotificationsReadResponse {
+    pub updated: i64,
+}
@@ -0,0 +1,51 @@
+use crate::enums::{NotificationChannel, NotificationType};
+#[cfg(feature = "server")]
+use crate::models::NotificationPreference;
+use serde::{Deserialize, Serialize};
+
+// enabled None clears the setting so the account default applies again (or, for the
+// account default itself, delivery)
+#[derive(Clone, Deserialize, Serialize)]
+pub struct NotificationPreferenceSetting {
+    pub notification_type: NotificationType,
+    pub channel: NotificationChannel,
+    pub enabled: Option<bool>,
+}
+
+// organization_id None updates the account defaults, otherwise the overrides for
+// that organization
+#[derive(Deserialize, Serialize)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub organization_id: Option<i32>,
+    pub preferences: Vec<NotificationPreferenceSetting>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct NotificationPreferenceResponse {
+    pub organization_id: Option<i32>,
+    pub notification_type: NotificationType,
+    pub channel: NotificationChannel,
+    pub enabled: bool,
+}
+
+#[cfg(feature = "server")]
+impl From<NotificationPreference> for NotificationPreferenceResponse {
+    fn from(preference: NotificationPreference) -> Self {
+        Self {
+            organization_id: preference.organization_id,
+            notification_type: preference.get_notification_type(),
+            channel: preference.get_channel(),
+            enabled: preference.enabled,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct NotificationPreferencesResponse {
+    pub preferences: Vec<NotificationPreferenceResponse>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct UnsubscribeNotificationsResponse {
+    pub notification_type: NotificationType,
+}
@@ -0,0 +1,80 @@
+#[cfg(feature = "server")]
+use crate::enums::DEFAULT_SUBSCRIPTION_PLAN;
+use crate::enums::{PlanEntitlements, SubscriptionPlan};
+#[cfg(feature = "server")]
+use crate::models::OrganizationPlan;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// effective_from defaults to now, a later date schedules the change
+#[derive(Deserialize, Serialize)]
+pub struct ChangeOrganizationPlanRequest {
+    pub plan: SubscriptionPlan,
+    pub effective_from: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OrganizationPlanChangeResponse {
+    pub id: i32,
+    pub organization_id: i32,
+    pub plan: SubscriptionPlan,
+    pub effective_from: DateTime<Utc>,
+    pub changed_by: Option<i32>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<OrganizationPlan> for OrganizationPlanChangeResponse {
+    fn from(organization_plan: OrganizationPlan) -> Self {
+        Self {
+            id: organization_plan.id,
+            organization_id: organization_plan.organization_id,
+            plan: organization_plan.get_plan(),
+            effective_from: organization_plan.effective_from,
+            changed_by: organization_plan.changed_by,
+            note: organization_plan.note,
+            created_at: organization_plan.created_at,
+        }
+    }
+}
+
+// history is newest first and includes a change that is still scheduled
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OrganizationPlanResponse {
+    pub organization_id: i32,
+    pub plan: SubscriptionPlan,
+    pub entitlements: PlanEntitlements,
+    pub history: Vec<OrganizationPlanChangeResponse>,
+}
+
+#[cfg(feature = "server")]
+impl OrganizationPlanResponse {
+    // history has to be newest first, the current plan is the first change in effect
+    pub fn new(organization_id: i32, history: Vec<OrganizationPlan>) -> Self {
+        let plan = history
+            .iter()
+            .find(|organization_plan| organization_plan.is_effective())
+            .map(|organization_plan| organization_plan.get_plan())
+            .unwrap_or(DEFAULT_SUBSCRIPTION_PLAN);
+
+        Self {
+            organization_id,
+            plan,
+            entitlements: plan.entitlements(),
+            history: history
+                .into_iter()
+                .map(OrganizationPlanChangeResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PlanUsageResponse {
+    pub member_seats: i64,
+    pub emails_this_month: i64,
+    pub sms_this_month: i64,
+    pub storage_bytes: i64,
+}
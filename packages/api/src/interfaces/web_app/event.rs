@@ -0,0 +1,193 @@
+use crate::enums::{EventType, EventVisibility};
+use crate::interfaces::{EventCoHostResponse, EventImageVariant};
+#[cfg(feature = "server")]
+use crate::models::{Event, EventShift};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventShiftRequest {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateEventShiftRequest {
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub timezone: Option<String>,
+    pub capacity: Option<Option<i32>>,
+    pub notes: Option<Option<String>>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateEventRequest {
+    pub name: String,
+    pub event_type: EventType,
+    pub description: Option<String>,
+    pub attendee_message: Option<String>,
+    pub image_url: Option<String>,
+    pub location_in_person: Option<String>,
+    pub location_online: Option<String>,
+    pub location_zip_code: Option<String>,
+    pub communication_bring_a_friend: Option<bool>,
+    pub communication_other_events: Option<bool>,
+    pub communication_confirmation: Option<bool>,
+    pub communication_check_in: Option<bool>,
+    pub contact_name: String,
+    pub contact_email: Option<String>,
+    pub contact_phone: Option<String>,
+    pub invite_groups: Vec<String>,
+    pub shifts: Vec<EventShiftRequest>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateEventRequest {
+    pub name: Option<String>,
+    pub event_type: Option<EventType>,
+    pub description: Option<Option<String>>,
+    pub attendee_message: Option<Option<String>>,
+    pub image_url: Option<Option<String>>,
+    pub location_in_person: Option<Option<String>>,
+    pub location_online: Option<Option<String>>,
+    pub location_zip_code: Option<Option<String>>,
+    pub communication_bring_a_friend: Option<bool>,
+    pub communication_other_events: Option<bool>,
+    pub communication_confirmation: Option<bool>,
+    pub communication_check_in: Option<bool>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<Option<String>>,
+    pub contact_phone: Option<Option<String>>,
+    pub invite_groups: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ListEventsRequest {
+    pub event_type: Option<EventType>,
+    pub visibility: Option<EventVisibility>,
+    pub include_cancelled: Option<bool>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventShiftResponse {
+    pub id: i32,
+    pub event_id: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    pub notes: Option<String>,
+    pub series_id: Option<i32>,
+    pub series_occurrence_date: Option<NaiveDate>,
+}
+
+#[cfg(feature = "server")]
+impl From<EventShift> for EventShiftResponse {
+    fn from(shift: EventShift) -> Self {
+        Self {
+            id: shift.id,
+            event_id: shift.event_id,
+            start_time: shift.start_time,
+            end_time: shift.end_time,
+            timezone: shift.timezone,
+            capacity: shift.capacity,
+            notes: shift.notes,
+            series_id: shift.series_id,
+            series_occurrence_date: shift.series_occurrence_date,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventResponse {
+    pub id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    pub event_type: EventType,
+    pub visibility: EventVisibility,
+    pub description: Option<String>,
+    pub attendee_message: Option<String>,
+    pub image_url: Option<String>,
+    // resized copies of an uploaded image, smallest first
+    pub image_variants: Vec<EventImageVariant>,
+    pub location_in_person: Option<String>,
+    pub location_online: Option<String>,
+    pub location_zip_code: Option<String>,
+    pub communication_bring_a_friend: bool,
+    pub communication_other_events: bool,
+    pub communication_confirmation: bool,
+    pub communication_check_in: bool,
+    pub contact_name: String,
+    pub contact_email: Option<String>,
+    pub contact_phone: Option<String>,
+    pub co_hosts: Vec<EventCoHostResponse>,
+    pub invite_groups: Vec<String>,
+    pub shifts: Vec<EventShiftResponse>,
+    pub created_by: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub cancellation_reason: Option<String>,
+}
+
+#[cfg(feature = "server")]
+impl EventResponse {
+    pub fn new(event: Event, shifts: Vec<EventShift>, co_hosts: Vec<EventCoHostResponse>) -> Self {
+        let event_type = event.get_event_type();
+        let visibility = event.get_visibility();
+        let image_variants = EventImageVariant::list_from_json(&event.image_variants);
+        Self {
+            id: event.id,
+            organization_id: event.organization_id,
+            name: event.name,
+            event_type,
+            visibility,
+            description: event.description,
+            attendee_message: event.attendee_message,
+            image_url: event.image_url,
+            image_variants,
+            location_in_person: event.location_in_person,
+            location_online: event.location_online,
+            location_zip_code: event.location_zip_code,
+            communication_bring_a_friend: event.communication_bring_a_friend,
+            communication_other_events: event.communication_other_events,
+            communication_confirmation: event.communication_confirmation,
+            communication_check_in: event.communication_check_in,
+            contact_name: event.contact_name,
+            contact_email: event.contact_email,
+            contact_phone: event.contact_phone,
+            co_hosts,
+            invite_groups: event.invite_groups.into_iter().flatten().collect(),
+            shifts: shifts.into_iter().map(EventShiftResponse::from).collect(),
+            created_by: event.created_by,
+            created_at: event.created_at,
+            updated_at: event.updated_at,
+            cancelled_at: event.cancelled_at,
+            cancellation_reason: event.cancellation_reason,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CancelEventResponse {
+    pub event: EventResponse,
+    // attendee emails and texts waiting to be sent
+    pub messages_queued: usize,
+    // stored as failed because the organization's monthly quota ran out
+    pub messages_over_quota: usize,
+    // not sent because the attendee turned off cancellation emails or texts
+    pub messages_opted_out: usize,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventListResponse {
+    pub events: Vec<EventResponse>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
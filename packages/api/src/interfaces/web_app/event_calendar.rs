@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CalendarFeedResponse {
+    pub token: String,
+    // relative url calendar apps can subscribe to
+    pub path: String,
+}
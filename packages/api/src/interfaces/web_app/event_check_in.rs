@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CheckInCodeResponse {
+    pub signup_id: i32,
+    pub token: String,
+    // server-rendered svg markup for the token's qr code
+    pub qr_svg: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct WalkInCheckInRequest {
+    pub email: String,
+    pub notes: Option<String>,
+}
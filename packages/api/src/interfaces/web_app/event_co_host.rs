@@ -0,0 +1,71 @@
+use crate::enums::{CoHostDataSharing, CoHostStatus};
+#[cfg(feature = "server")]
+use crate::models::{Event, EventCoHost, Organization};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct InviteCoHostRequest {
+    pub organization_slug: String,
+    pub data_sharing: CoHostDataSharing,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventCoHostResponse {
+    pub id: i32,
+    pub event_id: i32,
+    pub organization_id: i32,
+    pub organization_name: String,
+    pub organization_slug: String,
+    pub status: CoHostStatus,
+    pub data_sharing: CoHostDataSharing,
+    pub created_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "server")]
+impl EventCoHostResponse {
+    pub fn new(co_host: EventCoHost, organization: Organization) -> Self {
+        let status = co_host.get_status();
+        let data_sharing = co_host.get_data_sharing();
+        Self {
+            id: co_host.id,
+            event_id: co_host.event_id,
+            organization_id: organization.id,
+            organization_name: organization.name,
+            organization_slug: organization.slug,
+            status,
+            data_sharing,
+            created_at: co_host.created_at,
+            responded_at: co_host.responded_at,
+        }
+    }
+}
+
+// a pending invitation as seen by the invited organization
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CoHostInvitationResponse {
+    pub id: i32,
+    pub event_id: i32,
+    pub event_name: String,
+    pub host_organization_name: String,
+    pub host_organization_slug: String,
+    pub data_sharing: CoHostDataSharing,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl CoHostInvitationResponse {
+    pub fn new(co_host: EventCoHost, event: Event, host: Organization) -> Self {
+        let data_sharing = co_host.get_data_sharing();
+        Self {
+            id: co_host.id,
+            event_id: event.id,
+            event_name: event.name,
+            host_organization_name: host.name,
+            host_organization_slug: host.slug,
+            data_sharing,
+            created_at: co_host.created_at,
+        }
+    }
+}
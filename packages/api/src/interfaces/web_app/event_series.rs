@@ -0,0 +1,100 @@
+use crate::enums::{RecurrenceFrequency, RecurrenceWeekday, SeriesEditScope};
+use crate::interfaces::EventShiftResponse;
+#[cfg(feature = "server")]
+use crate::models::{EventSeries, EventShift};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateEventSeriesRequest {
+    pub frequency: RecurrenceFrequency,
+    pub repeat_interval: Option<i32>,
+    pub by_weekday: Vec<RecurrenceWeekday>,
+    pub starts_on: NaiveDate,
+    pub until_date: Option<NaiveDate>,
+    pub occurrence_count: Option<i32>,
+    pub excluded_dates: Vec<NaiveDate>,
+    pub start_time_local: NaiveTime,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    pub notes: Option<String>,
+}
+
+// rule fields only apply to this_and_following and all edits, a single-shift edit
+// may only change the time, duration, timezone, capacity and notes
+#[derive(Deserialize, Serialize)]
+pub struct UpdateEventSeriesRequest {
+    pub scope: SeriesEditScope,
+    pub frequency: Option<RecurrenceFrequency>,
+    pub repeat_interval: Option<i32>,
+    pub by_weekday: Option<Vec<RecurrenceWeekday>>,
+    pub until_date: Option<Option<NaiveDate>>,
+    pub occurrence_count: Option<Option<i32>>,
+    pub excluded_dates: Option<Vec<NaiveDate>>,
+    pub start_time_local: Option<NaiveTime>,
+    pub duration_minutes: Option<i32>,
+    pub timezone: Option<String>,
+    pub capacity: Option<Option<i32>>,
+    pub notes: Option<Option<String>>,
+}
+
+impl UpdateEventSeriesRequest {
+    pub fn changes_rule(&self) -> bool {
+        self.frequency.is_some()
+            || self.repeat_interval.is_some()
+            || self.by_weekday.is_some()
+            || self.until_date.is_some()
+            || self.occurrence_count.is_some()
+            || self.excluded_dates.is_some()
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventSeriesResponse {
+    pub id: i32,
+    pub event_id: i32,
+    pub frequency: RecurrenceFrequency,
+    pub repeat_interval: i32,
+    pub by_weekday: Vec<RecurrenceWeekday>,
+    pub starts_on: NaiveDate,
+    pub until_date: Option<NaiveDate>,
+    pub occurrence_count: Option<i32>,
+    pub excluded_dates: Vec<NaiveDate>,
+    pub start_time_local: NaiveTime,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    pub notes: Option<String>,
+    pub shifts: Vec<EventShiftResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl EventSeriesResponse {
+    pub fn new(series: EventSeries, shifts: Vec<EventShift>) -> Self {
+        let frequency = series.get_frequency();
+        let by_weekday = series.get_by_weekday();
+        let excluded_dates = series.get_excluded_dates();
+        Self {
+            id: series.id,
+            event_id: series.event_id,
+            frequency,
+            repeat_interval: series.repeat_interval,
+            by_weekday,
+            starts_on: series.starts_on,
+            until_date: series.until_date,
+            occurrence_count: series.occurrence_count,
+            excluded_dates,
+            start_time_local: series.start_time_local,
+            duration_minutes: series.duration_minutes,
+            timezone: series.timezone,
+            capacity: series.capacity,
+            notes: series.notes,
+            shifts: shifts.into_iter().map(EventShiftResponse::from).collect(),
+            created_at: series.created_at,
+            updated_at: series.updated_at,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::enums::SignupStatus;
+use crate::interfaces::ShiftConflictResponse;
+#[cfg(feature = "server")]
+use crate::models::EventSignup;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventSignupResponse {
+    pub id: i32,
+    pub event_shift_id: i32,
+    // guest signups have no user until the guest registers
+    pub user_id: Option<i32>,
+    pub contact_id: Option<i32>,
+    pub status: SignupStatus,
+    pub notes: Option<String>,
+    pub signed_up_at: DateTime<Utc>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "server")]
+impl From<EventSignup> for EventSignupResponse {
+    fn from(signup: EventSignup) -> Self {
+        let status = signup.get_status();
+        Self {
+            id: signup.id,
+            event_shift_id: signup.event_shift_id,
+            user_id: signup.user_id,
+            contact_id: signup.contact_id,
+            status,
+            notes: signup.notes,
+            signed_up_at: signup.signed_up_at,
+            checked_in_at: signup.checked_in_at,
+            cancelled_at: signup.cancelled_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ShiftSignupResponse {
+    pub signup: EventSignupResponse,
+    // overlapping shifts the volunteer is already signed up for, the signup still
+    // went through unless the organization blocks conflicts
+    pub conflicts: Vec<ShiftConflictResponse>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ShiftSignupListResponse {
+    pub event_shift_id: i32,
+    pub capacity: Option<i32>,
+    pub seated_count: i64,
+    pub waitlist_count: i64,
+    // seated signups first, then the waitlist in promotion order
+    pub signups: Vec<EventSignupResponse>,
+}
@@ -0,0 +1,98 @@
+use crate::enums::EventType;
+use crate::interfaces::EventImageVariant;
+#[cfg(feature = "server")]
+use crate::models::EventTemplate;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// one shift in a saved layout, placed relative to the event's first day in its own timezone
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventTemplateShift {
+    pub day_offset: i32,
+    pub start_time_local: NaiveTime,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct DuplicateEventRequest {
+    // the first shift lands on this date, later shifts keep their spacing
+    pub start_date: NaiveDate,
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateEventTemplateRequest {
+    pub event_id: i32,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateEventFromTemplateRequest {
+    pub start_date: NaiveDate,
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct EventTemplateResponse {
+    pub id: i32,
+    pub name: String,
+    pub event_name: String,
+    pub event_type: EventType,
+    pub description: Option<String>,
+    pub attendee_message: Option<String>,
+    pub image_url: Option<String>,
+    // resized copies of the event's uploaded image, smallest first
+    pub image_variants: Vec<EventImageVariant>,
+    pub location_in_person: Option<String>,
+    pub location_online: Option<String>,
+    pub location_zip_code: Option<String>,
+    pub communication_bring_a_friend: bool,
+    pub communication_other_events: bool,
+    pub communication_confirmation: bool,
+    pub communication_check_in: bool,
+    pub contact_name: String,
+    pub contact_email: Option<String>,
+    pub contact_phone: Option<String>,
+    pub invite_groups: Vec<String>,
+    pub shifts: Vec<EventTemplateShift>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<EventTemplate> for EventTemplateResponse {
+    fn from(template: EventTemplate) -> Self {
+        let event_type = template.get_event_type();
+        let shifts = serde_json::from_value(template.shifts).unwrap_or_default();
+        let image_variants = EventImageVariant::list_from_json(&template.image_variants);
+        Self {
+            id: template.id,
+            name: template.name,
+            event_name: template.event_name,
+            event_type,
+            description: template.description,
+            attendee_message: template.attendee_message,
+            image_url: template.image_url,
+            image_variants,
+            location_in_person: template.location_in_person,
+            location_online: template.location_online,
+            location_zip_code: template.location_zip_code,
+            communication_bring_a_friend: template.communication_bring_a_friend,
+            communication_other_events: template.communication_other_events,
+            communication_confirmation: template.communication_confirmation,
+            communication_check_in: template.communication_check_in,
+            contact_name: template.contact_name,
+            contact_email: template.contact_email,
+            contact_phone: template.contact_phone,
+            invite_groups: template.invite_groups.into_iter().flatten().collect(),
+            shifts,
+            created_by: template.created_by,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        }
+    }
+}
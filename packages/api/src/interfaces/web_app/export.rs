@@ -0,0 +1,71 @@
+use crate::enums::{ExportFormat, ExportType};
+#[cfg(feature = "server")]
+use crate::models::Export;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateAttendeeExportRequest {
+    pub format: ExportFormat,
+    // either an event, a shift start date range, or both
+    pub event_id: Option<i32>,
+    pub range_start: Option<DateTime<Utc>>,
+    pub range_end: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ListExportsRequest {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ExportResponse {
+    pub id: i32,
+    pub export_type: ExportType,
+    pub format: ExportFormat,
+    pub event_id: Option<i32>,
+    pub range_start: Option<DateTime<Utc>>,
+    pub range_end: Option<DateTime<Utc>>,
+    pub filename: String,
+    pub file_size_bytes: i64,
+    pub row_count: i32,
+    pub requested_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<Export> for ExportResponse {
+    fn from(export: Export) -> Self {
+        let export_type = export.get_export_type();
+        let format = export.get_format();
+        Self {
+            id: export.id,
+            export_type,
+            format,
+            event_id: export.event_id,
+            range_start: export.range_start,
+            range_end: export.range_end,
+            filename: export.filename,
+            file_size_bytes: export.file_size_bytes,
+            row_count: export.row_count,
+            requested_by: export.requested_by,
+            created_at: export.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ExportListResponse {
+    pub exports: Vec<ExportResponse>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ExportDownloadResponse {
+    pub export: ExportResponse,
+    // presigned and short-lived, fetch a fresh one for each download
+    pub download_url: String,
+}
@@ -0,0 +1,59 @@
+use crate::enums::SignupStatus;
+#[cfg(feature = "server")]
+use crate::models::{Event, EventShift, EventSignup};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct GuestSignupRequest {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub phone_number: Option<String>,
+    pub zip_code: Option<String>,
+    pub notes: Option<String>,
+    // ticking the text message consent box, requires a phone number
+    pub sms_opt_in: Option<bool>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct GuestSignupResponse {
+    pub id: i32,
+    pub event_id: i32,
+    pub event_name: String,
+    pub event_shift_id: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub status: SignupStatus,
+    pub notes: Option<String>,
+    pub signed_up_at: DateTime<Utc>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "server")]
+impl GuestSignupResponse {
+    pub fn new(signup: EventSignup, shift: EventShift, event: Event) -> Self {
+        let status = signup.get_status();
+        Self {
+            id: signup.id,
+            event_id: event.id,
+            event_name: event.name,
+            event_shift_id: shift.id,
+            start_time: shift.start_time,
+            end_time: shift.end_time,
+            timezone: shift.timezone,
+            status,
+            notes: signup.notes,
+            signed_up_at: signup.signed_up_at,
+            cancelled_at: signup.cancelled_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct GuestSignupListResponse {
+    pub first_name: String,
+    pub last_name: String,
+    pub signups: Vec<GuestSignupResponse>,
+}
@@ -1,6 +1,10 @@
-use crate::enums::OrganizationType;
+use crate::enums::{
+    Capability, InvitationStatus, MemberRole, OrganizationType, OwnershipTransferStatus, ShiftConflictPolicy,
+};
 #[cfg(feature = "server")]
-use crate::models::Organization;
+use crate::models::{
+    Invitation, Organization, OrganizationRole, OwnershipTransfer, RetiredOrganizationSlug,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +31,7 @@ pub struct UpdateOrganizationRequest {
     pub zip_code: Option<Option<String>>,
     pub country: Option<Option<String>>,
     pub timezone: Option<String>,
+    pub shift_conflict_policy: Option<ShiftConflictPolicy>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -47,6 +52,9 @@ pub struct OrganizationResponse {
     pub zip_code: Option<String>,
     pub country: Option<String>,
     pub timezone: String,
+    pub shift_conflict_policy: ShiftConflictPolicy,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub purge_scheduled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -55,6 +63,7 @@ pub struct OrganizationResponse {
 impl From<Organization> for OrganizationResponse {
     fn from(organization: Organization) -> Self {
         let organization_type = organization.get_organization_type();
+        let shift_conflict_policy = organization.get_shift_conflict_policy();
         Self {
             id: organization.id,
             name: organization.name,
@@ -72,6 +81,9 @@ impl From<Organization> for OrganizationResponse {
             zip_code: organization.zip_code,
             country: organization.country,
             timezone: organization.timezone,
+            shift_conflict_policy,
+            archived_at: organization.archived_at,
+            purge_scheduled_at: organization.purge_scheduled_at,
             created_at: organization.created_at,
             updated_at: organization.updated_at,
         }
@@ -80,9 +92,11 @@ impl From<Organization> for OrganizationResponse {
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct OrganizationMemberResponse {
+    pub id: i32,
     pub user_id: i32,
     pub organization_id: i32,
     pub role: String,
+    pub custom_role_id: Option<i32>,
     pub email: String,
     pub first_name: String,
     pub last_name: String,
@@ -101,3 +115,176 @@ pub struct OrganizationMemberListResponse {
     pub page: i64,
     pub per_page: i64,
 }
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct InvitationResponse {
+    pub id: i32,
+    pub organization_id: i32,
+    pub email: String,
+    pub role: MemberRole,
+    pub status: InvitationStatus,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+// a pending invitation past its expiry is reported as expired
+#[cfg(feature = "server")]
+fn get_effective_status(invitation: &Invitation) -> InvitationStatus {
+    match invitation.get_status() {
+        InvitationStatus::Pending if invitation.is_expired() => InvitationStatus::Expired,
+        status => status,
+    }
+}
+
+#[cfg(feature = "server")]
+impl From<Invitation> for InvitationResponse {
+    fn from(invitation: Invitation) -> Self {
+        let role = invitation.get_role();
+        let status = get_effective_status(&invitation);
+        Self {
+            id: invitation.id,
+            organization_id: invitation.organization_id,
+            email: invitation.email,
+            role,
+            status,
+            created_at: invitation.created_at,
+            expires_at: invitation.expires_at,
+            accepted_at: invitation.accepted_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct InvitationListResponse {
+    pub invitations: Vec<InvitationResponse>,
+}
+
+// what the invitee sees when they open the link, before signing in
+#[derive(Clone, Deserialize, Serialize)]
+pub struct InvitationDetailsResponse {
+    pub organization_name: String,
+    pub inviter_name: String,
+    pub email: String,
+    pub role: MemberRole,
+    pub status: InvitationStatus,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl InvitationDetailsResponse {
+    pub fn new(invitation: Invitation, organization_name: String, inviter_name: String) -> Self {
+        let role = invitation.get_role();
+        let status = get_effective_status(&invitation);
+        Self {
+            organization_name,
+            inviter_name,
+            email: invitation.email,
+            role,
+            status,
+            expires_at: invitation.expires_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OwnershipTransferResponse {
+    pub id: i32,
+    pub organization_id: i32,
+    pub organization_name: String,
+    pub organization_slug: String,
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+    pub status: OwnershipTransferStatus,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "server")]
+impl OwnershipTransferResponse {
+    pub fn new(transfer: OwnershipTransfer, organization: Organization) -> Self {
+        let status = transfer.get_status();
+        Self {
+            id: transfer.id,
+            organization_id: organization.id,
+            organization_name: organization.name,
+            organization_slug: organization.slug,
+            from_user_id: transfer.from_user_id,
+            to_user_id: transfer.to_user_id,
+            status,
+            created_at: transfer.created_at,
+            expires_at: transfer.expires_at,
+            responded_at: transfer.responded_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateOrganizationRoleRequest {
+    pub name: String,
+    pub capabilities: Vec<Capability>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateOrganizationRoleRequest {
+    pub name: Option<String>,
+    pub capabilities: Option<Vec<Capability>>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OrganizationRoleResponse {
+    pub id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    pub capabilities: Vec<Capability>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<OrganizationRole> for OrganizationRoleResponse {
+    fn from(role: OrganizationRole) -> Self {
+        let capabilities = role.get_capabilities();
+        Self {
+            id: role.id,
+            organization_id: role.organization_id,
+            name: role.name,
+            capabilities,
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ChangeOrganizationSlugRequest {
+    pub slug: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RetiredOrganizationSlugResponse {
+    pub slug: String,
+    pub retired_at: DateTime<Utc>,
+    pub reserved_until: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<RetiredOrganizationSlug> for RetiredOrganizationSlugResponse {
+    fn from(retired: RetiredOrganizationSlug) -> Self {
+        Self {
+            slug: retired.slug,
+            retired_at: retired.retired_at,
+            reserved_until: retired.reserved_until,
+        }
+    }
+}
+
+// redirected is set when the requested slug was retired, public pages should move
+// to the current slug
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PublicOrganizationSlugResponse {
+    pub name: String,
+    pub slug: String,
+    pub redirected: bool,
+}
@@ -0,0 +1,53 @@
+use crate::enums::SignupStatus;
+#[cfg(feature = "server")]
+use crate::models::{Event, EventShift, EventSignup};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ShiftConflictResponse {
+    pub event_shift_id: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub timezone: String,
+    pub status: SignupStatus,
+    // left out when the shift belongs to another organization
+    pub event_id: Option<i32>,
+    pub event_name: Option<String>,
+}
+
+#[cfg(feature = "server")]
+impl ShiftConflictResponse {
+    pub fn new(signup: EventSignup, shift: EventShift, event: Option<Event>) -> Self {
+        let status = signup.get_status();
+        Self {
+            event_shift_id: shift.id,
+            start_time: shift.start_time,
+            end_time: shift.end_time,
+            timezone: shift.timezone,
+            status,
+            event_id: event.as_ref().map(|event| event.id),
+            event_name: event.map(|event| event.name),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RosterShiftConflictResponse {
+    pub signup_id: i32,
+    pub user_id: Option<i32>,
+    pub contact_id: Option<i32>,
+    pub first_name: String,
+    pub last_name: String,
+    pub event_id: i32,
+    pub event_name: String,
+    pub event_shift_id: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub conflicts: Vec<ShiftConflictResponse>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RosterShiftConflictListResponse {
+    pub conflicts: Vec<RosterShiftConflictResponse>,
+}
@@ -0,0 +1,33 @@
+use crate::enums::{SmsConsentAction, SmsConsentSource};
+#[cfg(feature = "server")]
+use crate::models::SmsConsent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// the phone number is only needed to opt in, opting out applies to the number
+// already on the account
+#[derive(Deserialize, Serialize)]
+pub struct UpdateSmsConsentRequest {
+    pub phone_number: Option<String>,
+    pub opt_in: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct SmsConsentResponse {
+    pub phone_number: Option<String>,
+    pub action: Option<SmsConsentAction>,
+    pub source: Option<SmsConsentSource>,
+    pub recorded_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "server")]
+impl SmsConsentResponse {
+    pub fn new(phone_number: Option<String>, consent: Option<SmsConsent>) -> Self {
+        Self {
+            phone_number,
+            action: consent.as_ref().map(|consent| consent.get_action()),
+            source: consent.as_ref().map(|consent| consent.get_source()),
+            recorded_at: consent.map(|consent| consent.created_at),
+        }
+    }
+}
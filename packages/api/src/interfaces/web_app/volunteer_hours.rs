@@ -0,0 +1,87 @@
+#[cfg(feature = "server")]
+use crate::models::VolunteerHourAdjustment;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct VolunteerHoursLeaderboardRequest {
+    // check-ins count by shift start and adjustments by when they were made,
+    // both sides are optional for all-time totals
+    pub range_start: Option<DateTime<Utc>>,
+    pub range_end: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct VolunteerHoursResponse {
+    pub user_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub shift_count: i64,
+    pub checked_in_minutes: i64,
+    pub adjustment_minutes: i64,
+    pub total_minutes: i64,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct VolunteerHoursLeaderboardResponse {
+    pub volunteers: Vec<VolunteerHoursResponse>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct OrganizationVolunteerHoursResponse {
+    pub organization_id: i32,
+    pub organization_name: String,
+    pub organization_slug: String,
+    pub shift_count: i64,
+    pub checked_in_minutes: i64,
+    pub adjustment_minutes: i64,
+    pub total_minutes: i64,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct UserVolunteerHoursResponse {
+    pub organizations: Vec<OrganizationVolunteerHoursResponse>,
+    pub total_minutes: i64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateVolunteerHourAdjustmentRequest {
+    pub user_id: i32,
+    // set when correcting a specific shift
+    pub event_signup_id: Option<i32>,
+    // negative to remove time
+    pub minutes: i32,
+    pub reason: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct VolunteerHourAdjustmentResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub event_signup_id: Option<i32>,
+    pub minutes: i32,
+    pub reason: String,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl From<VolunteerHourAdjustment> for VolunteerHourAdjustmentResponse {
+    fn from(adjustment: VolunteerHourAdjustment) -> Self {
+        Self {
+            id: adjustment.id,
+            user_id: adjustment.user_id,
+            event_signup_id: adjustment.event_signup_id,
+            minutes: adjustment.minutes,
+            reason: adjustment.reason,
+            created_by: adjustment.created_by,
+            created_at: adjustment.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct VolunteerHourAdjustmentListResponse {
+    pub adjustments: Vec<VolunteerHourAdjustmentResponse>,
+}
@@ -3,6 +3,8 @@ pub mod error;
 pub mod http;
 pub mod interfaces;
 #[cfg(feature = "server")]
+pub mod mailer;
+#[cfg(feature = "server")]
 pub mod minio;
 #[cfg(feature = "server")]
 pub mod models;
@@ -15,16 +17,28 @@ pub mod redis;
 pub mod schema;
 #[cfg(feature = "server")]
 pub mod services;
+#[cfg(feature = "server")]
+pub mod signing;
+#[cfg(feature = "server")]
+pub mod sms;
 pub mod state;
 
 #[cfg(feature = "server")]
 use crate::error::AppError;
 #[cfg(feature = "server")]
+use crate::mailer::{initialize_mailer, is_mailer_initialized};
+#[cfg(feature = "server")]
 use crate::minio::{initialize_minio_client, is_minio_initialized};
 #[cfg(feature = "server")]
 use crate::postgres::{initialize_postgres_pool, is_postgres_initialized};
 #[cfg(feature = "server")]
 use crate::redis::{initialize_redis_pool, is_redis_initialized};
+#[cfg(all(feature = "server", feature = "events"))]
+use crate::services::events::{initialize_zip_centroids, is_zip_centroids_initialized};
+#[cfg(feature = "server")]
+use crate::signing::{initialize_token_signing, is_token_signing_initialized};
+#[cfg(feature = "server")]
+use crate::sms::{initialize_sms, is_sms_initialized};
 
 #[cfg(feature = "server")]
 pub fn initialize_services() -> Result<(), AppError> {
@@ -37,5 +51,18 @@ pub fn initialize_services() -> Result<(), AppError> {
     if !is_minio_initialized() {
         initialize_minio_client()?;
     }
+    if !is_token_signing_initialized() {
+        initialize_token_signing()?;
+    }
+    if !is_mailer_initialized() {
+        initialize_mailer()?;
+    }
+    if !is_sms_initialized() {
+        initialize_sms()?;
+    }
+    #[cfg(feature = "events")]
+    if !is_zip_centroids_initialized() {
+        initialize_zip_centroids()?;
+    }
     Ok(())
 }
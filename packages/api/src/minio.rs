@@ -9,6 +9,11 @@ use std::{env, sync::OnceLock, time::Duration};
 
 const MINIO_MEDIA_URL_EXPIRY_SECONDS: u64 = 3600;
 const MINIO_MEDIA_BUCKET: &str = "media";
+// exports contain volunteer contact details so download links stay short-lived
+const MINIO_EXPORT_URL_EXPIRY_SECONDS: u64 = 900;
+const MINIO_EXPORTS_BUCKET: &str = "exports";
+// the events bucket is publicly readable so image urls never expire
+const MINIO_EVENTS_BUCKET: &str = "events";
 
 static MINIO_CLIENT: OnceLock<Client> = OnceLock::new();
 static MINIO_ENDPOINT_URL: OnceLock<String> = OnceLock::new();
@@ -115,6 +120,38 @@ pub async fn minio_delete_object(bucket: &str, key: &str) -> Result<(), AppError
     Ok(())
 }
 
+pub async fn minio_delete_prefix(bucket: &str, prefix: &str) -> Result<(), AppError> {
+    let client = get_minio_client()?;
+
+    let mut continuation_token: Option<String> = None;
+
+    loop {
+        let listing = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(prefix)
+            .set_continuation_token(continuation_token.take())
+            .send()
+            .await
+            .map_err(minio_error)?;
+
+        for object in listing.contents() {
+            if let Some(key) = object.key() {
+                minio_delete_object(bucket, key).await?;
+            }
+        }
+
+        match listing.next_continuation_token() {
+            Some(token) if listing.is_truncated() == Some(true) => {
+                continuation_token = Some(token.to_string());
+            }
+            _ => break,
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn get_minio_presigned_url(
     bucket: &str,
     key: &str,
@@ -182,3 +219,45 @@ pub async fn get_minio_media_url(key: &str) -> Result<String, AppError> {
     )
     .await
 }
+
+pub async fn minio_upload_export(
+    key: &str,
+    data: Vec<u8>,
+    content_type: &str,
+) -> Result<String, AppError> {
+    minio_upload_object(MINIO_EXPORTS_BUCKET, key, data, content_type).await
+}
+
+pub async fn minio_delete_export(key: &str) -> Result<(), AppError> {
+    minio_delete_object(MINIO_EXPORTS_BUCKET, key).await
+}
+
+pub async fn get_minio_export_url(key: &str) -> Result<String, AppError> {
+    get_minio_presigned_url(
+        MINIO_EXPORTS_BUCKET,
+        key,
+        Duration::from_secs(MINIO_EXPORT_URL_EXPIRY_SECONDS),
+    )
+    .await
+}
+
+pub async fn minio_upload_event_image(
+    key: &str,
+    data: Vec<u8>,
+    content_type: &str,
+) -> Result<String, AppError> {
+    minio_upload_object(MINIO_EVENTS_BUCKET, key, data, content_type).await
+}
+
+pub async fn minio_delete_event_images(prefix: &str) -> Result<(), AppError> {
+    minio_delete_prefix(MINIO_EVENTS_BUCKET, prefix).await
+}
+
+pub fn get_minio_event_image_url(key: &str) -> Result<String, AppError> {
+    Ok(format!(
+        "{}/{}/{}",
+        get_minio_public_url()?.trim_end_matches('/'),
+        MINIO_EVENTS_BUCKET,
+        key
+    ))
+}
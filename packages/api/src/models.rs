@@ -2,26 +2,52 @@ mod article;
 mod article_category;
 mod article_revision;
 mod article_tag;
+mod audit_log;
+mod calendar_feed_token;
+mod contact;
 mod event;
+mod event_template;
+mod export;
 mod invitation;
 mod media_asset;
 mod notification;
+mod notification_preference;
 mod organization;
 mod organization_member;
+mod organization_plan;
+mod organization_role;
+mod outbound_message;
+mod ownership_transfer;
 mod password_reset_token;
+mod retired_organization_slug;
 mod session;
+mod sms_consent;
 mod user;
+mod volunteer_hour_adjustment;
 
 pub use article::*;
 pub use article_category::*;
 pub use article_revision::*;
 pub use article_tag::*;
+pub use audit_log::*;
+pub use calendar_feed_token::*;
+pub use contact::*;
 pub use event::*;
+pub use event_template::*;
+pub use export::*;
 pub use invitation::*;
 pub use media_asset::*;
 pub use notification::*;
+pub use notification_preference::*;
 pub use organization::*;
 pub use organization_member::*;
+pub use organization_plan::*;
+pub use organization_role::*;
+pub use outbound_message::*;
+pub use ownership_transfer::*;
 pub use password_reset_token::*;
+pub use retired_organization_slug::*;
 pub use session::*;
+pub use sms_consent::*;
 pub use user::*;
+pub use volunteer_hour_adjustment::*;
@@ -0,0 +1,53 @@
+use crate::enums::AuditAction;
+use crate::schema::audit_logs;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+use serde_json::Value as JsonValue;
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = audit_logs)]
+#[diesel(check_for_backend(Postgres))]
+pub struct AuditLog {
+    pub id: i32,
+    pub organization_id: Option<i32>,
+    pub actor_user_id: Option<i32>,
+    pub action: String,
+    pub details: JsonValue,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLog {
+    pub fn get_action(&self) -> Option<AuditAction> {
+        AuditAction::from_str(&self.action)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = audit_logs)]
+pub struct NewAuditLog {
+    pub organization_id: Option<i32>,
+    pub actor_user_id: Option<i32>,
+    pub action: String,
+    pub details: JsonValue,
+}
+
+impl NewAuditLog {
+    pub fn new(action: AuditAction, details: JsonValue) -> Self {
+        Self {
+            organization_id: None,
+            actor_user_id: None,
+            action: action.as_str().to_string(),
+            details,
+        }
+    }
+
+    pub fn set_organization(mut self, organization_id: i32) -> Self {
+        self.organization_id = Some(organization_id);
+        self
+    }
+
+    pub fn set_actor(mut self, actor_user_id: i32) -> Self {
+        self.actor_user_id = Some(actor_user_id);
+        self
+    }
+}
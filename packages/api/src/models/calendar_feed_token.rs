@@ -0,0 +1,30 @@
+use crate::schema::calendar_feed_tokens;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+use uuid::Uuid;
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = calendar_feed_tokens)]
+#[diesel(check_for_backend(Postgres))]
+pub struct CalendarFeedToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub token: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = calendar_feed_tokens)]
+pub struct NewCalendarFeedToken {
+    pub user_id: i32,
+    pub token: Uuid,
+}
+
+impl NewCalendarFeedToken {
+    pub fn new(user_id: i32) -> Self {
+        Self {
+            user_id,
+            token: Uuid::new_v4(),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::schema::contacts;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = contacts)]
+#[diesel(check_for_backend(Postgres))]
+pub struct Contact {
+    pub id: i32,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub phone_number: Option<String>,
+    pub zip_code: Option<String>,
+    pub user_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = contacts)]
+pub struct NewContact {
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub phone_number: Option<String>,
+    pub zip_code: Option<String>,
+}
+
+impl NewContact {
+    pub fn new(email: String, first_name: String, last_name: String) -> Self {
+        Self {
+            email,
+            first_name,
+            last_name,
+            phone_number: None,
+            zip_code: None,
+        }
+    }
+
+    pub fn set_phone_number(mut self, phone_number: String) -> Self {
+        self.phone_number = Some(phone_number);
+        self
+    }
+
+    pub fn set_zip_code(mut self, zip_code: String) -> Self {
+        self.zip_code = Some(zip_code);
+        self
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = contacts)]
+pub struct ContactUpdate {
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone_number: Option<Option<String>>,
+    pub zip_code: Option<Option<String>>,
+    pub user_id: Option<Option<i32>>,
+}
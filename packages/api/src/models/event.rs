@@ -1,7 +1,11 @@
-use crate::enums::{EventType, EventVisibility, SignupStatus};
-use crate::schema::{event_shifts, event_signups, events};
-use chrono::{DateTime, Utc};
+use crate::enums::{
+    CoHostDataSharing, CoHostStatus, EventType, EventVisibility, RecurrenceFrequency,
+    RecurrenceWeekday, SignupStatus,
+};
+use crate::schema::{event_co_hosts, event_series, event_shifts, event_signups, events};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use diesel::{pg::Pg as Postgres, prelude::*};
+use serde_json::Value as JsonValue;
 
 #[derive(Identifiable, Queryable, Selectable)]
 #[diesel(table_name = events)]
@@ -24,11 +28,18 @@ pub struct Event {
     pub contact_name: String,
     pub contact_email: Option<String>,
     pub contact_phone: Option<String>,
-    pub co_hosts: Vec<Option<String>>,
     pub invite_groups: Vec<Option<String>>,
     pub created_by: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub location_zip_code: Option<String>,
+    pub cancellation_reason: Option<String>,
+    pub image_storage_prefix: Option<String>,
+    pub image_variants: JsonValue,
+    pub image_storage_bytes: i64,
+    // bumped by a trigger on every update
+    pub revision: i32,
 }
 
 impl Event {
@@ -39,6 +50,14 @@ impl Event {
     pub fn get_visibility(&self) -> EventVisibility {
         EventVisibility::from_str(&self.visibility).unwrap_or(EventVisibility::Draft)
     }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled_at.is_some()
+    }
+
+    pub fn is_published(&self) -> bool {
+        self.get_visibility() != EventVisibility::Draft
+    }
 }
 
 #[derive(Insertable)]
@@ -51,8 +70,10 @@ pub struct NewEvent {
     pub description: Option<String>,
     pub attendee_message: Option<String>,
     pub image_url: Option<String>,
+    pub image_variants: JsonValue,
     pub location_in_person: Option<String>,
     pub location_online: Option<String>,
+    pub location_zip_code: Option<String>,
     pub communication_bring_a_friend: bool,
     pub communication_other_events: bool,
     pub communication_confirmation: bool,
@@ -60,7 +81,6 @@ pub struct NewEvent {
     pub contact_name: String,
     pub contact_email: Option<String>,
     pub contact_phone: Option<String>,
-    pub co_hosts: Vec<Option<String>>,
     pub invite_groups: Vec<Option<String>>,
     pub created_by: i32,
 }
@@ -81,8 +101,10 @@ impl NewEvent {
             description: None,
             attendee_message: None,
             image_url: None,
+            image_variants: JsonValue::Array(vec![]),
             location_in_person: None,
             location_online: None,
+            location_zip_code: None,
             communication_bring_a_friend: false,
             communication_other_events: false,
             communication_confirmation: true,
@@ -90,11 +112,55 @@ impl NewEvent {
             contact_name,
             contact_email: None,
             contact_phone: None,
-            co_hosts: vec![],
             invite_groups: vec![],
             created_by,
         }
     }
+
+    pub fn set_description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn set_attendee_message(mut self, attendee_message: String) -> Self {
+        self.attendee_message = Some(attendee_message);
+        self
+    }
+
+    pub fn set_image_url(mut self, image_url: String) -> Self {
+        self.image_url = Some(image_url);
+        self
+    }
+
+    pub fn set_location_in_person(mut self, location_in_person: String) -> Self {
+        self.location_in_person = Some(location_in_person);
+        self
+    }
+
+    pub fn set_location_online(mut self, location_online: String) -> Self {
+        self.location_online = Some(location_online);
+        self
+    }
+
+    pub fn set_location_zip_code(mut self, location_zip_code: String) -> Self {
+        self.location_zip_code = Some(location_zip_code);
+        self
+    }
+
+    pub fn set_contact_email(mut self, contact_email: String) -> Self {
+        self.contact_email = Some(contact_email);
+        self
+    }
+
+    pub fn set_contact_phone(mut self, contact_phone: String) -> Self {
+        self.contact_phone = Some(contact_phone);
+        self
+    }
+
+    pub fn set_invite_groups(mut self, invite_groups: Vec<String>) -> Self {
+        self.invite_groups = invite_groups.into_iter().map(Some).collect();
+        self
+    }
 }
 
 #[derive(AsChangeset, Default)]
@@ -108,6 +174,7 @@ pub struct EventUpdate {
     pub image_url: Option<Option<String>>,
     pub location_in_person: Option<Option<String>>,
     pub location_online: Option<Option<String>>,
+    pub location_zip_code: Option<Option<String>>,
     pub communication_bring_a_friend: Option<bool>,
     pub communication_other_events: Option<bool>,
     pub communication_confirmation: Option<bool>,
@@ -115,8 +182,12 @@ pub struct EventUpdate {
     pub contact_name: Option<String>,
     pub contact_email: Option<Option<String>>,
     pub contact_phone: Option<Option<String>>,
-    pub co_hosts: Option<Vec<Option<String>>>,
     pub invite_groups: Option<Vec<Option<String>>>,
+    pub cancelled_at: Option<Option<DateTime<Utc>>>,
+    pub cancellation_reason: Option<Option<String>>,
+    pub image_storage_prefix: Option<Option<String>>,
+    pub image_variants: Option<JsonValue>,
+    pub image_storage_bytes: Option<i64>,
 }
 
 #[derive(Identifiable, Queryable, Selectable)]
@@ -132,6 +203,10 @@ pub struct EventShift {
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub series_id: Option<i32>,
+    pub series_occurrence_date: Option<NaiveDate>,
+    // bumped by a trigger on every update
+    pub revision: i32,
 }
 
 #[derive(Insertable)]
@@ -143,6 +218,8 @@ pub struct NewEventShift {
     pub timezone: String,
     pub capacity: Option<i32>,
     pub notes: Option<String>,
+    pub series_id: Option<i32>,
+    pub series_occurrence_date: Option<NaiveDate>,
 }
 
 impl NewEventShift {
@@ -166,9 +243,17 @@ impl NewEventShift {
             timezone,
             capacity: None,
             notes: None,
+            series_id: None,
+            series_occurrence_date: None,
         })
     }
 
+    pub fn set_series(mut self, series_id: i32, occurrence_date: NaiveDate) -> Self {
+        self.series_id = Some(series_id);
+        self.series_occurrence_date = Some(occurrence_date);
+        self
+    }
+
     pub fn set_capacity(mut self, capacity: i32) -> Self {
         self.capacity = Some(capacity);
         self
@@ -188,6 +273,148 @@ pub struct EventShiftUpdate {
     pub timezone: Option<String>,
     pub capacity: Option<Option<i32>>,
     pub notes: Option<Option<String>>,
+    pub series_id: Option<Option<i32>>,
+    pub series_occurrence_date: Option<Option<NaiveDate>>,
+}
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = event_series)]
+#[diesel(check_for_backend(Postgres))]
+pub struct EventSeries {
+    pub id: i32,
+    pub event_id: i32,
+    pub frequency: String,
+    pub repeat_interval: i32,
+    pub by_weekday: Vec<Option<String>>,
+    pub starts_on: NaiveDate,
+    pub until_date: Option<NaiveDate>,
+    pub occurrence_count: Option<i32>,
+    pub excluded_dates: Vec<Option<NaiveDate>>,
+    pub start_time_local: NaiveTime,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EventSeries {
+    pub fn get_frequency(&self) -> RecurrenceFrequency {
+        RecurrenceFrequency::from_str(&self.frequency).unwrap_or(RecurrenceFrequency::Weekly)
+    }
+
+    pub fn get_by_weekday(&self) -> Vec<RecurrenceWeekday> {
+        self.by_weekday
+            .iter()
+            .flatten()
+            .filter_map(|weekday| RecurrenceWeekday::from_str(weekday))
+            .collect()
+    }
+
+    pub fn get_excluded_dates(&self) -> Vec<NaiveDate> {
+        self.excluded_dates.iter().flatten().copied().collect()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = event_series)]
+pub struct NewEventSeries {
+    pub event_id: i32,
+    pub frequency: String,
+    pub repeat_interval: i32,
+    pub by_weekday: Vec<Option<String>>,
+    pub starts_on: NaiveDate,
+    pub until_date: Option<NaiveDate>,
+    pub occurrence_count: Option<i32>,
+    pub excluded_dates: Vec<Option<NaiveDate>>,
+    pub start_time_local: NaiveTime,
+    pub duration_minutes: i32,
+    pub timezone: String,
+    pub capacity: Option<i32>,
+    pub notes: Option<String>,
+}
+
+impl NewEventSeries {
+    pub fn new(
+        event_id: i32,
+        frequency: RecurrenceFrequency,
+        starts_on: NaiveDate,
+        start_time_local: NaiveTime,
+        duration_minutes: i32,
+        timezone: String,
+    ) -> Self {
+        Self {
+            event_id,
+            frequency: frequency.as_str().to_string(),
+            repeat_interval: 1,
+            by_weekday: vec![],
+            starts_on,
+            until_date: None,
+            occurrence_count: None,
+            excluded_dates: vec![],
+            start_time_local,
+            duration_minutes,
+            timezone,
+            capacity: None,
+            notes: None,
+        }
+    }
+
+    pub fn set_repeat_interval(mut self, repeat_interval: i32) -> Self {
+        self.repeat_interval = repeat_interval;
+        self
+    }
+
+    pub fn set_by_weekday(mut self, by_weekday: Vec<RecurrenceWeekday>) -> Self {
+        self.by_weekday = by_weekday
+            .into_iter()
+            .map(|weekday| Some(weekday.as_str().to_string()))
+            .collect();
+        self
+    }
+
+    pub fn set_until_date(mut self, until_date: NaiveDate) -> Self {
+        self.until_date = Some(until_date);
+        self
+    }
+
+    pub fn set_occurrence_count(mut self, occurrence_count: i32) -> Self {
+        self.occurrence_count = Some(occurrence_count);
+        self
+    }
+
+    pub fn set_excluded_dates(mut self, excluded_dates: Vec<NaiveDate>) -> Self {
+        self.excluded_dates = excluded_dates.into_iter().map(Some).collect();
+        self
+    }
+
+    pub fn set_capacity(mut self, capacity: i32) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    pub fn set_notes(mut self, notes: String) -> Self {
+        self.notes = Some(notes);
+        self
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = event_series)]
+pub struct EventSeriesUpdate {
+    pub frequency: Option<String>,
+    pub repeat_interval: Option<i32>,
+    pub by_weekday: Option<Vec<Option<String>>>,
+    pub starts_on: Option<NaiveDate>,
+    pub until_date: Option<Option<NaiveDate>>,
+    pub occurrence_count: Option<Option<i32>>,
+    pub excluded_dates: Option<Vec<Option<NaiveDate>>>,
+    pub start_time_local: Option<NaiveTime>,
+    pub duration_minutes: Option<i32>,
+    pub timezone: Option<String>,
+    pub capacity: Option<Option<i32>>,
+    pub notes: Option<Option<String>>,
 }
 
 #[derive(Identifiable, Queryable, Selectable)]
@@ -196,12 +423,13 @@ pub struct EventShiftUpdate {
 pub struct EventSignup {
     pub id: i32,
     pub event_shift_id: i32,
-    pub user_id: i32,
+    pub user_id: Option<i32>,
     pub status: String,
     pub notes: Option<String>,
     pub signed_up_at: DateTime<Utc>,
     pub checked_in_at: Option<DateTime<Utc>>,
     pub cancelled_at: Option<DateTime<Utc>>,
+    pub contact_id: Option<i32>,
 }
 
 impl EventSignup {
@@ -214,25 +442,51 @@ impl EventSignup {
 #[diesel(table_name = event_signups)]
 pub struct NewEventSignup {
     pub event_shift_id: i32,
-    pub user_id: i32,
+    pub user_id: Option<i32>,
+    pub contact_id: Option<i32>,
     pub status: String,
     pub notes: Option<String>,
+    pub checked_in_at: Option<DateTime<Utc>>,
 }
 
 impl NewEventSignup {
     pub fn new(event_shift_id: i32, user_id: i32) -> Self {
         Self {
             event_shift_id,
-            user_id,
+            user_id: Some(user_id),
+            contact_id: None,
             status: SignupStatus::SignedUp.as_str().to_string(),
             notes: None,
+            checked_in_at: None,
         }
     }
 
+    // guest signups belong to a contact until that person registers
+    pub fn new_for_contact(event_shift_id: i32, contact_id: i32) -> Self {
+        Self {
+            event_shift_id,
+            user_id: None,
+            contact_id: Some(contact_id),
+            status: SignupStatus::SignedUp.as_str().to_string(),
+            notes: None,
+            checked_in_at: None,
+        }
+    }
+
+    pub fn set_status(mut self, status: SignupStatus) -> Self {
+        self.status = status.as_str().to_string();
+        self
+    }
+
     pub fn set_notes(mut self, notes: String) -> Self {
         self.notes = Some(notes);
         self
     }
+
+    pub fn set_checked_in_at(mut self, checked_in_at: DateTime<Utc>) -> Self {
+        self.checked_in_at = Some(checked_in_at);
+        self
+    }
 }
 
 #[derive(AsChangeset, Default)]
@@ -240,6 +494,74 @@ impl NewEventSignup {
 pub struct EventSignupUpdate {
     pub status: Option<String>,
     pub notes: Option<Option<String>>,
+    pub signed_up_at: Option<DateTime<Utc>>,
     pub checked_in_at: Option<Option<DateTime<Utc>>>,
     pub cancelled_at: Option<Option<DateTime<Utc>>>,
 }
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = event_co_hosts)]
+#[diesel(check_for_backend(Postgres))]
+pub struct EventCoHost {
+    pub id: i32,
+    pub event_id: i32,
+    pub organization_id: i32,
+    pub status: String,
+    pub data_sharing: String,
+    pub invited_by: Option<i32>,
+    pub responded_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+impl EventCoHost {
+    pub fn get_status(&self) -> CoHostStatus {
+        CoHostStatus::from_str(&self.status).unwrap_or(CoHostStatus::Pending)
+    }
+
+    pub fn get_data_sharing(&self) -> CoHostDataSharing {
+        CoHostDataSharing::from_str(&self.data_sharing).unwrap_or(CoHostDataSharing::Summary)
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.get_status() == CoHostStatus::Accepted
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = event_co_hosts)]
+pub struct NewEventCoHost {
+    pub event_id: i32,
+    pub organization_id: i32,
+    pub status: String,
+    pub data_sharing: String,
+    pub invited_by: Option<i32>,
+}
+
+impl NewEventCoHost {
+    pub fn new(event_id: i32, organization_id: i32, data_sharing: CoHostDataSharing) -> Self {
+        Self {
+            event_id,
+            organization_id,
+            status: CoHostStatus::Pending.as_str().to_string(),
+            data_sharing: data_sharing.as_str().to_string(),
+            invited_by: None,
+        }
+    }
+
+    pub fn set_invited_by(mut self, invited_by: i32) -> Self {
+        self.invited_by = Some(invited_by);
+        self
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = event_co_hosts)]
+pub struct EventCoHostUpdate {
+    pub status: Option<String>,
+    pub data_sharing: Option<String>,
+    pub invited_by: Option<Option<i32>>,
+    pub responded_by: Option<Option<i32>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub responded_at: Option<Option<DateTime<Utc>>>,
+}
@@ -0,0 +1,135 @@
+use crate::enums::EventType;
+use crate::models::{Event, NewEvent};
+use crate::schema::event_templates;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+use serde_json::Value as JsonValue;
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = event_templates)]
+#[diesel(check_for_backend(Postgres))]
+pub struct EventTemplate {
+    pub id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    pub event_name: String,
+    pub event_type: String,
+    pub description: Option<String>,
+    pub attendee_message: Option<String>,
+    pub image_url: Option<String>,
+    pub location_in_person: Option<String>,
+    pub location_online: Option<String>,
+    pub location_zip_code: Option<String>,
+    pub communication_bring_a_friend: bool,
+    pub communication_other_events: bool,
+    pub communication_confirmation: bool,
+    pub communication_check_in: bool,
+    pub contact_name: String,
+    pub contact_email: Option<String>,
+    pub contact_phone: Option<String>,
+    pub invite_groups: Vec<Option<String>>,
+    pub shifts: JsonValue,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub image_variants: JsonValue,
+}
+
+impl EventTemplate {
+    pub fn get_event_type(&self) -> EventType {
+        EventType::from_str(&self.event_type).unwrap_or(EventType::Other)
+    }
+
+    // new events start as drafts like any other event
+    pub fn to_new_event(&self, created_by: i32) -> NewEvent {
+        let mut new_event = NewEvent::new(
+            self.organization_id,
+            self.event_name.clone(),
+            self.get_event_type(),
+            self.contact_name.clone(),
+            created_by,
+        );
+
+        new_event.description = self.description.clone();
+        new_event.attendee_message = self.attendee_message.clone();
+        new_event.image_url = self.image_url.clone();
+        new_event.image_variants = self.image_variants.clone();
+        new_event.location_in_person = self.location_in_person.clone();
+        new_event.location_online = self.location_online.clone();
+        new_event.location_zip_code = self.location_zip_code.clone();
+        new_event.communication_bring_a_friend = self.communication_bring_a_friend;
+        new_event.communication_other_events = self.communication_other_events;
+        new_event.communication_confirmation = self.communication_confirmation;
+        new_event.communication_check_in = self.communication_check_in;
+        new_event.contact_email = self.contact_email.clone();
+        new_event.contact_phone = self.contact_phone.clone();
+        new_event.invite_groups = self.invite_groups.clone();
+
+        new_event
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = event_templates)]
+pub struct NewEventTemplate {
+    pub organization_id: i32,
+    pub name: String,
+    pub event_name: String,
+    pub event_type: String,
+    pub description: Option<String>,
+    pub attendee_message: Option<String>,
+    pub image_url: Option<String>,
+    pub location_in_person: Option<String>,
+    pub location_online: Option<String>,
+    pub location_zip_code: Option<String>,
+    pub communication_bring_a_friend: bool,
+    pub communication_other_events: bool,
+    pub communication_confirmation: bool,
+    pub communication_check_in: bool,
+    pub contact_name: String,
+    pub contact_email: Option<String>,
+    pub contact_phone: Option<String>,
+    pub invite_groups: Vec<Option<String>>,
+    pub shifts: JsonValue,
+    pub created_by: Option<i32>,
+    pub image_variants: JsonValue,
+}
+
+impl NewEventTemplate {
+    pub fn from_event(name: String, event: &Event, shifts: JsonValue) -> Self {
+        Self {
+            organization_id: event.organization_id,
+            name,
+            event_name: event.name.clone(),
+            event_type: event.event_type.clone(),
+            description: event.description.clone(),
+            attendee_message: event.attendee_message.clone(),
+            image_url: event.image_url.clone(),
+            location_in_person: event.location_in_person.clone(),
+            location_online: event.location_online.clone(),
+            location_zip_code: event.location_zip_code.clone(),
+            communication_bring_a_friend: event.communication_bring_a_friend,
+            communication_other_events: event.communication_other_events,
+            communication_confirmation: event.communication_confirmation,
+            communication_check_in: event.communication_check_in,
+            contact_name: event.contact_name.clone(),
+            contact_email: event.contact_email.clone(),
+            contact_phone: event.contact_phone.clone(),
+            invite_groups: event.invite_groups.clone(),
+            shifts,
+            created_by: None,
+            image_variants: event.image_variants.clone(),
+        }
+    }
+
+    pub fn set_created_by(mut self, created_by: i32) -> Self {
+        self.created_by = Some(created_by);
+        self
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = event_templates)]
+pub struct EventTemplateUpdate {
+    pub name: Option<String>,
+}
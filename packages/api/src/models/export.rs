@@ -0,0 +1,98 @@
+use crate::enums::{ExportFormat, ExportType};
+use crate::schema::exports;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = exports)]
+#[diesel(check_for_backend(Postgres))]
+pub struct Export {
+    pub id: i32,
+    pub organization_id: i32,
+    pub requested_by: Option<i32>,
+    pub export_type: String,
+    pub format: String,
+    pub event_id: Option<i32>,
+    pub range_start: Option<DateTime<Utc>>,
+    pub range_end: Option<DateTime<Utc>>,
+    pub filename: String,
+    pub mime_type: String,
+    pub file_size_bytes: i64,
+    pub row_count: i32,
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Export {
+    pub fn get_export_type(&self) -> ExportType {
+        ExportType::from_str(&self.export_type).unwrap_or(ExportType::EventAttendees)
+    }
+
+    pub fn get_format(&self) -> ExportFormat {
+        ExportFormat::from_str(&self.format).unwrap_or(ExportFormat::Csv)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = exports)]
+pub struct NewExport {
+    pub organization_id: i32,
+    pub requested_by: Option<i32>,
+    pub export_type: String,
+    pub format: String,
+    pub event_id: Option<i32>,
+    pub range_start: Option<DateTime<Utc>>,
+    pub range_end: Option<DateTime<Utc>>,
+    pub filename: String,
+    pub mime_type: String,
+    pub file_size_bytes: i64,
+    pub row_count: i32,
+    pub storage_key: String,
+}
+
+impl NewExport {
+    pub fn new(
+        organization_id: i32,
+        export_type: ExportType,
+        format: ExportFormat,
+        filename: String,
+        file_size_bytes: i64,
+        row_count: i32,
+        storage_key: String,
+    ) -> Self {
+        Self {
+            organization_id,
+            requested_by: None,
+            export_type: export_type.as_str().to_string(),
+            format: format.as_str().to_string(),
+            event_id: None,
+            range_start: None,
+            range_end: None,
+            filename,
+            mime_type: format.mime_type().to_string(),
+            file_size_bytes,
+            row_count,
+            storage_key,
+        }
+    }
+
+    pub fn set_requested_by(mut self, user_id: i32) -> Self {
+        self.requested_by = Some(user_id);
+        self
+    }
+
+    pub fn set_event(mut self, event_id: i32) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+
+    pub fn set_range(
+        mut self,
+        range_start: Option<DateTime<Utc>>,
+        range_end: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.range_start = range_start;
+        self.range_end = range_end;
+        self
+    }
+}
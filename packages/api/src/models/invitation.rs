@@ -1,4 +1,4 @@
-use crate::enums::{InvitationStatus, DEFAULT_INVITATION_EXPIRY_DAYS};
+use crate::enums::{InvitationStatus, MemberRole, DEFAULT_INVITATION_EXPIRY_DAYS};
 use crate::schema::invitations;
 use chrono::{DateTime, Duration, Utc};
 use diesel::{pg::Pg as Postgres, prelude::*};
@@ -25,6 +25,14 @@ impl Invitation {
         InvitationStatus::from_str(&self.status).unwrap_or(InvitationStatus::Pending)
     }
 
+    // owner is never handed out by invitation, anything unreadable joins as a member
+    pub fn get_role(&self) -> MemberRole {
+        match MemberRole::from_str(&self.role) {
+            Some(MemberRole::Owner) | None => MemberRole::Member,
+            Some(role) => role,
+        }
+    }
+
     pub fn is_expired(&self) -> bool {
         self.expires_at < Utc::now() || self.get_status() == InvitationStatus::Expired
     }
@@ -64,5 +72,6 @@ impl NewInvitation {
 #[diesel(table_name = invitations)]
 pub struct InvitationUpdate {
     pub status: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
     pub accepted_at: Option<DateTime<Utc>>,
 }
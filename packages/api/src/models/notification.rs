@@ -3,7 +3,7 @@ use crate::schema::notifications;
 use chrono::{DateTime, Utc};
 use diesel::{pg::Pg as Postgres, prelude::*};
 
-#[derive(Identifiable, Queryable, Selectable)]
+#[derive(Clone, Identifiable, Queryable, Selectable)]
 #[diesel(table_name = notifications)]
 #[diesel(check_for_backend(Postgres))]
 pub struct Notification {
@@ -0,0 +1,66 @@
+use crate::enums::{NotificationChannel, NotificationType};
+use crate::schema::notification_preferences;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = notification_preferences)]
+#[diesel(check_for_backend(Postgres))]
+pub struct NotificationPreference {
+    pub id: i32,
+    pub user_id: i32,
+    pub organization_id: Option<i32>,
+    pub notification_type: String,
+    pub channel: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NotificationPreference {
+    pub fn get_notification_type(&self) -> NotificationType {
+        NotificationType::from_str(&self.notification_type).unwrap_or(NotificationType::Info)
+    }
+
+    pub fn get_channel(&self) -> NotificationChannel {
+        NotificationChannel::from_str(&self.channel).unwrap_or(NotificationChannel::InApp)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = notification_preferences)]
+pub struct NewNotificationPreference {
+    pub user_id: i32,
+    pub organization_id: Option<i32>,
+    pub notification_type: String,
+    pub channel: String,
+    pub enabled: bool,
+}
+
+impl NewNotificationPreference {
+    pub fn new(
+        user_id: i32,
+        notification_type: NotificationType,
+        channel: NotificationChannel,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            user_id,
+            organization_id: None,
+            notification_type: notification_type.as_str().to_string(),
+            channel: channel.as_str().to_string(),
+            enabled,
+        }
+    }
+
+    pub fn set_organization(mut self, organization_id: i32) -> Self {
+        self.organization_id = Some(organization_id);
+        self
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = notification_preferences)]
+pub struct NotificationPreferenceUpdate {
+    pub enabled: Option<bool>,
+}
@@ -1,4 +1,4 @@
-use crate::enums::{OrganizationType, SubscriptionType};
+use crate::enums::{OrganizationType, ShiftConflictPolicy};
 use crate::schema::organizations;
 use chrono::{DateTime, Utc};
 use diesel::{pg::Pg as Postgres, prelude::*};
@@ -23,27 +23,29 @@ pub struct Organization {
     pub zip_code: Option<String>,
     pub country: Option<String>,
     pub timezone: String,
-    pub subscriptions: Vec<Option<String>>,
     pub created_by: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub shift_conflict_policy: String,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub archived_by: Option<i32>,
+    pub purge_scheduled_at: Option<DateTime<Utc>>,
+    pub purged_at: Option<DateTime<Utc>>,
 }
 
 impl Organization {
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
     pub fn get_organization_type(&self) -> OrganizationType {
         OrganizationType::from_str(&self.organization_type)
             .unwrap_or(OrganizationType::Organization)
     }
 
-    pub fn get_subscriptions(&self) -> Vec<SubscriptionType> {
-        self.subscriptions
-            .iter()
-            .filter_map(|subscription| {
-                subscription
-                    .as_ref()
-                    .and_then(|string| SubscriptionType::from_str(string))
-            })
-            .collect()
+    pub fn get_shift_conflict_policy(&self) -> ShiftConflictPolicy {
+        ShiftConflictPolicy::from_str(&self.shift_conflict_policy)
+            .unwrap_or(ShiftConflictPolicy::Warn)
     }
 }
 
@@ -55,7 +57,6 @@ pub struct NewOrganization {
     pub organization_type: String,
     pub description: Option<String>,
     pub timezone: String,
-    pub subscriptions: Vec<Option<String>>,
     pub created_by: i32,
 }
 
@@ -66,7 +67,6 @@ impl NewOrganization {
         organization_type: OrganizationType,
         description: Option<String>,
         timezone: String,
-        subscriptions: Vec<SubscriptionType>,
         created_by: i32,
     ) -> Self {
         Self {
@@ -75,10 +75,6 @@ impl NewOrganization {
             organization_type: organization_type.as_str().to_string(),
             description,
             timezone,
-            subscriptions: subscriptions
-                .into_iter()
-                .map(|subscription| Some(subscription.as_str().to_string()))
-                .collect(),
             created_by,
         }
     }
@@ -88,6 +84,7 @@ impl NewOrganization {
 #[diesel(table_name = organizations)]
 pub struct OrganizationUpdate {
     pub name: Option<String>,
+    pub slug: Option<String>,
     pub description: Option<Option<String>>,
     pub avatar_url: Option<Option<String>>,
     pub website_url: Option<Option<String>>,
@@ -100,5 +97,9 @@ pub struct OrganizationUpdate {
     pub zip_code: Option<Option<String>>,
     pub country: Option<Option<String>>,
     pub timezone: Option<String>,
-    pub subscriptions: Option<Vec<Option<String>>>,
+    pub shift_conflict_policy: Option<String>,
+    pub archived_at: Option<Option<DateTime<Utc>>>,
+    pub archived_by: Option<Option<i32>>,
+    pub purge_scheduled_at: Option<Option<DateTime<Utc>>>,
+    pub purged_at: Option<Option<DateTime<Utc>>>,
 }
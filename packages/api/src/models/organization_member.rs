@@ -14,6 +14,7 @@ pub struct OrganizationMember {
     pub invited_by: Option<i32>,
     pub joined_at: DateTime<Utc>,
     pub last_active_at: Option<DateTime<Utc>>,
+    pub custom_role_id: Option<i32>,
 }
 
 impl OrganizationMember {
@@ -52,4 +53,5 @@ impl NewOrganizationMember {
 pub struct OrganizationMemberUpdate {
     pub role: Option<String>,
     pub last_active_at: Option<Option<DateTime<Utc>>>,
+    pub custom_role_id: Option<Option<i32>>,
 }
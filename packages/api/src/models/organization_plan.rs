@@ -0,0 +1,63 @@
+use crate::enums::{SubscriptionPlan, DEFAULT_SUBSCRIPTION_PLAN};
+use crate::schema::organization_plans;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = organization_plans)]
+#[diesel(check_for_backend(Postgres))]
+pub struct OrganizationPlan {
+    pub id: i32,
+    pub organization_id: i32,
+    pub plan: String,
+    pub effective_from: DateTime<Utc>,
+    pub changed_by: Option<i32>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OrganizationPlan {
+    pub fn get_plan(&self) -> SubscriptionPlan {
+        SubscriptionPlan::from_str(&self.plan).unwrap_or(DEFAULT_SUBSCRIPTION_PLAN)
+    }
+
+    pub fn is_effective(&self) -> bool {
+        self.effective_from <= Utc::now()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = organization_plans)]
+pub struct NewOrganizationPlan {
+    pub organization_id: i32,
+    pub plan: String,
+    pub effective_from: DateTime<Utc>,
+    pub changed_by: Option<i32>,
+    pub note: Option<String>,
+}
+
+impl NewOrganizationPlan {
+    pub fn new(
+        organization_id: i32,
+        plan: SubscriptionPlan,
+        effective_from: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            organization_id,
+            plan: plan.as_str().to_string(),
+            effective_from,
+            changed_by: None,
+            note: None,
+        }
+    }
+
+    pub fn set_changed_by(mut self, changed_by: i32) -> Self {
+        self.changed_by = Some(changed_by);
+        self
+    }
+
+    pub fn set_note(mut self, note: String) -> Self {
+        self.note = Some(note);
+        self
+    }
+}
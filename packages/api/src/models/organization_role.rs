@@ -0,0 +1,58 @@
+use crate::enums::Capability;
+use crate::schema::organization_roles;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = organization_roles)]
+#[diesel(check_for_backend(Postgres))]
+pub struct OrganizationRole {
+    pub id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    pub capabilities: Vec<Option<String>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrganizationRole {
+    // capabilities that are no longer recognized are dropped rather than granted
+    pub fn get_capabilities(&self) -> Vec<Capability> {
+        self.capabilities
+            .iter()
+            .filter_map(|capability| {
+                capability
+                    .as_ref()
+                    .and_then(|string| Capability::from_str(string))
+            })
+            .collect()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = organization_roles)]
+pub struct NewOrganizationRole {
+    pub organization_id: i32,
+    pub name: String,
+    pub capabilities: Vec<Option<String>>,
+}
+
+impl NewOrganizationRole {
+    pub fn new(organization_id: i32, name: String, capabilities: &[Capability]) -> Self {
+        Self {
+            organization_id,
+            name,
+            capabilities: capabilities
+                .iter()
+                .map(|capability| Some(capability.as_str().to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = organization_roles)]
+pub struct OrganizationRoleUpdate {
+    pub name: Option<String>,
+    pub capabilities: Option<Vec<Option<String>>>,
+}
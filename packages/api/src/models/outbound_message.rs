@@ -0,0 +1,119 @@
+use crate::enums::{MessageChannel, OutboundMessageStatus};
+use crate::schema::outbound_messages;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = outbound_messages)]
+#[diesel(check_for_backend(Postgres))]
+pub struct OutboundMessage {
+    pub id: i32,
+    pub channel: String,
+    pub recipient: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub status: String,
+    pub user_id: Option<i32>,
+    pub contact_id: Option<i32>,
+    pub event_id: Option<i32>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub claimed_at: Option<DateTime<Utc>>,
+    pub unsubscribe_token: Option<String>,
+    pub organization_id: Option<i32>,
+}
+
+impl OutboundMessage {
+    pub fn get_channel(&self) -> MessageChannel {
+        MessageChannel::from_str(&self.channel).unwrap_or(MessageChannel::Email)
+    }
+
+    pub fn get_status(&self) -> OutboundMessageStatus {
+        OutboundMessageStatus::from_str(&self.status).unwrap_or(OutboundMessageStatus::Pending)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = outbound_messages)]
+pub struct NewOutboundMessage {
+    pub channel: String,
+    pub recipient: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub user_id: Option<i32>,
+    pub contact_id: Option<i32>,
+    pub event_id: Option<i32>,
+    pub unsubscribe_token: Option<String>,
+    pub organization_id: Option<i32>,
+    pub status: Option<String>,
+    pub last_error: Option<String>,
+}
+
+impl NewOutboundMessage {
+    pub fn new(channel: MessageChannel, recipient: String, body: String) -> Self {
+        Self {
+            channel: channel.as_str().to_string(),
+            recipient,
+            subject: None,
+            body,
+            user_id: None,
+            contact_id: None,
+            event_id: None,
+            unsubscribe_token: None,
+            organization_id: None,
+            status: None,
+            last_error: None,
+        }
+    }
+
+    pub fn set_subject(mut self, subject: String) -> Self {
+        self.subject = Some(subject);
+        self
+    }
+
+    pub fn set_user(mut self, user_id: i32) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn set_contact(mut self, contact_id: i32) -> Self {
+        self.contact_id = Some(contact_id);
+        self
+    }
+
+    pub fn set_event(mut self, event_id: i32) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+
+    pub fn set_unsubscribe_token(mut self, unsubscribe_token: String) -> Self {
+        self.unsubscribe_token = Some(unsubscribe_token);
+        self
+    }
+
+    pub fn set_organization(mut self, organization_id: i32) -> Self {
+        self.organization_id = Some(organization_id);
+        self
+    }
+
+    // stored as failed without ever being sent
+    pub fn set_failed(mut self, last_error: String) -> Self {
+        self.status = Some(OutboundMessageStatus::Failed.as_str().to_string());
+        self.last_error = Some(last_error);
+        self
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = outbound_messages)]
+pub struct OutboundMessageUpdate {
+    pub status: Option<String>,
+    pub attempts: Option<i32>,
+    pub last_error: Option<Option<String>>,
+    pub sent_at: Option<Option<DateTime<Utc>>>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub claimed_at: Option<Option<DateTime<Utc>>>,
+}
@@ -0,0 +1,61 @@
+use crate::enums::{OwnershipTransferStatus, DEFAULT_OWNERSHIP_TRANSFER_EXPIRY_DAYS};
+use crate::schema::ownership_transfers;
+use chrono::{DateTime, Duration, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = ownership_transfers)]
+#[diesel(check_for_backend(Postgres))]
+pub struct OwnershipTransfer {
+    pub id: i32,
+    pub organization_id: i32,
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+impl OwnershipTransfer {
+    pub fn get_status(&self) -> OwnershipTransferStatus {
+        OwnershipTransferStatus::from_str(&self.status).unwrap_or(OwnershipTransferStatus::Pending)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at < Utc::now()
+    }
+
+    pub fn can_respond(&self) -> bool {
+        !self.is_expired() && self.get_status() == OwnershipTransferStatus::Pending
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = ownership_transfers)]
+pub struct NewOwnershipTransfer {
+    pub organization_id: i32,
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl NewOwnershipTransfer {
+    pub fn new(organization_id: i32, from_user_id: i32, to_user_id: i32) -> Self {
+        Self {
+            organization_id,
+            from_user_id,
+            to_user_id,
+            status: OwnershipTransferStatus::Pending.as_str().to_string(),
+            expires_at: Utc::now() + Duration::days(DEFAULT_OWNERSHIP_TRANSFER_EXPIRY_DAYS),
+        }
+    }
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = ownership_transfers)]
+pub struct OwnershipTransferUpdate {
+    pub status: Option<String>,
+    pub responded_at: Option<Option<DateTime<Utc>>>,
+}
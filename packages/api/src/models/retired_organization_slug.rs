@@ -0,0 +1,46 @@
+use crate::schema::retired_organization_slugs;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = retired_organization_slugs)]
+#[diesel(check_for_backend(Postgres))]
+pub struct RetiredOrganizationSlug {
+    pub id: i32,
+    pub organization_id: i32,
+    pub slug: String,
+    pub changed_by: Option<i32>,
+    pub retired_at: DateTime<Utc>,
+    pub reserved_until: DateTime<Utc>,
+}
+
+impl RetiredOrganizationSlug {
+    pub fn is_reserved(&self) -> bool {
+        self.reserved_until > Utc::now()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = retired_organization_slugs)]
+pub struct NewRetiredOrganizationSlug {
+    pub organization_id: i32,
+    pub slug: String,
+    pub changed_by: Option<i32>,
+    pub reserved_until: DateTime<Utc>,
+}
+
+impl NewRetiredOrganizationSlug {
+    pub fn new(organization_id: i32, slug: String, reserved_until: DateTime<Utc>) -> Self {
+        Self {
+            organization_id,
+            slug,
+            changed_by: None,
+            reserved_until,
+        }
+    }
+
+    pub fn set_changed_by(mut self, changed_by: i32) -> Self {
+        self.changed_by = Some(changed_by);
+        self
+    }
+}
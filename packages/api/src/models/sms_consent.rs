@@ -0,0 +1,93 @@
+use crate::enums::{SmsConsentAction, SmsConsentSource};
+use crate::schema::sms_consents;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+use ipnetwork::IpNetwork;
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = sms_consents)]
+#[diesel(check_for_backend(Postgres))]
+pub struct SmsConsent {
+    pub id: i32,
+    pub phone_number: String,
+    pub action: String,
+    pub source: String,
+    pub user_id: Option<i32>,
+    pub contact_id: Option<i32>,
+    pub organization_id: Option<i32>,
+    pub consent_text: Option<String>,
+    pub ip_address: Option<IpNetwork>,
+    pub provider_message_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SmsConsent {
+    // an entry that can't be read is treated as an opt-out, never as consent
+    pub fn get_action(&self) -> SmsConsentAction {
+        SmsConsentAction::from_str(&self.action).unwrap_or(SmsConsentAction::OptOut)
+    }
+
+    pub fn get_source(&self) -> SmsConsentSource {
+        SmsConsentSource::from_str(&self.source).unwrap_or(SmsConsentSource::Account)
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = sms_consents)]
+pub struct NewSmsConsent {
+    pub phone_number: String,
+    pub action: String,
+    pub source: String,
+    pub user_id: Option<i32>,
+    pub contact_id: Option<i32>,
+    pub organization_id: Option<i32>,
+    pub consent_text: Option<String>,
+    pub ip_address: Option<IpNetwork>,
+    pub provider_message_id: Option<String>,
+}
+
+impl NewSmsConsent {
+    pub fn new(phone_number: String, action: SmsConsentAction, source: SmsConsentSource) -> Self {
+        Self {
+            phone_number,
+            action: action.as_str().to_string(),
+            source: source.as_str().to_string(),
+            user_id: None,
+            contact_id: None,
+            organization_id: None,
+            consent_text: None,
+            ip_address: None,
+            provider_message_id: None,
+        }
+    }
+
+    pub fn set_user(mut self, user_id: i32) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn set_contact(mut self, contact_id: i32) -> Self {
+        self.contact_id = Some(contact_id);
+        self
+    }
+
+    pub fn set_organization(mut self, organization_id: i32) -> Self {
+        self.organization_id = Some(organization_id);
+        self
+    }
+
+    pub fn set_consent_text(mut self, consent_text: String) -> Self {
+        self.consent_text = Some(consent_text);
+        self
+    }
+
+    pub fn set_ip_address(mut self, ip_address: IpNetwork) -> Self {
+        self.ip_address = Some(ip_address);
+        self
+    }
+
+    pub fn set_provider_message_id(mut self, provider_message_id: String) -> Self {
+        self.provider_message_id = Some(provider_message_id);
+        self
+    }
+}
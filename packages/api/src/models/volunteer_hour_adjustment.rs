@@ -0,0 +1,52 @@
+use crate::schema::volunteer_hour_adjustments;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg as Postgres, prelude::*};
+
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = volunteer_hour_adjustments)]
+#[diesel(check_for_backend(Postgres))]
+pub struct VolunteerHourAdjustment {
+    pub id: i32,
+    pub organization_id: i32,
+    pub user_id: i32,
+    pub event_signup_id: Option<i32>,
+    pub minutes: i32,
+    pub reason: String,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+// adjustments are never edited or deleted, a mistake is corrected with another one
+#[derive(Insertable)]
+#[diesel(table_name = volunteer_hour_adjustments)]
+pub struct NewVolunteerHourAdjustment {
+    pub organization_id: i32,
+    pub user_id: i32,
+    pub event_signup_id: Option<i32>,
+    pub minutes: i32,
+    pub reason: String,
+    pub created_by: Option<i32>,
+}
+
+impl NewVolunteerHourAdjustment {
+    pub fn new(organization_id: i32, user_id: i32, minutes: i32, reason: String) -> Self {
+        Self {
+            organization_id,
+            user_id,
+            event_signup_id: None,
+            minutes,
+            reason,
+            created_by: None,
+        }
+    }
+
+    pub fn set_event_signup(mut self, event_signup_id: i32) -> Self {
+        self.event_signup_id = Some(event_signup_id);
+        self
+    }
+
+    pub fn set_created_by(mut self, user_id: i32) -> Self {
+        self.created_by = Some(user_id);
+        self
+    }
+}
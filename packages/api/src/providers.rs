@@ -14,8 +14,8 @@ pub mod web_app;
 
 // #[cfg(feature = "cms")]
 // pub use cms::*;
-// #[cfg(feature = "events")]
-// pub use events::*;
+#[cfg(feature = "events")]
+pub use events::*;
 // #[cfg(feature = "mobile_app")]
 // pub use mobile_app::*;
 pub use shared::*;
@@ -2,9 +2,11 @@ mod article;
 mod article_category;
 mod article_tag;
 mod media;
+mod organization_plan;
 
 pub use article::*;
 pub use article_category::*;
 pub use article_tag::*;
 pub use media::*;
+pub use organization_plan::*;
 
@@ -0,0 +1,42 @@
+use crate::http::AuthSession;
+use crate::interfaces::{ChangeOrganizationPlanRequest, OrganizationPlanResponse};
+#[cfg(feature = "server")]
+use crate::services::{
+    change_organization_plan as change_organization_plan_service, get_organization_by_id,
+    list_organization_plan_history,
+};
+use dioxus::prelude::*;
+
+#[get("/api/cms/organizations/{organization_id}/plan", auth: AuthSession)]
+pub async fn get_organization_plan(
+    organization_id: i32,
+) -> Result<OrganizationPlanResponse, ServerFnError> {
+    let _session = auth.require_staff()?;
+
+    let organization = get_organization_by_id(organization_id).await?;
+    let history = list_organization_plan_history(organization.id).await?;
+
+    Ok(OrganizationPlanResponse::new(organization.id, history))
+}
+
+// staff move an organization to another plan, now or from a later date
+#[post("/api/cms/organizations/{organization_id}/plan/change", auth: AuthSession)]
+pub async fn change_organization_plan(
+    organization_id: i32,
+    request: ChangeOrganizationPlanRequest,
+) -> Result<OrganizationPlanResponse, ServerFnError> {
+    let session = auth.require_staff()?;
+
+    change_organization_plan_service(
+        organization_id,
+        request.plan,
+        request.effective_from,
+        session.user_id,
+        request.note,
+    )
+    .await?;
+
+    let history = list_organization_plan_history(organization_id).await?;
+
+    Ok(OrganizationPlanResponse::new(organization_id, history))
+}
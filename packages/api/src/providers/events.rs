@@ -0,0 +1,3 @@
+mod event_discovery;
+
+pub use event_discovery::*;
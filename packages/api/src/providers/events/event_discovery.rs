@@ -0,0 +1,22 @@
+use crate::interfaces::{
+    ListPublicEventsRequest, PaginationParams, PublicEventListResponse, PublicEventResponse,
+};
+#[cfg(feature = "server")]
+use crate::services::events::{
+    get_public_event as get_public_event_service, list_public_events as list_public_events_service,
+};
+use dioxus::prelude::*;
+
+#[post("/api/public/events/list")]
+pub async fn list_public_events(
+    request: ListPublicEventsRequest,
+) -> Result<PublicEventListResponse, ServerFnError> {
+    let (page, per_page) = PaginationParams::resolve(request.page, request.per_page);
+
+    Ok(list_public_events_service(request, page, per_page).await?)
+}
+
+#[get("/api/public/events/{event_id}")]
+pub async fn get_public_event(event_id: i32) -> Result<PublicEventResponse, ServerFnError> {
+    Ok(get_public_event_service(event_id).await?)
+}
@@ -1,8 +1,10 @@
 mod article;
 mod auth;
+mod email_verification;
 mod password_reset;
 
 pub use article::*;
 pub use auth::*;
+pub use email_verification::*;
 pub use password_reset::*;
 
@@ -1,19 +1,24 @@
-use crate::enums::SubscriptionType;
+use crate::enums::DEFAULT_SUBSCRIPTION_PLAN;
+#[cfg(feature = "server")]
+use crate::error::AppError;
 #[cfg(feature = "server")]
 use crate::http::{
     clear_session_token_response, extract_client_ip, extract_user_agent, set_session_token_response,
 };
 use crate::http::{AuthSession, WithToken};
 use crate::interfaces::{
-    AuthResponse, LoginRequest, LogoutRequest, LogoutResponse, OrganizationInfo,
-    OrganizationMembershipInfo, RegisterRequest, UserAccountResponse,
+    AuthResponse, LoginRequest, LogoutRequest, LogoutResponse, NotificationResponse,
+    OrganizationInfo, OrganizationMembershipInfo, RegisterRequest, UserAccountResponse,
 };
 #[cfg(feature = "server")]
 use crate::redis::{invalidate_redis_cached_session, redis_cache_session, CachedSession};
 #[cfg(feature = "server")]
 use crate::services::{
-    authenticate_user, batch_count_members, change_password as change_password_service,
-    create_session, delete_session, get_user_by_id, list_user_organizations, register_user,
+    accept_invitation as accept_invitation_service, authenticate_user, batch_count_members,
+    batch_get_current_organization_plans, batch_get_member_capabilities,
+    change_password as change_password_service, claim_verified_email, create_session,
+    delete_session, get_user_by_id, list_unread_notifications, list_user_organizations,
+    mark_email_verified, register_user, send_email_verification, set_active_organization,
 };
 use dioxus::fullstack::HeaderMap;
 use dioxus::prelude::*;
@@ -27,7 +32,7 @@ pub async fn register(request: RegisterRequest) -> Result<WithToken<AuthResponse
     let user_agent = extract_user_agent(&headers);
     let ip_address = extract_client_ip(&headers);
 
-    let user = register_user(
+    let mut user = register_user(
         request.email,
         request.password,
         request.first_name,
@@ -36,15 +41,50 @@ pub async fn register(request: RegisterRequest) -> Result<WithToken<AuthResponse
     )
     .await?;
 
+    // the invitation link only went to the invitee, so registering through it proves
+    // the address, a failure here shouldn't stop the account from being created
+    let mut memberships = vec![];
+    if let Some(invitation_token) = request.invitation_token {
+        let accepted = match Uuid::parse_str(&invitation_token) {
+            Ok(token) => accept_invitation_service(token, user.id).await,
+            Err(_) => Err(AppError::validation("invitation_token", "Invalid token format")),
+        };
+        match accepted {
+            Ok(membership) => {
+                memberships.push(membership);
+                user = mark_email_verified(user)
+                    .await?;
+            }
+            Err(error) => {
+                tracing::warn!("failed to accept invitation during register: {}", error);
+            }
+        }
+    }
+
+    // other invitations and guest RSVPs for this email wait until it's verified
+    if user.email_verified_at.is_some() {
+        memberships.extend(claim_verified_email(&user).await);
+    } else if let Err(error) = send_email_verification(&user) {
+        tracing::warn!(
+            "failed to send email verification during register: {}",
+            error
+        );
+    }
+    let active_organization_membership_id = memberships.first().map(|membership| membership.id);
+
     let session = create_session(user.id, request.platform, user_agent, ip_address)
         .await?;
 
+    if active_organization_membership_id.is_some() {
+        set_active_organization(session.id, active_organization_membership_id).await?;
+    }
+
     let token = session.token.to_string();
 
     let cached = CachedSession {
         session_id: session.id,
         user_id: user.id,
-        active_organization_membership_id: None,
+        active_organization_membership_id,
         is_staff: user.is_staff,
     };
 
@@ -143,20 +183,28 @@ pub async fn get_current_user() -> Result<Option<UserAccountResponse>, ServerFnE
     let org_ids: Vec<i32> = organizations.iter().map(|(org, _)| org.id).collect();
     let member_counts = batch_count_members(&org_ids)
         .await?;
+    let organization_plans = batch_get_current_organization_plans(&org_ids)
+        .await?;
+
+    let members: Vec<_> = organizations.iter().map(|(_, member)| member).collect();
+    let mut member_capabilities = batch_get_member_capabilities(&members)
+        .await?;
 
     let mut organization_memberships = HashMap::new();
 
     for (organization, member) in organizations {
         let member_count = member_counts.get(&organization.id).copied().unwrap_or(0);
 
-        let mut permissions = HashMap::new();
-        for subscription in &organization.subscriptions {
-            if let Some(sub_str) = subscription {
-                if let Some(sub_type) = SubscriptionType::from_str(sub_str) {
-                    permissions.insert(sub_type, true);
-                }
-            }
-        }
+        let plan = organization_plans
+            .get(&organization.id)
+            .copied()
+            .unwrap_or(DEFAULT_SUBSCRIPTION_PLAN);
+        let permissions = plan
+            .entitlements()
+            .features
+            .into_iter()
+            .map(|feature| (feature, true))
+            .collect();
 
         let membership_info = OrganizationMembershipInfo {
             id: member.id,
@@ -169,11 +217,18 @@ pub async fn get_current_user() -> Result<Option<UserAccountResponse>, ServerFnE
             },
             user_role: member.get_role(),
             permissions,
+            capabilities: member_capabilities.remove(&member.id).unwrap_or_default(),
         };
 
         organization_memberships.insert(member.id, membership_info);
     }
 
+    let notifications = list_unread_notifications(session.user_id)
+        .await?
+        .into_iter()
+        .map(NotificationResponse::from)
+        .collect();
+
     Ok(Some(UserAccountResponse {
         id: user.id,
         email: user.email,
@@ -182,6 +237,7 @@ pub async fn get_current_user() -> Result<Option<UserAccountResponse>, ServerFnE
         avatar_url: user.avatar_url,
         active_organization_membership_id: session.active_organization_membership_id,
         organization_memberships,
+        notifications,
     }))
 }
 
@@ -0,0 +1,47 @@
+use crate::http::AuthSession;
+use crate::interfaces::{ResendEmailVerificationResponse, VerifyEmailRequest, VerifyEmailResponse};
+#[cfg(feature = "server")]
+use crate::redis::update_redis_cached_session_active_organization_membership_id;
+#[cfg(feature = "server")]
+use crate::services::{
+    get_user_by_id, send_email_verification, set_active_organization,
+    verify_email as verify_email_service,
+};
+use dioxus::prelude::*;
+
+// the token is the only credential, a session is only used to switch a signed in
+// user without an active organization into one they just joined
+#[post("/api/auth/verify-email", auth: AuthSession)]
+pub async fn verify_email(
+    request: VerifyEmailRequest,
+) -> Result<VerifyEmailResponse, ServerFnError> {
+    let (user, memberships) = verify_email_service(&request.token).await?;
+
+    if let (Some(session), Some(membership)) = (auth.current, memberships.first()) {
+        if session.user_id == user.id && session.active_organization_membership_id.is_none() {
+            set_active_organization(session.session_id, Some(membership.id)).await?;
+
+            update_redis_cached_session_active_organization_membership_id(
+                &session.token,
+                Some(membership.id),
+            )
+            .await
+            .ok();
+        }
+    }
+
+    Ok(VerifyEmailResponse {
+        email: user.email,
+        organizations_joined: memberships.len(),
+    })
+}
+
+#[post("/api/auth/resend-verification", auth: AuthSession)]
+pub async fn resend_email_verification() -> Result<ResendEmailVerificationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let user = get_user_by_id(session.user_id).await?;
+    send_email_verification(&user)?;
+
+    Ok(ResendEmailVerificationResponse { success: true })
+}
@@ -16,19 +16,9 @@ use uuid::Uuid;
 pub async fn request_password_reset(
     request: RequestPasswordResetRequest,
 ) -> Result<RequestPasswordResetResponse, ServerFnError> {
-    let result = request_password_reset_service(&request.email)
+    request_password_reset_service(&request.email)
         .await?;
 
-    // TODO: if result is Some(token), send the reset email with the token link via AWS SES.
-    // For now, log the token in development for testing.
-    if let Some(token) = result {
-        tracing::info!(
-            "Password reset token generated for {}: {} (email sending not yet implemented)",
-            request.email,
-            token
-        );
-    }
-
     // always return success to prevent user enumeration
     Ok(RequestPasswordResetResponse { success: true })
 }
@@ -1,3 +1,29 @@
+mod event;
+mod event_calendar;
+mod event_check_in;
+mod event_co_host;
+mod event_image;
+mod event_series;
+mod event_signup;
+mod event_template;
+mod export;
+mod guest_signup;
+mod notification;
 mod organization;
+mod sms_consent;
+mod volunteer_hours;
 
+pub use event::*;
+pub use event_calendar::*;
+pub use event_check_in::*;
+pub use event_co_host::*;
+pub use event_image::*;
+pub use event_series::*;
+pub use event_signup::*;
+pub use event_template::*;
+pub use export::*;
+pub use guest_signup::*;
+pub use notification::*;
 pub use organization::*;
+pub use sms_consent::*;
+pub use volunteer_hours::*;
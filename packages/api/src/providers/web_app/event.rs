@@ -0,0 +1,279 @@
+use crate::enums::{Capability, EventVisibility};
+use crate::http::AuthSession;
+use crate::interfaces::{
+    CancelEventResponse, CreateEventRequest, EventListResponse, EventResponse, EventShiftRequest,
+    EventShiftResponse, ListEventsRequest, PaginationParams, UpdateEventRequest,
+    UpdateEventShiftRequest,
+};
+#[cfg(feature = "server")]
+use crate::models::{EventShift, EventShiftUpdate, EventUpdate, NewEvent};
+#[cfg(feature = "server")]
+use crate::providers::require_active_membership_with_capability;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    add_event_shift as add_event_shift_service, batch_build_event_responses, build_event_response,
+    cancel_event as cancel_event_service, create_event as create_event_service,
+    delete_event_shift as delete_event_shift_service, get_accessible_event, get_event_shift,
+    get_organization_event, list_events as list_events_service,
+    publish_event as publish_event_service, unpublish_event as unpublish_event_service,
+    update_event as update_event_service, update_event_shift as update_event_shift_service,
+};
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+pub(crate) async fn require_organization_shift(
+    organization_id: i32,
+    shift_id: i32,
+) -> Result<EventShift, ServerFnError> {
+    let shift = get_event_shift(shift_id).await?;
+    get_organization_event(organization_id, shift.event_id).await?;
+    Ok(shift)
+}
+
+#[post("/api/events/create", auth: AuthSession)]
+pub async fn create_event(request: CreateEventRequest) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let mut new_event = NewEvent::new(
+        membership.organization_id,
+        request.name.trim().to_string(),
+        request.event_type,
+        request.contact_name.trim().to_string(),
+        session.user_id,
+    )
+    .set_invite_groups(request.invite_groups);
+
+    if let Some(description) = request.description {
+        new_event = new_event.set_description(description);
+    }
+    if let Some(attendee_message) = request.attendee_message {
+        new_event = new_event.set_attendee_message(attendee_message);
+    }
+    if let Some(image_url) = request.image_url {
+        new_event = new_event.set_image_url(image_url);
+    }
+    if let Some(location_in_person) = request.location_in_person {
+        new_event = new_event.set_location_in_person(location_in_person);
+    }
+    if let Some(location_online) = request.location_online {
+        new_event = new_event.set_location_online(location_online);
+    }
+    if let Some(location_zip_code) = request.location_zip_code {
+        new_event = new_event.set_location_zip_code(location_zip_code.trim().to_string());
+    }
+    if let Some(contact_email) = request.contact_email {
+        new_event = new_event.set_contact_email(contact_email);
+    }
+    if let Some(contact_phone) = request.contact_phone {
+        new_event = new_event.set_contact_phone(contact_phone);
+    }
+    if let Some(flag) = request.communication_bring_a_friend {
+        new_event.communication_bring_a_friend = flag;
+    }
+    if let Some(flag) = request.communication_other_events {
+        new_event.communication_other_events = flag;
+    }
+    if let Some(flag) = request.communication_confirmation {
+        new_event.communication_confirmation = flag;
+    }
+    if let Some(flag) = request.communication_check_in {
+        new_event.communication_check_in = flag;
+    }
+
+    let (event, shifts) = create_event_service(new_event, request.shifts).await?;
+
+    Ok(EventResponse::new(event, shifts, vec![]))
+}
+
+#[get("/api/events/{event_id}", auth: AuthSession)]
+pub async fn get_event(event_id: i32) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsView).await?;
+
+    let (event, _co_host) = get_accessible_event(membership.organization_id, event_id).await?;
+
+    Ok(build_event_response(event).await?)
+}
+
+#[post("/api/events/list", auth: AuthSession)]
+pub async fn list_events(request: ListEventsRequest) -> Result<EventListResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsView).await?;
+
+    let (page, per_page) = PaginationParams::resolve(request.page, request.per_page);
+
+    let (events, total) = list_events_service(
+        membership.organization_id,
+        request.event_type,
+        request.visibility,
+        request.include_cancelled.unwrap_or(false),
+        page,
+        per_page,
+    )
+    .await?;
+
+    let responses = batch_build_event_responses(events).await?;
+
+    Ok(EventListResponse {
+        events: responses,
+        total,
+        page,
+        per_page,
+    })
+}
+
+#[post("/api/events/{event_id}/update", auth: AuthSession)]
+pub async fn update_event(
+    event_id: i32,
+    request: UpdateEventRequest,
+) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    get_organization_event(membership.organization_id, event_id).await?;
+
+    let update = EventUpdate {
+        name: request.name.map(|name| name.trim().to_string()),
+        event_type: request
+            .event_type
+            .map(|event_type| event_type.as_str().to_string()),
+        description: request.description,
+        attendee_message: request.attendee_message,
+        image_url: request.image_url,
+        location_in_person: request.location_in_person,
+        location_online: request.location_online,
+        location_zip_code: request
+            .location_zip_code
+            .map(|zip_code| zip_code.map(|zip_code| zip_code.trim().to_string())),
+        communication_bring_a_friend: request.communication_bring_a_friend,
+        communication_other_events: request.communication_other_events,
+        communication_confirmation: request.communication_confirmation,
+        communication_check_in: request.communication_check_in,
+        contact_name: request
+            .contact_name
+            .map(|contact_name| contact_name.trim().to_string()),
+        contact_email: request.contact_email,
+        contact_phone: request.contact_phone,
+        invite_groups: request
+            .invite_groups
+            .map(|invite_groups| invite_groups.into_iter().map(Some).collect()),
+        ..Default::default()
+    };
+
+    let event = update_event_service(event_id, update).await?;
+
+    Ok(build_event_response(event).await?)
+}
+
+#[post("/api/events/{event_id}/publish", auth: AuthSession)]
+pub async fn publish_event(
+    event_id: i32,
+    visibility: EventVisibility,
+) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsPublish).await?;
+
+    get_organization_event(membership.organization_id, event_id).await?;
+
+    let event = publish_event_service(event_id, visibility).await?;
+
+    Ok(build_event_response(event).await?)
+}
+
+#[post("/api/events/{event_id}/unpublish", auth: AuthSession)]
+pub async fn unpublish_event(event_id: i32) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsPublish).await?;
+
+    get_organization_event(membership.organization_id, event_id).await?;
+
+    let event = unpublish_event_service(event_id).await?;
+
+    Ok(build_event_response(event).await?)
+}
+
+#[post("/api/events/{event_id}/cancel", auth: AuthSession)]
+pub async fn cancel_event(
+    event_id: i32,
+    reason: Option<String>,
+) -> Result<CancelEventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsPublish).await?;
+
+    get_organization_event(membership.organization_id, event_id).await?;
+
+    let reason = reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+
+    let (event, message_counts) = cancel_event_service(event_id, reason).await?;
+
+    Ok(CancelEventResponse {
+        event: build_event_response(event).await?,
+        messages_queued: message_counts.queued,
+        messages_over_quota: message_counts.over_quota,
+        messages_opted_out: message_counts.opted_out,
+    })
+}
+
+#[post("/api/events/{event_id}/shifts/add", auth: AuthSession)]
+pub async fn add_event_shift(
+    event_id: i32,
+    request: EventShiftRequest,
+) -> Result<EventShiftResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    get_organization_event(membership.organization_id, event_id).await?;
+
+    let shift = add_event_shift_service(event_id, request).await?;
+
+    Ok(EventShiftResponse::from(shift))
+}
+
+#[post("/api/events/shifts/{shift_id}/update", auth: AuthSession)]
+pub async fn update_event_shift(
+    shift_id: i32,
+    request: UpdateEventShiftRequest,
+) -> Result<EventShiftResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    require_organization_shift(membership.organization_id, shift_id).await?;
+
+    let update = EventShiftUpdate {
+        start_time: request.start_time,
+        end_time: request.end_time,
+        timezone: request.timezone,
+        capacity: request.capacity,
+        notes: request.notes,
+        ..Default::default()
+    };
+
+    let shift = update_event_shift_service(shift_id, update).await?;
+
+    Ok(EventShiftResponse::from(shift))
+}
+
+#[post("/api/events/shifts/{shift_id}/delete", auth: AuthSession)]
+pub async fn delete_event_shift(shift_id: i32) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    require_organization_shift(membership.organization_id, shift_id).await?;
+
+    delete_event_shift_service(shift_id).await?;
+
+    Ok(())
+}
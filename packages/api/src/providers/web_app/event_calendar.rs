@@ -0,0 +1,91 @@
+use crate::http::AuthSession;
+use crate::interfaces::CalendarFeedResponse;
+#[cfg(feature = "server")]
+use crate::services::resolve_organization_slug;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    build_organization_calendar, build_shift_calendar, build_user_calendar,
+    get_calendar_feed_user_id, get_or_create_calendar_feed_token,
+    reset_calendar_feed_token as reset_calendar_feed_token_service,
+};
+use dioxus::fullstack::response::Response;
+#[cfg(feature = "server")]
+use dioxus::fullstack::{body::Body, http::header};
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+#[cfg(feature = "server")]
+fn calendar_response(calendar: String, filename: Option<&str>) -> Result<Response, ServerFnError> {
+    let mut builder = Response::builder()
+        .header(header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .header(header::CACHE_CONTROL, "private, max-age=300");
+
+    if let Some(filename) = filename {
+        builder = builder.header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        );
+    }
+
+    builder
+        .body(Body::from(calendar))
+        .map_err(|error| ServerFnError::new(error.to_string()))
+}
+
+#[cfg(feature = "server")]
+fn calendar_feed_response(token: Uuid) -> CalendarFeedResponse {
+    CalendarFeedResponse {
+        token: token.to_string(),
+        path: format!("/api/calendar/feeds/{}/signups.ics", token),
+    }
+}
+
+// public subscription feed, calendar apps can't authenticate so no session is required
+#[get("/api/calendar/organizations/{slug}/events.ics")]
+pub async fn get_organization_calendar(slug: String) -> Result<Response, ServerFnError> {
+    let (organization, _) = resolve_organization_slug(&slug)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Organization not found"))?;
+
+    let calendar = build_organization_calendar(&organization).await?;
+
+    calendar_response(calendar, None)
+}
+
+// the unguessable token stands in for the session
+#[get("/api/calendar/feeds/{token}/signups.ics")]
+pub async fn get_signup_calendar(token: String) -> Result<Response, ServerFnError> {
+    let token =
+        Uuid::parse_str(&token).map_err(|_| ServerFnError::new("Calendar feed not found"))?;
+
+    let user_id = get_calendar_feed_user_id(token).await?;
+    let calendar = build_user_calendar(user_id).await?;
+
+    calendar_response(calendar, None)
+}
+
+#[get("/api/calendar/shifts/{shift_id}/shift.ics")]
+pub async fn download_shift_calendar(shift_id: i32) -> Result<Response, ServerFnError> {
+    let calendar = build_shift_calendar(shift_id).await?;
+
+    calendar_response(calendar, Some(&format!("shift-{}.ics", shift_id)))
+}
+
+#[get("/api/calendar/feed", auth: AuthSession)]
+pub async fn get_calendar_feed() -> Result<CalendarFeedResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let token = get_or_create_calendar_feed_token(session.user_id).await?;
+
+    Ok(calendar_feed_response(token))
+}
+
+#[post("/api/calendar/feed/reset", auth: AuthSession)]
+pub async fn reset_calendar_feed_token() -> Result<CalendarFeedResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let token = reset_calendar_feed_token_service(session.user_id).await?;
+
+    Ok(calendar_feed_response(token))
+}
@@ -0,0 +1,98 @@
+#[cfg(feature = "server")]
+use crate::enums::Capability;
+use crate::http::AuthSession;
+use crate::interfaces::{CheckInCodeResponse, EventSignupResponse, WalkInCheckInRequest};
+#[cfg(feature = "server")]
+use crate::providers::{require_active_membership_with_capability, require_organization_shift};
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    check_in_signup, check_in_walk_in as check_in_walk_in_service, create_check_in_token,
+    get_event_signup, get_signup_by_check_in_token, mark_remaining_no_shows,
+    render_check_in_qr_svg, SignupOwner,
+};
+#[cfg(feature = "server")]
+use crate::services::{get_contact_by_email, get_user_by_email};
+use dioxus::prelude::*;
+
+#[get("/api/events/signups/{signup_id}/check-in-code", auth: AuthSession)]
+pub async fn get_check_in_code(signup_id: i32) -> Result<CheckInCodeResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let signup = get_event_signup(signup_id).await?;
+
+    if signup.user_id != Some(session.user_id) {
+        return Err(ServerFnError::new("Event signup not found"));
+    }
+
+    let token = create_check_in_token(signup.id)?;
+    let qr_svg = render_check_in_qr_svg(&token)?;
+
+    Ok(CheckInCodeResponse {
+        signup_id: signup.id,
+        token,
+        qr_svg,
+    })
+}
+
+// door staff only need member access to scan volunteers in
+#[post("/api/events/check-in/scan", auth: AuthSession)]
+pub async fn check_in_by_token(token: String) -> Result<EventSignupResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsCheckIn).await?;
+
+    let signup = get_signup_by_check_in_token(&token).await?;
+    require_organization_shift(membership.organization_id, signup.event_shift_id).await?;
+
+    let signup = check_in_signup(signup.id).await?;
+
+    Ok(EventSignupResponse::from(signup))
+}
+
+#[post("/api/events/shifts/{shift_id}/check-in/walk-in", auth: AuthSession)]
+pub async fn check_in_walk_in(
+    shift_id: i32,
+    request: WalkInCheckInRequest,
+) -> Result<EventSignupResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsCheckIn).await?;
+
+    require_organization_shift(membership.organization_id, shift_id).await?;
+
+    // an account wins over a guest contact with the same email
+    let email = request.email.trim();
+    let owner = match get_user_by_email(email).await? {
+        Some(user) => SignupOwner::User(user.id),
+        None => match get_contact_by_email(email).await? {
+            Some(contact) => SignupOwner::Contact(contact.id),
+            None => {
+                return Err(ServerFnError::new(
+                    "No account or RSVP found for that email",
+                ))
+            }
+        },
+    };
+
+    let notes = request
+        .notes
+        .map(|notes| notes.trim().to_string())
+        .filter(|notes| !notes.is_empty());
+
+    let signup = check_in_walk_in_service(shift_id, owner, notes).await?;
+
+    Ok(EventSignupResponse::from(signup))
+}
+
+#[post("/api/events/shifts/{shift_id}/check-in/no-shows", auth: AuthSession)]
+pub async fn mark_shift_no_shows(shift_id: i32) -> Result<i64, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::SignupsManage).await?;
+
+    let shift = require_organization_shift(membership.organization_id, shift_id).await?;
+
+    let marked = mark_remaining_no_shows(&shift).await?;
+
+    Ok(marked as i64)
+}
@@ -0,0 +1,134 @@
+#[cfg(feature = "server")]
+use crate::enums::Capability;
+use crate::enums::CoHostDataSharing;
+use crate::http::AuthSession;
+use crate::interfaces::{CoHostInvitationResponse, EventCoHostResponse, InviteCoHostRequest};
+#[cfg(feature = "server")]
+use crate::providers::require_active_membership_with_capability;
+#[cfg(feature = "server")]
+use crate::services::get_organization_by_id;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    batch_get_event_co_hosts, get_event_co_host, get_organization_event,
+    invite_co_host as invite_co_host_service,
+    list_co_host_invitations as list_co_host_invitations_service,
+    remove_co_host as remove_co_host_service,
+    respond_to_co_host_invitation as respond_to_co_host_invitation_service,
+    update_co_host_data_sharing as update_co_host_data_sharing_service,
+};
+use dioxus::prelude::*;
+
+#[post("/api/events/{event_id}/co-hosts/invite", auth: AuthSession)]
+pub async fn invite_co_host(
+    event_id: i32,
+    request: InviteCoHostRequest,
+) -> Result<EventCoHostResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let event = get_organization_event(membership.organization_id, event_id).await?;
+
+    let (co_host, organization) = invite_co_host_service(
+        &event,
+        session.user_id,
+        &request.organization_slug,
+        request.data_sharing,
+    )
+    .await?;
+
+    Ok(EventCoHostResponse::new(co_host, organization))
+}
+
+#[get("/api/events/{event_id}/co-hosts", auth: AuthSession)]
+pub async fn list_event_co_hosts(event_id: i32) -> Result<Vec<EventCoHostResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    get_organization_event(membership.organization_id, event_id).await?;
+
+    let mut co_hosts = batch_get_event_co_hosts(&[event_id]).await?;
+
+    Ok(co_hosts
+        .remove(&event_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(co_host, organization)| EventCoHostResponse::new(co_host, organization))
+        .collect())
+}
+
+// only the host organization decides how much signup data a co-host sees
+#[post("/api/events/co-hosts/{co_host_id}/data-sharing", auth: AuthSession)]
+pub async fn update_co_host_data_sharing(
+    co_host_id: i32,
+    data_sharing: CoHostDataSharing,
+) -> Result<EventCoHostResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let co_host = get_event_co_host(co_host_id).await?;
+    get_organization_event(membership.organization_id, co_host.event_id).await?;
+
+    let co_host = update_co_host_data_sharing_service(co_host.id, data_sharing).await?;
+    let organization = get_organization_by_id(co_host.organization_id).await?;
+
+    Ok(EventCoHostResponse::new(co_host, organization))
+}
+
+// the host can revoke a co-host and an admin of the co-host organization can step down
+#[post("/api/events/co-hosts/{co_host_id}/remove", auth: AuthSession)]
+pub async fn remove_co_host(co_host_id: i32) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let co_host = get_event_co_host(co_host_id).await?;
+
+    if co_host.organization_id == membership.organization_id {
+        require_active_membership_with_capability(&session, Capability::CoHostsRespond).await?;
+    } else {
+        get_organization_event(membership.organization_id, co_host.event_id).await?;
+    }
+
+    remove_co_host_service(co_host.id).await?;
+
+    Ok(())
+}
+
+#[get("/api/events/co-host-invitations", auth: AuthSession)]
+pub async fn list_co_host_invitations() -> Result<Vec<CoHostInvitationResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::CoHostsRespond).await?;
+
+    let invitations = list_co_host_invitations_service(membership.organization_id).await?;
+
+    Ok(invitations
+        .into_iter()
+        .map(|(co_host, event, host)| CoHostInvitationResponse::new(co_host, event, host))
+        .collect())
+}
+
+#[post("/api/events/co-host-invitations/{co_host_id}/respond", auth: AuthSession)]
+pub async fn respond_to_co_host_invitation(
+    co_host_id: i32,
+    accept: bool,
+) -> Result<EventCoHostResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::CoHostsRespond).await?;
+
+    let co_host = get_event_co_host(co_host_id).await?;
+
+    // invitations for other organizations are reported as missing
+    if co_host.organization_id != membership.organization_id {
+        return Err(ServerFnError::new("Co-host not found"));
+    }
+
+    let co_host = respond_to_co_host_invitation_service(co_host, session.user_id, accept).await?;
+    let organization = get_organization_by_id(co_host.organization_id).await?;
+
+    Ok(EventCoHostResponse::new(co_host, organization))
+}
@@ -0,0 +1,71 @@
+#[cfg(feature = "server")]
+use crate::enums::Capability;
+use crate::http::AuthSession;
+use crate::interfaces::EventResponse;
+#[cfg(feature = "server")]
+use crate::providers::require_active_membership_with_capability;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    build_event_response, get_organization_event, remove_event_image as remove_event_image_service,
+    upload_event_image as upload_event_image_service,
+};
+use dioxus::fullstack::MultipartFormData;
+use dioxus::prelude::*;
+
+// the largest image plus multipart overhead, every other route keeps axum's default
+// body limit
+pub const MAX_EVENT_IMAGE_REQUEST_BYTES: usize = 12 * 1024 * 1024;
+
+// expects a multipart form with the image in a "file" field
+#[post("/api/events/{event_id}/image/upload", auth: AuthSession)]
+#[middleware(axum::extract::DefaultBodyLimit::max(MAX_EVENT_IMAGE_REQUEST_BYTES))]
+pub async fn upload_event_image(
+    event_id: i32,
+    mut form: MultipartFormData,
+) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let event = get_organization_event(membership.organization_id, event_id).await?;
+
+    let mut upload = None;
+    while let Some(field) = form
+        .next_field()
+        .await
+        .map_err(|error| ServerFnError::new(error.to_string()))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field.file_name().unwrap_or_default().to_string();
+        let mime_type = field.content_type().unwrap_or_default().to_string();
+        let data = field
+            .bytes()
+            .await
+            .map_err(|error| ServerFnError::new(error.to_string()))?;
+
+        upload = Some((filename, mime_type, data.to_vec()));
+        break;
+    }
+
+    let (filename, mime_type, data) =
+        upload.ok_or_else(|| ServerFnError::new("No image file was uploaded"))?;
+
+    let event = upload_event_image_service(event, filename, mime_type, data).await?;
+
+    Ok(build_event_response(event).await?)
+}
+
+#[post("/api/events/{event_id}/image/remove", auth: AuthSession)]
+pub async fn remove_event_image(event_id: i32) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let event = get_organization_event(membership.organization_id, event_id).await?;
+    let event = remove_event_image_service(event).await?;
+
+    Ok(build_event_response(event).await?)
+}
@@ -0,0 +1,213 @@
+#[cfg(feature = "server")]
+use crate::enums::Capability;
+use crate::enums::SeriesEditScope;
+use crate::http::AuthSession;
+use crate::interfaces::{
+    CreateEventSeriesRequest, EventResponse, EventSeriesResponse, UpdateEventSeriesRequest,
+};
+#[cfg(feature = "server")]
+use crate::models::{EventSeriesUpdate, EventShift, NewEventSeries};
+#[cfg(feature = "server")]
+use crate::providers::{require_active_membership_with_capability, require_organization_shift};
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    build_event_response, create_event_series as create_event_series_service, delete_event_series,
+    delete_event_shift as delete_event_shift_service, end_event_series, get_organization_event,
+    list_event_series as list_event_series_service, list_event_series_shifts, series_shift_update,
+    split_event_series, update_event_series as update_event_series_service,
+    update_event_shift as update_event_shift_service,
+};
+use dioxus::prelude::*;
+
+// the shift along with the id of the series it belongs to
+#[cfg(feature = "server")]
+async fn require_organization_series_shift(
+    organization_id: i32,
+    shift_id: i32,
+) -> Result<(EventShift, i32), ServerFnError> {
+    let shift = require_organization_shift(organization_id, shift_id).await?;
+
+    let Some(series_id) = shift.series_id else {
+        return Err(ServerFnError::new(
+            "Shift is not part of a recurring series",
+        ));
+    };
+
+    Ok((shift, series_id))
+}
+
+#[cfg(feature = "server")]
+fn build_event_series_update(request: UpdateEventSeriesRequest) -> EventSeriesUpdate {
+    EventSeriesUpdate {
+        frequency: request
+            .frequency
+            .map(|frequency| frequency.as_str().to_string()),
+        repeat_interval: request.repeat_interval,
+        by_weekday: request.by_weekday.map(|by_weekday| {
+            by_weekday
+                .into_iter()
+                .map(|weekday| Some(weekday.as_str().to_string()))
+                .collect()
+        }),
+        until_date: request.until_date,
+        occurrence_count: request.occurrence_count,
+        excluded_dates: request
+            .excluded_dates
+            .map(|excluded_dates| excluded_dates.into_iter().map(Some).collect()),
+        start_time_local: request.start_time_local,
+        duration_minutes: request.duration_minutes,
+        timezone: request.timezone,
+        capacity: request.capacity,
+        notes: request.notes,
+        ..Default::default()
+    }
+}
+
+#[post("/api/events/{event_id}/series/create", auth: AuthSession)]
+pub async fn create_event_series(
+    event_id: i32,
+    request: CreateEventSeriesRequest,
+) -> Result<EventSeriesResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let event = get_organization_event(membership.organization_id, event_id).await?;
+
+    if event.is_cancelled() {
+        return Err(ServerFnError::new("Cannot add shifts to a cancelled event"));
+    }
+
+    let mut new_series = NewEventSeries::new(
+        event_id,
+        request.frequency,
+        request.starts_on,
+        request.start_time_local,
+        request.duration_minutes,
+        request.timezone.trim().to_string(),
+    )
+    .set_by_weekday(request.by_weekday)
+    .set_excluded_dates(request.excluded_dates);
+
+    if let Some(repeat_interval) = request.repeat_interval {
+        new_series = new_series.set_repeat_interval(repeat_interval);
+    }
+    if let Some(until_date) = request.until_date {
+        new_series = new_series.set_until_date(until_date);
+    }
+    if let Some(occurrence_count) = request.occurrence_count {
+        new_series = new_series.set_occurrence_count(occurrence_count);
+    }
+    if let Some(capacity) = request.capacity {
+        new_series = new_series.set_capacity(capacity);
+    }
+    if let Some(notes) = request.notes {
+        new_series = new_series.set_notes(notes);
+    }
+
+    let (series, shifts) = create_event_series_service(new_series).await?;
+
+    Ok(EventSeriesResponse::new(series, shifts))
+}
+
+#[get("/api/events/{event_id}/series", auth: AuthSession)]
+pub async fn list_event_series(event_id: i32) -> Result<Vec<EventSeriesResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsView).await?;
+
+    get_organization_event(membership.organization_id, event_id).await?;
+
+    let mut responses = vec![];
+    for series in list_event_series_service(event_id).await? {
+        let shifts = list_event_series_shifts(series.id).await?;
+        responses.push(EventSeriesResponse::new(series, shifts));
+    }
+
+    Ok(responses)
+}
+
+#[post("/api/events/shifts/{shift_id}/series/update", auth: AuthSession)]
+pub async fn update_event_series_shift(
+    shift_id: i32,
+    request: UpdateEventSeriesRequest,
+) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let (shift, series_id) =
+        require_organization_series_shift(membership.organization_id, shift_id).await?;
+    let event = get_organization_event(membership.organization_id, shift.event_id).await?;
+
+    if event.is_cancelled() {
+        return Err(ServerFnError::new(
+            "Cannot edit shifts of a cancelled event",
+        ));
+    }
+
+    match request.scope {
+        SeriesEditScope::This => {
+            if request.changes_rule() {
+                return Err(ServerFnError::new(
+                    "The recurrence rule can only be changed for this and following or all shifts",
+                ));
+            }
+
+            let mut update = series_shift_update(
+                &shift,
+                request.start_time_local,
+                request.duration_minutes,
+                request.timezone,
+            )?;
+            update.capacity = request.capacity;
+            update.notes = request.notes;
+
+            update_event_shift_service(shift_id, update).await?;
+        }
+        SeriesEditScope::ThisAndFollowing => {
+            let split_date = shift
+                .series_occurrence_date
+                .ok_or_else(|| ServerFnError::new("Shift is not part of a recurring series"))?;
+
+            split_event_series(series_id, split_date, build_event_series_update(request)).await?;
+        }
+        SeriesEditScope::All => {
+            update_event_series_service(series_id, build_event_series_update(request)).await?;
+        }
+    }
+
+    Ok(build_event_response(event).await?)
+}
+
+#[post("/api/events/shifts/{shift_id}/series/delete", auth: AuthSession)]
+pub async fn delete_event_series_shift(
+    shift_id: i32,
+    scope: SeriesEditScope,
+) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let (shift, series_id) =
+        require_organization_series_shift(membership.organization_id, shift_id).await?;
+    let event = get_organization_event(membership.organization_id, shift.event_id).await?;
+
+    match scope {
+        SeriesEditScope::This => {
+            delete_event_shift_service(shift_id).await?;
+        }
+        SeriesEditScope::ThisAndFollowing => {
+            let end_date = shift
+                .series_occurrence_date
+                .ok_or_else(|| ServerFnError::new("Shift is not part of a recurring series"))?;
+
+            end_event_series(series_id, end_date).await?;
+        }
+        SeriesEditScope::All => {
+            delete_event_series(series_id).await?;
+        }
+    }
+
+    Ok(build_event_response(event).await?)
+}
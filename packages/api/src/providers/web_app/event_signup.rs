@@ -0,0 +1,103 @@
+#[cfg(feature = "server")]
+use crate::enums::{Capability, CoHostDataSharing, SignupStatus};
+use crate::http::AuthSession;
+use crate::interfaces::{
+    EventSignupResponse, RosterShiftConflictListResponse, ShiftConflictResponse,
+    ShiftSignupListResponse, ShiftSignupResponse,
+};
+#[cfg(feature = "server")]
+use crate::providers::{require_active_membership_with_capability, require_organization_shift};
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    cancel_event_signup as cancel_event_signup_service, get_accessible_event, get_event_shift,
+    get_event_signup, list_roster_shift_conflicts as list_roster_shift_conflicts_service,
+    list_shift_signups as list_shift_signups_service,
+    sign_up_for_shift as sign_up_for_shift_service,
+};
+use dioxus::prelude::*;
+
+#[post("/api/events/shifts/{shift_id}/signup", auth: AuthSession)]
+pub async fn sign_up_for_shift(
+    shift_id: i32,
+    notes: Option<String>,
+) -> Result<ShiftSignupResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let notes = notes
+        .map(|notes| notes.trim().to_string())
+        .filter(|notes| !notes.is_empty());
+
+    let (signup, conflicts) = sign_up_for_shift_service(shift_id, session.user_id, notes).await?;
+
+    Ok(ShiftSignupResponse {
+        signup: EventSignupResponse::from(signup),
+        conflicts: conflicts
+            .into_iter()
+            .map(|(signup, shift, event)| ShiftConflictResponse::new(signup, shift, Some(event)))
+            .collect(),
+    })
+}
+
+#[post("/api/events/signups/{signup_id}/cancel", auth: AuthSession)]
+pub async fn cancel_event_signup(signup_id: i32) -> Result<EventSignupResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let signup = get_event_signup(signup_id).await?;
+
+    // volunteers can cancel their own signups, managers can cancel any in their organization
+    if signup.user_id != Some(session.user_id) {
+        let membership =
+            require_active_membership_with_capability(&session, Capability::SignupsManage).await?;
+        require_organization_shift(membership.organization_id, signup.event_shift_id).await?;
+    }
+
+    let (cancelled, _promoted) = cancel_event_signup_service(signup_id).await?;
+
+    Ok(EventSignupResponse::from(cancelled))
+}
+
+#[get("/api/events/shifts/{shift_id}/signups", auth: AuthSession)]
+pub async fn list_shift_signups(shift_id: i32) -> Result<ShiftSignupListResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsView).await?;
+
+    let shift = get_event_shift(shift_id).await?;
+    let (_event, co_host) =
+        get_accessible_event(membership.organization_id, shift.event_id).await?;
+
+    let signups = list_shift_signups_service(shift_id).await?;
+
+    let waitlist_count = signups
+        .iter()
+        .filter(|signup| signup.get_status() == SignupStatus::Waitlisted)
+        .count() as i64;
+    let seated_count = signups
+        .iter()
+        .filter(|signup| signup.get_status().is_seated())
+        .count() as i64;
+
+    Ok(ShiftSignupListResponse {
+        event_shift_id: shift.id,
+        capacity: shift.capacity,
+        seated_count,
+        waitlist_count,
+        // co-hosts limited to summary sharing only see the counts
+        signups: match co_host {
+            Some(co_host) if co_host.get_data_sharing() == CoHostDataSharing::Summary => vec![],
+            _ => signups.into_iter().map(EventSignupResponse::from).collect(),
+        },
+    })
+}
+
+#[get("/api/events/shift-conflicts", auth: AuthSession)]
+pub async fn list_roster_shift_conflicts() -> Result<RosterShiftConflictListResponse, ServerFnError>
+{
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::SignupsManage).await?;
+
+    let conflicts = list_roster_shift_conflicts_service(membership.organization_id).await?;
+
+    Ok(RosterShiftConflictListResponse { conflicts })
+}
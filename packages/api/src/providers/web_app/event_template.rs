@@ -0,0 +1,138 @@
+#[cfg(feature = "server")]
+use crate::enums::Capability;
+use crate::http::AuthSession;
+use crate::interfaces::{
+    CreateEventFromTemplateRequest, CreateEventTemplateRequest, DuplicateEventRequest,
+    EventResponse, EventTemplateResponse,
+};
+#[cfg(feature = "server")]
+use crate::providers::require_active_membership_with_capability;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    create_event_from_template as create_event_from_template_service,
+    create_event_template as create_event_template_service,
+    delete_event_template as delete_event_template_service,
+    duplicate_event as duplicate_event_service, get_organization_event,
+    get_organization_event_template, list_event_templates as list_event_templates_service,
+    rename_event_template as rename_event_template_service,
+};
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+fn trim_event_name(name: Option<String>) -> Option<String> {
+    name.map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[post("/api/events/{event_id}/duplicate", auth: AuthSession)]
+pub async fn duplicate_event(
+    event_id: i32,
+    request: DuplicateEventRequest,
+) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let event = get_organization_event(membership.organization_id, event_id).await?;
+
+    let (event, shifts) = duplicate_event_service(
+        &event,
+        session.user_id,
+        request.start_date,
+        trim_event_name(request.name),
+    )
+    .await?;
+
+    Ok(EventResponse::new(event, shifts, vec![]))
+}
+
+#[post("/api/events/templates/create", auth: AuthSession)]
+pub async fn create_event_template(
+    request: CreateEventTemplateRequest,
+) -> Result<EventTemplateResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let event = get_organization_event(membership.organization_id, request.event_id).await?;
+
+    let template = create_event_template_service(&event, request.name, session.user_id).await?;
+
+    Ok(EventTemplateResponse::from(template))
+}
+
+#[get("/api/events/templates", auth: AuthSession)]
+pub async fn list_event_templates() -> Result<Vec<EventTemplateResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsView).await?;
+
+    let templates = list_event_templates_service(membership.organization_id).await?;
+
+    Ok(templates
+        .into_iter()
+        .map(EventTemplateResponse::from)
+        .collect())
+}
+
+#[get("/api/events/templates/{template_id}", auth: AuthSession)]
+pub async fn get_event_template(template_id: i32) -> Result<EventTemplateResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsView).await?;
+
+    let template = get_organization_event_template(membership.organization_id, template_id).await?;
+
+    Ok(EventTemplateResponse::from(template))
+}
+
+#[post("/api/events/templates/{template_id}/rename", auth: AuthSession)]
+pub async fn rename_event_template(
+    template_id: i32,
+    name: String,
+) -> Result<EventTemplateResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let template = get_organization_event_template(membership.organization_id, template_id).await?;
+
+    let template = rename_event_template_service(&template, name).await?;
+
+    Ok(EventTemplateResponse::from(template))
+}
+
+#[post("/api/events/templates/{template_id}/delete", auth: AuthSession)]
+pub async fn delete_event_template(template_id: i32) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    get_organization_event_template(membership.organization_id, template_id).await?;
+
+    delete_event_template_service(template_id).await?;
+
+    Ok(())
+}
+
+#[post("/api/events/templates/{template_id}/create-event", auth: AuthSession)]
+pub async fn create_event_from_template(
+    template_id: i32,
+    request: CreateEventFromTemplateRequest,
+) -> Result<EventResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::EventsManage).await?;
+
+    let template = get_organization_event_template(membership.organization_id, template_id).await?;
+
+    let (event, shifts) = create_event_from_template_service(
+        &template,
+        session.user_id,
+        request.start_date,
+        trim_event_name(request.name),
+    )
+    .await?;
+
+    Ok(EventResponse::new(event, shifts, vec![]))
+}
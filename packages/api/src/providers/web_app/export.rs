@@ -0,0 +1,93 @@
+#[cfg(feature = "server")]
+use crate::enums::Capability;
+use crate::http::AuthSession;
+use crate::interfaces::{
+    CreateAttendeeExportRequest, ExportDownloadResponse, ExportListResponse, ExportResponse,
+    ListExportsRequest, PaginationParams,
+};
+#[cfg(feature = "server")]
+use crate::providers::require_active_membership_with_capability;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    create_attendee_export as create_attendee_export_service,
+    delete_export as delete_export_service,
+    get_export_download_url as get_export_download_url_service, get_organization_export,
+    list_exports as list_exports_service,
+};
+use dioxus::prelude::*;
+
+// exports include volunteer contact details so they are limited to managers
+#[post("/api/exports/attendees/create", auth: AuthSession)]
+pub async fn create_attendee_export(
+    request: CreateAttendeeExportRequest,
+) -> Result<ExportDownloadResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::ExportsDownload).await?;
+
+    let export = create_attendee_export_service(
+        membership.organization_id,
+        session.user_id,
+        request.format,
+        request.event_id,
+        request.range_start,
+        request.range_end,
+    )
+    .await?;
+
+    let download_url = get_export_download_url_service(&export).await?;
+
+    Ok(ExportDownloadResponse {
+        export: ExportResponse::from(export),
+        download_url,
+    })
+}
+
+#[post("/api/exports/list", auth: AuthSession)]
+pub async fn list_exports(
+    request: ListExportsRequest,
+) -> Result<ExportListResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::ExportsDownload).await?;
+
+    let (page, per_page) = PaginationParams::resolve(request.page, request.per_page);
+
+    let (exports, total) = list_exports_service(membership.organization_id, page, per_page).await?;
+
+    Ok(ExportListResponse {
+        exports: exports.into_iter().map(ExportResponse::from).collect(),
+        total,
+        page,
+        per_page,
+    })
+}
+
+#[get("/api/exports/{export_id}/download", auth: AuthSession)]
+pub async fn get_export_download_url(
+    export_id: i32,
+) -> Result<ExportDownloadResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::ExportsDownload).await?;
+
+    let export = get_organization_export(membership.organization_id, export_id).await?;
+    let download_url = get_export_download_url_service(&export).await?;
+
+    Ok(ExportDownloadResponse {
+        export: ExportResponse::from(export),
+        download_url,
+    })
+}
+
+#[post("/api/exports/{export_id}/delete", auth: AuthSession)]
+pub async fn delete_export(export_id: i32) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::ExportsDownload).await?;
+
+    let export = get_organization_export(membership.organization_id, export_id).await?;
+    delete_export_service(export).await?;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "server")]
+use crate::http::extract_client_ip;
+use crate::interfaces::{
+    EventSignupResponse, GuestSignupListResponse, GuestSignupRequest, GuestSignupResponse,
+};
+#[cfg(feature = "server")]
+use crate::services::get_contact;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    cancel_guest_signup as cancel_guest_signup_service, create_guest_signup_token,
+    list_guest_signups as list_guest_signups_service, opt_in_contact_to_sms,
+    parse_sms_phone_number, send_guest_signup_confirmation,
+    sign_up_guest_for_shift as sign_up_guest_for_shift_service, verify_guest_signup_token,
+};
+use dioxus::fullstack::HeaderMap;
+use dioxus::prelude::*;
+
+// public RSVP for people without an account, the manage link is only sent by email
+#[post("/api/public/events/shifts/{shift_id}/rsvp", headers: HeaderMap)]
+pub async fn sign_up_guest_for_shift(
+    shift_id: i32,
+    request: GuestSignupRequest,
+) -> Result<GuestSignupResponse, ServerFnError> {
+    let trim_optional = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let phone_number = trim_optional(request.phone_number);
+
+    // checked before signing up so a bad number doesn't leave a signup without consent
+    let sms_phone_number = match (request.sms_opt_in.unwrap_or(false), &phone_number) {
+        (true, Some(phone_number)) => Some(parse_sms_phone_number(phone_number)?),
+        (true, None) => {
+            return Err(ServerFnError::new(
+                "A mobile number is required to receive text messages",
+            ))
+        }
+        (false, _) => None,
+    };
+
+    let (contact, signup, shift, event) = sign_up_guest_for_shift_service(
+        shift_id,
+        request.email,
+        request.first_name,
+        request.last_name,
+        phone_number,
+        trim_optional(request.zip_code),
+        trim_optional(request.notes),
+    )
+    .await?;
+
+    if let Some(sms_phone_number) = sms_phone_number {
+        opt_in_contact_to_sms(
+            contact.id,
+            event.organization_id,
+            sms_phone_number,
+            extract_client_ip(&headers),
+        )
+        .await?;
+    }
+
+    let token = create_guest_signup_token(contact.id)?;
+    send_guest_signup_confirmation(&contact, &signup, &shift, &event, &token)?;
+
+    Ok(GuestSignupResponse::new(signup, shift, event))
+}
+
+#[get("/api/public/rsvp/{token}")]
+pub async fn list_guest_signups(token: String) -> Result<GuestSignupListResponse, ServerFnError> {
+    let contact_id = verify_guest_signup_token(&token)?;
+    let contact = get_contact(contact_id).await?;
+
+    let signups = list_guest_signups_service(contact.id).await?;
+
+    Ok(GuestSignupListResponse {
+        first_name: contact.first_name,
+        last_name: contact.last_name,
+        signups: signups
+            .into_iter()
+            .map(|(signup, shift, event)| GuestSignupResponse::new(signup, shift, event))
+            .collect(),
+    })
+}
+
+#[post("/api/public/rsvp/{token}/signups/{signup_id}/cancel")]
+pub async fn cancel_guest_signup(
+    token: String,
+    signup_id: i32,
+) -> Result<EventSignupResponse, ServerFnError> {
+    let contact_id = verify_guest_signup_token(&token)?;
+
+    let cancelled = cancel_guest_signup_service(contact_id, signup_id).await?;
+
+    Ok(EventSignupResponse::from(cancelled))
+}
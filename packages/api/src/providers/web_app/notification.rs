@@ -0,0 +1,224 @@
+#[cfg(feature = "server")]
+use crate::enums::{Capability, NotificationType};
+use crate::http::AuthSession;
+use crate::interfaces::{
+    CreateNotificationRequest, ListNotificationsRequest, MarkAllNotificationsReadResponse,
+    NotificationListResponse, NotificationPreferenceResponse, NotificationPreferencesResponse,
+    NotificationResponse, PaginationParams, UnsubscribeNotificationsResponse,
+    UpdateNotificationPreferencesRequest,
+};
+#[cfg(feature = "server")]
+use crate::providers::require_active_membership_with_capability;
+#[cfg(feature = "server")]
+use crate::redis::redis_subscribe_notifications;
+#[cfg(feature = "server")]
+use crate::services::{
+    create_notification as create_notification_service,
+    delete_notification as delete_notification_service, get_membership, get_session_by_token,
+    list_notification_preferences as list_notification_preferences_service,
+    list_notifications as list_notifications_service,
+    mark_all_notifications_read as mark_all_notifications_read_service,
+    mark_notification_read as mark_notification_read_service, unsubscribe_from_notifications,
+    update_notification_preferences as update_notification_preferences_service,
+};
+use dioxus::fullstack::ServerEvents;
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use futures::StreamExt;
+#[cfg(feature = "server")]
+use std::time::Duration;
+#[cfg(feature = "server")]
+use tokio::time::{timeout_at, Instant};
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+#[post("/api/notifications/list", auth: AuthSession)]
+pub async fn list_notifications(
+    request: ListNotificationsRequest,
+) -> Result<NotificationListResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let (page, per_page) = PaginationParams::resolve(request.page, request.per_page);
+    let (notifications, total, unread_count) = list_notifications_service(
+        session.user_id,
+        request.unread_only.unwrap_or(false),
+        page,
+        per_page,
+    )
+    .await?;
+
+    Ok(NotificationListResponse {
+        notifications: notifications
+            .into_iter()
+            .map(NotificationResponse::from)
+            .collect(),
+        total,
+        unread_count,
+        page,
+        per_page,
+    })
+}
+
+#[post("/api/notifications/create", auth: AuthSession)]
+pub async fn create_notification(
+    request: CreateNotificationRequest,
+) -> Result<NotificationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::NotificationsSend).await?;
+
+    get_membership(membership.organization_id, request.user_id)
+        .await?
+        .ok_or_else(|| ServerFnError::new("Member not found in this organization"))?;
+
+    let link = request
+        .link
+        .map(|link| link.trim().to_string())
+        .filter(|link| !link.is_empty());
+
+    let notification = create_notification_service(
+        request.user_id,
+        Some(membership.organization_id),
+        request.notification_type.unwrap_or(NotificationType::Info),
+        request.title.trim().to_string(),
+        request.message.trim().to_string(),
+        link,
+    )
+    .await?;
+
+    Ok(NotificationResponse::from(notification))
+}
+
+#[post("/api/notifications/{notification_id}/read", auth: AuthSession)]
+pub async fn mark_notification_read(
+    notification_id: i32,
+) -> Result<NotificationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let notification = mark_notification_read_service(session.user_id, notification_id).await?;
+
+    Ok(NotificationResponse::from(notification))
+}
+
+#[post("/api/notifications/read-all", auth: AuthSession)]
+pub async fn mark_all_notifications_read() -> Result<MarkAllNotificationsReadResponse, ServerFnError>
+{
+    let session = auth.require_auth()?;
+
+    let updated = mark_all_notifications_read_service(session.user_id).await?;
+
+    Ok(MarkAllNotificationsReadResponse {
+        updated: updated as i64,
+    })
+}
+
+#[post("/api/notifications/{notification_id}/delete", auth: AuthSession)]
+pub async fn delete_notification(notification_id: i32) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+
+    delete_notification_service(session.user_id, notification_id).await?;
+
+    Ok(())
+}
+
+// how often an open stream makes sure its session hasn't been logged out or expired
+#[cfg(feature = "server")]
+const NOTIFICATION_STREAM_SESSION_CHECK_SECONDS: u64 = 60;
+
+// looked up without sliding the expiry, an open stream shouldn't keep a session alive
+#[cfg(feature = "server")]
+async fn is_session_active(token: &str) -> bool {
+    match Uuid::parse_str(token) {
+        Ok(token) => get_session_by_token(token).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+// pushes each notification for the signed-in user as it's created, on any server
+// instance, for as long as the client keeps the connection open and the session lasts
+#[get("/api/notifications/stream", auth: AuthSession)]
+pub async fn stream_notifications() -> Result<ServerEvents<NotificationResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let mut published = Box::pin(redis_subscribe_notifications(session.user_id)?);
+
+    Ok(ServerEvents::new(move |mut sender| async move {
+        let check_interval = Duration::from_secs(NOTIFICATION_STREAM_SESSION_CHECK_SECONDS);
+        let mut next_check = Instant::now() + check_interval;
+
+        loop {
+            let json = match timeout_at(next_check, published.next()).await {
+                Ok(Some(json)) => json,
+                Ok(None) => break,
+                // the session was logged out or ran out since the stream opened
+                Err(_) => {
+                    if !is_session_active(&session.token).await {
+                        break;
+                    }
+                    next_check = Instant::now() + check_interval;
+                    continue;
+                }
+            };
+
+            let Ok(notification) = serde_json::from_str::<NotificationResponse>(&json) else {
+                continue;
+            };
+
+            // the client went away
+            if sender.send(notification).await.is_err() {
+                break;
+            }
+        }
+    }))
+}
+
+#[get("/api/account/notification-preferences", auth: AuthSession)]
+pub async fn list_notification_preferences(
+) -> Result<NotificationPreferencesResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let preferences = list_notification_preferences_service(session.user_id).await?;
+
+    Ok(NotificationPreferencesResponse {
+        preferences: preferences
+            .into_iter()
+            .map(NotificationPreferenceResponse::from)
+            .collect(),
+    })
+}
+
+#[post("/api/account/notification-preferences/update", auth: AuthSession)]
+pub async fn update_notification_preferences(
+    request: UpdateNotificationPreferencesRequest,
+) -> Result<NotificationPreferencesResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let preferences = update_notification_preferences_service(
+        session.user_id,
+        request.organization_id,
+        request
+            .preferences
+            .into_iter()
+            .map(|setting| (setting.notification_type, setting.channel, setting.enabled))
+            .collect(),
+    )
+    .await?;
+
+    Ok(NotificationPreferencesResponse {
+        preferences: preferences
+            .into_iter()
+            .map(NotificationPreferenceResponse::from)
+            .collect(),
+    })
+}
+
+// confirmed from the unsubscribe page linked in the footer of notification emails,
+// the signed token is the only credential so it works without signing in
+#[post("/api/public/unsubscribe/{token}")]
+pub async fn unsubscribe_notifications(
+    token: String,
+) -> Result<UnsubscribeNotificationsResponse, ServerFnError> {
+    let (_, _, notification_type) = unsubscribe_from_notifications(&token).await?;
+
+    Ok(UnsubscribeNotificationsResponse { notification_type })
+}
@@ -1,21 +1,45 @@
-use crate::enums::MemberRole;
+use crate::enums::{Capability, MemberRole};
 use crate::http::AuthSession;
+#[cfg(feature = "server")]
+use crate::http::ValidatedSession;
 use crate::interfaces::{
-    CreateOrganizationRequest, InviteMemberRequest, OrganizationMemberListResponse,
-    OrganizationMemberResponse, OrganizationResponse, PaginationParams, UpdateOrganizationRequest,
+    ChangeOrganizationSlugRequest, CreateOrganizationRequest, CreateOrganizationRoleRequest,
+    InvitationDetailsResponse, InvitationListResponse, InvitationResponse, InviteMemberRequest,
+    OrganizationMemberListResponse, OrganizationMemberResponse, OrganizationPlanResponse,
+    OrganizationResponse, OrganizationRoleResponse, OwnershipTransferResponse, PaginationParams,
+    PlanUsageResponse, PublicOrganizationSlugResponse, RetiredOrganizationSlugResponse,
+    UpdateOrganizationRequest, UpdateOrganizationRoleRequest,
 };
 #[cfg(feature = "server")]
 use crate::models::OrganizationMember;
 #[cfg(feature = "server")]
 use crate::redis::update_redis_cached_session_active_organization_membership_id;
 #[cfg(feature = "server")]
+use crate::services::web_app::get_organization_plan_usage as get_organization_plan_usage_service;
+#[cfg(feature = "server")]
 use crate::services::{
-    create_invitation, create_organization as create_organization_service, get_member_by_id,
-    get_members_with_user_info, get_membership, get_organization_by_id, list_user_organizations,
-    remove_member, set_active_organization as set_active_organization_service, update_member_role,
+    accept_invitation as accept_invitation_service,
+    accept_ownership_transfer as accept_ownership_transfer_service,
+    archive_organization as archive_organization_service, assign_member_custom_role,
+    cancel_ownership_transfer as cancel_ownership_transfer_service, change_organization_slug,
+    create_invitation, create_organization as create_organization_service,
+    create_organization_role as create_organization_role_service,
+    decline_ownership_transfer as decline_ownership_transfer_service,
+    delete_organization_role as delete_organization_role_service, get_active_member_by_id,
+    get_invitation_by_token, get_member_by_id, get_member_capabilities, get_members_with_user_info,
+    get_membership, get_organization_by_id, get_pending_ownership_transfer,
+    leave_organization as leave_organization_service, list_incoming_ownership_transfers,
+    list_organization_plan_history, list_organization_roles, list_pending_invitations,
+    list_retired_organization_slugs, list_user_organizations, remove_member,
+    request_ownership_transfer, resend_invitation as resend_invitation_service,
+    resolve_organization_slug, revoke_invitation as revoke_invitation_service,
+    set_active_organization as set_active_organization_service, update_member_role,
     update_organization as update_organization_service,
+    update_organization_role as update_organization_role_service,
 };
 use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use uuid::Uuid;
 
 #[cfg(feature = "server")]
 async fn require_membership(
@@ -28,32 +52,64 @@ async fn require_membership(
 }
 
 #[cfg(feature = "server")]
-async fn require_membership_with_role(
+async fn require_capability(
+    membership: &OrganizationMember,
+    capability: Capability,
+) -> Result<(), ServerFnError> {
+    let capabilities = get_member_capabilities(membership).await?;
+    if !capabilities.contains(&capability) {
+        return Err(ServerFnError::new(format!(
+            "Missing permission: {}",
+            capability.display_name()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+pub(crate) async fn require_membership_with_capability(
     organization_id: i32,
     user_id: i32,
-    min_role: MemberRole,
+    capability: Capability,
 ) -> Result<OrganizationMember, ServerFnError> {
     let membership = require_membership(organization_id, user_id).await?;
-    let caller_role = membership.get_role();
-    let allowed = match min_role {
-        MemberRole::Owner => caller_role == MemberRole::Owner,
-        MemberRole::Admin => caller_role == MemberRole::Owner || caller_role == MemberRole::Admin,
-        MemberRole::Manager => {
-            caller_role == MemberRole::Owner
-                || caller_role == MemberRole::Admin
-                || caller_role == MemberRole::Manager
-        }
-        MemberRole::Member => true,
-    };
-    if !allowed {
-        return Err(ServerFnError::new(format!(
-            "Requires {} role or higher",
-            min_role.display_name()
-        )));
+    require_capability(&membership, capability).await?;
+    Ok(membership)
+}
+
+// ownership itself can't be granted, transferring and archiving stay with the owner
+#[cfg(feature = "server")]
+async fn require_ownership(
+    organization_id: i32,
+    user_id: i32,
+) -> Result<OrganizationMember, ServerFnError> {
+    let membership = require_membership(organization_id, user_id).await?;
+    if membership.get_role() != MemberRole::Owner {
+        return Err(ServerFnError::new("Only the owner can do this"));
     }
     Ok(membership)
 }
 
+// resolves the session's active organization membership and checks the caller's capabilities in it
+#[cfg(feature = "server")]
+pub(crate) async fn require_active_membership_with_capability(
+    session: &ValidatedSession,
+    capability: Capability,
+) -> Result<OrganizationMember, ServerFnError> {
+    let membership_id = session
+        .active_organization_membership_id
+        .ok_or_else(|| ServerFnError::new("No active organization selected"))?;
+
+    let membership = get_active_member_by_id(membership_id).await?;
+
+    if membership.user_id != session.user_id {
+        return Err(ServerFnError::new("Not a member of this organization"));
+    }
+
+    require_capability(&membership, capability).await?;
+    Ok(membership)
+}
+
 #[post("/api/org/create", auth: AuthSession)]
 pub async fn create_organization(
     request: CreateOrganizationRequest,
@@ -126,7 +182,12 @@ pub async fn update_organization(
     request: UpdateOrganizationRequest,
 ) -> Result<OrganizationResponse, ServerFnError> {
     let session = auth.require_auth()?;
-    require_membership_with_role(organization_id, session.user_id, MemberRole::Admin).await?;
+    require_membership_with_capability(
+        organization_id,
+        session.user_id,
+        Capability::OrganizationManage,
+    )
+    .await?;
 
     let update = crate::models::OrganizationUpdate {
         name: request.name,
@@ -142,6 +203,9 @@ pub async fn update_organization(
         zip_code: request.zip_code,
         country: request.country,
         timezone: request.timezone,
+        shift_conflict_policy: request
+            .shift_conflict_policy
+            .map(|policy| policy.as_str().to_string()),
         ..Default::default()
     };
 
@@ -150,6 +214,62 @@ pub async fn update_organization(
     Ok(OrganizationResponse::from(organization))
 }
 
+// the previous slug keeps redirecting to the organization and stays reserved for a while
+#[post("/api/org/{organization_id}/slug", auth: AuthSession)]
+pub async fn change_slug(
+    organization_id: i32,
+    request: ChangeOrganizationSlugRequest,
+) -> Result<OrganizationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(
+        organization_id,
+        session.user_id,
+        Capability::OrganizationManage,
+    )
+    .await?;
+
+    let organization =
+        change_organization_slug(organization_id, request.slug, session.user_id).await?;
+
+    Ok(OrganizationResponse::from(organization))
+}
+
+#[get("/api/org/{organization_id}/retired-slugs", auth: AuthSession)]
+pub async fn get_retired_slugs(
+    organization_id: i32,
+) -> Result<Vec<RetiredOrganizationSlugResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(
+        organization_id,
+        session.user_id,
+        Capability::OrganizationManage,
+    )
+    .await?;
+
+    let retired_slugs = list_retired_organization_slugs(organization_id).await?;
+
+    Ok(retired_slugs
+        .into_iter()
+        .map(RetiredOrganizationSlugResponse::from)
+        .collect())
+}
+
+// public event and survey pages look up their organization here and follow retired
+// slugs, archived organizations are hidden
+#[get("/api/public/organizations/{slug}")]
+pub async fn resolve_slug(slug: String) -> Result<PublicOrganizationSlugResponse, ServerFnError> {
+    let (organization, redirected) = resolve_organization_slug(&slug.trim().to_lowercase())
+        .await?
+        .filter(|(organization, _)| !organization.is_archived())
+        .ok_or_else(|| ServerFnError::new("Organization not found"))?;
+
+    Ok(PublicOrganizationSlugResponse {
+        name: organization.name,
+        slug: organization.slug,
+        redirected,
+    })
+}
+
 #[get("/api/org/{organization_id}/members", auth: AuthSession)]
 pub async fn get_organization_members(
     organization_id: i32,
@@ -157,7 +277,8 @@ pub async fn get_organization_members(
     per_page: Option<i64>,
 ) -> Result<OrganizationMemberListResponse, ServerFnError> {
     let session = auth.require_auth()?;
-    require_membership(organization_id, session.user_id).await?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::TeamView)
+        .await?;
 
     let (page, per_page) = PaginationParams::resolve(page, per_page);
     let (members_with_info, total) =
@@ -166,9 +287,11 @@ pub async fn get_organization_members(
     let members = members_with_info
         .into_iter()
         .map(|info| OrganizationMemberResponse {
+            id: info.member.id,
             user_id: info.member.user_id,
             organization_id: info.member.organization_id,
             role: info.member.role,
+            custom_role_id: info.member.custom_role_id,
             email: info.email,
             first_name: info.first_name,
             last_name: info.last_name,
@@ -189,7 +312,8 @@ pub async fn invite_member(
     request: InviteMemberRequest,
 ) -> Result<(), ServerFnError> {
     let session = auth.require_auth()?;
-    require_membership_with_role(organization_id, session.user_id, MemberRole::Admin).await?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::TeamInvite)
+        .await?;
 
     let role =
         MemberRole::from_str(&request.role).ok_or_else(|| ServerFnError::new("Invalid role"))?;
@@ -209,7 +333,8 @@ pub async fn remove_organization_member(
     member_id: i32,
 ) -> Result<(), ServerFnError> {
     let session = auth.require_auth()?;
-    require_membership_with_role(organization_id, session.user_id, MemberRole::Admin).await?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::TeamManage)
+        .await?;
 
     let target_member = get_member_by_id(member_id).await?;
 
@@ -229,7 +354,8 @@ pub async fn update_organization_member_role(
     new_role: String,
 ) -> Result<(), ServerFnError> {
     let session = auth.require_auth()?;
-    require_membership_with_role(organization_id, session.user_id, MemberRole::Owner).await?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::RolesManage)
+        .await?;
 
     let target_member = get_member_by_id(member_id).await?;
 
@@ -243,3 +369,322 @@ pub async fn update_organization_member_role(
 
     Ok(())
 }
+
+// any member but the owner can leave, if it was their active organization the
+// session goes back to having none selected
+#[post("/api/org/{organization_id}/leave", auth: AuthSession)]
+pub async fn leave_organization(organization_id: i32) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+
+    leave_organization_service(organization_id, session.user_id).await?;
+
+    Ok(())
+}
+
+#[post("/api/org/{organization_id}/archive", auth: AuthSession)]
+pub async fn archive_organization(
+    organization_id: i32,
+) -> Result<OrganizationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_ownership(organization_id, session.user_id).await?;
+
+    let organization = archive_organization_service(organization_id, session.user_id).await?;
+
+    Ok(OrganizationResponse::from(organization))
+}
+
+#[get("/api/org/{organization_id}/invitations", auth: AuthSession)]
+pub async fn get_organization_invitations(
+    organization_id: i32,
+) -> Result<InvitationListResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::TeamInvite)
+        .await?;
+
+    let invitations = list_pending_invitations(organization_id).await?;
+
+    Ok(InvitationListResponse {
+        invitations: invitations
+            .into_iter()
+            .map(InvitationResponse::from)
+            .collect(),
+    })
+}
+
+#[post("/api/org/{organization_id}/invitations/{invitation_id}/revoke", auth: AuthSession)]
+pub async fn revoke_invitation(
+    organization_id: i32,
+    invitation_id: i32,
+) -> Result<InvitationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::TeamInvite)
+        .await?;
+
+    let invitation = revoke_invitation_service(organization_id, invitation_id).await?;
+
+    Ok(InvitationResponse::from(invitation))
+}
+
+#[post("/api/org/{organization_id}/invitations/{invitation_id}/resend", auth: AuthSession)]
+pub async fn resend_invitation(
+    organization_id: i32,
+    invitation_id: i32,
+) -> Result<InvitationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::TeamInvite)
+        .await?;
+
+    let invitation = resend_invitation_service(organization_id, invitation_id).await?;
+
+    Ok(InvitationResponse::from(invitation))
+}
+
+// the token from the invitation email is the only credential needed to look
+#[get("/api/public/invitations/{token}")]
+pub async fn get_invitation(token: String) -> Result<InvitationDetailsResponse, ServerFnError> {
+    let token = Uuid::parse_str(&token).map_err(|_| ServerFnError::new("Invalid token format"))?;
+
+    let (invitation, organization_name, inviter_name) = get_invitation_by_token(token).await?;
+
+    Ok(InvitationDetailsResponse::new(
+        invitation,
+        organization_name,
+        inviter_name,
+    ))
+}
+
+// the new organization becomes the active one, like creating an organization does
+#[post("/api/invitations/{token}/accept", auth: AuthSession)]
+pub async fn accept_invitation(token: String) -> Result<OrganizationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let token = Uuid::parse_str(&token).map_err(|_| ServerFnError::new("Invalid token format"))?;
+
+    let membership = accept_invitation_service(token, session.user_id).await?;
+
+    set_active_organization_service(session.session_id, Some(membership.id)).await?;
+
+    update_redis_cached_session_active_organization_membership_id(
+        &session.token,
+        Some(membership.id),
+    )
+    .await
+    .ok();
+
+    let organization = get_organization_by_id(membership.organization_id).await?;
+
+    Ok(OrganizationResponse::from(organization))
+}
+
+#[post("/api/org/{organization_id}/transfer-ownership", auth: AuthSession)]
+pub async fn transfer_ownership(
+    organization_id: i32,
+    member_id: i32,
+) -> Result<OwnershipTransferResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_ownership(organization_id, session.user_id).await?;
+
+    let transfer = request_ownership_transfer(organization_id, session.user_id, member_id).await?;
+    let organization = get_organization_by_id(organization_id).await?;
+
+    Ok(OwnershipTransferResponse::new(transfer, organization))
+}
+
+#[get("/api/org/{organization_id}/ownership-transfer", auth: AuthSession)]
+pub async fn get_ownership_transfer(
+    organization_id: i32,
+) -> Result<Option<OwnershipTransferResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_ownership(organization_id, session.user_id).await?;
+
+    let Some(transfer) = get_pending_ownership_transfer(organization_id).await? else {
+        return Ok(None);
+    };
+    let organization = get_organization_by_id(organization_id).await?;
+
+    Ok(Some(OwnershipTransferResponse::new(transfer, organization)))
+}
+
+#[post("/api/org/{organization_id}/ownership-transfer/cancel", auth: AuthSession)]
+pub async fn cancel_ownership_transfer(
+    organization_id: i32,
+) -> Result<OwnershipTransferResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_ownership(organization_id, session.user_id).await?;
+
+    let transfer = cancel_ownership_transfer_service(organization_id, session.user_id).await?;
+    let organization = get_organization_by_id(organization_id).await?;
+
+    Ok(OwnershipTransferResponse::new(transfer, organization))
+}
+
+#[get("/api/account/ownership-transfers", auth: AuthSession)]
+pub async fn get_incoming_ownership_transfers(
+) -> Result<Vec<OwnershipTransferResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let transfers = list_incoming_ownership_transfers(session.user_id).await?;
+
+    Ok(transfers
+        .into_iter()
+        .map(|(transfer, organization)| OwnershipTransferResponse::new(transfer, organization))
+        .collect())
+}
+
+#[post("/api/ownership-transfers/{transfer_id}/accept", auth: AuthSession)]
+pub async fn accept_ownership_transfer(
+    transfer_id: i32,
+) -> Result<OrganizationResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let membership = accept_ownership_transfer_service(transfer_id, session.user_id).await?;
+    let organization = get_organization_by_id(membership.organization_id).await?;
+
+    Ok(OrganizationResponse::from(organization))
+}
+
+#[post("/api/ownership-transfers/{transfer_id}/decline", auth: AuthSession)]
+pub async fn decline_ownership_transfer(
+    transfer_id: i32,
+) -> Result<OwnershipTransferResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let transfer = decline_ownership_transfer_service(transfer_id, session.user_id).await?;
+    let organization = get_organization_by_id(transfer.organization_id).await?;
+
+    Ok(OwnershipTransferResponse::new(transfer, organization))
+}
+
+#[get("/api/org/{organization_id}/roles", auth: AuthSession)]
+pub async fn get_organization_roles(
+    organization_id: i32,
+) -> Result<Vec<OrganizationRoleResponse>, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::RolesManage)
+        .await?;
+
+    let roles = list_organization_roles(organization_id).await?;
+
+    Ok(roles
+        .into_iter()
+        .map(OrganizationRoleResponse::from)
+        .collect())
+}
+
+#[post("/api/org/{organization_id}/roles/create", auth: AuthSession)]
+pub async fn create_organization_role(
+    organization_id: i32,
+    request: CreateOrganizationRoleRequest,
+) -> Result<OrganizationRoleResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership = require_membership_with_capability(
+        organization_id,
+        session.user_id,
+        Capability::RolesManage,
+    )
+    .await?;
+
+    let role = create_organization_role_service(
+        organization_id,
+        request.name,
+        request.capabilities,
+        &membership,
+    )
+    .await?;
+
+    Ok(OrganizationRoleResponse::from(role))
+}
+
+#[post("/api/org/{organization_id}/roles/{role_id}/update", auth: AuthSession)]
+pub async fn update_organization_role(
+    organization_id: i32,
+    role_id: i32,
+    request: UpdateOrganizationRoleRequest,
+) -> Result<OrganizationRoleResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership = require_membership_with_capability(
+        organization_id,
+        session.user_id,
+        Capability::RolesManage,
+    )
+    .await?;
+
+    let role = update_organization_role_service(
+        organization_id,
+        role_id,
+        request.name,
+        request.capabilities,
+        &membership,
+    )
+    .await?;
+
+    Ok(OrganizationRoleResponse::from(role))
+}
+
+#[post("/api/org/{organization_id}/roles/{role_id}/delete", auth: AuthSession)]
+pub async fn delete_organization_role(
+    organization_id: i32,
+    role_id: i32,
+) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership = require_membership_with_capability(
+        organization_id,
+        session.user_id,
+        Capability::RolesManage,
+    )
+    .await?;
+
+    delete_organization_role_service(organization_id, role_id, &membership).await?;
+
+    Ok(())
+}
+
+// a custom role replaces the defaults of the member's role, None goes back to them
+#[post("/api/org/{organization_id}/members/{member_id}/custom-role", auth: AuthSession)]
+pub async fn set_member_custom_role(
+    organization_id: i32,
+    member_id: i32,
+    role_id: Option<i32>,
+) -> Result<(), ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership = require_membership_with_capability(
+        organization_id,
+        session.user_id,
+        Capability::RolesManage,
+    )
+    .await?;
+
+    assign_member_custom_role(organization_id, member_id, role_id, &membership).await?;
+
+    Ok(())
+}
+
+#[get("/api/org/{organization_id}/plan", auth: AuthSession)]
+pub async fn get_organization_plan(
+    organization_id: i32,
+) -> Result<OrganizationPlanResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::BillingManage)
+        .await?;
+
+    let history = list_organization_plan_history(organization_id).await?;
+
+    Ok(OrganizationPlanResponse::new(organization_id, history))
+}
+
+#[get("/api/org/{organization_id}/plan/usage", auth: AuthSession)]
+pub async fn get_organization_plan_usage(
+    organization_id: i32,
+) -> Result<PlanUsageResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    require_membership_with_capability(organization_id, session.user_id, Capability::BillingManage)
+        .await?;
+
+    let usage = get_organization_plan_usage_service(organization_id).await?;
+
+    Ok(PlanUsageResponse {
+        member_seats: usage.member_seats,
+        emails_this_month: usage.emails_this_month,
+        sms_this_month: usage.sms_this_month,
+        storage_bytes: usage.storage_bytes,
+    })
+}
@@ -0,0 +1,40 @@
+#[cfg(feature = "server")]
+use crate::http::extract_client_ip;
+use crate::http::AuthSession;
+use crate::interfaces::{SmsConsentResponse, UpdateSmsConsentRequest};
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    get_user_sms_consent, opt_in_user_to_sms, opt_out_user_from_sms, parse_sms_phone_number,
+};
+use dioxus::fullstack::HeaderMap;
+use dioxus::prelude::*;
+
+#[get("/api/account/sms-consent", auth: AuthSession)]
+pub async fn get_sms_consent() -> Result<SmsConsentResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    let (phone_number, consent) = get_user_sms_consent(session.user_id).await?;
+
+    Ok(SmsConsentResponse::new(phone_number, consent))
+}
+
+// every change is a new ledger entry with the address it came from
+#[post("/api/account/sms-consent/update", auth: AuthSession, headers: HeaderMap)]
+pub async fn update_sms_consent(
+    request: UpdateSmsConsentRequest,
+) -> Result<SmsConsentResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let ip_address = extract_client_ip(&headers);
+
+    let consent = if request.opt_in {
+        let phone_number = parse_sms_phone_number(request.phone_number.as_deref().unwrap_or(""))?;
+        opt_in_user_to_sms(session.user_id, phone_number, ip_address).await?
+    } else {
+        opt_out_user_from_sms(session.user_id, ip_address).await?
+    };
+
+    Ok(SmsConsentResponse::new(
+        Some(consent.phone_number.clone()),
+        Some(consent),
+    ))
+}
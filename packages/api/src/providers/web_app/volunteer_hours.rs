@@ -0,0 +1,87 @@
+#[cfg(feature = "server")]
+use crate::enums::Capability;
+use crate::http::AuthSession;
+use crate::interfaces::{
+    CreateVolunteerHourAdjustmentRequest, UserVolunteerHoursResponse,
+    VolunteerHourAdjustmentListResponse, VolunteerHourAdjustmentResponse,
+    VolunteerHoursLeaderboardRequest, VolunteerHoursLeaderboardResponse,
+};
+#[cfg(feature = "server")]
+use crate::providers::require_active_membership_with_capability;
+#[cfg(feature = "server")]
+use crate::services::web_app::{
+    create_volunteer_hour_adjustment as create_volunteer_hour_adjustment_service,
+    get_user_volunteer_hours,
+    get_volunteer_hours_leaderboard as get_volunteer_hours_leaderboard_service,
+    list_volunteer_hour_adjustments as list_volunteer_hour_adjustments_service,
+};
+use dioxus::prelude::*;
+
+#[post("/api/volunteer-hours/leaderboard", auth: AuthSession)]
+pub async fn get_volunteer_hours_leaderboard(
+    request: VolunteerHoursLeaderboardRequest,
+) -> Result<VolunteerHoursLeaderboardResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::VolunteerHoursView).await?;
+
+    let volunteers = get_volunteer_hours_leaderboard_service(
+        membership.organization_id,
+        request.range_start,
+        request.range_end,
+        request.limit,
+    )
+    .await?;
+
+    Ok(VolunteerHoursLeaderboardResponse { volunteers })
+}
+
+#[get("/api/volunteer-hours/users/{user_id}/adjustments", auth: AuthSession)]
+pub async fn list_volunteer_hour_adjustments(
+    user_id: i32,
+) -> Result<VolunteerHourAdjustmentListResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::VolunteerHoursManage)
+            .await?;
+
+    let adjustments =
+        list_volunteer_hour_adjustments_service(membership.organization_id, user_id).await?;
+
+    Ok(VolunteerHourAdjustmentListResponse {
+        adjustments: adjustments
+            .into_iter()
+            .map(VolunteerHourAdjustmentResponse::from)
+            .collect(),
+    })
+}
+
+#[post("/api/volunteer-hours/adjustments/create", auth: AuthSession)]
+pub async fn create_volunteer_hour_adjustment(
+    request: CreateVolunteerHourAdjustmentRequest,
+) -> Result<VolunteerHourAdjustmentResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+    let membership =
+        require_active_membership_with_capability(&session, Capability::VolunteerHoursManage)
+            .await?;
+
+    let adjustment = create_volunteer_hour_adjustment_service(
+        membership.organization_id,
+        session.user_id,
+        request.user_id,
+        request.event_signup_id,
+        request.minutes,
+        request.reason.trim().to_string(),
+    )
+    .await?;
+
+    Ok(VolunteerHourAdjustmentResponse::from(adjustment))
+}
+
+// the signed-in user's own totals across every organization they've volunteered with
+#[get("/api/account/volunteer-hours", auth: AuthSession)]
+pub async fn get_my_volunteer_hours() -> Result<UserVolunteerHoursResponse, ServerFnError> {
+    let session = auth.require_auth()?;
+
+    Ok(get_user_volunteer_hours(session.user_id).await?)
+}
@@ -1,10 +1,29 @@
-use crate::enums::{ARTICLE_CACHE_EXPIRY_SECONDS, SESSION_EXPIRY_SECONDS};
+use crate::enums::{
+    ARTICLE_CACHE_EXPIRY_SECONDS, PUBLIC_EVENT_CACHE_EXPIRY_SECONDS, SESSION_EXPIRY_SECONDS,
+};
 use crate::error::{redis_error, AppError};
 use deadpool_redis::{redis, redis::AsyncCommands, Config, Connection, Pool, Runtime::Tokio1};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::future::{select, Either};
+use futures::{stream, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::{env, sync::OnceLock};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::{env, time::Duration};
+use tokio::sync::broadcast::{self, error::RecvError};
 
 static REDIS_POOL: OnceLock<Pool> = OnceLock::new();
+// pub/sub subscribers need their own connection, pooled connections can't be put
+// into subscriber mode
+static REDIS_CLIENT: OnceLock<redis::Client> = OnceLock::new();
+// every notification stream on this server shares one subscriber connection, which
+// is subscribed to the channel of each user with an open stream. payloads are
+// fanned out to that user's streams only
+static NOTIFICATION_SUBSCRIBER: OnceLock<NotificationSubscriber> = OnceLock::new();
+
+// how many notifications one user's slow stream can fall behind before it skips ahead
+const NOTIFICATION_USER_BROADCAST_CAPACITY: usize = 64;
+const NOTIFICATION_SUBSCRIBER_RETRY_SECONDS: u64 = 5;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CachedSession {
@@ -28,9 +47,15 @@ pub fn initialize_redis_pool() -> Result<(), AppError> {
         .create_pool(Some(Tokio1))
         .map_err(|error| AppError::ConfigError(format!("Redis pool error: {}", error)))?;
 
+    let client = redis::Client::open(redis_url.as_str())
+        .map_err(|error| AppError::ConfigError(format!("Redis client error: {}", error)))?;
+
     REDIS_POOL
         .set(pool)
         .map_err(|_| AppError::ConfigError("Redis pool already initialized".to_string()))?;
+    REDIS_CLIENT
+        .set(client)
+        .map_err(|_| AppError::ConfigError("Redis client already initialized".to_string()))?;
 
     tracing::info!("Redis connection pool initialized");
 
@@ -261,3 +286,270 @@ pub async fn invalidate_redis_cached_article(slug: &str) -> Result<(), AppError>
 
     Ok(())
 }
+
+pub async fn redis_cache_public_event(event_id: i32, json: &str) -> Result<(), AppError> {
+    let mut connection = get_redis_connection().await?;
+
+    let key = format!("public_event:{}", event_id);
+
+    connection
+        .set_ex::<&str, &str, ()>(&key, json, PUBLIC_EVENT_CACHE_EXPIRY_SECONDS)
+        .await
+        .map_err(redis_error)?;
+
+    Ok(())
+}
+
+pub async fn get_redis_cached_public_event(event_id: i32) -> Result<Option<String>, AppError> {
+    let mut connection = get_redis_connection().await?;
+
+    let key = format!("public_event:{}", event_id);
+
+    let value: Option<String> = connection.get(&key).await.map_err(redis_error)?;
+
+    Ok(value)
+}
+
+pub async fn invalidate_redis_cached_public_event(event_id: i32) -> Result<(), AppError> {
+    let mut connection = get_redis_connection().await?;
+
+    let key = format!("public_event:{}", event_id);
+
+    connection
+        .del::<&str, ()>(&key)
+        .await
+        .map_err(redis_error)?;
+
+    Ok(())
+}
+
+// search results are keyed by a hash of the normalized filters and simply expire,
+// there are too many filter combinations to invalidate individually
+pub async fn redis_cache_public_event_search(query_hash: &str, json: &str) -> Result<(), AppError> {
+    let mut connection = get_redis_connection().await?;
+
+    let key = format!("public_event_search:{}", query_hash);
+
+    connection
+        .set_ex::<&str, &str, ()>(&key, json, PUBLIC_EVENT_CACHE_EXPIRY_SECONDS)
+        .await
+        .map_err(redis_error)?;
+
+    Ok(())
+}
+
+pub async fn get_redis_cached_public_event_search(
+    query_hash: &str,
+) -> Result<Option<String>, AppError> {
+    let mut connection = get_redis_connection().await?;
+
+    let key = format!("public_event_search:{}", query_hash);
+
+    let value: Option<String> = connection.get(&key).await.map_err(redis_error)?;
+
+    Ok(value)
+}
+
+const NOTIFICATION_CHANNEL_PREFIX: &str = "notifications:";
+
+fn notification_channel(user_id: i32) -> String {
+    format!("{}{}", NOTIFICATION_CHANNEL_PREFIX, user_id)
+}
+
+pub async fn redis_publish_notification(user_id: i32, json: &str) -> Result<(), AppError> {
+    let mut connection = get_redis_connection().await?;
+
+    connection
+        .publish::<String, &str, ()>(notification_channel(user_id), json)
+        .await
+        .map_err(redis_error)?;
+
+    Ok(())
+}
+
+struct NotificationSubscriber {
+    users: Mutex<HashMap<i32, broadcast::Sender<String>>>,
+    // user ids whose subscription should be brought in line with `users`
+    changes: UnboundedSender<i32>,
+}
+
+impl NotificationSubscriber {
+    fn get_sender(&self, user_id: i32) -> Option<broadcast::Sender<String>> {
+        self.users
+            .lock()
+            .ok()
+            .and_then(|users| users.get(&user_id).cloned())
+    }
+
+    fn is_subscribed(&self, user_id: i32) -> bool {
+        self.users
+            .lock()
+            .is_ok_and(|users| users.contains_key(&user_id))
+    }
+
+    fn user_ids(&self) -> Vec<i32> {
+        self.users
+            .lock()
+            .map(|users| users.keys().copied().collect())
+            .unwrap_or_default()
+    }
+}
+
+// one open stream, the user's channel is unsubscribed once their last stream drops
+struct NotificationSubscription {
+    user_id: i32,
+    receiver: broadcast::Receiver<String>,
+}
+
+impl Drop for NotificationSubscription {
+    fn drop(&mut self) {
+        let Some(subscriber) = NOTIFICATION_SUBSCRIBER.get() else {
+            return;
+        };
+        let Ok(mut users) = subscriber.users.lock() else {
+            return;
+        };
+
+        // this stream's receiver is the last one left
+        if users
+            .get(&self.user_id)
+            .is_some_and(|sender| sender.receiver_count() <= 1)
+        {
+            users.remove(&self.user_id);
+            subscriber.changes.unbounded_send(self.user_id).ok();
+        }
+    }
+}
+
+// holds the subscriber connection for the life of the server and reconnects when it
+// drops, re-subscribing every user with an open stream. anything published while
+// it's down is picked up on the next page load
+fn spawn_notification_subscriber(
+    client: &'static redis::Client,
+    mut changes: UnboundedReceiver<i32>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match client.get_async_pubsub().await {
+                Ok(pubsub) => {
+                    let (mut sink, mut messages) = pubsub.split();
+                    let Some(subscriber) = NOTIFICATION_SUBSCRIBER.get() else {
+                        return;
+                    };
+
+                    let channels: Vec<String> = subscriber
+                        .user_ids()
+                        .into_iter()
+                        .map(notification_channel)
+                        .collect();
+                    let resubscribed = if channels.is_empty() {
+                        Ok(())
+                    } else {
+                        sink.subscribe(channels).await
+                    };
+
+                    match resubscribed {
+                        Ok(()) => loop {
+                            match select(changes.next(), messages.next()).await {
+                                // a change is applied from the current state of the map,
+                                // so a late unsubscribe can't undo a newer subscribe
+                                Either::Left((Some(user_id), _)) => {
+                                    let channel = notification_channel(user_id);
+                                    let result = if subscriber.is_subscribed(user_id) {
+                                        sink.subscribe(channel).await
+                                    } else {
+                                        sink.unsubscribe(channel).await
+                                    };
+                                    if let Err(error) = result {
+                                        tracing::warn!(
+                                            "failed to update notification subscription: {}",
+                                            error
+                                        );
+                                        break;
+                                    }
+                                }
+                                Either::Left((None, _)) => return,
+                                Either::Right((Some(message), _)) => {
+                                    let user_id = message
+                                        .get_channel_name()
+                                        .strip_prefix(NOTIFICATION_CHANNEL_PREFIX)
+                                        .and_then(|user_id| user_id.parse::<i32>().ok());
+                                    let (Some(user_id), Ok(json)) =
+                                        (user_id, message.get_payload::<String>())
+                                    else {
+                                        continue;
+                                    };
+
+                                    if let Some(sender) = subscriber.get_sender(user_id) {
+                                        // the user's streams all closed in the meantime
+                                        sender.send(json).ok();
+                                    }
+                                }
+                                Either::Right((None, _)) => {
+                                    tracing::warn!("notification subscriber disconnected");
+                                    break;
+                                }
+                            }
+                        },
+                        Err(error) => {
+                            tracing::warn!("failed to subscribe to notifications: {}", error)
+                        }
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!("failed to connect notification subscriber: {}", error)
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(NOTIFICATION_SUBSCRIBER_RETRY_SECONDS)).await;
+        }
+    });
+}
+
+// yields the json payload of every notification published for the user until the
+// stream is dropped, the shared subscriber starts with the first stream
+pub fn redis_subscribe_notifications(user_id: i32) -> Result<impl Stream<Item = String>, AppError> {
+    let client = REDIS_CLIENT
+        .get()
+        .ok_or_else(|| AppError::ConfigError("Redis client not initialized".to_string()))?;
+
+    let subscriber = NOTIFICATION_SUBSCRIBER.get_or_init(|| {
+        let (changes, change_receiver) = unbounded();
+        spawn_notification_subscriber(client, change_receiver);
+        NotificationSubscriber {
+            users: Mutex::new(HashMap::new()),
+            changes,
+        }
+    });
+
+    let receiver = {
+        let mut users = subscriber.users.lock().map_err(|_| {
+            AppError::InternalError("Notification subscribers poisoned".to_string())
+        })?;
+
+        match users.get(&user_id) {
+            Some(sender) => sender.subscribe(),
+            None => {
+                let (sender, receiver) = broadcast::channel(NOTIFICATION_USER_BROADCAST_CAPACITY);
+                users.insert(user_id, sender);
+                subscriber.changes.unbounded_send(user_id).ok();
+                receiver
+            }
+        }
+    };
+
+    Ok(stream::unfold(
+        NotificationSubscription { user_id, receiver },
+        |mut subscription| async move {
+            loop {
+                match subscription.receiver.recv().await {
+                    Ok(json) => return Some((json, subscription)),
+                    // live delivery is best effort, skipped notifications still show
+                    // up on the next page load
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    ))
+}
@@ -71,6 +71,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    audit_logs (id) {
+        id -> Int4,
+        organization_id -> Nullable<Int4>,
+        actor_user_id -> Nullable<Int4>,
+        #[max_length = 50]
+        action -> Varchar,
+        details -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    calendar_feed_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        token -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     chat_conversations (id) {
         id -> Int4,
@@ -111,6 +132,64 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    contacts (id) {
+        id -> Int4,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 100]
+        first_name -> Varchar,
+        #[max_length = 100]
+        last_name -> Varchar,
+        #[max_length = 20]
+        phone_number -> Nullable<Varchar>,
+        #[max_length = 5]
+        zip_code -> Nullable<Varchar>,
+        user_id -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    event_co_hosts (id) {
+        id -> Int4,
+        event_id -> Int4,
+        organization_id -> Int4,
+        #[max_length = 20]
+        status -> Varchar,
+        #[max_length = 20]
+        data_sharing -> Varchar,
+        invited_by -> Nullable<Int4>,
+        responded_by -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        responded_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    event_series (id) {
+        id -> Int4,
+        event_id -> Int4,
+        #[max_length = 20]
+        frequency -> Varchar,
+        repeat_interval -> Int4,
+        by_weekday -> Array<Nullable<Text>>,
+        starts_on -> Date,
+        until_date -> Nullable<Date>,
+        occurrence_count -> Nullable<Int4>,
+        excluded_dates -> Array<Nullable<Date>>,
+        start_time_local -> Time,
+        duration_minutes -> Int4,
+        #[max_length = 50]
+        timezone -> Varchar,
+        capacity -> Nullable<Int4>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     event_shifts (id) {
         id -> Int4,
@@ -123,6 +202,9 @@ diesel::table! {
         notes -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        series_id -> Nullable<Int4>,
+        series_occurrence_date -> Nullable<Date>,
+        revision -> Int4,
     }
 }
 
@@ -130,13 +212,50 @@ diesel::table! {
     event_signups (id) {
         id -> Int4,
         event_shift_id -> Int4,
-        user_id -> Int4,
+        user_id -> Nullable<Int4>,
         #[max_length = 20]
         status -> Varchar,
         notes -> Nullable<Text>,
         signed_up_at -> Timestamptz,
         checked_in_at -> Nullable<Timestamptz>,
         cancelled_at -> Nullable<Timestamptz>,
+        contact_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    event_templates (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        event_name -> Varchar,
+        #[max_length = 50]
+        event_type -> Varchar,
+        description -> Nullable<Text>,
+        attendee_message -> Nullable<Text>,
+        image_url -> Nullable<Text>,
+        location_in_person -> Nullable<Text>,
+        location_online -> Nullable<Text>,
+        #[max_length = 5]
+        location_zip_code -> Nullable<Varchar>,
+        communication_bring_a_friend -> Bool,
+        communication_other_events -> Bool,
+        communication_confirmation -> Bool,
+        communication_check_in -> Bool,
+        #[max_length = 255]
+        contact_name -> Varchar,
+        #[max_length = 255]
+        contact_email -> Nullable<Varchar>,
+        #[max_length = 20]
+        contact_phone -> Nullable<Varchar>,
+        invite_groups -> Array<Nullable<Text>>,
+        shifts -> Jsonb,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        image_variants -> Jsonb,
     }
 }
 
@@ -165,11 +284,42 @@ diesel::table! {
         contact_email -> Nullable<Varchar>,
         #[max_length = 20]
         contact_phone -> Nullable<Varchar>,
-        co_hosts -> Array<Nullable<Text>>,
         invite_groups -> Array<Nullable<Text>>,
         created_by -> Int4,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        cancelled_at -> Nullable<Timestamptz>,
+        #[max_length = 5]
+        location_zip_code -> Nullable<Varchar>,
+        cancellation_reason -> Nullable<Text>,
+        #[max_length = 255]
+        image_storage_prefix -> Nullable<Varchar>,
+        image_variants -> Jsonb,
+        image_storage_bytes -> Int8,
+        revision -> Int4,
+    }
+}
+
+diesel::table! {
+    exports (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        requested_by -> Nullable<Int4>,
+        #[max_length = 50]
+        export_type -> Varchar,
+        #[max_length = 10]
+        format -> Varchar,
+        event_id -> Nullable<Int4>,
+        range_start -> Nullable<Timestamptz>,
+        range_end -> Nullable<Timestamptz>,
+        #[max_length = 255]
+        filename -> Varchar,
+        #[max_length = 100]
+        mime_type -> Varchar,
+        file_size_bytes -> Int8,
+        row_count -> Int4,
+        storage_key -> Text,
+        created_at -> Timestamptz,
     }
 }
 
@@ -191,6 +341,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    legacy_event_co_hosts (id) {
+        id -> Int4,
+        event_id -> Int4,
+        name -> Text,
+    }
+}
+
 diesel::table! {
     media_assets (id) {
         id -> Int4,
@@ -208,6 +366,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    notification_preferences (id) {
+        id -> Int4,
+        user_id -> Int4,
+        organization_id -> Nullable<Int4>,
+        #[max_length = 50]
+        notification_type -> Varchar,
+        #[max_length = 20]
+        channel -> Varchar,
+        enabled -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     notifications (id) {
         id -> Int4,
@@ -235,6 +408,32 @@ diesel::table! {
         invited_by -> Nullable<Int4>,
         joined_at -> Timestamptz,
         last_active_at -> Nullable<Timestamptz>,
+        custom_role_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    organization_plans (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        #[max_length = 50]
+        plan -> Varchar,
+        effective_from -> Timestamptz,
+        changed_by -> Nullable<Int4>,
+        note -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    organization_roles (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        capabilities -> Array<Nullable<Text>>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -268,10 +467,55 @@ diesel::table! {
         country -> Nullable<Varchar>,
         #[max_length = 50]
         timezone -> Varchar,
-        subscriptions -> Array<Nullable<Text>>,
         created_by -> Int4,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        #[max_length = 10]
+        shift_conflict_policy -> Varchar,
+        archived_at -> Nullable<Timestamptz>,
+        archived_by -> Nullable<Int4>,
+        purge_scheduled_at -> Nullable<Timestamptz>,
+        purged_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    outbound_messages (id) {
+        id -> Int4,
+        #[max_length = 10]
+        channel -> Varchar,
+        #[max_length = 255]
+        recipient -> Varchar,
+        #[max_length = 255]
+        subject -> Nullable<Varchar>,
+        body -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        user_id -> Nullable<Int4>,
+        contact_id -> Nullable<Int4>,
+        event_id -> Nullable<Int4>,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamptz,
+        sent_at -> Nullable<Timestamptz>,
+        next_attempt_at -> Timestamptz,
+        claimed_at -> Nullable<Timestamptz>,
+        unsubscribe_token -> Nullable<Text>,
+        organization_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    ownership_transfers (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        from_user_id -> Int4,
+        to_user_id -> Int4,
+        #[max_length = 20]
+        status -> Varchar,
+        created_at -> Timestamptz,
+        expires_at -> Timestamptz,
+        responded_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -286,6 +530,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    retired_organization_slugs (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        #[max_length = 100]
+        slug -> Varchar,
+        changed_by -> Nullable<Int4>,
+        retired_at -> Timestamptz,
+        reserved_until -> Timestamptz,
+    }
+}
+
 diesel::table! {
     sessions (id) {
         id -> Int4,
@@ -302,6 +558,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sms_consents (id) {
+        id -> Int4,
+        #[max_length = 20]
+        phone_number -> Varchar,
+        #[max_length = 20]
+        action -> Varchar,
+        #[max_length = 20]
+        source -> Varchar,
+        user_id -> Nullable<Int4>,
+        contact_id -> Nullable<Int4>,
+        organization_id -> Nullable<Int4>,
+        consent_text -> Nullable<Text>,
+        ip_address -> Nullable<Inet>,
+        #[max_length = 64]
+        provider_message_id -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Int4,
@@ -327,6 +603,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    volunteer_hour_adjustments (id) {
+        id -> Int4,
+        organization_id -> Int4,
+        user_id -> Int4,
+        event_signup_id -> Nullable<Int4>,
+        minutes -> Int4,
+        reason -> Text,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamptz,
+    }
+}
+
 // content tables
 diesel::joinable!(articles -> users (author_id));
 diesel::joinable!(articles -> article_categories (category_id));
@@ -341,20 +630,54 @@ diesel::joinable!(chat_messages -> chat_conversations (conversation_id));
 diesel::joinable!(chat_participants -> chat_conversations (conversation_id));
 
 // core + event tables
+diesel::joinable!(audit_logs -> organizations (organization_id));
+diesel::joinable!(audit_logs -> users (actor_user_id));
+diesel::joinable!(calendar_feed_tokens -> users (user_id));
+diesel::joinable!(contacts -> users (user_id));
+diesel::joinable!(event_co_hosts -> events (event_id));
+diesel::joinable!(event_co_hosts -> organizations (organization_id));
+diesel::joinable!(event_series -> events (event_id));
+diesel::joinable!(event_shifts -> event_series (series_id));
 diesel::joinable!(event_shifts -> events (event_id));
+diesel::joinable!(event_signups -> contacts (contact_id));
 diesel::joinable!(event_signups -> event_shifts (event_shift_id));
 diesel::joinable!(event_signups -> users (user_id));
+diesel::joinable!(event_templates -> organizations (organization_id));
+diesel::joinable!(event_templates -> users (created_by));
 diesel::joinable!(events -> organizations (organization_id));
 diesel::joinable!(events -> users (created_by));
+diesel::joinable!(exports -> events (event_id));
+diesel::joinable!(exports -> organizations (organization_id));
+diesel::joinable!(exports -> users (requested_by));
 diesel::joinable!(invitations -> organizations (organization_id));
 diesel::joinable!(invitations -> users (invited_by));
+diesel::joinable!(legacy_event_co_hosts -> events (event_id));
+diesel::joinable!(notification_preferences -> organizations (organization_id));
+diesel::joinable!(notification_preferences -> users (user_id));
 diesel::joinable!(notifications -> organizations (organization_id));
 diesel::joinable!(notifications -> users (user_id));
+diesel::joinable!(organization_members -> organization_roles (custom_role_id));
 diesel::joinable!(organization_members -> organizations (organization_id));
+diesel::joinable!(organization_plans -> organizations (organization_id));
+diesel::joinable!(organization_plans -> users (changed_by));
+diesel::joinable!(organization_roles -> organizations (organization_id));
 diesel::joinable!(organizations -> users (created_by));
+diesel::joinable!(outbound_messages -> contacts (contact_id));
+diesel::joinable!(outbound_messages -> events (event_id));
+diesel::joinable!(outbound_messages -> organizations (organization_id));
+diesel::joinable!(outbound_messages -> users (user_id));
+diesel::joinable!(ownership_transfers -> organizations (organization_id));
 diesel::joinable!(password_reset_tokens -> users (user_id));
+diesel::joinable!(retired_organization_slugs -> organizations (organization_id));
+diesel::joinable!(retired_organization_slugs -> users (changed_by));
 diesel::joinable!(sessions -> organization_members (active_organization_membership_id));
 diesel::joinable!(sessions -> users (user_id));
+diesel::joinable!(sms_consents -> contacts (contact_id));
+diesel::joinable!(sms_consents -> organizations (organization_id));
+diesel::joinable!(sms_consents -> users (user_id));
+diesel::joinable!(volunteer_hour_adjustments -> event_signups (event_signup_id));
+diesel::joinable!(volunteer_hour_adjustments -> organizations (organization_id));
+diesel::joinable!(volunteer_hour_adjustments -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     article_categories,
@@ -362,18 +685,34 @@ diesel::allow_tables_to_appear_in_same_query!(
     article_tags,
     articles,
     articles_tags,
+    audit_logs,
+    calendar_feed_tokens,
     chat_conversations,
     chat_messages,
     chat_participants,
+    contacts,
+    event_co_hosts,
+    event_series,
     event_shifts,
     event_signups,
+    event_templates,
     events,
+    exports,
     invitations,
+    legacy_event_co_hosts,
     media_assets,
+    notification_preferences,
     notifications,
     organization_members,
+    organization_plans,
+    organization_roles,
     organizations,
+    outbound_messages,
+    ownership_transfers,
     password_reset_tokens,
+    retired_organization_slugs,
     sessions,
+    sms_consents,
     users,
+    volunteer_hour_adjustments,
 );
@@ -0,0 +1,5 @@
+mod event_discovery;
+mod zip_code;
+
+pub use event_discovery::*;
+pub use zip_code::*;
@@ -0,0 +1,435 @@
+use crate::enums::{EventLocationType, EventVisibility, SignupStatus};
+use crate::error::{postgres_error, AppError};
+use crate::interfaces::{
+    EventImageVariant, ListPublicEventsRequest, PublicEventListResponse,
+    PublicEventOrganizationInfo, PublicEventResponse, PublicEventShiftResponse,
+};
+use crate::models::{Event, EventShift};
+use crate::postgres::get_postgres_connection;
+use crate::redis::{
+    get_redis_cached_public_event, get_redis_cached_public_event_search, redis_cache_public_event,
+    redis_cache_public_event_search,
+};
+use crate::schema::{event_shifts, event_signups, events, organizations};
+use crate::services::events::{
+    get_zip_centroid, haversine_miles, require_zip_centroid, zip_codes_within_radius,
+};
+use crate::services::resolve_organization_slug;
+use chrono::{DateTime, Utc};
+use diesel::dsl::{count, min};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_DISCOVERY_RADIUS_MILES: f64 = 25.0;
+pub const MAX_DISCOVERY_RADIUS_MILES: f64 = 250.0;
+
+// archived organizations' events drop out of discovery while they wait to be purged
+fn active_organization_ids(
+) -> organizations::BoxedQuery<'static, diesel::pg::Pg, diesel::sql_types::Integer> {
+    organizations::table
+        .filter(organizations::archived_at.is_null())
+        .select(organizations::id)
+        .into_boxed()
+}
+
+// which shifts make an event discoverable and are listed on it
+struct ShiftWindow {
+    now: DateTime<Utc>,
+    starts_after: Option<DateTime<Utc>>,
+    starts_before: Option<DateTime<Utc>>,
+}
+
+fn normalize_request(
+    mut request: ListPublicEventsRequest,
+) -> Result<ListPublicEventsRequest, AppError> {
+    request.organization_slug = request
+        .organization_slug
+        .map(|slug| slug.trim().to_lowercase())
+        .filter(|slug| !slug.is_empty());
+    request.zip_code = request
+        .zip_code
+        .map(|zip_code| zip_code.trim().to_string())
+        .filter(|zip_code| !zip_code.is_empty());
+
+    if request.zip_code.is_some() {
+        let radius_miles = request
+            .radius_miles
+            .unwrap_or(DEFAULT_DISCOVERY_RADIUS_MILES);
+        if !(radius_miles > 0.0 && radius_miles <= MAX_DISCOVERY_RADIUS_MILES) {
+            return Err(AppError::validation(
+                "radius_miles",
+                format!(
+                    "Radius must be between 0 and {} miles",
+                    MAX_DISCOVERY_RADIUS_MILES
+                ),
+            ));
+        }
+        request.radius_miles = Some(radius_miles);
+    } else {
+        request.radius_miles = None;
+    }
+
+    if let (Some(starts_after), Some(starts_before)) = (request.starts_after, request.starts_before)
+    {
+        if starts_before <= starts_after {
+            return Err(AppError::validation(
+                "starts_before",
+                "End date must be after start date",
+            ));
+        }
+    }
+
+    // pagination is applied separately so it isn't part of the cached filters
+    request.page = None;
+    request.per_page = None;
+
+    Ok(request)
+}
+
+fn search_cache_key(
+    request: &ListPublicEventsRequest,
+    page: i64,
+    per_page: i64,
+) -> Result<String, AppError> {
+    let json = serde_json::to_string(&(request, page, per_page))
+        .map_err(|error| AppError::InternalError(error.to_string()))?;
+
+    Ok(Sha256::digest(json.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+async fn load_shift_spots_remaining(
+    connection: &mut AsyncPgConnection,
+    shifts: &[EventShift],
+) -> Result<HashMap<i32, i32>, AppError> {
+    let limited_ids: Vec<i32> = shifts
+        .iter()
+        .filter(|shift| shift.capacity.is_some())
+        .map(|shift| shift.id)
+        .collect();
+
+    if limited_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let seated_statuses: Vec<&str> = SignupStatus::SEATED
+        .iter()
+        .map(|status| status.as_str())
+        .collect();
+
+    let seated: HashMap<i32, i64> = event_signups::table
+        .filter(event_signups::event_shift_id.eq_any(&limited_ids))
+        .filter(event_signups::status.eq_any(seated_statuses))
+        .group_by(event_signups::event_shift_id)
+        .select((event_signups::event_shift_id, count(event_signups::id)))
+        .load::<(i32, i64)>(connection)
+        .await
+        .map_err(postgres_error)?
+        .into_iter()
+        .collect();
+
+    Ok(shifts
+        .iter()
+        .filter_map(|shift| {
+            let capacity = shift.capacity?;
+            let taken = seated.get(&shift.id).copied().unwrap_or(0);
+            Some((shift.id, (capacity as i64 - taken).max(0) as i32))
+        })
+        .collect())
+}
+
+// keeps the order of the given events
+async fn build_public_event_responses(
+    connection: &mut AsyncPgConnection,
+    events_list: Vec<Event>,
+    window: &ShiftWindow,
+    origin: Option<(f64, f64)>,
+) -> Result<Vec<PublicEventResponse>, AppError> {
+    if events_list.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let event_ids: Vec<i32> = events_list.iter().map(|event| event.id).collect();
+    let organization_ids: Vec<i32> = events_list
+        .iter()
+        .map(|event| event.organization_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let organizations_by_id: HashMap<i32, PublicEventOrganizationInfo> = organizations::table
+        .filter(organizations::id.eq_any(&organization_ids))
+        .select((
+            organizations::id,
+            organizations::name,
+            organizations::slug,
+            organizations::avatar_url,
+        ))
+        .load::<(i32, String, String, Option<String>)>(connection)
+        .await
+        .map_err(postgres_error)?
+        .into_iter()
+        .map(|(id, name, slug, avatar_url)| {
+            (
+                id,
+                PublicEventOrganizationInfo {
+                    name,
+                    slug,
+                    avatar_url,
+                },
+            )
+        })
+        .collect();
+
+    let mut shift_query = event_shifts::table
+        .filter(event_shifts::event_id.eq_any(&event_ids))
+        .filter(event_shifts::end_time.gt(window.now))
+        .into_boxed();
+    if let Some(starts_after) = window.starts_after {
+        shift_query = shift_query.filter(event_shifts::start_time.ge(starts_after));
+    }
+    if let Some(starts_before) = window.starts_before {
+        shift_query = shift_query.filter(event_shifts::start_time.lt(starts_before));
+    }
+
+    let shifts: Vec<EventShift> = shift_query
+        .order((event_shifts::start_time.asc(), event_shifts::id.asc()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let spots_remaining = load_shift_spots_remaining(connection, &shifts).await?;
+
+    let mut shifts_by_event: HashMap<i32, Vec<PublicEventShiftResponse>> = HashMap::new();
+    for shift in shifts {
+        shifts_by_event
+            .entry(shift.event_id)
+            .or_default()
+            .push(PublicEventShiftResponse {
+                id: shift.id,
+                start_time: shift.start_time,
+                end_time: shift.end_time,
+                timezone: shift.timezone,
+                capacity: shift.capacity,
+                spots_remaining: spots_remaining.get(&shift.id).copied(),
+            });
+    }
+
+    let mut responses = Vec::with_capacity(events_list.len());
+
+    for event in events_list {
+        let organization = organizations_by_id
+            .get(&event.organization_id)
+            .cloned()
+            .ok_or_else(|| AppError::not_found("Organization"))?;
+
+        let distance_miles = origin.and_then(|origin| {
+            let centroid = event
+                .location_zip_code
+                .as_deref()
+                .and_then(get_zip_centroid)?;
+            // one decimal is plenty for display and keeps cached json small
+            Some((haversine_miles(origin, centroid) * 10.0).round() / 10.0)
+        });
+
+        responses.push(PublicEventResponse {
+            id: event.id,
+            event_type: event.get_event_type(),
+            shifts: shifts_by_event.remove(&event.id).unwrap_or_default(),
+            name: event.name,
+            description: event.description,
+            image_url: event.image_url,
+            image_variants: EventImageVariant::list_from_json(&event.image_variants),
+            location_in_person: event.location_in_person,
+            location_online: event.location_online,
+            location_zip_code: event.location_zip_code,
+            contact_name: event.contact_name,
+            organization,
+            distance_miles,
+            cancelled_at: event.cancelled_at,
+            cancellation_reason: event.cancellation_reason,
+        });
+    }
+
+    Ok(responses)
+}
+
+// public, uncancelled events with at least one shift that hasn't ended, ordered by
+// their next matching shift
+pub async fn list_public_events(
+    request: ListPublicEventsRequest,
+    page: i64,
+    per_page: i64,
+) -> Result<PublicEventListResponse, AppError> {
+    let request = normalize_request(request)?;
+
+    let cache_key = search_cache_key(&request, page, per_page)?;
+    if let Ok(Some(cached_json)) = get_redis_cached_public_event_search(&cache_key).await {
+        if let Ok(response) = serde_json::from_str::<PublicEventListResponse>(&cached_json) {
+            return Ok(response);
+        }
+    }
+
+    let empty_response = PublicEventListResponse {
+        events: vec![],
+        total: 0,
+        page,
+        per_page,
+    };
+
+    let organization_id = match request.organization_slug {
+        Some(ref slug) => match resolve_organization_slug(slug).await? {
+            Some((organization, _)) if !organization.is_archived() => Some(organization.id),
+            _ => return Ok(empty_response),
+        },
+        None => None,
+    };
+
+    let origin = match request.zip_code {
+        Some(ref zip_code) => Some(require_zip_centroid("zip_code", zip_code)?),
+        None => None,
+    };
+    let nearby_zip_codes = match (origin, request.radius_miles) {
+        (Some(origin), Some(radius_miles)) => {
+            let zip_codes = zip_codes_within_radius(origin, radius_miles)?;
+            if zip_codes.is_empty() {
+                return Ok(empty_response);
+            }
+            Some(zip_codes)
+        }
+        _ => None,
+    };
+
+    let window = ShiftWindow {
+        now: Utc::now(),
+        starts_after: request.starts_after,
+        starts_before: request.starts_before,
+    };
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let mut query = event_shifts::table
+        .inner_join(events::table)
+        .filter(events::visibility.eq(EventVisibility::Public.as_str()))
+        .filter(events::cancelled_at.is_null())
+        .filter(events::organization_id.eq_any(active_organization_ids()))
+        .filter(event_shifts::end_time.gt(window.now))
+        .group_by(events::id)
+        .select((events::id, min(event_shifts::start_time)))
+        .into_boxed();
+
+    if let Some(starts_after) = window.starts_after {
+        query = query.filter(event_shifts::start_time.ge(starts_after));
+    }
+    if let Some(starts_before) = window.starts_before {
+        query = query.filter(event_shifts::start_time.lt(starts_before));
+    }
+    if let Some(event_type) = request.event_type {
+        query = query.filter(events::event_type.eq(event_type.as_str()));
+    }
+    if let Some(organization_id) = organization_id {
+        query = query.filter(events::organization_id.eq(organization_id));
+    }
+    match request.location_type {
+        Some(EventLocationType::InPerson) => {
+            query = query.filter(events::location_in_person.is_not_null());
+        }
+        Some(EventLocationType::Online) => {
+            query = query.filter(events::location_online.is_not_null());
+        }
+        None => {}
+    }
+    if let Some(ref zip_codes) = nearby_zip_codes {
+        query = query.filter(events::location_zip_code.eq_any(zip_codes));
+    }
+
+    // the matching set is small enough to rank in one pass and page in memory
+    let ranked: Vec<(i32, Option<DateTime<Utc>>)> = query
+        .order((min(event_shifts::start_time).asc(), events::id.asc()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let total = ranked.len() as i64;
+    let offset = ((page - 1) * per_page).max(0) as usize;
+    let page_ids: Vec<i32> = ranked
+        .into_iter()
+        .skip(offset)
+        .take(per_page as usize)
+        .map(|(event_id, _)| event_id)
+        .collect();
+
+    let mut events_by_id: HashMap<i32, Event> = events::table
+        .filter(events::id.eq_any(&page_ids))
+        .load::<Event>(connection)
+        .await
+        .map_err(postgres_error)?
+        .into_iter()
+        .map(|event| (event.id, event))
+        .collect();
+    let events_list: Vec<Event> = page_ids
+        .iter()
+        .filter_map(|event_id| events_by_id.remove(event_id))
+        .collect();
+
+    let responses = build_public_event_responses(connection, events_list, &window, origin).await?;
+
+    let response = PublicEventListResponse {
+        events: responses,
+        total,
+        page,
+        per_page,
+    };
+
+    if let Ok(json) = serde_json::to_string(&response) {
+        redis_cache_public_event_search(&cache_key, &json)
+            .await
+            .ok();
+    }
+
+    Ok(response)
+}
+
+// cancelled public events stay reachable so shared links can show the cancellation,
+// events of archived organizations don't
+pub async fn get_public_event(event_id: i32) -> Result<PublicEventResponse, AppError> {
+    if let Ok(Some(cached_json)) = get_redis_cached_public_event(event_id).await {
+        if let Ok(response) = serde_json::from_str::<PublicEventResponse>(&cached_json) {
+            return Ok(response);
+        }
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let event: Event = events::table
+        .find(event_id)
+        .filter(events::visibility.eq(EventVisibility::Public.as_str()))
+        .filter(events::organization_id.eq_any(active_organization_ids()))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event"))?;
+
+    let window = ShiftWindow {
+        now: Utc::now(),
+        starts_after: None,
+        starts_before: None,
+    };
+
+    let response = build_public_event_responses(connection, vec![event], &window, None)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::InternalError("Failed to build event response".to_string()))?;
+
+    if let Ok(json) = serde_json::to_string(&response) {
+        redis_cache_public_event(event_id, &json).await.ok();
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,152 @@
+use crate::error::AppError;
+use crate::services::validate_zip_code;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::{env, fs};
+
+// census ZCTA gazetteer trimmed to its GEOID / INTPTLAT / INTPTLONG columns (tab
+// separated), from https://www.census.gov/geographies/reference-files/time-series/geo/gazetteer-files.html
+// ZIP_CENTROIDS_PATH points at a newer copy in the same format
+const BUNDLED_ZIP_CENTROIDS: &str = include_str!("../../../data/zcta_centroids.tsv");
+
+const EARTH_RADIUS_MILES: f64 = 3958.8;
+
+static ZIP_CENTROIDS: OnceLock<HashMap<String, (f64, f64)>> = OnceLock::new();
+
+fn parse_zip_centroids(dataset: &str) -> Result<HashMap<String, (f64, f64)>, AppError> {
+    let mut lines = dataset.lines();
+
+    let header = lines.next().unwrap_or_default();
+    let columns: Vec<&str> = header.split('\t').map(str::trim).collect();
+    let column_index = |name: &str| columns.iter().position(|column| *column == name);
+
+    let (Some(zip_index), Some(latitude_index), Some(longitude_index)) = (
+        column_index("GEOID"),
+        column_index("INTPTLAT"),
+        column_index("INTPTLONG"),
+    ) else {
+        return Err(AppError::ConfigError(
+            "ZIP centroid dataset is missing GEOID/INTPTLAT/INTPTLONG columns".to_string(),
+        ));
+    };
+
+    Ok(lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let zip_code = fields.get(zip_index)?;
+            let latitude = fields.get(latitude_index)?.parse().ok()?;
+            let longitude = fields.get(longitude_index)?.parse().ok()?;
+            Some((zip_code.to_string(), (latitude, longitude)))
+        })
+        .collect())
+}
+
+pub fn is_zip_centroids_initialized() -> bool {
+    ZIP_CENTROIDS.get().is_some()
+}
+
+// the app runs fine without the dataset, an empty or unreadable file only turns
+// ZIP code search off
+pub fn initialize_zip_centroids() -> Result<(), AppError> {
+    let centroids = match load_zip_centroids() {
+        Ok(centroids) => {
+            tracing::info!("Loaded {} ZIP code centroids", centroids.len());
+            centroids
+        }
+        Err(error) => {
+            tracing::warn!("ZIP code search disabled: {}", error);
+            HashMap::new()
+        }
+    };
+
+    ZIP_CENTROIDS
+        .set(centroids)
+        .map_err(|_| AppError::ConfigError("ZIP centroids already initialized".to_string()))
+}
+
+fn load_zip_centroids() -> Result<HashMap<String, (f64, f64)>, AppError> {
+    let (source, dataset) = match env::var("ZIP_CENTROIDS_PATH") {
+        Ok(path) => {
+            let dataset = fs::read_to_string(&path).map_err(|error| {
+                AppError::ConfigError(format!(
+                    "Failed to read ZIP centroid dataset at {}: {}",
+                    path, error
+                ))
+            })?;
+            (path, dataset)
+        }
+        Err(_) => (
+            "bundled with this build".to_string(),
+            BUNDLED_ZIP_CENTROIDS.to_string(),
+        ),
+    };
+
+    if dataset.is_empty() {
+        return Err(AppError::ConfigError(format!(
+            "ZIP centroid dataset {} is empty",
+            source
+        )));
+    }
+
+    let centroids = parse_zip_centroids(&dataset)?;
+    if centroids.is_empty() {
+        return Err(AppError::ConfigError(format!(
+            "ZIP centroid dataset {} has no entries",
+            source
+        )));
+    }
+
+    Ok(centroids)
+}
+
+fn zip_centroids() -> Result<&'static HashMap<String, (f64, f64)>, AppError> {
+    ZIP_CENTROIDS
+        .get()
+        .ok_or_else(|| AppError::ConfigError("ZIP centroids not initialized".to_string()))
+}
+
+// great-circle distance between two (latitude, longitude) points
+pub fn haversine_miles(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (from_latitude, from_longitude) = (from.0.to_radians(), from.1.to_radians());
+    let (to_latitude, to_longitude) = (to.0.to_radians(), to.1.to_radians());
+
+    let half_chord = ((to_latitude - from_latitude) / 2.0).sin().powi(2)
+        + from_latitude.cos()
+            * to_latitude.cos()
+            * ((to_longitude - from_longitude) / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_MILES * half_chord.sqrt().asin()
+}
+
+pub fn get_zip_centroid(zip_code: &str) -> Option<(f64, f64)> {
+    zip_centroids().ok()?.get(zip_code).copied()
+}
+
+pub fn require_zip_centroid(field: &str, zip_code: &str) -> Result<(f64, f64), AppError> {
+    validate_zip_code(field, zip_code)?;
+
+    let centroids = zip_centroids()?;
+    if centroids.is_empty() {
+        return Err(AppError::validation(
+            field,
+            "Searching by ZIP code is not available right now",
+        ));
+    }
+
+    centroids
+        .get(zip_code)
+        .copied()
+        .ok_or_else(|| AppError::validation(field, "Unknown ZIP code"))
+}
+
+// every known ZIP code whose centroid falls within the radius of the origin
+pub fn zip_codes_within_radius(
+    origin: (f64, f64),
+    radius_miles: f64,
+) -> Result<Vec<String>, AppError> {
+    Ok(zip_centroids()?
+        .iter()
+        .filter(|(_, centroid)| haversine_miles(origin, **centroid) <= radius_miles)
+        .map(|(zip_code, _)| zip_code.clone())
+        .collect())
+}
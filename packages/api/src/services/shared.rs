@@ -1,14 +1,34 @@
 mod article;
+mod audit_log;
 mod auth;
+mod contact;
+mod email;
+mod email_verification;
+mod notification;
+mod notification_preference;
 mod organization;
+mod organization_plan;
+mod organization_role;
+mod organization_slug;
+mod ownership_transfer;
 mod password_reset;
 mod session;
 mod user;
 mod validation;
 
 pub use article::*;
+pub use audit_log::*;
 pub use auth::*;
+pub use contact::*;
+pub use email::*;
+pub use email_verification::*;
+pub use notification::*;
+pub use notification_preference::*;
 pub use organization::*;
+pub use organization_plan::*;
+pub use organization_role::*;
+pub use organization_slug::*;
+pub use ownership_transfer::*;
 pub use password_reset::*;
 pub use session::*;
 pub use user::*;
@@ -0,0 +1,17 @@
+use crate::error::{postgres_error, AppError};
+use crate::models::{AuditLog, NewAuditLog};
+use crate::schema::audit_logs;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+// entries are written on the connection making the change, so inside a transaction
+// the log never records something that was rolled back
+pub async fn record_audit_log(
+    connection: &mut AsyncPgConnection,
+    entry: NewAuditLog,
+) -> Result<AuditLog, AppError> {
+    diesel::insert_into(audit_logs::table)
+        .values(&entry)
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
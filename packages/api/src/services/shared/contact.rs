@@ -0,0 +1,163 @@
+use crate::error::{postgres_error, AppError};
+use crate::models::{Contact, NewContact, User};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{contacts, event_signups};
+use crate::services::{
+    validate_email, validate_optional_string, validate_required_string, validate_zip_code,
+    MAX_PHONE_NUMBER_LENGTH, MAX_USER_NAME_LENGTH,
+};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+// contacts are keyed by lowercased email, a repeat guest reuses the record instead of
+// creating a second one. nothing proves a guest owns the email, so a repeat signup
+// only fills in details the contact doesn't have yet and never overwrites them
+pub async fn upsert_contact(
+    email: String,
+    first_name: String,
+    last_name: String,
+    phone_number: Option<String>,
+    zip_code: Option<String>,
+) -> Result<Contact, AppError> {
+    let email = email.trim().to_lowercase();
+    let first_name = first_name.trim().to_string();
+    let last_name = last_name.trim().to_string();
+
+    validate_email(&email)?;
+    validate_required_string("first_name", &first_name, MAX_USER_NAME_LENGTH)?;
+    validate_required_string("last_name", &last_name, MAX_USER_NAME_LENGTH)?;
+    validate_optional_string("phone_number", &phone_number, MAX_PHONE_NUMBER_LENGTH)?;
+    if let Some(ref zip_code) = zip_code {
+        validate_zip_code("zip_code", zip_code)?;
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    // a contact that belongs to an account keeps the details the account holder gave
+    let linked: Option<Contact> = contacts::table
+        .filter(contacts::email.eq(&email))
+        .filter(contacts::user_id.is_not_null())
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    if let Some(contact) = linked {
+        return Ok(contact);
+    }
+
+    let mut new_contact = NewContact::new(email, first_name, last_name);
+    if let Some(phone_number) = phone_number {
+        new_contact = new_contact.set_phone_number(phone_number);
+    }
+    if let Some(zip_code) = zip_code {
+        new_contact = new_contact.set_zip_code(zip_code);
+    }
+
+    diesel::insert_into(contacts::table)
+        .values(&new_contact)
+        .on_conflict(contacts::email)
+        .do_update()
+        .set((
+            contacts::phone_number.eq(sql::<Nullable<Text>>(
+                "COALESCE(contacts.phone_number, EXCLUDED.phone_number)",
+            )),
+            contacts::zip_code.eq(sql::<Nullable<Text>>(
+                "COALESCE(contacts.zip_code, EXCLUDED.zip_code)",
+            )),
+        ))
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn get_contact(contact_id: i32) -> Result<Contact, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    contacts::table
+        .find(contact_id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Contact"))
+}
+
+pub async fn get_contact_by_email(email: &str) -> Result<Option<Contact>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    contacts::table
+        .filter(contacts::email.eq(&email.to_lowercase()))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)
+}
+
+// hands the contact's guest signups over to the account, a shift the user already
+// signed up for themselves keeps their own signup and the guest one stays with the contact
+async fn link_contact_to_user(contact: Contact, user_id: i32) -> Result<Contact, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let contact: Contact = diesel::update(contacts::table.find(contact.id))
+                    .set(contacts::user_id.eq(Some(user_id)))
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                let existing_shift_ids: Vec<i32> = event_signups::table
+                    .filter(event_signups::user_id.eq(user_id))
+                    .select(event_signups::event_shift_id)
+                    .load(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                diesel::update(
+                    event_signups::table
+                        .filter(event_signups::contact_id.eq(contact.id))
+                        .filter(event_signups::user_id.is_null())
+                        .filter(event_signups::event_shift_id.ne_all(existing_shift_ids)),
+                )
+                .set(event_signups::user_id.eq(Some(user_id)))
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                Ok(contact)
+            })
+        })
+        .await
+}
+
+// RSVPs made as a guest show up in the account once its email is verified, this runs
+// again on login to pick up guest signups made since
+pub async fn attach_contact_signups_to_user(user: &User) -> Result<(), AppError> {
+    if user.email_verified_at.is_none() {
+        return Ok(());
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let contact: Option<Contact> = contacts::table
+        .filter(contacts::email.eq(&user.email))
+        .filter(
+            contacts::user_id
+                .is_null()
+                .or(contacts::user_id.eq(user.id)),
+        )
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    if let Some(contact) = contact {
+        link_contact_to_user(contact, user.id).await?;
+    }
+
+    Ok(())
+}
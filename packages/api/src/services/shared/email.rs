@@ -0,0 +1,297 @@
+use crate::error::AppError;
+use crate::mailer::{get_app_url, send_email, OutgoingEmail};
+
+const EMAIL_LAYOUT_HTML: &str = include_str!("../../../templates/email/layout.html");
+const EMAIL_BUTTON_HTML: &str = include_str!("../../../templates/email/button.html");
+const EMAIL_FOOTER_HTML: &str = include_str!("../../../templates/email/footer.html");
+const EMAIL_FOOTER_TEXT: &str = include_str!("../../../templates/email/footer.txt");
+
+// each template is a subject, a text body and an html body that gets wrapped in the
+// shared layout, plus the call to action rendered as a button in html
+#[derive(Clone, Copy)]
+pub enum EmailTemplate {
+    PasswordReset,
+    Invitation,
+    SignupConfirmation,
+    EmailVerification,
+}
+
+impl EmailTemplate {
+    fn subject(&self) -> &'static str {
+        match self {
+            EmailTemplate::PasswordReset => "Reset your password",
+            EmailTemplate::Invitation => "You're invited to join {{organization_name}}",
+            EmailTemplate::SignupConfirmation => "Your RSVP for {{event_name}}",
+            EmailTemplate::EmailVerification => "Confirm your email address",
+        }
+    }
+
+    fn text(&self) -> &'static str {
+        match self {
+            EmailTemplate::PasswordReset => {
+                include_str!("../../../templates/email/password_reset.txt")
+            }
+            EmailTemplate::Invitation => include_str!("../../../templates/email/invitation.txt"),
+            EmailTemplate::SignupConfirmation => {
+                include_str!("../../../templates/email/signup_confirmation.txt")
+            }
+            EmailTemplate::EmailVerification => {
+                include_str!("../../../templates/email/email_verification.txt")
+            }
+        }
+    }
+
+    fn html(&self) -> &'static str {
+        match self {
+            EmailTemplate::PasswordReset => {
+                include_str!("../../../templates/email/password_reset.html")
+            }
+            EmailTemplate::Invitation => include_str!("../../../templates/email/invitation.html"),
+            EmailTemplate::SignupConfirmation => {
+                include_str!("../../../templates/email/signup_confirmation.html")
+            }
+            EmailTemplate::EmailVerification => {
+                include_str!("../../../templates/email/email_verification.html")
+            }
+        }
+    }
+
+    // the button label and the variable holding its link
+    fn button(&self) -> (&'static str, &'static str) {
+        match self {
+            EmailTemplate::PasswordReset => ("Reset password", "reset_url"),
+            EmailTemplate::Invitation => ("Accept invitation", "invitation_url"),
+            EmailTemplate::SignupConfirmation => ("Manage my RSVP", "manage_url"),
+            EmailTemplate::EmailVerification => ("Confirm email", "verify_url"),
+        }
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+// {{name}} is replaced with the variable (escaped when rendering html) and {{{name}}}
+// with already rendered html, a placeholder without a variable is a template bug
+fn render_template(
+    template: &str,
+    variables: &[(&str, &str)],
+    escape: bool,
+) -> Result<String, AppError> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+
+        let (open, close) = if placeholder.starts_with("{{{") {
+            ("{{{", "}}}")
+        } else {
+            ("{{", "}}")
+        };
+        let end = placeholder[open.len()..].find(close).ok_or_else(|| {
+            AppError::InternalError("Email template has an unclosed placeholder".to_string())
+        })?;
+        let name = placeholder[open.len()..open.len() + end].trim();
+
+        let value = variables
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| {
+                AppError::InternalError(format!("Email template variable {} is missing", name))
+            })?;
+
+        if escape && open == "{{" {
+            rendered.push_str(&escape_html(value));
+        } else {
+            rendered.push_str(value);
+        }
+
+        rest = &placeholder[open.len() + end + close.len()..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+fn wrap_email(
+    to: String,
+    subject: String,
+    mut text: String,
+    content: String,
+    unsubscribe_url: Option<String>,
+) -> Result<OutgoingEmail, AppError> {
+    let footer = match unsubscribe_url {
+        Some(ref unsubscribe_url) => {
+            let variables = [("unsubscribe_url", unsubscribe_url.as_str())];
+            text.push('\n');
+            text.push_str(render_template(EMAIL_FOOTER_TEXT, &variables, false)?.trim_end());
+            render_template(EMAIL_FOOTER_HTML, &variables, true)?
+        }
+        None => String::new(),
+    };
+
+    let html = render_template(
+        EMAIL_LAYOUT_HTML,
+        &[
+            ("subject", &subject),
+            ("content", &content),
+            ("footer", &footer),
+        ],
+        true,
+    )?;
+
+    Ok(OutgoingEmail {
+        to,
+        subject,
+        text,
+        html,
+        list_unsubscribe_url: None,
+    })
+}
+
+pub fn render_email(
+    to: String,
+    template: EmailTemplate,
+    variables: &[(&str, &str)],
+) -> Result<OutgoingEmail, AppError> {
+    let (label, url_variable) = template.button();
+    let url = variables
+        .iter()
+        .find(|(key, _)| *key == url_variable)
+        .map(|(_, value)| *value)
+        .unwrap_or_default();
+    let button = render_template(EMAIL_BUTTON_HTML, &[("label", label), ("url", url)], true)?;
+
+    let mut html_variables = variables.to_vec();
+    html_variables.push(("button", &button));
+
+    let subject = render_template(template.subject(), variables, false)?;
+    let text = render_template(template.text(), variables, false)?
+        .trim_end()
+        .to_string();
+    let content = render_template(template.html(), &html_variables, true)?;
+
+    wrap_email(to, subject, text, content, None)
+}
+
+// queued messages are stored as plain text, blank lines become paragraphs in html
+pub fn render_plain_email(
+    to: String,
+    subject: String,
+    body: String,
+    unsubscribe_url: Option<String>,
+) -> Result<OutgoingEmail, AppError> {
+    let content = body
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim())
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| format!("<p>{}</p>", escape_html(paragraph).replace('\n', "<br>")))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    wrap_email(to, subject, body, content, unsubscribe_url)
+}
+
+pub fn get_app_link(path: &str) -> Result<String, AppError> {
+    Ok(format!("{}{}", get_app_url()?, path))
+}
+
+// sends outside the request so callers neither wait on the mail server nor leak
+// through their response time whether an email went out, failures are only logged
+pub fn send_email_in_background(email: OutgoingEmail) {
+    tokio::spawn(async move {
+        let to = email.to.clone();
+        if let Err(error) = send_email(email).await {
+            tracing::warn!("failed to send email to {}: {}", to, error);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_html_replaces_markup_characters() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("plain text"), "plain text");
+    }
+
+    #[test]
+    fn placeholders_are_escaped_only_when_rendering_html() {
+        let variables = [("name", "<b>Ana</b> & co")];
+
+        assert_eq!(
+            render_template("Hi {{name}}!", &variables, true).unwrap(),
+            "Hi &lt;b&gt;Ana&lt;/b&gt; &amp; co!"
+        );
+        assert_eq!(
+            render_template("Hi {{name}}!", &variables, false).unwrap(),
+            "Hi <b>Ana</b> & co!"
+        );
+    }
+
+    #[test]
+    fn triple_braces_insert_raw_html() {
+        let variables = [("content", "<p>Hello</p>"), ("subject", "A & B")];
+
+        assert_eq!(
+            render_template("<h1>{{ subject }}</h1>{{{content}}}", &variables, true).unwrap(),
+            "<h1>A &amp; B</h1><p>Hello</p>"
+        );
+    }
+
+    #[test]
+    fn repeated_placeholders_are_all_replaced() {
+        let variables = [("url", "https://example.com")];
+
+        assert_eq!(
+            render_template("{{url}} or {{url}}", &variables, false).unwrap(),
+            "https://example.com or https://example.com"
+        );
+    }
+
+    #[test]
+    fn missing_variable_is_an_error() {
+        let error = render_template("Hi {{name}}", &[("other", "x")], true).unwrap_err();
+
+        assert!(matches!(error, AppError::InternalError(message) if message.contains("name")));
+    }
+
+    #[test]
+    fn unclosed_placeholder_is_an_error() {
+        assert!(matches!(
+            render_template("Hi {{name", &[("name", "Ana")], true),
+            Err(AppError::InternalError(_))
+        ));
+        assert!(matches!(
+            render_template("{{{content}}", &[("content", "x")], true),
+            Err(AppError::InternalError(_))
+        ));
+    }
+
+    #[test]
+    fn template_without_placeholders_is_unchanged() {
+        assert_eq!(
+            render_template("No variables here } {", &[], true).unwrap(),
+            "No variables here } {"
+        );
+    }
+}
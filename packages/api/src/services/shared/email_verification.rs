@@ -0,0 +1,117 @@
+use crate::error::{postgres_error, AppError};
+use crate::models::{OrganizationMember, User};
+use crate::postgres::get_postgres_connection;
+use crate::schema::users;
+use crate::services::{
+    accept_pending_invitations, attach_contact_signups_to_user, get_app_link, get_user_by_id,
+    render_email, send_email_in_background, EmailTemplate,
+};
+use crate::signing::{sign_token, verify_signed_token};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+const EMAIL_VERIFICATION_TOKEN_PURPOSE: &str = "email_verification";
+pub const EMAIL_VERIFICATION_TOKEN_EXPIRY_DAYS: i64 = 7;
+
+// the token carries the address it was sent to, so it stops working if the
+// account's email changes
+fn create_email_verification_token(user: &User) -> Result<String, AppError> {
+    let expires_at = Utc::now() + Duration::days(EMAIL_VERIFICATION_TOKEN_EXPIRY_DAYS);
+
+    sign_token(
+        EMAIL_VERIFICATION_TOKEN_PURPOSE,
+        &format!("{}:{}:{}", user.id, expires_at.timestamp(), user.email),
+    )
+}
+
+pub fn send_email_verification(user: &User) -> Result<(), AppError> {
+    if user.email_verified_at.is_some() {
+        return Ok(());
+    }
+
+    let token = create_email_verification_token(user)?;
+    let verify_url = get_app_link(&format!("/verify-email/{}", token))?;
+    let expiry_days = EMAIL_VERIFICATION_TOKEN_EXPIRY_DAYS.to_string();
+
+    send_email_in_background(render_email(
+        user.email.clone(),
+        EmailTemplate::EmailVerification,
+        &[
+            ("first_name", &user.first_name),
+            ("verify_url", &verify_url),
+            ("expiry_days", &expiry_days),
+        ],
+    )?);
+
+    Ok(())
+}
+
+pub async fn mark_email_verified(user: User) -> Result<User, AppError> {
+    if user.email_verified_at.is_some() {
+        return Ok(user);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::update(users::table.find(user.id))
+        .set(users::email_verified_at.eq(Some(Utc::now())))
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// invitations and guest RSVPs sent to an address only reach the account once it's
+// verified, failures are logged so they don't undo the verification
+pub async fn claim_verified_email(user: &User) -> Vec<OrganizationMember> {
+    if user.email_verified_at.is_none() {
+        return vec![];
+    }
+
+    if let Err(error) = attach_contact_signups_to_user(user).await {
+        tracing::warn!(
+            "failed to attach guest signups to user {}: {}",
+            user.id,
+            error
+        );
+    }
+
+    accept_pending_invitations(user.id, &user.email)
+        .await
+        .unwrap_or_else(|error| {
+            tracing::warn!(
+                "failed to accept pending invitations for user {}: {}",
+                user.id,
+                error
+            );
+            vec![]
+        })
+}
+
+// returns the verified user and the memberships from invitations accepted with it
+pub async fn verify_email(token: &str) -> Result<(User, Vec<OrganizationMember>), AppError> {
+    let invalid = || AppError::validation("token", "Invalid or expired link");
+
+    let payload =
+        verify_signed_token(EMAIL_VERIFICATION_TOKEN_PURPOSE, token)?.ok_or_else(invalid)?;
+    let mut parts = payload.splitn(3, ':');
+    let (Some(user_id), Some(expires_at), Some(email)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+
+    let expires_at: i64 = expires_at.parse().map_err(|_| invalid())?;
+    if expires_at <= Utc::now().timestamp() {
+        return Err(invalid());
+    }
+
+    let user = get_user_by_id(user_id.parse().map_err(|_| invalid())?).await?;
+    if user.email != email {
+        return Err(invalid());
+    }
+
+    let user = mark_email_verified(user).await?;
+    let memberships = claim_verified_email(&user).await;
+
+    Ok((user, memberships))
+}
@@ -0,0 +1,308 @@
+use crate::enums::{MemberRole, NotificationChannel, NotificationType};
+use crate::error::{postgres_error, AppError};
+use crate::interfaces::NotificationResponse;
+use crate::models::{NewNotification, Notification, NotificationUpdate};
+use crate::postgres::get_postgres_connection;
+use crate::redis::redis_publish_notification;
+use crate::schema::{notifications, organization_members};
+use crate::services::{
+    load_notification_preferences, validate_app_path, validate_optional_string,
+    validate_required_string,
+};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+// validation constants based on database column limits
+pub const MAX_NOTIFICATION_TITLE_LENGTH: usize = 255;
+pub const MAX_NOTIFICATION_MESSAGE_LENGTH: usize = 2_000;
+pub const MAX_NOTIFICATION_LINK_LENGTH: usize = 2048;
+// the sidebar badge tops out at 99+, so loading more unread than this is wasted
+pub const MAX_ACCOUNT_UNREAD_NOTIFICATIONS: i64 = 100;
+// the types a member can send by hand, every other type is only raised by the flow it
+// describes so it can't be faked
+pub const MANUAL_NOTIFICATION_TYPES: [NotificationType; 2] =
+    [NotificationType::Info, NotificationType::EventReminder];
+
+// live delivery is best effort, anything missed is picked up on the next page load,
+// call it once the rows are committed so subscribers never see a rolled back one
+pub async fn publish_notifications(notifications: &[Notification]) {
+    for notification in notifications {
+        let response = NotificationResponse::from(notification.clone());
+
+        let json = match serde_json::to_string(&response) {
+            Ok(json) => json,
+            Err(error) => {
+                tracing::warn!(
+                    "failed to serialize notification {}: {}",
+                    notification.id,
+                    error
+                );
+                continue;
+            }
+        };
+
+        if let Err(error) = redis_publish_notification(notification.user_id, &json).await {
+            tracing::warn!(
+                "failed to publish notification {}: {}",
+                notification.id,
+                error
+            );
+        }
+    }
+}
+
+// every notification insert goes through here so flows that already hold a
+// connection (or a transaction) can emit them alongside their own writes, they're
+// dropped for users who turned the type off in-app, callers publish the returned rows
+// after their transaction commits
+pub async fn insert_notifications(
+    connection: &mut AsyncPgConnection,
+    mut new_notifications: Vec<NewNotification>,
+) -> Result<Vec<Notification>, AppError> {
+    let user_ids: Vec<i32> = new_notifications
+        .iter()
+        .map(|notification| notification.user_id)
+        .collect();
+    let preferences =
+        load_notification_preferences(connection, &user_ids, NotificationChannel::InApp).await?;
+
+    new_notifications.retain(|notification| {
+        NotificationType::from_str(&notification.notification_type).is_none_or(
+            |notification_type| {
+                preferences.allows(
+                    notification.user_id,
+                    notification.organization_id,
+                    notification_type,
+                )
+            },
+        )
+    });
+
+    if new_notifications.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let notifications: Vec<Notification> = diesel::insert_into(notifications::table)
+        .values(&new_notifications)
+        .get_results(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(notifications)
+}
+
+pub async fn create_notification(
+    user_id: i32,
+    organization_id: Option<i32>,
+    notification_type: NotificationType,
+    title: String,
+    message: String,
+    link: Option<String>,
+) -> Result<Notification, AppError> {
+    if !MANUAL_NOTIFICATION_TYPES.contains(&notification_type) {
+        return Err(AppError::validation(
+            "notification_type",
+            "This type of notification can't be sent manually",
+        ));
+    }
+    validate_required_string("title", &title, MAX_NOTIFICATION_TITLE_LENGTH)?;
+    validate_required_string("message", &message, MAX_NOTIFICATION_MESSAGE_LENGTH)?;
+    validate_optional_string("link", &link, MAX_NOTIFICATION_LINK_LENGTH)?;
+    if let Some(ref link) = link {
+        validate_app_path("link", link)?;
+    }
+
+    let mut new_notification = NewNotification::new(user_id, notification_type, title, message);
+    if let Some(organization_id) = organization_id {
+        new_notification = new_notification.set_organization(organization_id);
+    }
+    if let Some(link) = link {
+        new_notification = new_notification.set_link(link);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let notification = insert_notifications(connection, vec![new_notification])
+        .await?
+        .pop()
+        .ok_or_else(|| {
+            AppError::validation(
+                "notification_type",
+                "This user has turned off in-app notifications of this type",
+            )
+        })?;
+
+    publish_notifications(std::slice::from_ref(&notification)).await;
+
+    Ok(notification)
+}
+
+// notifies every admin and the owner of an organization, optionally skipping the
+// user who caused the notification
+pub async fn notify_organization_admins(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    skip_user_id: Option<i32>,
+    notification_type: NotificationType,
+    title: String,
+    message: String,
+    link: Option<String>,
+) -> Result<Vec<Notification>, AppError> {
+    let mut admin_user_ids: Vec<i32> = organization_members::table
+        .filter(organization_members::organization_id.eq(organization_id))
+        .filter(
+            organization_members::role
+                .eq_any([MemberRole::Owner.as_str(), MemberRole::Admin.as_str()]),
+        )
+        .select(organization_members::user_id)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    if let Some(skip_user_id) = skip_user_id {
+        admin_user_ids.retain(|user_id| *user_id != skip_user_id);
+    }
+
+    let new_notifications: Vec<NewNotification> = admin_user_ids
+        .into_iter()
+        .map(|user_id| {
+            let notification =
+                NewNotification::new(user_id, notification_type, title.clone(), message.clone())
+                    .set_organization(organization_id);
+            match link {
+                Some(ref link) => notification.set_link(link.clone()),
+                None => notification,
+            }
+        })
+        .collect();
+
+    insert_notifications(connection, new_notifications).await
+}
+
+// returns the page of notifications, the total matching the filter and the total
+// unread regardless of the filter
+pub async fn list_notifications(
+    user_id: i32,
+    unread_only: bool,
+    page: i64,
+    per_page: i64,
+) -> Result<(Vec<Notification>, i64, i64), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let mut count_query = notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .into_boxed();
+    let mut page_query = notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .into_boxed();
+
+    if unread_only {
+        count_query = count_query.filter(notifications::read.eq(false));
+        page_query = page_query.filter(notifications::read.eq(false));
+    }
+
+    let total: i64 = count_query
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let unread_count: i64 = notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .filter(notifications::read.eq(false))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let offset = (page - 1) * per_page;
+
+    let notifications: Vec<Notification> = page_query
+        .order((notifications::created_at.desc(), notifications::id.desc()))
+        .offset(offset)
+        .limit(per_page)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok((notifications, total, unread_count))
+}
+
+pub async fn list_unread_notifications(user_id: i32) -> Result<Vec<Notification>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .filter(notifications::read.eq(false))
+        .order((notifications::created_at.desc(), notifications::id.desc()))
+        .limit(MAX_ACCOUNT_UNREAD_NOTIFICATIONS)
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn mark_notification_read(
+    user_id: i32,
+    notification_id: i32,
+) -> Result<Notification, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let notification: Notification = notifications::table
+        .find(notification_id)
+        .filter(notifications::user_id.eq(user_id))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Notification"))?;
+
+    if notification.read {
+        return Ok(notification);
+    }
+
+    diesel::update(notifications::table.find(notification.id))
+        .set(NotificationUpdate {
+            read: Some(true),
+            read_at: Some(Some(Utc::now())),
+        })
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn mark_all_notifications_read(user_id: i32) -> Result<usize, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::update(
+        notifications::table
+            .filter(notifications::user_id.eq(user_id))
+            .filter(notifications::read.eq(false)),
+    )
+    .set(NotificationUpdate {
+        read: Some(true),
+        read_at: Some(Some(Utc::now())),
+    })
+    .execute(connection)
+    .await
+    .map_err(postgres_error)
+}
+
+pub async fn delete_notification(user_id: i32, notification_id: i32) -> Result<(), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let deleted = diesel::delete(
+        notifications::table
+            .filter(notifications::id.eq(notification_id))
+            .filter(notifications::user_id.eq(user_id)),
+    )
+    .execute(connection)
+    .await
+    .map_err(postgres_error)?;
+
+    if deleted == 0 {
+        return Err(AppError::not_found("Notification"));
+    }
+
+    Ok(())
+}
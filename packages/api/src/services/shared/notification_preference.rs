@@ -0,0 +1,264 @@
+use crate::enums::{NotificationChannel, NotificationType};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    NewNotificationPreference, NotificationPreference, NotificationPreferenceUpdate,
+};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{notification_preferences, organization_members};
+use crate::signing::{sign_token, verify_signed_token};
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::HashMap;
+
+const NOTIFICATION_UNSUBSCRIBE_TOKEN_PURPOSE: &str = "notification_unsubscribe";
+
+// a notification type and channel to switch on or off, None removes the setting so
+// the account default (or delivery, for the default itself) applies again
+pub type NotificationPreferenceChange = (NotificationType, NotificationChannel, Option<bool>);
+
+// the stored preferences of a set of users for one channel, resolved the way
+// delivery is: an organization override, then the account default, then delivered
+pub struct NotificationPreferenceLookup {
+    preferences: HashMap<(i32, Option<i32>, String), bool>,
+}
+
+impl NotificationPreferenceLookup {
+    pub fn allows(
+        &self,
+        user_id: i32,
+        organization_id: Option<i32>,
+        notification_type: NotificationType,
+    ) -> bool {
+        let notification_type = notification_type.as_str().to_string();
+
+        organization_id
+            .and_then(|organization_id| {
+                self.preferences
+                    .get(&(user_id, Some(organization_id), notification_type.clone()))
+            })
+            .or_else(|| self.preferences.get(&(user_id, None, notification_type)))
+            .copied()
+            .unwrap_or(true)
+    }
+}
+
+pub async fn load_notification_preferences(
+    connection: &mut AsyncPgConnection,
+    user_ids: &[i32],
+    channel: NotificationChannel,
+) -> Result<NotificationPreferenceLookup, AppError> {
+    if user_ids.is_empty() {
+        return Ok(NotificationPreferenceLookup {
+            preferences: HashMap::new(),
+        });
+    }
+
+    let rows: Vec<NotificationPreference> = notification_preferences::table
+        .filter(notification_preferences::user_id.eq_any(user_ids))
+        .filter(notification_preferences::channel.eq(channel.as_str()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(NotificationPreferenceLookup {
+        preferences: rows
+            .into_iter()
+            .map(|row| {
+                (
+                    (row.user_id, row.organization_id, row.notification_type),
+                    row.enabled,
+                )
+            })
+            .collect(),
+    })
+}
+
+// the users out of user_ids who still want this notification on this channel
+pub async fn filter_notification_recipients(
+    connection: &mut AsyncPgConnection,
+    user_ids: &[i32],
+    organization_id: Option<i32>,
+    notification_type: NotificationType,
+    channel: NotificationChannel,
+) -> Result<Vec<i32>, AppError> {
+    let lookup = load_notification_preferences(connection, user_ids, channel).await?;
+
+    Ok(user_ids
+        .iter()
+        .copied()
+        .filter(|user_id| lookup.allows(*user_id, organization_id, notification_type))
+        .collect())
+}
+
+pub async fn list_notification_preferences(
+    user_id: i32,
+) -> Result<Vec<NotificationPreference>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    notification_preferences::table
+        .filter(notification_preferences::user_id.eq(user_id))
+        .order((
+            notification_preferences::organization_id
+                .asc()
+                .nulls_first(),
+            notification_preferences::notification_type.asc(),
+            notification_preferences::channel.asc(),
+        ))
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+async fn save_notification_preference(
+    connection: &mut AsyncPgConnection,
+    user_id: i32,
+    organization_id: Option<i32>,
+    setting: NotificationPreferenceChange,
+) -> Result<(), AppError> {
+    let (notification_type, channel, enabled) = setting;
+
+    let mut query = notification_preferences::table
+        .filter(notification_preferences::user_id.eq(user_id))
+        .filter(notification_preferences::notification_type.eq(notification_type.as_str()))
+        .filter(notification_preferences::channel.eq(channel.as_str()))
+        .select(notification_preferences::id)
+        .into_boxed();
+    query = match organization_id {
+        Some(organization_id) => {
+            query.filter(notification_preferences::organization_id.eq(organization_id))
+        }
+        None => query.filter(notification_preferences::organization_id.is_null()),
+    };
+
+    let existing: Option<i32> = query
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    match (existing, enabled) {
+        (Some(id), Some(enabled)) => {
+            diesel::update(notification_preferences::table.find(id))
+                .set(NotificationPreferenceUpdate {
+                    enabled: Some(enabled),
+                })
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+        }
+        (Some(id), None) => {
+            diesel::delete(notification_preferences::table.find(id))
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+        }
+        (None, Some(enabled)) => {
+            let mut new_preference =
+                NewNotificationPreference::new(user_id, notification_type, channel, enabled);
+            if let Some(organization_id) = organization_id {
+                new_preference = new_preference.set_organization(organization_id);
+            }
+
+            diesel::insert_into(notification_preferences::table)
+                .values(&new_preference)
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+        }
+        (None, None) => {}
+    }
+
+    Ok(())
+}
+
+// organization overrides can only be set for organizations the user belongs to
+pub async fn update_notification_preferences(
+    user_id: i32,
+    organization_id: Option<i32>,
+    settings: Vec<NotificationPreferenceChange>,
+) -> Result<Vec<NotificationPreference>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    if let Some(organization_id) = organization_id {
+        let membership: Option<i32> = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .filter(organization_members::user_id.eq(user_id))
+            .select(organization_members::id)
+            .first(connection)
+            .await
+            .optional()
+            .map_err(postgres_error)?;
+
+        if membership.is_none() {
+            return Err(AppError::not_found("Organization"));
+        }
+    }
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                for setting in settings {
+                    save_notification_preference(connection, user_id, organization_id, setting)
+                        .await?;
+                }
+
+                Ok(())
+            })
+        })
+        .await?;
+
+    list_notification_preferences(user_id).await
+}
+
+// unsubscribe links never expire and only ever turn email off for the one type
+// (and organization) the email was about
+pub fn create_notification_unsubscribe_token(
+    user_id: i32,
+    organization_id: Option<i32>,
+    notification_type: NotificationType,
+) -> Result<String, AppError> {
+    sign_token(
+        NOTIFICATION_UNSUBSCRIBE_TOKEN_PURPOSE,
+        &format!(
+            "{}:{}:{}",
+            user_id,
+            organization_id.map(|id| id.to_string()).unwrap_or_default(),
+            notification_type.as_str()
+        ),
+    )
+}
+
+pub async fn unsubscribe_from_notifications(
+    token: &str,
+) -> Result<(i32, Option<i32>, NotificationType), AppError> {
+    let invalid = || AppError::validation("token", "Invalid unsubscribe link");
+
+    let payload =
+        verify_signed_token(NOTIFICATION_UNSUBSCRIBE_TOKEN_PURPOSE, token)?.ok_or_else(invalid)?;
+
+    let mut parts = payload.splitn(3, ':');
+    let user_id: i32 = parts
+        .next()
+        .and_then(|user_id| user_id.parse().ok())
+        .ok_or_else(invalid)?;
+    let organization_id: Option<i32> = match parts.next().ok_or_else(invalid)? {
+        "" => None,
+        organization_id => Some(organization_id.parse().map_err(|_| invalid())?),
+    };
+    let notification_type = parts
+        .next()
+        .and_then(NotificationType::from_str)
+        .ok_or_else(invalid)?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    save_notification_preference(
+        connection,
+        user_id,
+        organization_id,
+        (notification_type, NotificationChannel::Email, Some(false)),
+    )
+    .await?;
+
+    Ok((user_id, organization_id, notification_type))
+}
@@ -1,19 +1,37 @@
-use crate::enums::{InvitationStatus, MemberRole, OrganizationType, SubscriptionType};
+use crate::enums::{
+    AuditAction, InvitationStatus, MemberRole, NotificationChannel, NotificationType,
+    OrganizationType, OwnershipTransferStatus, ARCHIVED_ORGANIZATION_PURGE_DAYS,
+    DEFAULT_INVITATION_EXPIRY_DAYS, DEFAULT_SUBSCRIPTION_PLAN,
+};
 use crate::error::{postgres_error, AppError};
 use crate::models::{
-    NewInvitation, NewOrganization, NewOrganizationMember, Organization, OrganizationMember,
-    OrganizationMemberUpdate, OrganizationUpdate,
+    Invitation, InvitationUpdate, NewAuditLog, NewInvitation, NewNotification, NewOrganization,
+    NewOrganizationMember, NewOrganizationPlan, Notification, Organization, OrganizationMember,
+    OrganizationMemberUpdate, OrganizationUpdate, OwnershipTransferUpdate, User,
 };
 use crate::postgres::get_postgres_connection;
-use crate::schema::{invitations, organization_members, organizations, users};
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{
+    events, invitations, organization_members, organization_plans, organizations,
+    ownership_transfers, users,
+};
+#[cfg(feature = "web_app")]
+use crate::services::web_app::cancel_upcoming_organization_events;
 use crate::services::{
+    clear_active_organization_memberships, ensure_member_seat_available,
+    filter_notification_recipients, get_app_link, insert_notifications,
+    is_organization_slug_available, notify_organization_admins, publish_notifications,
+    record_audit_log, release_retired_organization_slug, render_email, send_email_in_background,
     validate_email, validate_nested_optional_string, validate_optional_slug,
-    validate_optional_string, validate_required_string, MAX_ORGANIZATION_NAME_LENGTH,
-    MAX_ORGANIZATION_SLUG_LENGTH,
+    validate_optional_string, validate_required_string, EmailTemplate,
+    MAX_ORGANIZATION_NAME_LENGTH, MAX_ORGANIZATION_SLUG_LENGTH,
 };
+use chrono::{Duration, Utc};
 use diesel::prelude::*;
 use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
 use slug::slugify;
+use uuid::Uuid;
 
 // validation constants based on database column limits
 pub const MAX_ORGANIZATION_WEBSITE_URL_LENGTH: usize = 2048;
@@ -50,19 +68,30 @@ pub async fn create_organization(
         organization_type,
         description,
         "America/New_York".to_string(), // TODO: allow timezone selection
-        Vec::from([SubscriptionType::Events]), // TODO: add in subscriptions
         user_id,
     );
 
     let (organization, membership) = connection
         .transaction::<_, AppError, _>(|connection| {
             Box::pin(async move {
+                release_retired_organization_slug(connection, &new_organization.slug).await?;
+
                 let organization: Organization = diesel::insert_into(organizations::table)
                     .values(&new_organization)
                     .get_result(connection)
                     .await
                     .map_err(postgres_error)?;
 
+                diesel::insert_into(organization_plans::table)
+                    .values(&NewOrganizationPlan::new(
+                        organization.id,
+                        DEFAULT_SUBSCRIPTION_PLAN,
+                        organization.created_at,
+                    ))
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
                 let owner_member =
                     NewOrganizationMember::new(organization.id, user_id, MemberRole::Owner);
 
@@ -89,15 +118,7 @@ async fn ensure_unique_slug(
     let mut counter = 1;
 
     loop {
-        let exists: bool = organizations::table
-            .filter(organizations::slug.eq(&slug))
-            .count()
-            .get_result::<i64>(connection)
-            .await
-            .map(|count| count > 0)
-            .map_err(postgres_error)?;
-
-        if !exists {
+        if is_organization_slug_available(connection, &slug).await? {
             return Ok(slug);
         }
 
@@ -193,6 +214,7 @@ pub async fn update_organization(
         .map_err(postgres_error)
 }
 
+// memberships of archived organizations are treated as gone
 pub async fn get_membership(
     organization_id: i32,
     user_id: i32,
@@ -200,8 +222,11 @@ pub async fn get_membership(
     let connection = &mut get_postgres_connection().await?;
 
     organization_members::table
+        .inner_join(organizations::table)
         .filter(organization_members::organization_id.eq(organization_id))
         .filter(organization_members::user_id.eq(user_id))
+        .filter(organizations::archived_at.is_null())
+        .select(OrganizationMember::as_select())
         .first(connection)
         .await
         .optional()
@@ -216,6 +241,7 @@ pub async fn list_user_organizations(
     organization_members::table
         .inner_join(organizations::table)
         .filter(organization_members::user_id.eq(user_id))
+        .filter(organizations::archived_at.is_null())
         .order(organizations::name.asc())
         .select((
             organizations::all_columns,
@@ -251,6 +277,77 @@ pub async fn get_member_by_id(member_id: i32) -> Result<OrganizationMember, AppE
         .ok_or_else(|| AppError::not_found("Member"))
 }
 
+// a membership stops granting access the moment its organization is archived, even to
+// sessions that still have it selected
+pub async fn get_active_member_by_id(member_id: i32) -> Result<OrganizationMember, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    organization_members::table
+        .inner_join(organizations::table)
+        .filter(organization_members::id.eq(member_id))
+        .filter(organizations::archived_at.is_null())
+        .select(OrganizationMember::as_select())
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Member"))
+}
+
+// inserts the membership and lets the organization's admins know, callers check
+// the role and that the user isn't already a member and publish the returned
+// notifications once they've committed
+async fn insert_member(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    user_id: i32,
+    role: MemberRole,
+    invited_by: Option<i32>,
+) -> Result<(OrganizationMember, Vec<Notification>), AppError> {
+    let mut new_member = NewOrganizationMember::new(organization_id, user_id, role);
+
+    if let Some(inviter) = invited_by {
+        new_member = new_member.set_invited_by(inviter);
+    }
+
+    let member = diesel::insert_into(organization_members::table)
+        .values(&new_member)
+        .get_result::<OrganizationMember>(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let user: User = users::table
+        .find(user_id)
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+    let organization_name: String = organizations::table
+        .find(organization_id)
+        .select(organizations::name)
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let notifications = notify_organization_admins(
+        connection,
+        organization_id,
+        Some(user_id),
+        NotificationType::MemberJoined,
+        "New team member".to_string(),
+        format!(
+            "{} {} joined {} with the {} role.",
+            user.first_name,
+            user.last_name,
+            organization_name,
+            role.display_name()
+        ),
+        Some("/team".to_string()),
+    )
+    .await?;
+
+    Ok((member, notifications))
+}
+
 pub async fn add_member(
     organization_id: i32,
     user_id: i32,
@@ -272,17 +369,19 @@ pub async fn add_member(
 
     let connection = &mut get_postgres_connection().await?;
 
-    let mut new_member = NewOrganizationMember::new(organization_id, user_id, role);
+    let (member, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                ensure_member_seat_available(connection, organization_id).await?;
 
-    if let Some(inviter) = invited_by {
-        new_member = new_member.set_invited_by(inviter);
-    }
+                insert_member(connection, organization_id, user_id, role, invited_by).await
+            })
+        })
+        .await?;
 
-    diesel::insert_into(organization_members::table)
-        .values(&new_member)
-        .get_result::<OrganizationMember>(connection)
-        .await
-        .map_err(postgres_error)
+    publish_notifications(&notifications).await;
+
+    Ok(member)
 }
 
 pub async fn update_member_role(
@@ -323,30 +422,197 @@ pub async fn update_member_role(
         .map_err(postgres_error)
 }
 
+// sessions that had the organization active drop back to no organization
 pub async fn remove_member(member_id: i32) -> Result<(), AppError> {
     let connection = &mut get_postgres_connection().await?;
 
-    let member: OrganizationMember = organization_members::table
-        .find(member_id)
-        .first(connection)
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let member: OrganizationMember = organization_members::table
+                    .find(member_id)
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Member"))?;
+
+                if member.get_role() == MemberRole::Owner {
+                    return Err(AppError::validation(
+                        "member",
+                        "Cannot remove the organization owner",
+                    ));
+                }
+
+                clear_active_organization_memberships(connection, &[member.id]).await?;
+
+                diesel::delete(organization_members::table.find(member.id))
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                Ok(())
+            })
+        })
         .await
-        .optional()
-        .map_err(postgres_error)?
-        .ok_or_else(|| AppError::not_found("Member"))?;
+}
 
-    if member.get_role() == MemberRole::Owner {
-        return Err(AppError::validation(
-            "member",
-            "Cannot remove the organization owner",
-        ));
-    }
+// the owner can't leave, ownership has to be handed to someone else first
+pub async fn leave_organization(organization_id: i32, user_id: i32) -> Result<(), AppError> {
+    let connection = &mut get_postgres_connection().await?;
 
-    diesel::delete(organization_members::table.find(member_id))
-        .execute(connection)
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let member: OrganizationMember = organization_members::table
+                    .filter(organization_members::organization_id.eq(organization_id))
+                    .filter(organization_members::user_id.eq(user_id))
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Member"))?;
+
+                if member.get_role() == MemberRole::Owner {
+                    return Err(AppError::validation(
+                        "member",
+                        "The owner can't leave the organization. Transfer ownership first.",
+                    ));
+                }
+
+                clear_active_organization_memberships(connection, &[member.id]).await?;
+
+                diesel::delete(organization_members::table.find(member.id))
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                Ok(())
+            })
+        })
+        .await
+}
+
+// archiving hides the organization and its events from members and the public
+// straight away, open invitations and ownership transfers are withdrawn, upcoming
+// events are cancelled with their attendees notified and its data is deleted for
+// good once the grace period is over
+pub async fn archive_organization(
+    organization_id: i32,
+    archived_by: i32,
+) -> Result<Organization, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (organization, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let organization: Organization = organizations::table
+                    .find(organization_id)
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Organization"))?;
+
+                if organization.is_archived() {
+                    return Err(AppError::validation(
+                        "organization",
+                        "This organization is already archived",
+                    ));
+                }
+
+                let now = Utc::now();
+                let purge_scheduled_at = now + Duration::days(ARCHIVED_ORGANIZATION_PURGE_DAYS);
+
+                let organization: Organization =
+                    diesel::update(organizations::table.find(organization_id))
+                        .set(OrganizationUpdate {
+                            archived_at: Some(Some(now)),
+                            archived_by: Some(Some(archived_by)),
+                            purge_scheduled_at: Some(Some(purge_scheduled_at)),
+                            ..Default::default()
+                        })
+                        .get_result(connection)
+                        .await
+                        .map_err(postgres_error)?;
+
+                diesel::update(
+                    invitations::table
+                        .filter(invitations::organization_id.eq(organization_id))
+                        .filter(invitations::status.eq(InvitationStatus::Pending.as_str())),
+                )
+                .set(InvitationUpdate {
+                    status: Some(InvitationStatus::Revoked.as_str().to_string()),
+                    ..Default::default()
+                })
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                diesel::update(
+                    ownership_transfers::table
+                        .filter(ownership_transfers::organization_id.eq(organization_id))
+                        .filter(
+                            ownership_transfers::status
+                                .eq(OwnershipTransferStatus::Pending.as_str()),
+                        ),
+                )
+                .set(OwnershipTransferUpdate {
+                    status: Some(OwnershipTransferStatus::Cancelled.as_str().to_string()),
+                    responded_at: Some(Some(now)),
+                })
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let membership_ids: Vec<i32> = organization_members::table
+                    .filter(organization_members::organization_id.eq(organization_id))
+                    .select(organization_members::id)
+                    .load(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                clear_active_organization_memberships(connection, &membership_ids).await?;
+
+                #[cfg(feature = "web_app")]
+                let notifications =
+                    cancel_upcoming_organization_events(connection, organization_id).await?;
+                #[cfg(not(feature = "web_app"))]
+                let notifications: Vec<Notification> = vec![];
+
+                record_audit_log(
+                    connection,
+                    NewAuditLog::new(
+                        AuditAction::OrganizationArchived,
+                        json!({ "purge_scheduled_at": purge_scheduled_at }),
+                    )
+                    .set_organization(organization_id)
+                    .set_actor(archived_by),
+                )
+                .await?;
+
+                Ok((organization, notifications))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+
+    // cached public event pages would keep serving the archived organization's events
+    let event_ids: Vec<i32> = events::table
+        .filter(events::organization_id.eq(organization_id))
+        .select(events::id)
+        .load(connection)
         .await
         .map_err(postgres_error)?;
+    for event_id in event_ids {
+        invalidate_redis_cached_public_event(event_id).await.ok();
+    }
 
-    Ok(())
+    Ok(organization)
 }
 
 pub async fn count_members(organization_id: i32) -> Result<i32, AppError> {
@@ -441,6 +707,54 @@ pub async fn get_members_with_user_info(
     Ok((members, total))
 }
 
+// invitees without an account yet always get the email, users can turn it off
+async fn send_invitation_email(
+    connection: &mut AsyncPgConnection,
+    invitation: &Invitation,
+    organization_name: &str,
+    invitee_user_id: Option<i32>,
+) -> Result<(), AppError> {
+    if let Some(user_id) = invitee_user_id {
+        let recipients = filter_notification_recipients(
+            connection,
+            &[user_id],
+            Some(invitation.organization_id),
+            NotificationType::TeamInvite,
+            NotificationChannel::Email,
+        )
+        .await?;
+
+        if recipients.is_empty() {
+            return Ok(());
+        }
+    }
+
+    let (inviter_first_name, inviter_last_name): (String, String) = users::table
+        .find(invitation.invited_by)
+        .select((users::first_name, users::last_name))
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let inviter_name = format!("{} {}", inviter_first_name, inviter_last_name);
+    let invitation_url = get_app_link(&format!("/invitations/{}", invitation.token))?;
+    let expires_on = invitation.expires_at.format("%b %-d, %Y").to_string();
+
+    send_email_in_background(render_email(
+        invitation.email.clone(),
+        EmailTemplate::Invitation,
+        &[
+            ("inviter_name", inviter_name.trim()),
+            ("organization_name", organization_name),
+            ("role", invitation.get_role().display_name()),
+            ("invitation_url", &invitation_url),
+            ("expires_on", &expires_on),
+        ],
+    )?);
+
+    Ok(())
+}
+
 pub async fn create_invitation(
     organization_id: i32,
     email: String,
@@ -453,74 +767,422 @@ pub async fn create_invitation(
 
     let connection = &mut get_postgres_connection().await?;
 
-    let existing = invitations::table
+    // the invitation holds a seat until it's accepted or lapses, the organization's
+    // row lock is held until it's inserted
+    let (invitation, user_with_email) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                ensure_member_seat_available(connection, organization_id).await?;
+
+                let existing = invitations::table
+                    .filter(invitations::organization_id.eq(organization_id))
+                    .filter(invitations::email.eq(&email))
+                    .filter(invitations::status.eq(InvitationStatus::Pending.as_str()))
+                    .first::<crate::models::Invitation>(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?;
+
+                if let Some(invite) = existing {
+                    if !invite.is_expired() {
+                        return Err(AppError::already_exists(
+                            "Pending invitation for this email",
+                        ));
+                    }
+
+                    diesel::delete(invitations::table.find(invite.id))
+                        .execute(connection)
+                        .await
+                        .map_err(postgres_error)?;
+                }
+
+                let user_with_email = users::table
+                    .filter(users::email.eq(&email))
+                    .select(users::id)
+                    .first::<i32>(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?;
+
+                if let Some(user_id) = user_with_email {
+                    let already_member = organization_members::table
+                        .filter(organization_members::organization_id.eq(organization_id))
+                        .filter(organization_members::user_id.eq(user_id))
+                        .first::<OrganizationMember>(connection)
+                        .await
+                        .optional()
+                        .map_err(postgres_error)?;
+
+                    if already_member.is_some() {
+                        return Err(AppError::already_exists(
+                            "User is already a member of this organization",
+                        ));
+                    }
+                }
+
+                diesel::delete(
+                    invitations::table
+                        .filter(invitations::organization_id.eq(organization_id))
+                        .filter(invitations::email.eq(&email))
+                        .filter(invitations::status.ne(InvitationStatus::Pending.as_str())),
+                )
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let invitation = NewInvitation::new(
+                    organization_id,
+                    email,
+                    role.as_str().to_string(),
+                    invited_by,
+                );
+
+                let invitation: Invitation = diesel::insert_into(invitations::table)
+                    .values(&invitation)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                Ok((invitation, user_with_email))
+            })
+        })
+        .await?;
+
+    let organization_name: String = organizations::table
+        .find(organization_id)
+        .select(organizations::name)
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    send_invitation_email(connection, &invitation, &organization_name, user_with_email).await?;
+
+    if let Some(user_id) = user_with_email {
+        let notification = NewNotification::new(
+            user_id,
+            NotificationType::TeamInvite,
+            "Team invitation".to_string(),
+            format!(
+                "You've been invited to join {} with the {} role.",
+                organization_name,
+                role.display_name()
+            ),
+        )
+        .set_organization(organization_id)
+        .set_link(format!("/invitations/{}", invitation.token));
+
+        let notifications = insert_notifications(connection, vec![notification]).await?;
+        publish_notifications(&notifications).await;
+    }
+
+    Ok(())
+}
+
+// pending includes invitations whose link has lapsed so they can still be resent
+pub async fn list_pending_invitations(organization_id: i32) -> Result<Vec<Invitation>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    invitations::table
         .filter(invitations::organization_id.eq(organization_id))
-        .filter(invitations::email.eq(&email))
         .filter(invitations::status.eq(InvitationStatus::Pending.as_str()))
-        .first::<crate::models::Invitation>(connection)
+        .order(invitations::created_at.desc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// the invitation with the organization's name and the inviter's full name
+pub async fn get_invitation_by_token(
+    token: Uuid,
+) -> Result<(Invitation, String, String), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (invitation, organization_name, inviter_first_name, inviter_last_name): (
+        Invitation,
+        String,
+        String,
+        String,
+    ) = invitations::table
+        .inner_join(organizations::table)
+        .inner_join(users::table.on(users::id.eq(invitations::invited_by)))
+        .filter(invitations::token.eq(token))
+        .select((
+            invitations::all_columns,
+            organizations::name,
+            users::first_name,
+            users::last_name,
+        ))
+        .first(connection)
         .await
         .optional()
-        .map_err(postgres_error)?;
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Invitation"))?;
 
-    if let Some(invite) = existing {
-        if !invite.is_expired() {
-            return Err(AppError::already_exists(
-                "Pending invitation for this email",
-            ));
-        }
+    let inviter_name = format!("{} {}", inviter_first_name, inviter_last_name)
+        .trim()
+        .to_string();
 
-        diesel::delete(invitations::table.find(invite.id))
-            .execute(connection)
-            .await
-            .map_err(postgres_error)?;
-    }
+    Ok((invitation, organization_name, inviter_name))
+}
 
-    let user_with_email = users::table
-        .filter(users::email.eq(&email))
-        .select(users::id)
-        .first::<i32>(connection)
+async fn is_organization_member(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<bool, AppError> {
+    let existing: Option<i32> = organization_members::table
+        .filter(organization_members::organization_id.eq(organization_id))
+        .filter(organization_members::user_id.eq(user_id))
+        .select(organization_members::id)
+        .first(connection)
         .await
         .optional()
         .map_err(postgres_error)?;
 
-    if let Some(user_id) = user_with_email {
-        let already_member = organization_members::table
-            .filter(organization_members::organization_id.eq(organization_id))
-            .filter(organization_members::user_id.eq(user_id))
-            .first::<OrganizationMember>(connection)
-            .await
-            .optional()
-            .map_err(postgres_error)?;
-
-        if already_member.is_some() {
-            return Err(AppError::already_exists(
-                "User is already a member of this organization",
-            ));
-        }
+    Ok(existing.is_some())
+}
+
+async fn mark_invitation_accepted(
+    connection: &mut AsyncPgConnection,
+    invitation_id: i32,
+) -> Result<(), AppError> {
+    diesel::update(invitations::table.find(invitation_id))
+        .set(InvitationUpdate {
+            status: Some(InvitationStatus::Accepted.as_str().to_string()),
+            accepted_at: Some(Utc::now()),
+            ..Default::default()
+        })
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(())
+}
+
+async fn accept_invitation_for_user(
+    connection: &mut AsyncPgConnection,
+    invitation: &Invitation,
+    user_id: i32,
+) -> Result<(OrganizationMember, Vec<Notification>), AppError> {
+    if is_organization_member(connection, invitation.organization_id, user_id).await? {
+        return Err(AppError::already_exists("Member"));
     }
 
-    diesel::delete(
-        invitations::table
-            .filter(invitations::organization_id.eq(organization_id))
-            .filter(invitations::email.eq(&email))
-            .filter(invitations::status.ne(InvitationStatus::Pending.as_str())),
+    let (member, notifications) = insert_member(
+        connection,
+        invitation.organization_id,
+        user_id,
+        invitation.get_role(),
+        Some(invitation.invited_by),
     )
-    .execute(connection)
-    .await
-    .map_err(postgres_error)?;
+    .await?;
 
-    let invitation = NewInvitation::new(
-        organization_id,
-        email,
-        role.as_str().to_string(),
-        invited_by,
-    );
+    mark_invitation_accepted(connection, invitation.id).await?;
 
-    diesel::insert_into(invitations::table)
-        .values(&invitation)
-        .execute(connection)
+    Ok((member, notifications))
+}
+
+// the invitation is locked so opening the link twice can't add the member twice,
+// and only the account the invitation was sent to can accept it
+pub async fn accept_invitation(token: Uuid, user_id: i32) -> Result<OrganizationMember, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (member, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let invitation: Invitation = invitations::table
+                    .filter(invitations::token.eq(token))
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Invitation"))?;
+
+                if !invitation.can_accept() {
+                    return Err(AppError::validation(
+                        "token",
+                        "This invitation has expired or is no longer valid",
+                    ));
+                }
+
+                let email: String = users::table
+                    .find(user_id)
+                    .select(users::email)
+                    .first(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                if !email.eq_ignore_ascii_case(&invitation.email) {
+                    return Err(AppError::validation(
+                        "email",
+                        "This invitation was sent to a different email address",
+                    ));
+                }
+
+                accept_invitation_for_user(connection, &invitation, user_id).await
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+
+    Ok(member)
+}
+
+// run once an account's email is verified, every open invitation sent to it is
+// accepted, returns the new memberships. an invitation to an organization the
+// account already belongs to is closed as accepted without touching the membership,
+// so one stale invitation doesn't hold back the others
+pub async fn accept_pending_invitations(
+    user_id: i32,
+    email: &str,
+) -> Result<Vec<OrganizationMember>, AppError> {
+    let email = email.to_lowercase();
+    let connection = &mut get_postgres_connection().await?;
+
+    let (members, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let pending: Vec<Invitation> = invitations::table
+                    .filter(invitations::email.eq(&email))
+                    .filter(invitations::status.eq(InvitationStatus::Pending.as_str()))
+                    .filter(invitations::expires_at.gt(Utc::now()))
+                    .order(invitations::created_at.asc())
+                    .for_update()
+                    .load(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                let mut members = Vec::with_capacity(pending.len());
+                let mut notifications = vec![];
+                for invitation in pending.iter() {
+                    if is_organization_member(connection, invitation.organization_id, user_id)
+                        .await?
+                    {
+                        mark_invitation_accepted(connection, invitation.id).await?;
+                        continue;
+                    }
+
+                    let (member, member_notifications) =
+                        accept_invitation_for_user(connection, invitation, user_id).await?;
+                    members.push(member);
+                    notifications.extend(member_notifications);
+                }
+
+                Ok((members, notifications))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+
+    Ok(members)
+}
+
+async fn get_organization_invitation(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    invitation_id: i32,
+) -> Result<Invitation, AppError> {
+    invitations::table
+        .find(invitation_id)
+        .filter(invitations::organization_id.eq(organization_id))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Invitation"))
+}
+
+// the link stops working straight away, the row is kept so the list shows who was
+// invited until a new invitation to the same email replaces it
+pub async fn revoke_invitation(
+    organization_id: i32,
+    invitation_id: i32,
+) -> Result<Invitation, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let invitation =
+        get_organization_invitation(connection, organization_id, invitation_id).await?;
+
+    if invitation.get_status() != InvitationStatus::Pending {
+        return Err(AppError::validation(
+            "invitation",
+            "Only pending invitations can be revoked",
+        ));
+    }
+
+    diesel::update(invitations::table.find(invitation.id))
+        .set(InvitationUpdate {
+            status: Some(InvitationStatus::Revoked.as_str().to_string()),
+            ..Default::default()
+        })
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// the same link is sent again with a full expiry window from now
+pub async fn resend_invitation(
+    organization_id: i32,
+    invitation_id: i32,
+) -> Result<Invitation, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    // a lapsed invitation no longer holds a seat, so reviving it takes one under
+    // the organization's row lock like a new invitation
+    let invitation = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let invitation =
+                    get_organization_invitation(connection, organization_id, invitation_id).await?;
+
+                if !matches!(
+                    invitation.get_status(),
+                    InvitationStatus::Pending | InvitationStatus::Expired
+                ) {
+                    return Err(AppError::validation(
+                        "invitation",
+                        "Only pending invitations can be resent",
+                    ));
+                }
+
+                if !invitation.can_accept() {
+                    ensure_member_seat_available(connection, organization_id).await?;
+                }
+
+                diesel::update(invitations::table.find(invitation.id))
+                    .set(InvitationUpdate {
+                        status: Some(InvitationStatus::Pending.as_str().to_string()),
+                        expires_at: Some(
+                            Utc::now() + Duration::days(DEFAULT_INVITATION_EXPIRY_DAYS),
+                        ),
+                        ..Default::default()
+                    })
+                    .get_result::<Invitation>(connection)
+                    .await
+                    .map_err(postgres_error)
+            })
+        })
+        .await?;
+
+    let organization_name: String = organizations::table
+        .find(organization_id)
+        .select(organizations::name)
+        .first(connection)
         .await
         .map_err(postgres_error)?;
 
-    Ok(())
+    let invitee_user_id: Option<i32> = users::table
+        .filter(users::email.eq(&invitation.email))
+        .select(users::id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    send_invitation_email(connection, &invitation, &organization_name, invitee_user_id).await?;
+
+    Ok(invitation)
 }
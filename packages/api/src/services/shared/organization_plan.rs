@@ -0,0 +1,390 @@
+use crate::enums::{
+    AuditAction, InvitationStatus, MessageChannel, SubscriptionPlan, DEFAULT_SUBSCRIPTION_PLAN,
+    MAX_PLAN_CHANGE_NOTE_LENGTH,
+};
+use crate::error::{postgres_error, AppError};
+use crate::models::{NewAuditLog, NewOrganizationPlan, Organization, OrganizationPlan};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{
+    events, invitations, organization_members, organization_plans, organizations, outbound_messages,
+};
+use crate::services::{record_audit_log, validate_optional_string};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+use std::collections::HashMap;
+
+// monthly quotas reset at the start of each calendar month in UTC
+pub fn start_of_current_month() -> DateTime<Utc> {
+    let now = Utc::now();
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+// the latest change that has taken effect, an organization without one is on the
+// default plan
+pub async fn get_current_organization_plan(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+) -> Result<SubscriptionPlan, AppError> {
+    let plan: Option<String> = organization_plans::table
+        .filter(organization_plans::organization_id.eq(organization_id))
+        .filter(organization_plans::effective_from.le(Utc::now()))
+        .order((
+            organization_plans::effective_from.desc(),
+            organization_plans::id.desc(),
+        ))
+        .select(organization_plans::plan)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    Ok(plan
+        .and_then(|plan| SubscriptionPlan::from_str(&plan))
+        .unwrap_or(DEFAULT_SUBSCRIPTION_PLAN))
+}
+
+pub async fn batch_get_current_organization_plans(
+    organization_ids: &[i32],
+) -> Result<HashMap<i32, SubscriptionPlan>, AppError> {
+    if organization_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let plans: Vec<OrganizationPlan> = organization_plans::table
+        .filter(organization_plans::organization_id.eq_any(organization_ids))
+        .filter(organization_plans::effective_from.le(Utc::now()))
+        .distinct_on(organization_plans::organization_id)
+        .order((
+            organization_plans::organization_id,
+            organization_plans::effective_from.desc(),
+            organization_plans::id.desc(),
+        ))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(organization_ids
+        .iter()
+        .map(|organization_id| {
+            let plan = plans
+                .iter()
+                .find(|plan| plan.organization_id == *organization_id)
+                .map(|plan| plan.get_plan())
+                .unwrap_or(DEFAULT_SUBSCRIPTION_PLAN);
+            (*organization_id, plan)
+        })
+        .collect())
+}
+
+// every change newest first, including ones scheduled for later
+pub async fn list_organization_plan_history(
+    organization_id: i32,
+) -> Result<Vec<OrganizationPlan>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    organization_plans::table
+        .filter(organization_plans::organization_id.eq(organization_id))
+        .order((
+            organization_plans::effective_from.desc(),
+            organization_plans::id.desc(),
+        ))
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// staff move an organization to a plan now or from a later date, a new change
+// replaces one that is still scheduled, past changes are kept as history
+pub async fn change_organization_plan(
+    organization_id: i32,
+    plan: SubscriptionPlan,
+    effective_from: Option<DateTime<Utc>>,
+    changed_by: i32,
+    note: Option<String>,
+) -> Result<OrganizationPlan, AppError> {
+    validate_optional_string("note", &note, MAX_PLAN_CHANGE_NOTE_LENGTH)?;
+
+    let now = Utc::now();
+    let effective_from = effective_from.unwrap_or(now);
+
+    if effective_from < now {
+        return Err(AppError::validation(
+            "effective_from",
+            "Plan changes can't take effect in the past",
+        ));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let organization: Organization = organizations::table
+                    .find(organization_id)
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Organization"))?;
+
+                if organization.is_archived() {
+                    return Err(AppError::validation(
+                        "organization",
+                        "Archived organizations can't change plans",
+                    ));
+                }
+
+                let previous_plan =
+                    get_current_organization_plan(connection, organization.id).await?;
+
+                diesel::delete(
+                    organization_plans::table
+                        .filter(organization_plans::organization_id.eq(organization.id))
+                        .filter(organization_plans::effective_from.gt(now)),
+                )
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let mut new_plan = NewOrganizationPlan::new(organization.id, plan, effective_from)
+                    .set_changed_by(changed_by);
+                if let Some(note) = note {
+                    new_plan = new_plan.set_note(note);
+                }
+
+                let organization_plan: OrganizationPlan =
+                    diesel::insert_into(organization_plans::table)
+                        .values(&new_plan)
+                        .get_result(connection)
+                        .await
+                        .map_err(postgres_error)?;
+
+                record_audit_log(
+                    connection,
+                    NewAuditLog::new(
+                        AuditAction::PlanChanged,
+                        json!({
+                            "previous_plan": previous_plan.as_str(),
+                            "plan": plan.as_str(),
+                            "effective_from": effective_from,
+                        }),
+                    )
+                    .set_organization(organization.id)
+                    .set_actor(changed_by),
+                )
+                .await?;
+
+                Ok(organization_plan)
+            })
+        })
+        .await
+}
+
+// seats are taken by members and by invitations that can still be accepted
+pub async fn count_used_member_seats(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+) -> Result<i64, AppError> {
+    let members: i64 = organization_members::table
+        .filter(organization_members::organization_id.eq(organization_id))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let pending_invitations: i64 = invitations::table
+        .filter(invitations::organization_id.eq(organization_id))
+        .filter(invitations::status.eq(InvitationStatus::Pending.as_str()))
+        .filter(invitations::expires_at.gt(Utc::now()))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(members + pending_invitations)
+}
+
+// usage is checked and taken under the organization's row lock, so the caller's
+// transaction has to make its insert before committing or two requests could both
+// take the last of a limit
+pub async fn lock_organization_for_quota(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+) -> Result<(), AppError> {
+    organizations::table
+        .find(organization_id)
+        .select(organizations::id)
+        .for_update()
+        .first::<i32>(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Organization"))?;
+
+    Ok(())
+}
+
+// must run in the transaction that adds the member or invitation
+pub async fn ensure_member_seat_available(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+) -> Result<(), AppError> {
+    lock_organization_for_quota(connection, organization_id).await?;
+
+    let plan = get_current_organization_plan(connection, organization_id).await?;
+
+    let Some(max_members) = plan.entitlements().max_members else {
+        return Ok(());
+    };
+
+    if count_used_member_seats(connection, organization_id).await? >= max_members {
+        return Err(AppError::PermissionDenied(format!(
+            "The {} plan includes up to {} members, including pending invitations",
+            plan.display_name(),
+            max_members
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn get_used_storage_bytes(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+) -> Result<i64, AppError> {
+    let sizes: Vec<i64> = events::table
+        .filter(events::organization_id.eq(organization_id))
+        .filter(events::image_storage_bytes.gt(0))
+        .select(events::image_storage_bytes)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(sizes.iter().sum())
+}
+
+// freed_bytes is what the upload replaces, so swapping an image only counts the
+// difference, must run in the transaction that records the upload
+pub async fn ensure_storage_available(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    additional_bytes: i64,
+    freed_bytes: i64,
+) -> Result<(), AppError> {
+    lock_organization_for_quota(connection, organization_id).await?;
+
+    let plan = get_current_organization_plan(connection, organization_id).await?;
+
+    let Some(storage_quota_bytes) = plan.entitlements().storage_quota_bytes else {
+        return Ok(());
+    };
+
+    let used = get_used_storage_bytes(connection, organization_id).await?;
+
+    if used - freed_bytes + additional_bytes > storage_quota_bytes {
+        return Err(AppError::PermissionDenied(format!(
+            "The {} plan includes {} MB of storage",
+            plan.display_name(),
+            storage_quota_bytes / (1024 * 1024)
+        )));
+    }
+
+    Ok(())
+}
+
+// organizations from before plans were introduced have no plan yet, they're put on
+// the smallest plan that covers what they already use so nobody loses seats or
+// sending. runs at startup rather than in the migration so the plan limits are only
+// defined in SubscriptionPlan::entitlements, returns how many were assigned
+pub async fn assign_missing_organization_plans() -> Result<usize, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let organization_ids: Vec<i32> = organizations::table
+        .filter(diesel::dsl::not(diesel::dsl::exists(
+            organization_plans::table
+                .filter(organization_plans::organization_id.eq(organizations::id)),
+        )))
+        .select(organizations::id)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let mut assigned = 0;
+
+    for organization_id in organization_ids {
+        // servers starting together each lock the organization and check again
+        let plan = connection
+            .transaction::<_, AppError, _>(|connection| {
+                Box::pin(async move {
+                    lock_organization_for_quota(connection, organization_id).await?;
+
+                    let has_plan: i64 = organization_plans::table
+                        .filter(organization_plans::organization_id.eq(organization_id))
+                        .count()
+                        .get_result(connection)
+                        .await
+                        .map_err(postgres_error)?;
+                    if has_plan > 0 {
+                        return Ok(None);
+                    }
+
+                    let created_at: DateTime<Utc> = organizations::table
+                        .find(organization_id)
+                        .select(organizations::created_at)
+                        .first(connection)
+                        .await
+                        .map_err(postgres_error)?;
+
+                    let member_seats = count_used_member_seats(connection, organization_id).await?;
+                    let monthly_messages: Vec<(String, i64)> = outbound_messages::table
+                        .filter(outbound_messages::organization_id.eq(organization_id))
+                        .filter(outbound_messages::created_at.ge(start_of_current_month()))
+                        .group_by(outbound_messages::channel)
+                        .select((outbound_messages::channel, diesel::dsl::count_star()))
+                        .load(connection)
+                        .await
+                        .map_err(postgres_error)?;
+                    let monthly_count = |channel: MessageChannel| {
+                        monthly_messages
+                            .iter()
+                            .find(|(message_channel, _)| message_channel == channel.as_str())
+                            .map(|(_, count)| *count)
+                            .unwrap_or(0)
+                    };
+
+                    let plan = SubscriptionPlan::smallest_covering(
+                        member_seats,
+                        monthly_count(MessageChannel::Email),
+                        monthly_count(MessageChannel::Sms),
+                    );
+
+                    diesel::insert_into(organization_plans::table)
+                        .values(
+                            NewOrganizationPlan::new(organization_id, plan, created_at).set_note(
+                                "Assigned from existing usage when plans were introduced"
+                                    .to_string(),
+                            ),
+                        )
+                        .execute(connection)
+                        .await
+                        .map_err(postgres_error)?;
+
+                    Ok(Some(plan))
+                })
+            })
+            .await?;
+
+        if plan.is_some() {
+            assigned += 1;
+        }
+    }
+
+    Ok(assigned)
+}
@@ -0,0 +1,304 @@
+use crate::enums::{
+    resolve_capabilities, Capability, MemberRole, MAX_ORGANIZATION_ROLE_NAME_LENGTH,
+};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    NewOrganizationRole, OrganizationMember, OrganizationMemberUpdate, OrganizationRole,
+    OrganizationRoleUpdate,
+};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{organization_members, organization_roles};
+use crate::services::validate_required_string;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::HashMap;
+
+pub async fn get_member_capabilities(
+    member: &OrganizationMember,
+) -> Result<Vec<Capability>, AppError> {
+    let custom_role_capabilities = match member.custom_role_id {
+        Some(custom_role_id) => {
+            let connection = &mut get_postgres_connection().await?;
+
+            organization_roles::table
+                .find(custom_role_id)
+                .first::<OrganizationRole>(connection)
+                .await
+                .optional()
+                .map_err(postgres_error)?
+                .map(|role| role.get_capabilities())
+        }
+        None => None,
+    };
+
+    Ok(resolve_capabilities(
+        member.get_role(),
+        custom_role_capabilities,
+    ))
+}
+
+// capabilities for several memberships at once, keyed by membership id
+pub async fn batch_get_member_capabilities(
+    members: &[&OrganizationMember],
+) -> Result<HashMap<i32, Vec<Capability>>, AppError> {
+    let custom_role_ids: Vec<i32> = members
+        .iter()
+        .filter_map(|member| member.custom_role_id)
+        .collect();
+
+    let custom_roles: HashMap<i32, Vec<Capability>> = if custom_role_ids.is_empty() {
+        HashMap::new()
+    } else {
+        let connection = &mut get_postgres_connection().await?;
+
+        organization_roles::table
+            .filter(organization_roles::id.eq_any(&custom_role_ids))
+            .load::<OrganizationRole>(connection)
+            .await
+            .map_err(postgres_error)?
+            .into_iter()
+            .map(|role| (role.id, role.get_capabilities()))
+            .collect()
+    };
+
+    Ok(members
+        .iter()
+        .map(|member| {
+            let custom_role_capabilities = member
+                .custom_role_id
+                .and_then(|custom_role_id| custom_roles.get(&custom_role_id).cloned());
+            (
+                member.id,
+                resolve_capabilities(member.get_role(), custom_role_capabilities),
+            )
+        })
+        .collect())
+}
+
+// nobody with roles.manage can hand out more than they hold themselves, otherwise
+// a delegated roles.manage would reach every owner capability
+fn ensure_grantable(
+    capabilities: &[Capability],
+    grantor_capabilities: &[Capability],
+) -> Result<(), AppError> {
+    if capabilities
+        .iter()
+        .all(|capability| grantor_capabilities.contains(capability))
+    {
+        Ok(())
+    } else {
+        Err(AppError::validation(
+            "capabilities",
+            "You can't grant permissions you don't have",
+        ))
+    }
+}
+
+async fn get_organization_role(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    role_id: i32,
+) -> Result<OrganizationRole, AppError> {
+    organization_roles::table
+        .find(role_id)
+        .filter(organization_roles::organization_id.eq(organization_id))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Role"))
+}
+
+async fn ensure_unique_role_name(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    name: &str,
+    exclude_role_id: Option<i32>,
+) -> Result<(), AppError> {
+    let existing: Vec<OrganizationRole> = organization_roles::table
+        .filter(organization_roles::organization_id.eq(organization_id))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let taken = existing
+        .iter()
+        .any(|role| Some(role.id) != exclude_role_id && role.name.eq_ignore_ascii_case(name));
+
+    if taken {
+        return Err(AppError::already_exists("Role with this name"));
+    }
+
+    Ok(())
+}
+
+pub async fn list_organization_roles(
+    organization_id: i32,
+) -> Result<Vec<OrganizationRole>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    organization_roles::table
+        .filter(organization_roles::organization_id.eq(organization_id))
+        .order(organization_roles::name.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn create_organization_role(
+    organization_id: i32,
+    name: String,
+    capabilities: Vec<Capability>,
+    grantor: &OrganizationMember,
+) -> Result<OrganizationRole, AppError> {
+    let name = name.trim().to_string();
+    validate_required_string("name", &name, MAX_ORGANIZATION_ROLE_NAME_LENGTH)?;
+    ensure_grantable(&capabilities, &get_member_capabilities(grantor).await?)?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    ensure_unique_role_name(connection, organization_id, &name, None).await?;
+
+    diesel::insert_into(organization_roles::table)
+        .values(&NewOrganizationRole::new(
+            organization_id,
+            name,
+            &capabilities,
+        ))
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// members holding the role pick up the new capabilities on their next request
+pub async fn update_organization_role(
+    organization_id: i32,
+    role_id: i32,
+    name: Option<String>,
+    capabilities: Option<Vec<Capability>>,
+    grantor: &OrganizationMember,
+) -> Result<OrganizationRole, AppError> {
+    let name = name.map(|name| name.trim().to_string());
+    if let Some(ref name) = name {
+        validate_required_string("name", name, MAX_ORGANIZATION_ROLE_NAME_LENGTH)?;
+    }
+    if let Some(ref capabilities) = capabilities {
+        ensure_grantable(capabilities, &get_member_capabilities(grantor).await?)?;
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let role = get_organization_role(connection, organization_id, role_id).await?;
+
+    if let Some(ref name) = name {
+        ensure_unique_role_name(connection, organization_id, name, Some(role.id)).await?;
+    }
+
+    diesel::update(organization_roles::table.find(role.id))
+        .set(OrganizationRoleUpdate {
+            name,
+            capabilities: capabilities.map(|capabilities| {
+                capabilities
+                    .iter()
+                    .map(|capability| Some(capability.as_str().to_string()))
+                    .collect()
+            }),
+        })
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// members holding the role go back to the defaults of their member role, which
+// can't go past what the grantor holds
+pub async fn delete_organization_role(
+    organization_id: i32,
+    role_id: i32,
+    grantor: &OrganizationMember,
+) -> Result<(), AppError> {
+    let grantor_capabilities = get_member_capabilities(grantor).await?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let role = get_organization_role(connection, organization_id, role_id).await?;
+
+    let holders: Vec<OrganizationMember> = organization_members::table
+        .filter(organization_members::custom_role_id.eq(role.id))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    for holder in holders {
+        ensure_grantable(
+            &resolve_capabilities(holder.get_role(), None),
+            &grantor_capabilities,
+        )?;
+    }
+
+    diesel::delete(organization_roles::table.find(role.id))
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(())
+}
+
+// None clears the custom role, the owner always keeps every capability so they
+// can't be given one. nobody changes their own role, and the member can't end up
+// with capabilities the grantor doesn't hold
+pub async fn assign_member_custom_role(
+    organization_id: i32,
+    member_id: i32,
+    role_id: Option<i32>,
+    grantor: &OrganizationMember,
+) -> Result<OrganizationMember, AppError> {
+    let grantor_capabilities = get_member_capabilities(grantor).await?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let member: OrganizationMember = organization_members::table
+        .find(member_id)
+        .filter(organization_members::organization_id.eq(organization_id))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Member"))?;
+
+    if member.get_role() == MemberRole::Owner {
+        return Err(AppError::validation(
+            "member",
+            "The owner always has every permission",
+        ));
+    }
+
+    if member.user_id == grantor.user_id {
+        return Err(AppError::validation(
+            "member",
+            "You can't change your own role",
+        ));
+    }
+
+    let custom_role_capabilities = match role_id {
+        Some(role_id) => Some(
+            get_organization_role(connection, organization_id, role_id)
+                .await?
+                .get_capabilities(),
+        ),
+        None => None,
+    };
+
+    ensure_grantable(
+        &resolve_capabilities(member.get_role(), custom_role_capabilities),
+        &grantor_capabilities,
+    )?;
+
+    diesel::update(organization_members::table.find(member.id))
+        .set(OrganizationMemberUpdate {
+            custom_role_id: Some(role_id),
+            ..Default::default()
+        })
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
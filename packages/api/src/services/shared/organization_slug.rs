@@ -0,0 +1,221 @@
+use crate::enums::{AuditAction, RETIRED_ORGANIZATION_SLUG_RESERVATION_DAYS};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    NewAuditLog, NewRetiredOrganizationSlug, Organization, OrganizationUpdate,
+    RetiredOrganizationSlug,
+};
+use crate::postgres::get_postgres_connection;
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{events, organizations, retired_organization_slugs};
+use crate::services::{
+    get_organization_by_slug, record_audit_log, validate_required_string, validate_slug,
+    MAX_ORGANIZATION_SLUG_LENGTH,
+};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+
+// a slug is free when no organization has it and it isn't a retired slug that is
+// still reserved, archived organizations keep theirs
+pub(crate) async fn is_organization_slug_available(
+    connection: &mut AsyncPgConnection,
+    slug: &str,
+) -> Result<bool, AppError> {
+    let in_use: i64 = organizations::table
+        .filter(organizations::slug.eq(slug))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let reserved: i64 = retired_organization_slugs::table
+        .filter(retired_organization_slugs::slug.eq(slug))
+        .filter(retired_organization_slugs::reserved_until.gt(Utc::now()))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(in_use == 0 && reserved == 0)
+}
+
+// once a retired slug's reservation is over, whoever claims it ends the redirect
+pub(crate) async fn release_retired_organization_slug(
+    connection: &mut AsyncPgConnection,
+    slug: &str,
+) -> Result<(), AppError> {
+    diesel::delete(
+        retired_organization_slugs::table
+            .filter(retired_organization_slugs::slug.eq(slug))
+            .filter(retired_organization_slugs::reserved_until.le(Utc::now())),
+    )
+    .execute(connection)
+    .await
+    .map_err(postgres_error)?;
+
+    Ok(())
+}
+
+// follows retired slugs to the organization that used them, the flag is set when
+// the slug is no longer current so callers can redirect
+pub async fn resolve_organization_slug(
+    slug: &str,
+) -> Result<Option<(Organization, bool)>, AppError> {
+    if let Some(organization) = get_organization_by_slug(slug).await? {
+        return Ok(Some((organization, false)));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let organization: Option<Organization> = retired_organization_slugs::table
+        .inner_join(organizations::table)
+        .filter(retired_organization_slugs::slug.eq(slug))
+        .select(Organization::as_select())
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    Ok(organization.map(|organization| (organization, true)))
+}
+
+pub async fn list_retired_organization_slugs(
+    organization_id: i32,
+) -> Result<Vec<RetiredOrganizationSlug>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    retired_organization_slugs::table
+        .filter(retired_organization_slugs::organization_id.eq(organization_id))
+        .order(retired_organization_slugs::retired_at.desc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// the old slug keeps resolving to the organization and is reserved from other
+// organizations for a while, an organization can always take back one of its own
+pub async fn change_organization_slug(
+    organization_id: i32,
+    slug: String,
+    changed_by: i32,
+) -> Result<Organization, AppError> {
+    let slug = slug.trim().to_lowercase();
+    validate_required_string("slug", &slug, MAX_ORGANIZATION_SLUG_LENGTH)?;
+    validate_slug("slug", &slug, MAX_ORGANIZATION_SLUG_LENGTH)?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let organization = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let organization: Organization = organizations::table
+                    .find(organization_id)
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Organization"))?;
+
+                if organization.is_archived() {
+                    return Err(AppError::validation(
+                        "organization",
+                        "Archived organizations can't change their slug",
+                    ));
+                }
+                if organization.slug == slug {
+                    return Err(AppError::validation(
+                        "slug",
+                        "This is already the organization's slug",
+                    ));
+                }
+
+                let retired: Option<RetiredOrganizationSlug> = retired_organization_slugs::table
+                    .filter(retired_organization_slugs::slug.eq(&slug))
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?;
+
+                if let Some(retired) = retired {
+                    if retired.organization_id != organization.id && retired.is_reserved() {
+                        return Err(AppError::validation(
+                            "slug",
+                            "This slug was recently used by another organization",
+                        ));
+                    }
+
+                    diesel::delete(retired_organization_slugs::table.find(retired.id))
+                        .execute(connection)
+                        .await
+                        .map_err(postgres_error)?;
+                }
+
+                let in_use: i64 = organizations::table
+                    .filter(organizations::slug.eq(&slug))
+                    .count()
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                if in_use > 0 {
+                    return Err(AppError::already_exists("Organization with this slug"));
+                }
+
+                diesel::insert_into(retired_organization_slugs::table)
+                    .values(
+                        &NewRetiredOrganizationSlug::new(
+                            organization.id,
+                            organization.slug.clone(),
+                            Utc::now() + Duration::days(RETIRED_ORGANIZATION_SLUG_RESERVATION_DAYS),
+                        )
+                        .set_changed_by(changed_by),
+                    )
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                let updated: Organization =
+                    diesel::update(organizations::table.find(organization.id))
+                        .set(OrganizationUpdate {
+                            slug: Some(slug.clone()),
+                            ..Default::default()
+                        })
+                        .get_result(connection)
+                        .await
+                        .map_err(postgres_error)?;
+
+                record_audit_log(
+                    connection,
+                    NewAuditLog::new(
+                        AuditAction::SlugChanged,
+                        json!({
+                            "previous_slug": organization.slug,
+                            "slug": updated.slug,
+                        }),
+                    )
+                    .set_organization(organization.id)
+                    .set_actor(changed_by),
+                )
+                .await?;
+
+                Ok(updated)
+            })
+        })
+        .await?;
+
+    // cached public event pages carry the organization's slug
+    let event_ids: Vec<i32> = events::table
+        .filter(events::organization_id.eq(organization.id))
+        .select(events::id)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+    for event_id in event_ids {
+        invalidate_redis_cached_public_event(event_id).await.ok();
+    }
+
+    Ok(organization)
+}
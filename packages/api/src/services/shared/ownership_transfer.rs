@@ -0,0 +1,383 @@
+use crate::enums::{AuditAction, MemberRole, NotificationType, OwnershipTransferStatus};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    NewAuditLog, NewNotification, NewOwnershipTransfer, Organization, OrganizationMember,
+    OrganizationMemberUpdate, OwnershipTransfer, OwnershipTransferUpdate,
+};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{organization_members, organizations, ownership_transfers, users};
+use crate::services::{insert_notifications, publish_notifications, record_audit_log};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
+
+async fn get_user_name(
+    connection: &mut AsyncPgConnection,
+    user_id: i32,
+) -> Result<String, AppError> {
+    let (first_name, last_name): (String, String) = users::table
+        .find(user_id)
+        .select((users::first_name, users::last_name))
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(format!("{} {}", first_name, last_name).trim().to_string())
+}
+
+async fn get_organization_name(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+) -> Result<String, AppError> {
+    organizations::table
+        .find(organization_id)
+        .select(organizations::name)
+        .first(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// locks the membership so role changes for the same person happen one at a time
+async fn lock_membership(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<Option<OrganizationMember>, AppError> {
+    organization_members::table
+        .filter(organization_members::organization_id.eq(organization_id))
+        .filter(organization_members::user_id.eq(user_id))
+        .for_update()
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)
+}
+
+// the transfer is only visible to the member it was offered to
+async fn lock_incoming_transfer(
+    connection: &mut AsyncPgConnection,
+    transfer_id: i32,
+    user_id: i32,
+) -> Result<OwnershipTransfer, AppError> {
+    let transfer: OwnershipTransfer = ownership_transfers::table
+        .find(transfer_id)
+        .filter(ownership_transfers::to_user_id.eq(user_id))
+        .for_update()
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Ownership transfer"))?;
+
+    if !transfer.can_respond() {
+        return Err(AppError::validation(
+            "transfer",
+            "This transfer has expired or is no longer valid",
+        ));
+    }
+
+    Ok(transfer)
+}
+
+// the owner offers the organization to another member, nothing changes until they
+// accept, a new offer replaces one that is still waiting
+pub async fn request_ownership_transfer(
+    organization_id: i32,
+    owner_user_id: i32,
+    member_id: i32,
+) -> Result<OwnershipTransfer, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (transfer, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let owner = lock_membership(connection, organization_id, owner_user_id)
+                    .await?
+                    .filter(|member| member.get_role() == MemberRole::Owner)
+                    .ok_or_else(|| {
+                        AppError::validation("member", "Only the owner can transfer ownership")
+                    })?;
+
+                let nominee: OrganizationMember = organization_members::table
+                    .find(member_id)
+                    .filter(organization_members::organization_id.eq(organization_id))
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Member"))?;
+
+                if nominee.id == owner.id {
+                    return Err(AppError::validation(
+                        "member",
+                        "You already own this organization",
+                    ));
+                }
+
+                diesel::update(
+                    ownership_transfers::table
+                        .filter(ownership_transfers::organization_id.eq(organization_id))
+                        .filter(
+                            ownership_transfers::status
+                                .eq(OwnershipTransferStatus::Pending.as_str()),
+                        ),
+                )
+                .set(OwnershipTransferUpdate {
+                    status: Some(OwnershipTransferStatus::Cancelled.as_str().to_string()),
+                    responded_at: Some(Some(Utc::now())),
+                })
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let transfer: OwnershipTransfer = diesel::insert_into(ownership_transfers::table)
+                    .values(&NewOwnershipTransfer::new(
+                        organization_id,
+                        owner_user_id,
+                        nominee.user_id,
+                    ))
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                let owner_name = get_user_name(connection, owner_user_id).await?;
+                let organization_name = get_organization_name(connection, organization_id).await?;
+
+                let notification = NewNotification::new(
+                    nominee.user_id,
+                    NotificationType::OwnershipTransfer,
+                    "Ownership transfer".to_string(),
+                    format!(
+                        "{} wants to make you the owner of {}.",
+                        owner_name, organization_name
+                    ),
+                )
+                .set_organization(organization_id)
+                .set_link("/account/organizations".to_string());
+
+                let notifications = insert_notifications(connection, vec![notification]).await?;
+
+                Ok((transfer, notifications))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+
+    Ok(transfer)
+}
+
+pub async fn get_pending_ownership_transfer(
+    organization_id: i32,
+) -> Result<Option<OwnershipTransfer>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    ownership_transfers::table
+        .filter(ownership_transfers::organization_id.eq(organization_id))
+        .filter(ownership_transfers::status.eq(OwnershipTransferStatus::Pending.as_str()))
+        .filter(ownership_transfers::expires_at.gt(Utc::now()))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)
+}
+
+// transfers waiting on the user, newest first
+pub async fn list_incoming_ownership_transfers(
+    user_id: i32,
+) -> Result<Vec<(OwnershipTransfer, Organization)>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    ownership_transfers::table
+        .inner_join(organizations::table)
+        .filter(ownership_transfers::to_user_id.eq(user_id))
+        .filter(ownership_transfers::status.eq(OwnershipTransferStatus::Pending.as_str()))
+        .filter(ownership_transfers::expires_at.gt(Utc::now()))
+        .select((OwnershipTransfer::as_select(), Organization::as_select()))
+        .order(ownership_transfers::created_at.desc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// both memberships are locked and the roles swap in one transaction, the previous
+// owner stays on as an admin, returns the new owner's membership
+pub async fn accept_ownership_transfer(
+    transfer_id: i32,
+    user_id: i32,
+) -> Result<OrganizationMember, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (member, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let transfer = lock_incoming_transfer(connection, transfer_id, user_id).await?;
+                let organization_id = transfer.organization_id;
+
+                let previous_owner =
+                    lock_membership(connection, organization_id, transfer.from_user_id)
+                        .await?
+                        .filter(|member| member.get_role() == MemberRole::Owner);
+                let nominee = lock_membership(connection, organization_id, user_id).await?;
+
+                let (Some(previous_owner), Some(nominee)) = (previous_owner, nominee) else {
+                    return Err(AppError::validation(
+                        "transfer",
+                        "This transfer is no longer valid",
+                    ));
+                };
+
+                diesel::update(organization_members::table.find(previous_owner.id))
+                    .set(OrganizationMemberUpdate {
+                        role: Some(MemberRole::Admin.as_str().to_string()),
+                        ..Default::default()
+                    })
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                let member: OrganizationMember =
+                    diesel::update(organization_members::table.find(nominee.id))
+                        .set(OrganizationMemberUpdate {
+                            role: Some(MemberRole::Owner.as_str().to_string()),
+                            custom_role_id: Some(None),
+                            ..Default::default()
+                        })
+                        .get_result(connection)
+                        .await
+                        .map_err(postgres_error)?;
+
+                diesel::update(ownership_transfers::table.find(transfer.id))
+                    .set(OwnershipTransferUpdate {
+                        status: Some(OwnershipTransferStatus::Accepted.as_str().to_string()),
+                        responded_at: Some(Some(Utc::now())),
+                    })
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                record_audit_log(
+                    connection,
+                    NewAuditLog::new(
+                        AuditAction::OwnershipTransferred,
+                        json!({
+                            "transfer_id": transfer.id,
+                            "from_user_id": transfer.from_user_id,
+                            "to_user_id": user_id,
+                        }),
+                    )
+                    .set_organization(organization_id)
+                    .set_actor(user_id),
+                )
+                .await?;
+
+                let new_owner_name = get_user_name(connection, user_id).await?;
+                let organization_name = get_organization_name(connection, organization_id).await?;
+
+                let notifications = vec![
+                    NewNotification::new(
+                        transfer.from_user_id,
+                        NotificationType::OwnershipTransfer,
+                        "Ownership transferred".to_string(),
+                        format!(
+                            "{} is now the owner of {}. You've been made an admin.",
+                            new_owner_name, organization_name
+                        ),
+                    )
+                    .set_organization(organization_id)
+                    .set_link("/team".to_string()),
+                    NewNotification::new(
+                        user_id,
+                        NotificationType::OwnershipTransfer,
+                        "Ownership transferred".to_string(),
+                        format!("You're now the owner of {}.", organization_name),
+                    )
+                    .set_organization(organization_id)
+                    .set_link("/team".to_string()),
+                ];
+
+                let notifications = insert_notifications(connection, notifications).await?;
+
+                Ok((member, notifications))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+
+    Ok(member)
+}
+
+pub async fn decline_ownership_transfer(
+    transfer_id: i32,
+    user_id: i32,
+) -> Result<OwnershipTransfer, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (transfer, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let transfer = lock_incoming_transfer(connection, transfer_id, user_id).await?;
+
+                let transfer: OwnershipTransfer =
+                    diesel::update(ownership_transfers::table.find(transfer.id))
+                        .set(OwnershipTransferUpdate {
+                            status: Some(OwnershipTransferStatus::Declined.as_str().to_string()),
+                            responded_at: Some(Some(Utc::now())),
+                        })
+                        .get_result(connection)
+                        .await
+                        .map_err(postgres_error)?;
+
+                let nominee_name = get_user_name(connection, user_id).await?;
+                let organization_name =
+                    get_organization_name(connection, transfer.organization_id).await?;
+
+                let notification = NewNotification::new(
+                    transfer.from_user_id,
+                    NotificationType::OwnershipTransfer,
+                    "Ownership transfer declined".to_string(),
+                    format!(
+                        "{} declined to become the owner of {}.",
+                        nominee_name, organization_name
+                    ),
+                )
+                .set_organization(transfer.organization_id)
+                .set_link("/team".to_string());
+
+                let notifications = insert_notifications(connection, vec![notification]).await?;
+
+                Ok((transfer, notifications))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+
+    Ok(transfer)
+}
+
+// the owner withdraws the offer before it's answered
+pub async fn cancel_ownership_transfer(
+    organization_id: i32,
+    owner_user_id: i32,
+) -> Result<OwnershipTransfer, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::update(
+        ownership_transfers::table
+            .filter(ownership_transfers::organization_id.eq(organization_id))
+            .filter(ownership_transfers::from_user_id.eq(owner_user_id))
+            .filter(ownership_transfers::status.eq(OwnershipTransferStatus::Pending.as_str())),
+    )
+    .set(OwnershipTransferUpdate {
+        status: Some(OwnershipTransferStatus::Cancelled.as_str().to_string()),
+        responded_at: Some(Some(Utc::now())),
+    })
+    .get_result(connection)
+    .await
+    .optional()
+    .map_err(postgres_error)?
+    .ok_or_else(|| AppError::not_found("Ownership transfer"))
+}
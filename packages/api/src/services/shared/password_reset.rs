@@ -1,19 +1,24 @@
 use crate::error::{postgres_error, AppError};
-use crate::models::{NewPasswordResetToken, PasswordResetToken};
+use crate::models::{
+    NewPasswordResetToken, PasswordResetToken, PASSWORD_RESET_TOKEN_EXPIRY_SECONDS,
+};
 use crate::postgres::get_postgres_connection;
 use crate::schema::{password_reset_tokens, users};
 use crate::services::{
-    delete_all_user_sessions, get_user_by_email, hash_password, validate_password,
+    delete_all_user_sessions, get_app_link, get_user_by_email, hash_password, render_email,
+    send_email_in_background, validate_password, EmailTemplate,
 };
 use chrono::Utc;
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use uuid::Uuid;
 
-pub async fn request_password_reset(email: &str) -> Result<Option<Uuid>, AppError> {
+// the email goes out in the background so the response is the same whether or not
+// the address belongs to an account
+pub async fn request_password_reset(email: &str) -> Result<(), AppError> {
     let user = match get_user_by_email(email).await? {
         Some(user) => user,
-        None => return Ok(None), // don't reveal whether email exists
+        None => return Ok(()), // don't reveal whether email exists
     };
 
     let connection = &mut get_postgres_connection().await?;
@@ -39,7 +44,20 @@ pub async fn request_password_reset(email: &str) -> Result<Option<Uuid>, AppErro
         .await
         .map_err(postgres_error)?;
 
-    Ok(Some(token_uuid))
+    let reset_url = get_app_link(&format!("/reset-password/{}", token_uuid))?;
+    let expiry_minutes = (PASSWORD_RESET_TOKEN_EXPIRY_SECONDS / 60).to_string();
+
+    send_email_in_background(render_email(
+        user.email,
+        EmailTemplate::PasswordReset,
+        &[
+            ("first_name", &user.first_name),
+            ("reset_url", &reset_url),
+            ("expiry_minutes", &expiry_minutes),
+        ],
+    )?);
+
+    Ok(())
 }
 
 pub async fn reset_password(token: Uuid, new_password: &str) -> Result<(), AppError> {
@@ -2,11 +2,14 @@ use crate::enums::{Platform, SESSION_EXPIRY_SECONDS, SLIDING_SESSION_THRESHOLD_S
 use crate::error::{postgres_error, AppError};
 use crate::models::{NewSession, Session, SessionUpdate};
 use crate::postgres::get_postgres_connection;
-use crate::redis::batch_invalidate_redis_cached_sessions;
+use crate::redis::{
+    batch_invalidate_redis_cached_sessions,
+    update_redis_cached_session_active_organization_membership_id,
+};
 use crate::schema::sessions;
 use chrono::{Duration, Utc};
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
 
 pub async fn create_session(
@@ -116,6 +119,43 @@ pub async fn set_active_organization(
         .map_err(postgres_error)
 }
 
+// sessions with any of the memberships active go back to having no organization
+// selected, in postgres and in the redis cache, returns how many were changed
+pub async fn clear_active_organization_memberships(
+    connection: &mut AsyncPgConnection,
+    membership_ids: &[i32],
+) -> Result<usize, AppError> {
+    if membership_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let tokens: Vec<Uuid> = diesel::update(
+        sessions::table.filter(sessions::active_organization_membership_id.eq_any(membership_ids)),
+    )
+    .set(SessionUpdate {
+        active_organization_membership_id: Some(None),
+        ..Default::default()
+    })
+    .returning(sessions::token)
+    .get_results(connection)
+    .await
+    .map_err(postgres_error)?;
+
+    for token in tokens.iter() {
+        if let Err(error) =
+            update_redis_cached_session_active_organization_membership_id(&token.to_string(), None)
+                .await
+        {
+            tracing::warn!(
+                "failed to clear active organization in redis session cache: {}",
+                error
+            );
+        }
+    }
+
+    Ok(tokens.len())
+}
+
 pub async fn delete_session(token: Uuid) -> Result<(), AppError> {
     let connection = &mut get_postgres_connection().await?;
 
@@ -3,8 +3,8 @@ use crate::models::{NewUser, User, UserUpdate};
 use crate::postgres::get_postgres_connection;
 use crate::schema::users;
 use crate::services::{
-    delete_all_user_sessions, hash_password, validate_email, validate_password,
-    validate_required_string, verify_password, MAX_USER_NAME_LENGTH,
+    attach_contact_signups_to_user, delete_all_user_sessions, hash_password, validate_email,
+    validate_password, validate_required_string, verify_password, MAX_USER_NAME_LENGTH,
 };
 use chrono::Utc;
 use diesel::prelude::*;
@@ -48,11 +48,13 @@ pub async fn register_user(
         is_staff,
     );
 
-    diesel::insert_into(users::table)
+    let user = diesel::insert_into(users::table)
         .values(&new_user)
         .get_result::<User>(connection)
         .await
-        .map_err(postgres_error)
+        .map_err(postgres_error)?;
+
+    Ok(user)
 }
 
 // hash for "hunter42" used for timing attack mitigation
@@ -85,6 +87,15 @@ pub async fn authenticate_user(email: &str, password: &str) -> Result<User, AppE
                 .await
                 .map_err(postgres_error)?;
 
+            // pick up RSVPs made as a guest since the last login
+            if let Err(error) = attach_contact_signups_to_user(&valid_user).await {
+                tracing::warn!(
+                    "failed to attach guest signups to user {}: {}",
+                    valid_user.id,
+                    error
+                );
+            }
+
             Ok(valid_user)
         }
         _ => {
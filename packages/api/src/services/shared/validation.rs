@@ -4,6 +4,7 @@ use crate::error::AppError;
 pub const MAX_ORGANIZATION_NAME_LENGTH: usize = 255;
 pub const MAX_ORGANIZATION_SLUG_LENGTH: usize = 100;
 pub const MAX_USER_NAME_LENGTH: usize = 100;
+pub const MAX_PHONE_NUMBER_LENGTH: usize = 20;
 pub const MAX_ARTICLE_TITLE_LENGTH: usize = 500;
 pub const MAX_ARTICLE_SLUG_LENGTH: usize = 500;
 pub const MAX_CATEGORY_NAME_LENGTH: usize = 100;
@@ -71,6 +72,14 @@ pub fn validate_nested_optional_string(
     Ok(())
 }
 
+// five digit US ZIP codes, matching the census ZIP centroid dataset
+pub fn validate_zip_code(field: &str, value: &str) -> Result<(), AppError> {
+    if value.len() != 5 || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppError::validation(field, "ZIP code must be 5 digits"));
+    }
+    Ok(())
+}
+
 pub fn validate_slug(field: &str, value: &str, max_length: usize) -> Result<(), AppError> {
     validate_max_length(field, value, max_length)?;
 
@@ -98,6 +107,22 @@ pub fn validate_optional_slug(
     Ok(())
 }
 
+// links rendered inside the app must stay inside it, so only paths like /events are
+// accepted, "//host" and "/\host" are treated as other sites by browsers
+pub fn validate_app_path(field: &str, value: &str) -> Result<(), AppError> {
+    if !value.starts_with('/')
+        || value.starts_with("//")
+        || value.starts_with("/\\")
+        || value.chars().any(|c| c.is_control())
+    {
+        return Err(AppError::validation(
+            field,
+            "Link must be a path within the app starting with /",
+        ));
+    }
+    Ok(())
+}
+
 pub fn validate_media_file(
     filename: &str,
     mime_type: &str,
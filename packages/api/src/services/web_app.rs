@@ -0,0 +1,35 @@
+mod event;
+mod event_calendar;
+mod event_cancellation;
+mod event_check_in;
+mod event_co_host;
+mod event_image;
+mod event_series;
+mod event_signup;
+mod event_template;
+mod export;
+mod guest_signup;
+mod organization_purge;
+mod outbound_message;
+mod plan_usage;
+mod shift_conflict;
+mod sms_consent;
+mod volunteer_hours;
+
+pub use event::*;
+pub use event_calendar::*;
+pub use event_cancellation::*;
+pub use event_check_in::*;
+pub use event_co_host::*;
+pub use event_image::*;
+pub use event_series::*;
+pub use event_signup::*;
+pub use event_template::*;
+pub use export::*;
+pub use guest_signup::*;
+pub use organization_purge::*;
+pub use outbound_message::*;
+pub use plan_usage::*;
+pub use shift_conflict::*;
+pub use sms_consent::*;
+pub use volunteer_hours::*;
@@ -0,0 +1,759 @@
+use crate::enums::{CoHostStatus, EventType, EventVisibility, SignupStatus};
+use crate::error::{postgres_error, AppError};
+use crate::interfaces::{EventCoHostResponse, EventResponse, EventShiftRequest};
+use crate::models::{
+    Event, EventShift, EventShiftUpdate, EventSignup, EventSignupUpdate, EventUpdate, NewEvent,
+    NewEventShift, Notification,
+};
+use crate::postgres::get_postgres_connection;
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{event_co_hosts, event_shifts, event_signups, events};
+use crate::services::web_app::{
+    batch_get_event_co_hosts, count_seated_signups, exclude_series_occurrence, has_active_signups,
+    lock_event_shift, notify_event_cancellation, promote_waitlisted_signups,
+    release_replaced_event_image, validate_timezone, CancellationMessageCounts,
+};
+use crate::services::{
+    publish_notifications, validate_email, validate_max_length, validate_nested_optional_string,
+    validate_optional_string, validate_required_string, validate_zip_code,
+};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::HashMap;
+
+// validation constants based on database column limits
+pub const MAX_EVENT_NAME_LENGTH: usize = 255;
+pub const MAX_EVENT_CONTACT_NAME_LENGTH: usize = 255;
+pub const MAX_EVENT_CONTACT_EMAIL_LENGTH: usize = 255;
+pub const MAX_EVENT_CONTACT_PHONE_LENGTH: usize = 20;
+pub const MAX_EVENT_SHIFT_TIMEZONE_LENGTH: usize = 50;
+pub const MAX_EVENT_TEXT_LENGTH: usize = 10_000;
+pub const MAX_EVENT_URL_LENGTH: usize = 2048;
+
+const ARCHIVED_ORGANIZATION_CANCELLATION_REASON: &str =
+    "The organization hosting this event has closed";
+
+fn validate_new_event(new_event: &NewEvent) -> Result<(), AppError> {
+    validate_required_string("name", &new_event.name, MAX_EVENT_NAME_LENGTH)?;
+    validate_required_string(
+        "contact_name",
+        &new_event.contact_name,
+        MAX_EVENT_CONTACT_NAME_LENGTH,
+    )?;
+    validate_optional_string("description", &new_event.description, MAX_EVENT_TEXT_LENGTH)?;
+    validate_optional_string(
+        "attendee_message",
+        &new_event.attendee_message,
+        MAX_EVENT_TEXT_LENGTH,
+    )?;
+    validate_optional_string("image_url", &new_event.image_url, MAX_EVENT_URL_LENGTH)?;
+    validate_optional_string(
+        "location_in_person",
+        &new_event.location_in_person,
+        MAX_EVENT_TEXT_LENGTH,
+    )?;
+    validate_optional_string(
+        "location_online",
+        &new_event.location_online,
+        MAX_EVENT_URL_LENGTH,
+    )?;
+    if let Some(ref zip_code) = new_event.location_zip_code {
+        validate_zip_code("location_zip_code", zip_code)?;
+    }
+    validate_optional_string(
+        "contact_email",
+        &new_event.contact_email,
+        MAX_EVENT_CONTACT_EMAIL_LENGTH,
+    )?;
+    if let Some(ref email) = new_event.contact_email {
+        validate_email(email)?;
+    }
+    validate_optional_string(
+        "contact_phone",
+        &new_event.contact_phone,
+        MAX_EVENT_CONTACT_PHONE_LENGTH,
+    )?;
+    Ok(())
+}
+
+fn validate_event_update(update: &EventUpdate) -> Result<(), AppError> {
+    // reject empty required fields if provided
+    if let Some(ref name) = update.name {
+        validate_required_string("name", name, MAX_EVENT_NAME_LENGTH)?;
+    }
+    if let Some(ref contact_name) = update.contact_name {
+        validate_required_string("contact_name", contact_name, MAX_EVENT_CONTACT_NAME_LENGTH)?;
+    }
+    if let Some(ref event_type) = update.event_type {
+        if EventType::from_str(event_type).is_none() {
+            return Err(AppError::validation("event_type", "Invalid event type"));
+        }
+    }
+
+    validate_nested_optional_string("description", &update.description, MAX_EVENT_TEXT_LENGTH)?;
+    validate_nested_optional_string(
+        "attendee_message",
+        &update.attendee_message,
+        MAX_EVENT_TEXT_LENGTH,
+    )?;
+    validate_nested_optional_string("image_url", &update.image_url, MAX_EVENT_URL_LENGTH)?;
+    validate_nested_optional_string(
+        "location_in_person",
+        &update.location_in_person,
+        MAX_EVENT_TEXT_LENGTH,
+    )?;
+    validate_nested_optional_string(
+        "location_online",
+        &update.location_online,
+        MAX_EVENT_URL_LENGTH,
+    )?;
+    if let Some(Some(ref zip_code)) = update.location_zip_code {
+        validate_zip_code("location_zip_code", zip_code)?;
+    }
+    validate_nested_optional_string(
+        "contact_email",
+        &update.contact_email,
+        MAX_EVENT_CONTACT_EMAIL_LENGTH,
+    )?;
+    if let Some(Some(ref email)) = update.contact_email {
+        validate_email(email)?;
+    }
+    validate_nested_optional_string(
+        "contact_phone",
+        &update.contact_phone,
+        MAX_EVENT_CONTACT_PHONE_LENGTH,
+    )?;
+    Ok(())
+}
+
+fn build_new_event_shift(
+    event_id: i32,
+    request: EventShiftRequest,
+) -> Result<NewEventShift, AppError> {
+    validate_timezone(&request.timezone)?;
+    validate_optional_string("notes", &request.notes, MAX_EVENT_TEXT_LENGTH)?;
+
+    let mut new_shift = NewEventShift::new(
+        event_id,
+        request.start_time,
+        request.end_time,
+        request.timezone,
+    )?;
+
+    if let Some(capacity) = request.capacity {
+        if capacity < 1 {
+            return Err(AppError::validation(
+                "capacity",
+                "Capacity must be at least 1",
+            ));
+        }
+        new_shift = new_shift.set_capacity(capacity);
+    }
+    if let Some(notes) = request.notes {
+        new_shift = new_shift.set_notes(notes);
+    }
+
+    Ok(new_shift)
+}
+
+pub async fn create_event(
+    new_event: NewEvent,
+    shifts: Vec<EventShiftRequest>,
+) -> Result<(Event, Vec<EventShift>), AppError> {
+    validate_new_event(&new_event)?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let event: Event = diesel::insert_into(events::table)
+                    .values(&new_event)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                let new_shifts = shifts
+                    .into_iter()
+                    .map(|shift| build_new_event_shift(event.id, shift))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let shifts: Vec<EventShift> = if new_shifts.is_empty() {
+                    vec![]
+                } else {
+                    diesel::insert_into(event_shifts::table)
+                        .values(&new_shifts)
+                        .get_results(connection)
+                        .await
+                        .map_err(postgres_error)?
+                };
+
+                Ok((event, shifts))
+            })
+        })
+        .await
+}
+
+pub async fn get_event(event_id: i32) -> Result<Event, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    events::table
+        .find(event_id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event"))
+}
+
+pub async fn get_organization_event(
+    organization_id: i32,
+    event_id: i32,
+) -> Result<Event, AppError> {
+    let event = get_event(event_id).await?;
+
+    // report a foreign event as missing so ids can't be probed across organizations
+    if event.organization_id != organization_id {
+        return Err(AppError::not_found("Event"));
+    }
+
+    Ok(event)
+}
+
+pub async fn get_event_shift(shift_id: i32) -> Result<EventShift, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_shifts::table
+        .find(shift_id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event shift"))
+}
+
+pub async fn list_events(
+    organization_id: i32,
+    event_type: Option<EventType>,
+    visibility: Option<EventVisibility>,
+    include_cancelled: bool,
+    page: i64,
+    per_page: i64,
+) -> Result<(Vec<Event>, i64), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    // events the organization hosts plus those it accepted a co-host invitation for
+    let co_hosted_event_ids = || {
+        event_co_hosts::table
+            .filter(event_co_hosts::organization_id.eq(organization_id))
+            .filter(event_co_hosts::status.eq(CoHostStatus::Accepted.as_str()))
+            .select(event_co_hosts::event_id)
+    };
+
+    let mut query = events::table
+        .filter(
+            events::organization_id
+                .eq(organization_id)
+                .or(events::id.eq_any(co_hosted_event_ids())),
+        )
+        .into_boxed();
+    let mut count_query = events::table
+        .filter(
+            events::organization_id
+                .eq(organization_id)
+                .or(events::id.eq_any(co_hosted_event_ids())),
+        )
+        .into_boxed();
+
+    if let Some(event_type) = event_type {
+        query = query.filter(events::event_type.eq(event_type.as_str()));
+        count_query = count_query.filter(events::event_type.eq(event_type.as_str()));
+    }
+
+    if let Some(visibility) = visibility {
+        query = query.filter(events::visibility.eq(visibility.as_str()));
+        count_query = count_query.filter(events::visibility.eq(visibility.as_str()));
+    }
+
+    if !include_cancelled {
+        query = query.filter(events::cancelled_at.is_null());
+        count_query = count_query.filter(events::cancelled_at.is_null());
+    }
+
+    let total: i64 = count_query
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let offset = (page - 1) * per_page;
+
+    let events_list: Vec<Event> = query
+        .order(events::created_at.desc())
+        .limit(per_page)
+        .offset(offset)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok((events_list, total))
+}
+
+pub async fn batch_get_event_shifts(
+    event_ids: &[i32],
+) -> Result<HashMap<i32, Vec<EventShift>>, AppError> {
+    if event_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let shifts: Vec<EventShift> = event_shifts::table
+        .filter(event_shifts::event_id.eq_any(event_ids))
+        .order(event_shifts::start_time.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let mut map: HashMap<i32, Vec<EventShift>> = HashMap::new();
+    for shift in shifts {
+        map.entry(shift.event_id).or_default().push(shift);
+    }
+
+    Ok(map)
+}
+
+pub async fn build_event_response(event: Event) -> Result<EventResponse, AppError> {
+    let mut responses = batch_build_event_responses(vec![event]).await?;
+
+    responses
+        .pop()
+        .ok_or_else(|| AppError::InternalError("Failed to build event response".to_string()))
+}
+
+pub async fn batch_build_event_responses(
+    events: Vec<Event>,
+) -> Result<Vec<EventResponse>, AppError> {
+    let event_ids: Vec<i32> = events.iter().map(|event| event.id).collect();
+    let mut shifts = batch_get_event_shifts(&event_ids).await?;
+    let mut co_hosts = batch_get_event_co_hosts(&event_ids).await?;
+
+    Ok(events
+        .into_iter()
+        .map(|event| {
+            let event_shifts = shifts.remove(&event.id).unwrap_or_default();
+            let event_co_hosts = co_hosts
+                .remove(&event.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(co_host, organization)| EventCoHostResponse::new(co_host, organization))
+                .collect();
+            EventResponse::new(event, event_shifts, event_co_hosts)
+        })
+        .collect())
+}
+
+pub async fn update_event(event_id: i32, mut update: EventUpdate) -> Result<Event, AppError> {
+    validate_event_update(&update)?;
+
+    let existing = get_event(event_id).await?;
+
+    if existing.is_cancelled() {
+        return Err(AppError::validation(
+            "event",
+            "Cancelled events cannot be edited",
+        ));
+    }
+
+    // a hand-entered image_url replaces any uploaded image and its variants
+    let image_replaced = update
+        .image_url
+        .as_ref()
+        .is_some_and(|image_url| *image_url != existing.image_url);
+    if image_replaced {
+        update.image_storage_prefix = Some(None);
+        update.image_variants = Some(serde_json::json!([]));
+        update.image_storage_bytes = Some(0);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let event = diesel::update(events::table.find(event_id))
+        .set(&update)
+        .get_result::<Event>(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    if image_replaced {
+        release_replaced_event_image(&existing).await;
+    }
+
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok(event)
+}
+
+pub async fn publish_event(event_id: i32, visibility: EventVisibility) -> Result<Event, AppError> {
+    if visibility == EventVisibility::Draft {
+        return Err(AppError::validation(
+            "visibility",
+            "Events must be published as public or private",
+        ));
+    }
+
+    let existing = get_event(event_id).await?;
+
+    if existing.is_cancelled() {
+        return Err(AppError::validation(
+            "event",
+            "Cancelled events cannot be published",
+        ));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let shift_count: i64 = event_shifts::table
+        .filter(event_shifts::event_id.eq(event_id))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    if shift_count == 0 {
+        return Err(AppError::validation(
+            "shifts",
+            "Add at least one shift before publishing",
+        ));
+    }
+
+    let event = diesel::update(events::table.find(event_id))
+        .set(EventUpdate {
+            visibility: Some(visibility.as_str().to_string()),
+            ..Default::default()
+        })
+        .get_result::<Event>(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok(event)
+}
+
+pub async fn unpublish_event(event_id: i32) -> Result<Event, AppError> {
+    let existing = get_event(event_id).await?;
+
+    if existing.is_cancelled() {
+        return Err(AppError::validation(
+            "event",
+            "Cancelled events cannot be unpublished",
+        ));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let event = diesel::update(events::table.find(event_id))
+        .set(EventUpdate {
+            visibility: Some(EventVisibility::Draft.as_str().to_string()),
+            ..Default::default()
+        })
+        .get_result::<Event>(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok(event)
+}
+
+// cancelling releases every upcoming signup and lets the attendees know, see
+// notify_event_cancellation
+pub async fn cancel_event(
+    event_id: i32,
+    reason: Option<String>,
+) -> Result<(Event, CancellationMessageCounts), AppError> {
+    validate_optional_string("cancellation_reason", &reason, MAX_EVENT_TEXT_LENGTH)?;
+
+    let existing = get_event(event_id).await?;
+
+    if existing.is_cancelled() {
+        return Err(AppError::validation("event", "Event is already cancelled"));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (event, notifications, message_counts) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move { cancel_event_with_signups(connection, event_id, reason).await })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok((event, message_counts))
+}
+
+// runs in the caller's transaction, the in-app notifications are returned for the
+// caller to publish once it commits
+async fn cancel_event_with_signups(
+    connection: &mut AsyncPgConnection,
+    event_id: i32,
+    reason: Option<String>,
+) -> Result<(Event, Vec<Notification>, CancellationMessageCounts), AppError> {
+    let now = Utc::now();
+
+    // the null guard keeps two concurrent cancels from notifying twice
+    let event: Event = diesel::update(
+        events::table
+            .find(event_id)
+            .filter(events::cancelled_at.is_null()),
+    )
+    .set(EventUpdate {
+        cancelled_at: Some(Some(now)),
+        cancellation_reason: Some(reason),
+        ..Default::default()
+    })
+    .get_result(connection)
+    .await
+    .optional()
+    .map_err(postgres_error)?
+    .ok_or_else(|| AppError::validation("event", "Event is already cancelled"))?;
+
+    let shift_ids = event_shifts::table
+        .filter(event_shifts::event_id.eq(event_id))
+        .select(event_shifts::id);
+
+    let cancelled_signups: Vec<EventSignup> = diesel::update(
+        event_signups::table
+            .filter(event_signups::event_shift_id.eq_any(shift_ids))
+            .filter(event_signups::status.eq_any([
+                SignupStatus::SignedUp.as_str(),
+                SignupStatus::Waitlisted.as_str(),
+            ])),
+    )
+    .set(EventSignupUpdate {
+        status: Some(SignupStatus::Cancelled.as_str().to_string()),
+        cancelled_at: Some(Some(now)),
+        ..Default::default()
+    })
+    .get_results(connection)
+    .await
+    .map_err(postgres_error)?;
+
+    let (notifications, message_counts) =
+        notify_event_cancellation(connection, &event, &cancelled_signups).await?;
+
+    Ok((event, notifications, message_counts))
+}
+
+// archiving an organization cancels its events that haven't ended the same way an
+// organizer would, so attendees hear about it rather than finding the event gone
+// after the purge. runs in archive_organization's transaction
+pub(crate) async fn cancel_upcoming_organization_events(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+) -> Result<Vec<Notification>, AppError> {
+    let event_ids: Vec<i32> = events::table
+        .inner_join(event_shifts::table)
+        .filter(events::organization_id.eq(organization_id))
+        .filter(events::cancelled_at.is_null())
+        .filter(event_shifts::end_time.gt(Utc::now()))
+        .select(events::id)
+        .distinct()
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let mut notifications = vec![];
+    for event_id in event_ids {
+        let (_, event_notifications, _) = cancel_event_with_signups(
+            connection,
+            event_id,
+            Some(ARCHIVED_ORGANIZATION_CANCELLATION_REASON.to_string()),
+        )
+        .await?;
+        notifications.extend(event_notifications);
+    }
+
+    Ok(notifications)
+}
+
+pub async fn add_event_shift(
+    event_id: i32,
+    request: EventShiftRequest,
+) -> Result<EventShift, AppError> {
+    let new_shift = build_new_event_shift(event_id, request)?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let shift = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                // shared with cancel_event's row lock so no shift lands after a cancel
+                let event: Event = events::table
+                    .find(event_id)
+                    .for_share()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Event"))?;
+
+                if event.is_cancelled() {
+                    return Err(AppError::validation(
+                        "event",
+                        "Cancelled events cannot be edited",
+                    ));
+                }
+
+                diesel::insert_into(event_shifts::table)
+                    .values(&new_shift)
+                    .get_result::<EventShift>(connection)
+                    .await
+                    .map_err(postgres_error)
+            })
+        })
+        .await?;
+
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok(shift)
+}
+
+// editing a shift that belongs to a series detaches it, the "this shift only" edit
+pub async fn update_event_shift(
+    shift_id: i32,
+    mut update: EventShiftUpdate,
+) -> Result<EventShift, AppError> {
+    if let Some(ref timezone) = update.timezone {
+        validate_timezone(timezone)?;
+    }
+    if let Some(Some(ref notes)) = update.notes {
+        validate_max_length("notes", notes, MAX_EVENT_TEXT_LENGTH)?;
+    }
+    if let Some(Some(capacity)) = update.capacity {
+        if capacity < 1 {
+            return Err(AppError::validation(
+                "capacity",
+                "Capacity must be at least 1",
+            ));
+        }
+    }
+
+    let existing = get_event_shift(shift_id).await?;
+
+    // validate the resulting time range, not just the provided half of it
+    let start_time = update.start_time.unwrap_or(existing.start_time);
+    let end_time = update.end_time.unwrap_or(existing.end_time);
+
+    if end_time <= start_time {
+        return Err(AppError::validation(
+            "end_time",
+            "End time must be after start time",
+        ));
+    }
+
+    if existing.series_id.is_some() {
+        update.series_id = Some(None);
+        update.series_occurrence_date = Some(None);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (shift, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                // held until commit so no signup takes a seat between the count and
+                // the new capacity
+                let locked = lock_event_shift(connection, shift_id).await?;
+
+                if let Some(Some(capacity)) = update.capacity {
+                    if count_seated_signups(connection, shift_id).await? > capacity as i64 {
+                        return Err(AppError::validation(
+                            "capacity",
+                            "Capacity can't be lower than the number of volunteers already signed up",
+                        ));
+                    }
+                }
+
+                if let (Some(series_id), Some(occurrence_date)) =
+                    (existing.series_id, existing.series_occurrence_date)
+                {
+                    exclude_series_occurrence(connection, series_id, occurrence_date).await?;
+                }
+
+                let shift: EventShift = diesel::update(event_shifts::table.find(shift_id))
+                    .set(&update)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                // a raised or removed capacity opens seats for the waitlist
+                let notifications = if shift.capacity != locked.capacity {
+                    promote_waitlisted_signups(connection, &shift).await?.1
+                } else {
+                    vec![]
+                };
+
+                Ok((shift, notifications))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+    invalidate_redis_cached_public_event(shift.event_id)
+        .await
+        .ok();
+
+    Ok(shift)
+}
+
+// shifts with signups are kept so volunteers aren't dropped without notice and
+// check-in history behind volunteer hours survives
+pub async fn delete_event_shift(shift_id: i32) -> Result<(), AppError> {
+    let existing = get_event_shift(shift_id).await?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                // shared with signup creation's row lock so no signup lands mid-delete
+                lock_event_shift(connection, shift_id).await?;
+
+                if has_active_signups(connection, shift_id).await? {
+                    return Err(AppError::validation(
+                        "shift_id",
+                        "Shifts with signups cannot be deleted, cancel the event or the signups first",
+                    ));
+                }
+
+                // keep the series from regenerating the deleted occurrence
+                if let (Some(series_id), Some(occurrence_date)) =
+                    (existing.series_id, existing.series_occurrence_date)
+                {
+                    exclude_series_occurrence(connection, series_id, occurrence_date).await?;
+                }
+
+                let deleted = diesel::delete(event_shifts::table.find(shift_id))
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                if deleted == 0 {
+                    return Err(AppError::not_found("Event shift"));
+                }
+
+                Ok(())
+            })
+        })
+        .await?;
+
+    invalidate_redis_cached_public_event(existing.event_id)
+        .await
+        .ok();
+
+    Ok(())
+}
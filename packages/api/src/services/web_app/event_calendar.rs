@@ -0,0 +1,542 @@
+use crate::enums::{EventVisibility, SignupStatus};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    CalendarFeedToken, Event, EventShift, EventSignup, NewCalendarFeedToken, Organization,
+};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{calendar_feed_tokens, event_shifts, event_signups, events, organizations};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+const CALENDAR_PRODUCT_ID: &str = "-//Campaign Center//Events//EN";
+const CALENDAR_UID_DOMAIN: &str = "campaign-center";
+// how far back feeds keep past shifts so recent history stays on calendars
+const CALENDAR_FEED_HISTORY_DAYS: i64 = 90;
+// content lines are folded at 75 octets per RFC 5545 section 3.1
+const CALENDAR_MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Clone, Copy, PartialEq)]
+enum CalendarEntryStatus {
+    Confirmed,
+    Tentative,
+    Cancelled,
+}
+
+impl CalendarEntryStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CalendarEntryStatus::Confirmed => "CONFIRMED",
+            CalendarEntryStatus::Tentative => "TENTATIVE",
+            CalendarEntryStatus::Cancelled => "CANCELLED",
+        }
+    }
+
+    fn for_event(event: &Event) -> Self {
+        if event.is_cancelled() {
+            CalendarEntryStatus::Cancelled
+        } else {
+            CalendarEntryStatus::Confirmed
+        }
+    }
+
+    fn for_signup(event: &Event, signup: &EventSignup) -> Self {
+        match signup.get_status() {
+            _ if event.is_cancelled() => CalendarEntryStatus::Cancelled,
+            SignupStatus::Cancelled => CalendarEntryStatus::Cancelled,
+            SignupStatus::Waitlisted => CalendarEntryStatus::Tentative,
+            _ => CalendarEntryStatus::Confirmed,
+        }
+    }
+}
+
+struct CalendarEntry {
+    event: Event,
+    shift: EventShift,
+    status: CalendarEntryStatus,
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn push_line(output: &mut String, line: &str) {
+    let mut remaining = line;
+    let mut limit = CALENDAR_MAX_LINE_OCTETS;
+
+    while remaining.len() > limit {
+        let mut split = limit;
+        while !remaining.is_char_boundary(split) {
+            split -= 1;
+        }
+        output.push_str(&remaining[..split]);
+        output.push_str("\r\n ");
+        remaining = &remaining[split..];
+        // continuation lines lose one octet to the leading space
+        limit = CALENDAR_MAX_LINE_OCTETS - 1;
+    }
+
+    output.push_str(remaining);
+    output.push_str("\r\n");
+}
+
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    format!("{}{:02}{:02}", sign, seconds / 3600, (seconds % 3600) / 60)
+}
+
+fn offset_seconds(timezone: Tz, time: DateTime<Utc>) -> i32 {
+    timezone
+        .offset_from_utc_datetime(&time.naive_utc())
+        .fix()
+        .local_minus_utc()
+}
+
+// finds the first second within (start, end] that uses the new offset
+fn find_transition(timezone: Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> DateTime<Utc> {
+    let start_offset = offset_seconds(timezone, start);
+    let (mut low, mut high) = (start, end);
+
+    while high - low > Duration::seconds(1) {
+        let middle = low + (high - low) / 2;
+        if offset_seconds(timezone, middle) == start_offset {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    high
+}
+
+fn push_timezone_component(
+    output: &mut String,
+    timezone: Tz,
+    at: DateTime<Utc>,
+    offset_from: i32,
+    offset_to: i32,
+) {
+    let offset = timezone.offset_from_utc_datetime(&at.naive_utc());
+    let component = if offset.dst_offset().num_seconds() != 0 {
+        "DAYLIGHT"
+    } else {
+        "STANDARD"
+    };
+    // DTSTART is the local time just before the change, expressed in the old offset
+    let local_start = at.naive_utc() + Duration::seconds(offset_from as i64);
+
+    push_line(output, &format!("BEGIN:{}", component));
+    push_line(
+        output,
+        &format!("DTSTART:{}", local_start.format("%Y%m%dT%H%M%S")),
+    );
+    push_line(
+        output,
+        &format!("TZOFFSETFROM:{}", format_offset(offset_from)),
+    );
+    push_line(output, &format!("TZOFFSETTO:{}", format_offset(offset_to)));
+    if let Some(name) = offset.abbreviation() {
+        push_line(output, &format!("TZNAME:{}", escape_text(name)));
+    }
+    push_line(output, &format!("END:{}", component));
+}
+
+// emits every offset transition between the given years as explicit observances,
+// which avoids guessing an RRULE for zones whose rules changed over time
+fn push_vtimezone(output: &mut String, timezone: Tz, first_year: i32, last_year: i32) {
+    let Some(range_start) = NaiveDate::from_ymd_opt(first_year, 1, 1) else {
+        return;
+    };
+    let Some(range_end) = NaiveDate::from_ymd_opt(last_year + 1, 1, 1) else {
+        return;
+    };
+    let range_start = Utc.from_utc_datetime(&range_start.and_time(Default::default()));
+    let range_end = Utc.from_utc_datetime(&range_end.and_time(Default::default()));
+
+    push_line(output, "BEGIN:VTIMEZONE");
+    push_line(output, &format!("TZID:{}", timezone.name()));
+
+    let initial_offset = offset_seconds(timezone, range_start);
+    push_timezone_component(
+        output,
+        timezone,
+        range_start,
+        initial_offset,
+        initial_offset,
+    );
+
+    let mut cursor = range_start;
+    let mut current_offset = initial_offset;
+    while cursor < range_end {
+        let next = cursor + Duration::days(1);
+        let next_offset = offset_seconds(timezone, next);
+        if next_offset != current_offset {
+            let transition = find_transition(timezone, cursor, next);
+            push_timezone_component(output, timezone, transition, current_offset, next_offset);
+            current_offset = next_offset;
+        }
+        cursor = next;
+    }
+
+    push_line(output, "END:VTIMEZONE");
+}
+
+fn push_local_time(output: &mut String, property: &str, time: DateTime<Utc>, timezone: Option<Tz>) {
+    match timezone {
+        Some(timezone) => push_line(
+            output,
+            &format!(
+                "{};TZID={}:{}",
+                property,
+                timezone.name(),
+                time.with_timezone(&timezone).format("%Y%m%dT%H%M%S")
+            ),
+        ),
+        None => push_line(output, &format!("{}:{}", property, format_utc(time))),
+    }
+}
+
+fn push_event(output: &mut String, entry: &CalendarEntry, stamp: DateTime<Utc>) {
+    let CalendarEntry {
+        event,
+        shift,
+        status,
+    } = entry;
+    let timezone = shift.timezone.parse::<Tz>().ok();
+    let last_modified = shift.updated_at.max(event.updated_at);
+
+    push_line(output, "BEGIN:VEVENT");
+    push_line(
+        output,
+        &format!("UID:event-shift-{}@{}", shift.id, CALENDAR_UID_DOMAIN),
+    );
+    push_line(output, &format!("DTSTAMP:{}", format_utc(stamp)));
+    push_local_time(output, "DTSTART", shift.start_time, timezone);
+    push_local_time(output, "DTEND", shift.end_time, timezone);
+    push_line(output, &format!("SUMMARY:{}", escape_text(&event.name)));
+
+    let description = [
+        event.description.as_deref(),
+        event.attendee_message.as_deref(),
+        shift.notes.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|text| !text.trim().is_empty())
+    .collect::<Vec<_>>()
+    .join("\n\n");
+    if !description.is_empty() {
+        push_line(
+            output,
+            &format!("DESCRIPTION:{}", escape_text(&description)),
+        );
+    }
+
+    if let Some(location) = event
+        .location_in_person
+        .as_deref()
+        .or(event.location_online.as_deref())
+    {
+        push_line(output, &format!("LOCATION:{}", escape_text(location)));
+    }
+    if let Some(url) = event
+        .location_online
+        .as_deref()
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+    {
+        push_line(output, &format!("URL:{}", url));
+    }
+
+    push_line(output, &format!("STATUS:{}", status.as_str()));
+    // calendar clients only apply updates when the sequence increases, both revisions
+    // only ever go up
+    push_line(
+        output,
+        &format!("SEQUENCE:{}", event.revision + shift.revision),
+    );
+    push_line(output, &format!("CREATED:{}", format_utc(shift.created_at)));
+    push_line(
+        output,
+        &format!("LAST-MODIFIED:{}", format_utc(last_modified)),
+    );
+    push_line(output, "END:VEVENT");
+}
+
+fn render_calendar(name: &str, entries: &[CalendarEntry]) -> String {
+    let stamp = Utc::now();
+    let mut output = String::new();
+
+    push_line(&mut output, "BEGIN:VCALENDAR");
+    push_line(&mut output, "VERSION:2.0");
+    push_line(&mut output, &format!("PRODID:{}", CALENDAR_PRODUCT_ID));
+    push_line(&mut output, "CALSCALE:GREGORIAN");
+    push_line(&mut output, "METHOD:PUBLISH");
+    push_line(&mut output, &format!("X-WR-CALNAME:{}", escape_text(name)));
+
+    let timezone_names: BTreeSet<&str> = entries
+        .iter()
+        .map(|entry| entry.shift.timezone.as_str())
+        .collect();
+    for timezone_name in timezone_names {
+        let Ok(timezone) = timezone_name.parse::<Tz>() else {
+            continue;
+        };
+        let years = entries
+            .iter()
+            .filter(|entry| entry.shift.timezone == timezone_name)
+            .flat_map(|entry| [entry.shift.start_time.year(), entry.shift.end_time.year()]);
+        let (Some(first_year), Some(last_year)) = (years.clone().min(), years.max()) else {
+            continue;
+        };
+        push_vtimezone(&mut output, timezone, first_year, last_year);
+    }
+
+    for entry in entries {
+        push_event(&mut output, entry, stamp);
+    }
+
+    push_line(&mut output, "END:VCALENDAR");
+    output
+}
+
+fn feed_history_start() -> DateTime<Utc> {
+    Utc::now() - Duration::days(CALENDAR_FEED_HISTORY_DAYS)
+}
+
+// public events only, cancelled ones stay in the feed so subscribers see the cancellation,
+// an archived organization's feed is gone
+pub async fn build_organization_calendar(organization: &Organization) -> Result<String, AppError> {
+    if organization.is_archived() {
+        return Err(AppError::not_found("Organization"));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let rows: Vec<(EventShift, Event)> = event_shifts::table
+        .inner_join(events::table)
+        .filter(events::organization_id.eq(organization.id))
+        .filter(events::visibility.eq(EventVisibility::Public.as_str()))
+        .filter(event_shifts::end_time.ge(feed_history_start()))
+        .order(event_shifts::start_time.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let entries: Vec<CalendarEntry> = rows
+        .into_iter()
+        .map(|(shift, event)| CalendarEntry {
+            status: CalendarEntryStatus::for_event(&event),
+            event,
+            shift,
+        })
+        .collect();
+
+    Ok(render_calendar(&organization.name, &entries))
+}
+
+// shifts with archived organizations drop out of the feed
+pub async fn build_user_calendar(user_id: i32) -> Result<String, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let rows: Vec<(EventSignup, (EventShift, Event))> = event_signups::table
+        .inner_join(event_shifts::table.inner_join(events::table.inner_join(organizations::table)))
+        .filter(event_signups::user_id.eq(user_id))
+        .filter(event_shifts::end_time.ge(feed_history_start()))
+        .filter(organizations::archived_at.is_null())
+        .order(event_shifts::start_time.asc())
+        .select((
+            EventSignup::as_select(),
+            (EventShift::as_select(), Event::as_select()),
+        ))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let entries: Vec<CalendarEntry> = rows
+        .into_iter()
+        .map(|(signup, (shift, event))| CalendarEntry {
+            status: CalendarEntryStatus::for_signup(&event, &signup),
+            event,
+            shift,
+        })
+        .collect();
+
+    Ok(render_calendar("My Shifts", &entries))
+}
+
+// only published public events of active organizations, the download needs no login
+pub async fn build_shift_calendar(shift_id: i32) -> Result<String, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (shift, event): (EventShift, Event) = event_shifts::table
+        .inner_join(events::table.inner_join(organizations::table))
+        .filter(event_shifts::id.eq(shift_id))
+        .filter(organizations::archived_at.is_null())
+        .select((EventShift::as_select(), Event::as_select()))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event shift"))?;
+
+    // private events only reach volunteers through their own feed
+    if !event.is_published() || event.get_visibility() != EventVisibility::Public {
+        return Err(AppError::not_found("Event shift"));
+    }
+
+    let name = event.name.clone();
+    let entries = [CalendarEntry {
+        status: CalendarEntryStatus::for_event(&event),
+        event,
+        shift,
+    }];
+
+    Ok(render_calendar(&name, &entries))
+}
+
+pub async fn get_or_create_calendar_feed_token(user_id: i32) -> Result<Uuid, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let existing: Option<CalendarFeedToken> = calendar_feed_tokens::table
+        .filter(calendar_feed_tokens::user_id.eq(user_id))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    if let Some(existing) = existing {
+        return Ok(existing.token);
+    }
+
+    let created: CalendarFeedToken = diesel::insert_into(calendar_feed_tokens::table)
+        .values(&NewCalendarFeedToken::new(user_id))
+        .on_conflict(calendar_feed_tokens::user_id)
+        .do_update()
+        .set(calendar_feed_tokens::user_id.eq(user_id))
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(created.token)
+}
+
+// invalidates any previously shared feed url
+pub async fn reset_calendar_feed_token(user_id: i32) -> Result<Uuid, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let new_token = NewCalendarFeedToken::new(user_id);
+
+    let reset: CalendarFeedToken = diesel::insert_into(calendar_feed_tokens::table)
+        .values(&new_token)
+        .on_conflict(calendar_feed_tokens::user_id)
+        .do_update()
+        .set((
+            calendar_feed_tokens::token.eq(new_token.token),
+            calendar_feed_tokens::created_at.eq(Utc::now()),
+        ))
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(reset.token)
+}
+
+pub async fn get_calendar_feed_user_id(token: Uuid) -> Result<i32, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    calendar_feed_tokens::table
+        .filter(calendar_feed_tokens::token.eq(token))
+        .select(calendar_feed_tokens::user_id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Calendar feed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the content lines without their CRLF terminators
+    fn lines(output: &str) -> Vec<&str> {
+        output.strip_suffix("\r\n").unwrap().split("\r\n").collect()
+    }
+
+    // undoes folding per RFC 5545 section 3.1
+    fn unfold(output: &str) -> String {
+        output.replace("\r\n ", "")
+    }
+
+    #[test]
+    fn escape_text_escapes_separators_and_newlines() {
+        assert_eq!(
+            escape_text(r"Park cleanup; bring gloves, water\snacks"),
+            r"Park cleanup\; bring gloves\, water\\snacks"
+        );
+        assert_eq!(
+            escape_text("line one\r\nline two\nline three"),
+            "line one\\nline two\\nline three"
+        );
+    }
+
+    #[test]
+    fn short_lines_are_not_folded() {
+        let mut output = String::new();
+        let line = "X".repeat(CALENDAR_MAX_LINE_OCTETS);
+        push_line(&mut output, &line);
+
+        assert_eq!(output, format!("{}\r\n", line));
+    }
+
+    #[test]
+    fn long_lines_fold_at_75_octets() {
+        let mut output = String::new();
+        let line = format!("DESCRIPTION:{}", "a".repeat(200));
+        push_line(&mut output, &line);
+
+        let folded = lines(&output);
+        assert_eq!(folded.len(), 3);
+        assert_eq!(folded[0].len(), CALENDAR_MAX_LINE_OCTETS);
+        assert!(folded[1..].iter().all(|line| line.starts_with(' ')));
+        assert!(folded
+            .iter()
+            .all(|line| line.len() <= CALENDAR_MAX_LINE_OCTETS));
+        assert_eq!(unfold(&output), format!("{}\r\n", line));
+    }
+
+    #[test]
+    fn folding_never_splits_a_multibyte_character() {
+        let mut output = String::new();
+        // 3 octets each, so 75 octets would land inside a character
+        let line = format!("SUMMARY:{}", "€".repeat(60));
+        push_line(&mut output, &line);
+
+        let folded = lines(&output);
+        // 22 characters after the property name fit, the 23rd would straddle the limit
+        assert_eq!(folded[0].len(), CALENDAR_MAX_LINE_OCTETS - 1);
+        assert!(folded
+            .iter()
+            .all(|line| line.len() <= CALENDAR_MAX_LINE_OCTETS));
+        assert_eq!(unfold(&output), format!("{}\r\n", line));
+    }
+
+    #[test]
+    fn offsets_are_formatted_as_signed_hours_and_minutes() {
+        assert_eq!(format_offset(-5 * 3600), "-0500");
+        assert_eq!(format_offset(5 * 3600 + 30 * 60), "+0530");
+        assert_eq!(format_offset(0), "+0000");
+    }
+}
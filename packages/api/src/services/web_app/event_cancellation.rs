@@ -0,0 +1,205 @@
+use crate::enums::{MessageChannel, NotificationChannel, NotificationType};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    Contact, Event, EventSignup, NewNotification, NewOutboundMessage, Notification, User,
+};
+use crate::schema::{contacts, organizations, users};
+use crate::services::web_app::enqueue_outbound_messages;
+use crate::services::{
+    create_notification_unsubscribe_token, insert_notifications, load_notification_preferences,
+};
+use crate::sms::get_sms_program_name;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeSet;
+
+#[derive(Clone, Copy, Default)]
+pub struct CancellationMessageCounts {
+    pub queued: usize,
+    // held back by the organization's monthly quota
+    pub over_quota: usize,
+    // emails and texts attendees turned off for event cancellations
+    pub opted_out: usize,
+}
+
+fn cancellation_email(event: &Event, first_name: &str) -> (String, String) {
+    let subject = format!("Cancelled: {}", event.name);
+
+    let mut body = format!(
+        "Hi {},\n\n{} has been cancelled and your signup has been released, there's nothing else you need to do.",
+        first_name, event.name
+    );
+    if let Some(ref reason) = event.cancellation_reason {
+        body.push_str(&format!("\n\nNote from the organizers: {}", reason));
+    }
+    body.push_str(&format!(
+        "\n\nQuestions? Reach out to {}.",
+        event.contact_name
+    ));
+
+    (subject, body)
+}
+
+// texts carry the program name and opt-out instructions, a STOP reply is handled by
+// the inbound SMS webhook
+fn cancellation_sms(event: &Event, organization_name: &str) -> Result<String, AppError> {
+    Ok(format!(
+        "{}: {} from {} has been cancelled and your signup has been released. Reply STOP to opt out.",
+        get_sms_program_name()?,
+        event.name,
+        organization_name
+    ))
+}
+
+// every attendee hears about it once however many shifts they held, registered users
+// get an in-app notification and email/SMS only goes out when the event sends
+// attendee confirmations, users can opt out of either channel per notification type.
+// the in-app notifications are returned for the caller to publish once it commits,
+// along with what became of the emails and texts
+pub async fn notify_event_cancellation(
+    connection: &mut AsyncPgConnection,
+    event: &Event,
+    cancelled_signups: &[EventSignup],
+) -> Result<(Vec<Notification>, CancellationMessageCounts), AppError> {
+    let user_ids: BTreeSet<i32> = cancelled_signups
+        .iter()
+        .filter_map(|signup| signup.user_id)
+        .collect();
+    let contact_ids: BTreeSet<i32> = cancelled_signups
+        .iter()
+        .filter(|signup| signup.user_id.is_none())
+        .filter_map(|signup| signup.contact_id)
+        .collect();
+
+    let attendees: Vec<User> = if user_ids.is_empty() {
+        vec![]
+    } else {
+        users::table
+            .filter(users::id.eq_any(&user_ids))
+            .load(connection)
+            .await
+            .map_err(postgres_error)?
+    };
+    let guests: Vec<Contact> = if contact_ids.is_empty() {
+        vec![]
+    } else {
+        contacts::table
+            .filter(contacts::id.eq_any(&contact_ids))
+            .load(connection)
+            .await
+            .map_err(postgres_error)?
+    };
+
+    let new_notifications: Vec<NewNotification> = attendees
+        .iter()
+        .map(|user| {
+            NewNotification::new(
+                user.id,
+                NotificationType::EventCancelled,
+                "Event cancelled".to_string(),
+                format!("{} has been cancelled.", event.name),
+            )
+            .set_organization(event.organization_id)
+        })
+        .collect();
+
+    let notifications = insert_notifications(connection, new_notifications).await?;
+
+    if !event.communication_confirmation {
+        return Ok((notifications, CancellationMessageCounts::default()));
+    }
+
+    let organization_name: String = organizations::table
+        .find(event.organization_id)
+        .select(organizations::name)
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+    let sms_body = cancellation_sms(event, &organization_name)?;
+
+    let attendee_ids: Vec<i32> = attendees.iter().map(|user| user.id).collect();
+    let email_preferences =
+        load_notification_preferences(connection, &attendee_ids, NotificationChannel::Email)
+            .await?;
+    let sms_preferences =
+        load_notification_preferences(connection, &attendee_ids, NotificationChannel::Sms).await?;
+
+    let mut messages: Vec<NewOutboundMessage> = vec![];
+    let mut opted_out = 0;
+
+    for user in attendees.iter() {
+        if email_preferences.allows(
+            user.id,
+            Some(event.organization_id),
+            NotificationType::EventCancelled,
+        ) {
+            let (subject, body) = cancellation_email(event, &user.first_name);
+            let unsubscribe_token = create_notification_unsubscribe_token(
+                user.id,
+                Some(event.organization_id),
+                NotificationType::EventCancelled,
+            )?;
+            messages.push(
+                NewOutboundMessage::new(MessageChannel::Email, user.email.clone(), body)
+                    .set_subject(subject)
+                    .set_user(user.id)
+                    .set_event(event.id)
+                    .set_organization(event.organization_id)
+                    .set_unsubscribe_token(unsubscribe_token),
+            );
+        } else {
+            opted_out += 1;
+        }
+        let Some(ref phone_number) = user.phone_number else {
+            continue;
+        };
+        if !sms_preferences.allows(
+            user.id,
+            Some(event.organization_id),
+            NotificationType::EventCancelled,
+        ) {
+            opted_out += 1;
+            continue;
+        }
+        messages.push(
+            NewOutboundMessage::new(MessageChannel::Sms, phone_number.clone(), sms_body.clone())
+                .set_user(user.id)
+                .set_event(event.id)
+                .set_organization(event.organization_id),
+        );
+    }
+
+    for guest in guests.iter() {
+        let (subject, body) = cancellation_email(event, &guest.first_name);
+        messages.push(
+            NewOutboundMessage::new(MessageChannel::Email, guest.email.clone(), body)
+                .set_subject(subject)
+                .set_contact(guest.id)
+                .set_event(event.id)
+                .set_organization(event.organization_id),
+        );
+        if let Some(ref phone_number) = guest.phone_number {
+            messages.push(
+                NewOutboundMessage::new(
+                    MessageChannel::Sms,
+                    phone_number.clone(),
+                    sms_body.clone(),
+                )
+                .set_contact(guest.id)
+                .set_event(event.id)
+                .set_organization(event.organization_id),
+            );
+        }
+    }
+
+    let enqueued = enqueue_outbound_messages(connection, messages).await?;
+
+    Ok((
+        notifications,
+        CancellationMessageCounts {
+            queued: enqueued.queued,
+            over_quota: enqueued.over_quota,
+            opted_out,
+        },
+    ))
+}
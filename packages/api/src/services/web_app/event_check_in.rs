@@ -0,0 +1,220 @@
+use crate::enums::SignupStatus;
+use crate::error::{postgres_error, AppError};
+use crate::models::{Event, EventShift, EventSignup, EventSignupUpdate, NewEventSignup};
+use crate::postgres::get_postgres_connection;
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{event_signups, events};
+use crate::services::validate_max_length;
+use crate::services::web_app::{
+    get_event_signup, lock_event_shift, SignupOwner, MAX_EVENT_TEXT_LENGTH,
+};
+use crate::signing::{sign_token, verify_signed_token};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use qrcode::{render::svg, QrCode};
+
+const CHECK_IN_TOKEN_PURPOSE: &str = "event_check_in";
+const CHECK_IN_QR_MIN_DIMENSION: u32 = 240;
+
+pub fn create_check_in_token(signup_id: i32) -> Result<String, AppError> {
+    sign_token(CHECK_IN_TOKEN_PURPOSE, &signup_id.to_string())
+}
+
+pub fn render_check_in_qr_svg(token: &str) -> Result<String, AppError> {
+    let code = QrCode::new(token.as_bytes()).map_err(|error| {
+        AppError::InternalError(format!("QR code generation failed: {}", error))
+    })?;
+
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(CHECK_IN_QR_MIN_DIMENSION, CHECK_IN_QR_MIN_DIMENSION)
+        .quiet_zone(true)
+        .build())
+}
+
+// resolves a scanned token to its signup without changing it, so the caller can
+// check organization access before checking in
+pub async fn get_signup_by_check_in_token(token: &str) -> Result<EventSignup, AppError> {
+    let signup_id = verify_signed_token(CHECK_IN_TOKEN_PURPOSE, token)?
+        .and_then(|payload| payload.parse::<i32>().ok())
+        .ok_or_else(|| AppError::validation("token", "Invalid check-in code"))?;
+
+    get_event_signup(signup_id).await
+}
+
+// the signup row stays locked from the status check to the update so a cancel or
+// waitlist change landing in between isn't overwritten
+pub async fn check_in_signup(signup_id: i32) -> Result<EventSignup, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let signup: EventSignup = event_signups::table
+                    .find(signup_id)
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?
+                    .ok_or_else(|| AppError::not_found("Event signup"))?;
+
+                match signup.get_status() {
+                    SignupStatus::SignedUp | SignupStatus::NoShow => {}
+                    SignupStatus::CheckedIn => {
+                        return Err(AppError::validation(
+                            "signup",
+                            "This volunteer is already checked in",
+                        ));
+                    }
+                    SignupStatus::Waitlisted => {
+                        return Err(AppError::validation(
+                            "signup",
+                            "This volunteer is on the waitlist, use walk-in check-in to admit them",
+                        ));
+                    }
+                    SignupStatus::Cancelled => {
+                        return Err(AppError::validation("signup", "This signup was cancelled"));
+                    }
+                }
+
+                diesel::update(event_signups::table.find(signup_id))
+                    .set(EventSignupUpdate {
+                        status: Some(SignupStatus::CheckedIn.as_str().to_string()),
+                        checked_in_at: Some(Some(Utc::now())),
+                        ..Default::default()
+                    })
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)
+            })
+        })
+        .await
+}
+
+// walk-ins are admitted regardless of capacity since they are already on site,
+// an existing signup in any state is reused instead of inserting a duplicate. the
+// shift stays locked so a cancellation of the event can't land in between
+pub(crate) async fn check_in_walk_in(
+    shift_id: i32,
+    owner: SignupOwner,
+    notes: Option<String>,
+) -> Result<EventSignup, AppError> {
+    if let Some(ref notes) = notes {
+        validate_max_length("notes", notes, MAX_EVENT_TEXT_LENGTH)?;
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (signup, event_id) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let shift = lock_event_shift(connection, shift_id).await?;
+
+                let event: Event = events::table
+                    .find(shift.event_id)
+                    .first(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                if !event.is_published() || event.is_cancelled() {
+                    return Err(AppError::validation(
+                        "event",
+                        "This event is not accepting check-ins",
+                    ));
+                }
+
+                let shift_signups =
+                    event_signups::table.filter(event_signups::event_shift_id.eq(shift_id));
+                let existing: Option<EventSignup> = match owner {
+                    SignupOwner::User(user_id) => {
+                        shift_signups
+                            .filter(event_signups::user_id.eq(user_id))
+                            .for_update()
+                            .first(connection)
+                            .await
+                    }
+                    SignupOwner::Contact(contact_id) => {
+                        shift_signups
+                            .filter(event_signups::contact_id.eq(contact_id))
+                            .for_update()
+                            .first(connection)
+                            .await
+                    }
+                }
+                .optional()
+                .map_err(postgres_error)?;
+
+                let signup = match existing {
+                    Some(signup) if signup.get_status() == SignupStatus::CheckedIn => Err(
+                        AppError::validation("signup", "This volunteer is already checked in"),
+                    ),
+                    Some(signup) => {
+                        let update = EventSignupUpdate {
+                            status: Some(SignupStatus::CheckedIn.as_str().to_string()),
+                            notes: notes.map(Some),
+                            checked_in_at: Some(Some(Utc::now())),
+                            cancelled_at: Some(None),
+                            ..Default::default()
+                        };
+
+                        diesel::update(event_signups::table.find(signup.id))
+                            .set(&update)
+                            .get_result(connection)
+                            .await
+                            .map_err(postgres_error)
+                    }
+                    None => {
+                        let mut new_signup = match owner {
+                            SignupOwner::User(user_id) => NewEventSignup::new(shift_id, user_id),
+                            SignupOwner::Contact(contact_id) => {
+                                NewEventSignup::new_for_contact(shift_id, contact_id)
+                            }
+                        }
+                        .set_status(SignupStatus::CheckedIn)
+                        .set_checked_in_at(Utc::now());
+                        if let Some(notes) = notes {
+                            new_signup = new_signup.set_notes(notes);
+                        }
+
+                        diesel::insert_into(event_signups::table)
+                            .values(&new_signup)
+                            .get_result(connection)
+                            .await
+                            .map_err(postgres_error)
+                    }
+                }?;
+
+                Ok((signup, event.id))
+            })
+        })
+        .await?;
+
+    // a walk-in can take a seat the public pages still show as open
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok(signup)
+}
+
+// marks every signup that never checked in as a no-show, only once the shift is over
+pub async fn mark_remaining_no_shows(shift: &EventShift) -> Result<usize, AppError> {
+    if shift.end_time > Utc::now() {
+        return Err(AppError::validation(
+            "shift",
+            "No-shows can only be marked after the shift ends",
+        ));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::update(
+        event_signups::table
+            .filter(event_signups::event_shift_id.eq(shift.id))
+            .filter(event_signups::status.eq(SignupStatus::SignedUp.as_str())),
+    )
+    .set(event_signups::status.eq(SignupStatus::NoShow.as_str()))
+    .execute(connection)
+    .await
+    .map_err(postgres_error)
+}
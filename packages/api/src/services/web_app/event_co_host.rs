@@ -0,0 +1,258 @@
+use crate::enums::{CoHostDataSharing, CoHostStatus, NotificationType};
+use crate::error::{postgres_error, AppError};
+use crate::models::{Event, EventCoHost, EventCoHostUpdate, NewEventCoHost, Organization};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{event_co_hosts, events, organizations};
+use crate::services::web_app::get_event;
+use crate::services::{
+    get_organization_by_slug, notify_organization_admins, publish_notifications,
+};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use std::collections::HashMap;
+
+pub async fn get_event_co_host(co_host_id: i32) -> Result<EventCoHost, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_co_hosts::table
+        .find(co_host_id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Co-host"))
+}
+
+// the host organization sees its own events, an accepted co-host sees the event along
+// with its co-host record so callers can apply the data sharing setting
+pub async fn get_accessible_event(
+    organization_id: i32,
+    event_id: i32,
+) -> Result<(Event, Option<EventCoHost>), AppError> {
+    let event = get_event(event_id).await?;
+
+    if event.organization_id == organization_id {
+        return Ok((event, None));
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let co_host: Option<EventCoHost> = event_co_hosts::table
+        .filter(event_co_hosts::event_id.eq(event_id))
+        .filter(event_co_hosts::organization_id.eq(organization_id))
+        .filter(event_co_hosts::status.eq(CoHostStatus::Accepted.as_str()))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    match co_host {
+        Some(co_host) => Ok((event, Some(co_host))),
+        None => Err(AppError::not_found("Event")),
+    }
+}
+
+pub async fn batch_get_event_co_hosts(
+    event_ids: &[i32],
+) -> Result<HashMap<i32, Vec<(EventCoHost, Organization)>>, AppError> {
+    if event_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let co_hosts: Vec<(EventCoHost, Organization)> = event_co_hosts::table
+        .inner_join(organizations::table)
+        .filter(event_co_hosts::event_id.eq_any(event_ids))
+        .order(event_co_hosts::id.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let mut map: HashMap<i32, Vec<(EventCoHost, Organization)>> = HashMap::new();
+    for (co_host, organization) in co_hosts {
+        map.entry(co_host.event_id)
+            .or_default()
+            .push((co_host, organization));
+    }
+
+    Ok(map)
+}
+
+// invitations go to an organization by slug and notify its owners and admins,
+// a previously declined organization can be invited again
+pub async fn invite_co_host(
+    event: &Event,
+    invited_by: i32,
+    organization_slug: &str,
+    data_sharing: CoHostDataSharing,
+) -> Result<(EventCoHost, Organization), AppError> {
+    if event.is_cancelled() {
+        return Err(AppError::validation(
+            "event",
+            "Cancelled events cannot add co-hosts",
+        ));
+    }
+
+    let organization = get_organization_by_slug(&organization_slug.trim().to_lowercase())
+        .await?
+        .ok_or_else(|| AppError::not_found("Organization"))?;
+
+    if organization.id == event.organization_id {
+        return Err(AppError::validation(
+            "organization_slug",
+            "An organization can't co-host its own event",
+        ));
+    }
+
+    let event_id = event.id;
+    let organization_id = organization.id;
+    let notification_message = format!("You've been invited to co-host {}.", event.name);
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (co_host, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let existing: Option<EventCoHost> = event_co_hosts::table
+                    .filter(event_co_hosts::event_id.eq(event_id))
+                    .filter(event_co_hosts::organization_id.eq(organization_id))
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?;
+
+                let co_host: EventCoHost = match existing {
+                    Some(co_host) if co_host.get_status() == CoHostStatus::Declined => {
+                        let update = EventCoHostUpdate {
+                            status: Some(CoHostStatus::Pending.as_str().to_string()),
+                            data_sharing: Some(data_sharing.as_str().to_string()),
+                            invited_by: Some(Some(invited_by)),
+                            responded_by: Some(None),
+                            created_at: Some(Utc::now()),
+                            responded_at: Some(None),
+                        };
+
+                        diesel::update(event_co_hosts::table.find(co_host.id))
+                            .set(&update)
+                            .get_result(connection)
+                            .await
+                            .map_err(postgres_error)?
+                    }
+                    Some(_) => return Err(AppError::already_exists("Co-host invitation")),
+                    None => {
+                        let new_co_host =
+                            NewEventCoHost::new(event_id, organization_id, data_sharing)
+                                .set_invited_by(invited_by);
+
+                        diesel::insert_into(event_co_hosts::table)
+                            .values(&new_co_host)
+                            .get_result(connection)
+                            .await
+                            .map_err(postgres_error)?
+                    }
+                };
+
+                let notifications = notify_organization_admins(
+                    connection,
+                    organization_id,
+                    None,
+                    NotificationType::CoHostInvite,
+                    "Co-host invitation".to_string(),
+                    notification_message,
+                    None,
+                )
+                .await?;
+
+                Ok((co_host, notifications))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+
+    Ok((co_host, organization))
+}
+
+pub async fn respond_to_co_host_invitation(
+    co_host: EventCoHost,
+    responded_by: i32,
+    accept: bool,
+) -> Result<EventCoHost, AppError> {
+    if co_host.get_status() != CoHostStatus::Pending {
+        return Err(AppError::validation(
+            "co_host",
+            "This invitation has already been answered",
+        ));
+    }
+
+    let status = if accept {
+        CoHostStatus::Accepted
+    } else {
+        CoHostStatus::Declined
+    };
+
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::update(event_co_hosts::table.find(co_host.id))
+        .set(EventCoHostUpdate {
+            status: Some(status.as_str().to_string()),
+            responded_by: Some(Some(responded_by)),
+            responded_at: Some(Some(Utc::now())),
+            ..Default::default()
+        })
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn update_co_host_data_sharing(
+    co_host_id: i32,
+    data_sharing: CoHostDataSharing,
+) -> Result<EventCoHost, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::update(event_co_hosts::table.find(co_host_id))
+        .set(EventCoHostUpdate {
+            data_sharing: Some(data_sharing.as_str().to_string()),
+            ..Default::default()
+        })
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn remove_co_host(co_host_id: i32) -> Result<(), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::delete(event_co_hosts::table.find(co_host_id))
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(())
+}
+
+// pending invitations addressed to the organization, newest first
+pub async fn list_co_host_invitations(
+    organization_id: i32,
+) -> Result<Vec<(EventCoHost, Event, Organization)>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_co_hosts::table
+        .inner_join(events::table.inner_join(organizations::table))
+        .filter(event_co_hosts::organization_id.eq(organization_id))
+        .filter(event_co_hosts::status.eq(CoHostStatus::Pending.as_str()))
+        .filter(events::cancelled_at.is_null())
+        .select((
+            EventCoHost::as_select(),
+            Event::as_select(),
+            Organization::as_select(),
+        ))
+        .order((event_co_hosts::created_at.desc(), event_co_hosts::id.desc()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
@@ -0,0 +1,280 @@
+use crate::error::{postgres_error, AppError};
+use crate::interfaces::EventImageVariant;
+use crate::minio::{
+    get_minio_event_image_url, minio_delete_event_images, minio_upload_event_image,
+};
+use crate::models::{Event, EventUpdate};
+use crate::postgres::get_postgres_connection;
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{event_templates, events};
+use crate::services::{ensure_storage_available, validate_media_file};
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use image::{codecs::jpeg::JpegEncoder, imageops::FilterType, DynamicImage, ImageReader, Limits};
+use std::io::Cursor;
+use uuid::Uuid;
+
+pub const MAX_EVENT_IMAGE_SIZE_BYTES: i64 = 10 * 1024 * 1024;
+pub const MAX_EVENT_IMAGE_DIMENSION: u32 = 8_000;
+// only formats the resizer can decode, so svg and avif from the media list are excluded
+pub const ALLOWED_EVENT_IMAGE_MIME_TYPES: [&str; 4] =
+    ["image/jpeg", "image/png", "image/gif", "image/webp"];
+
+const EVENT_IMAGE_VARIANT_WIDTHS: [u32; 3] = [320, 640, 1280];
+const EVENT_IMAGE_JPEG_QUALITY: u8 = 82;
+
+struct EncodedVariant {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+fn decode_event_image(data: &[u8]) -> Result<DynamicImage, AppError> {
+    let invalid = || AppError::validation("file", "Image could not be read");
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_EVENT_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_EVENT_IMAGE_DIMENSION);
+
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|_| invalid())?;
+    reader.limits(limits);
+
+    reader.decode().map_err(|_| invalid())
+}
+
+fn encode_variant(image: &DynamicImage, width: u32) -> Result<EncodedVariant, AppError> {
+    let resized = if width < image.width() {
+        image.resize(width, u32::MAX, FilterType::Triangle)
+    } else {
+        image.clone()
+    };
+
+    // jpeg has no alpha channel, transparent areas flatten to their stored color
+    let rgb = resized.to_rgb8();
+
+    let mut data = Vec::new();
+    JpegEncoder::new_with_quality(&mut data, EVENT_IMAGE_JPEG_QUALITY)
+        .encode_image(&rgb)
+        .map_err(|error| AppError::InternalError(format!("Image encoding error: {}", error)))?;
+
+    Ok(EncodedVariant {
+        width: rgb.width(),
+        height: rgb.height(),
+        data,
+    })
+}
+
+// never upscales, an image narrower than the smallest width gets a single variant
+fn build_event_image_variants(image: &DynamicImage) -> Result<Vec<EncodedVariant>, AppError> {
+    let mut widths: Vec<u32> = EVENT_IMAGE_VARIANT_WIDTHS
+        .into_iter()
+        .filter(|width| *width < image.width())
+        .collect();
+    if widths.len() < EVENT_IMAGE_VARIANT_WIDTHS.len() {
+        widths.push(image.width());
+    }
+
+    widths
+        .into_iter()
+        .map(|width| encode_variant(image, width))
+        .collect()
+}
+
+// duplicated events and templates copy image_url, so stored images are only removed
+// once nothing points at them anymore
+async fn release_event_image(
+    image_storage_prefix: &str,
+    image_url: Option<&str>,
+) -> Result<(), AppError> {
+    if let Some(image_url) = image_url {
+        let connection = &mut get_postgres_connection().await?;
+
+        let event_references: i64 = events::table
+            .filter(events::image_url.eq(image_url))
+            .count()
+            .get_result(connection)
+            .await
+            .map_err(postgres_error)?;
+        let template_references: i64 = event_templates::table
+            .filter(event_templates::image_url.eq(image_url))
+            .count()
+            .get_result(connection)
+            .await
+            .map_err(postgres_error)?;
+
+        if event_references + template_references > 0 {
+            return Ok(());
+        }
+    }
+
+    minio_delete_event_images(image_storage_prefix).await
+}
+
+pub(crate) async fn release_replaced_event_image(previous: &Event) {
+    if let Some(ref image_storage_prefix) = previous.image_storage_prefix {
+        if let Err(error) =
+            release_event_image(image_storage_prefix, previous.image_url.as_deref()).await
+        {
+            tracing::warn!(
+                "failed to remove replaced image for event {}: {}",
+                previous.id,
+                error
+            );
+        }
+    }
+}
+
+// the original upload isn't kept since the bucket is public and it may carry EXIF
+// location data, the largest variant becomes the event's image_url
+pub async fn upload_event_image(
+    event: Event,
+    filename: String,
+    mime_type: String,
+    data: Vec<u8>,
+) -> Result<Event, AppError> {
+    validate_media_file(&filename, &mime_type, data.len() as i64)?;
+
+    if !ALLOWED_EVENT_IMAGE_MIME_TYPES.contains(&mime_type.as_str()) {
+        return Err(AppError::validation(
+            "mime_type",
+            format!(
+                "Event images must be one of: {}",
+                ALLOWED_EVENT_IMAGE_MIME_TYPES.join(", ")
+            ),
+        ));
+    }
+    if data.len() as i64 > MAX_EVENT_IMAGE_SIZE_BYTES {
+        return Err(AppError::validation(
+            "file_size_bytes",
+            format!(
+                "Event images cannot be larger than {} MB",
+                MAX_EVENT_IMAGE_SIZE_BYTES / (1024 * 1024)
+            ),
+        ));
+    }
+    if event.is_cancelled() {
+        return Err(AppError::validation(
+            "event",
+            "Cancelled events cannot be edited",
+        ));
+    }
+
+    // decoding and resizing are CPU bound, so they stay off the async workers
+    let encoded = tokio::task::spawn_blocking(move || {
+        let image = decode_event_image(&data)?;
+        build_event_image_variants(&image)
+    })
+    .await
+    .map_err(|error| AppError::InternalError(format!("Image processing error: {}", error)))??;
+
+    // the variants are what's stored, the image they replace is released below
+    let image_storage_bytes: i64 = encoded
+        .iter()
+        .map(|variant| variant.data.len() as i64)
+        .sum();
+
+    let image_storage_prefix = format!(
+        "organizations/{}/events/{}/{}",
+        event.organization_id,
+        event.id,
+        Uuid::new_v4()
+    );
+
+    let mut variants: Vec<EventImageVariant> = vec![];
+    for variant in encoded {
+        let key = format!("{}/{}w.jpg", image_storage_prefix, variant.width);
+
+        if let Err(error) = minio_upload_event_image(&key, variant.data, "image/jpeg").await {
+            minio_delete_event_images(&image_storage_prefix).await.ok();
+            return Err(error);
+        }
+
+        variants.push(EventImageVariant {
+            width: variant.width,
+            height: variant.height,
+            url: get_minio_event_image_url(&key)?,
+        });
+    }
+
+    let image_url = variants.last().map(|variant| variant.url.clone());
+    let image_variants = serde_json::to_value(&variants)
+        .map_err(|error| AppError::InternalError(format!("JSON error: {}", error)))?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    // the quota is checked against the image being replaced as of the row lock, so
+    // concurrent uploads can't both fit under it
+    let event_id = event.id;
+    let organization_id = event.organization_id;
+    let stored_prefix = image_storage_prefix.clone();
+    let result = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let previous: Event = events::table
+                    .find(event_id)
+                    .for_update()
+                    .first(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                ensure_storage_available(
+                    connection,
+                    organization_id,
+                    image_storage_bytes,
+                    previous.image_storage_bytes,
+                )
+                .await?;
+
+                let updated: Event = diesel::update(events::table.find(event_id))
+                    .set(EventUpdate {
+                        image_url: Some(image_url),
+                        image_storage_prefix: Some(Some(stored_prefix)),
+                        image_variants: Some(image_variants),
+                        image_storage_bytes: Some(image_storage_bytes),
+                        ..Default::default()
+                    })
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                Ok((previous, updated))
+            })
+        })
+        .await;
+
+    let (previous, updated) = match result {
+        Ok(result) => result,
+        Err(error) => {
+            minio_delete_event_images(&image_storage_prefix).await.ok();
+            return Err(error);
+        }
+    };
+
+    release_replaced_event_image(&previous).await;
+    invalidate_redis_cached_public_event(event.id).await.ok();
+
+    Ok(updated)
+}
+
+pub async fn remove_event_image(event: Event) -> Result<Event, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let updated = diesel::update(events::table.find(event.id))
+        .set(EventUpdate {
+            image_url: Some(None),
+            image_storage_prefix: Some(None),
+            image_variants: Some(serde_json::json!([])),
+            image_storage_bytes: Some(0),
+            ..Default::default()
+        })
+        .get_result::<Event>(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    release_replaced_event_image(&event).await;
+    invalidate_redis_cached_public_event(event.id).await.ok();
+
+    Ok(updated)
+}
@@ -0,0 +1,969 @@
+use crate::enums::{RecurrenceFrequency, RecurrenceWeekday, SignupStatus};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    EventSeries, EventSeriesUpdate, EventShift, EventShiftUpdate, NewEventSeries, NewEventShift,
+    Notification,
+};
+use crate::postgres::get_postgres_connection;
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{event_series, event_shifts, event_signups};
+use crate::services::web_app::{
+    promote_waitlisted_signups, MAX_EVENT_SHIFT_TIMEZONE_LENGTH, MAX_EVENT_TEXT_LENGTH,
+};
+use crate::services::{publish_notifications, validate_max_length, validate_required_string};
+use chrono::{
+    DateTime, Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::{HashMap, HashSet};
+
+// upper bound on occurrences a single series may materialize
+pub const MAX_SERIES_OCCURRENCES: usize = 366;
+pub const MAX_SERIES_DURATION_MINUTES: i32 = 24 * 60;
+
+pub fn validate_timezone(timezone: &str) -> Result<Tz, AppError> {
+    validate_required_string("timezone", timezone, MAX_EVENT_SHIFT_TIMEZONE_LENGTH)?;
+    timezone
+        .parse::<Tz>()
+        .map_err(|_| AppError::validation("timezone", "Unknown timezone"))
+}
+
+fn validate_new_series(series: &NewEventSeries) -> Result<(), AppError> {
+    validate_timezone(&series.timezone)?;
+    if let Some(ref notes) = series.notes {
+        validate_max_length("notes", notes, MAX_EVENT_TEXT_LENGTH)?;
+    }
+    if series.repeat_interval < 1 {
+        return Err(AppError::validation(
+            "repeat_interval",
+            "Repeat interval must be at least 1",
+        ));
+    }
+    if !(1..=MAX_SERIES_DURATION_MINUTES).contains(&series.duration_minutes) {
+        return Err(AppError::validation(
+            "duration_minutes",
+            "Duration must be between 1 minute and 24 hours",
+        ));
+    }
+    if let Some(capacity) = series.capacity {
+        if capacity < 1 {
+            return Err(AppError::validation(
+                "capacity",
+                "Capacity must be at least 1",
+            ));
+        }
+    }
+    match (series.until_date, series.occurrence_count) {
+        (None, None) => {
+            return Err(AppError::validation(
+                "until_date",
+                "A series needs an end date or an occurrence count",
+            ));
+        }
+        (Some(until_date), _) if until_date < series.starts_on => {
+            return Err(AppError::validation(
+                "until_date",
+                "End date must be on or after the start date",
+            ));
+        }
+        (_, Some(count)) if count < 1 || count as usize > MAX_SERIES_OCCURRENCES => {
+            return Err(AppError::validation(
+                "occurrence_count",
+                format!(
+                    "Occurrence count must be between 1 and {}",
+                    MAX_SERIES_OCCURRENCES
+                ),
+            ));
+        }
+        _ => {}
+    }
+    if !series.by_weekday.is_empty() && series.frequency != RecurrenceFrequency::Weekly.as_str() {
+        return Err(AppError::validation(
+            "by_weekday",
+            "Weekdays can only be set on weekly series",
+        ));
+    }
+    let Some(frequency) = RecurrenceFrequency::from_str(&series.frequency) else {
+        return Err(AppError::validation("frequency", "Unknown frequency"));
+    };
+    // an occurrence count is already capped above, an end date alone could run past
+    // the cap and get cut off without anyone noticing
+    if let (Some(until_date), None) = (series.until_date, series.occurrence_count) {
+        let by_weekday = series
+            .by_weekday
+            .iter()
+            .flatten()
+            .filter_map(|weekday| RecurrenceWeekday::from_str(weekday))
+            .collect();
+
+        let occurrences = rule_candidate_dates(
+            frequency,
+            series.repeat_interval,
+            by_weekday,
+            series.starts_on,
+        )
+        .take_while(|date| *date <= until_date)
+        .take(MAX_SERIES_OCCURRENCES + 1)
+        .count();
+
+        if occurrences > MAX_SERIES_OCCURRENCES {
+            return Err(AppError::validation(
+                "until_date",
+                format!(
+                    "A series can have at most {} occurrences, choose an earlier end date",
+                    MAX_SERIES_OCCURRENCES
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// the series as it would look after the update, so the merged rule can be validated
+// before anything is written
+fn merge_series_update(series: &EventSeries, update: &EventSeriesUpdate) -> NewEventSeries {
+    NewEventSeries {
+        event_id: series.event_id,
+        frequency: update
+            .frequency
+            .clone()
+            .unwrap_or_else(|| series.frequency.clone()),
+        repeat_interval: update.repeat_interval.unwrap_or(series.repeat_interval),
+        by_weekday: update
+            .by_weekday
+            .clone()
+            .unwrap_or_else(|| series.by_weekday.clone()),
+        starts_on: update.starts_on.unwrap_or(series.starts_on),
+        until_date: update.until_date.unwrap_or(series.until_date),
+        occurrence_count: update.occurrence_count.unwrap_or(series.occurrence_count),
+        excluded_dates: update
+            .excluded_dates
+            .clone()
+            .unwrap_or_else(|| series.excluded_dates.clone()),
+        start_time_local: update.start_time_local.unwrap_or(series.start_time_local),
+        duration_minutes: update.duration_minutes.unwrap_or(series.duration_minutes),
+        timezone: update
+            .timezone
+            .clone()
+            .unwrap_or_else(|| series.timezone.clone()),
+        capacity: update.capacity.unwrap_or(series.capacity),
+        notes: update.notes.clone().unwrap_or_else(|| series.notes.clone()),
+    }
+}
+
+// the unbounded sequence of dates a rule produces from its start date
+fn rule_candidate_dates(
+    frequency: RecurrenceFrequency,
+    repeat_interval: i32,
+    by_weekday: Vec<RecurrenceWeekday>,
+    starts_on: NaiveDate,
+) -> Box<dyn Iterator<Item = NaiveDate>> {
+    let interval = repeat_interval.max(1) as u64;
+
+    match frequency {
+        RecurrenceFrequency::Daily => Box::new(
+            (0..)
+                .map_while(move |step: u64| starts_on.checked_add_days(Days::new(step * interval))),
+        ),
+        RecurrenceFrequency::Weekly => {
+            let mut weekdays: Vec<u64> = by_weekday
+                .into_iter()
+                .map(|weekday| chrono::Weekday::from(weekday).num_days_from_monday() as u64)
+                .collect();
+            if weekdays.is_empty() {
+                weekdays.push(starts_on.weekday().num_days_from_monday() as u64);
+            }
+            weekdays.sort_unstable();
+            weekdays.dedup();
+
+            let week_start =
+                starts_on - Days::new(starts_on.weekday().num_days_from_monday() as u64);
+
+            Box::new(
+                (0..)
+                    .map_while(move |step: u64| {
+                        week_start.checked_add_days(Days::new(step * 7 * interval))
+                    })
+                    .flat_map(move |week| {
+                        weekdays
+                            .clone()
+                            .into_iter()
+                            .filter_map(move |offset| week.checked_add_days(Days::new(offset)))
+                    })
+                    .filter(move |date| *date >= starts_on),
+            )
+        }
+        // months without the start day (e.g. the 31st) are skipped rather than clamped
+        RecurrenceFrequency::Monthly => {
+            let first_month = starts_on.year() as i64 * 12 + starts_on.month0() as i64;
+            let day = starts_on.day();
+
+            Box::new(
+                (0..)
+                    .map_while(move |step: i64| {
+                        let month = first_month + step * interval as i64;
+                        let year = i32::try_from(month.div_euclid(12)).ok()?;
+                        Some(NaiveDate::from_ymd_opt(
+                            year,
+                            month.rem_euclid(12) as u32 + 1,
+                            day,
+                        ))
+                    })
+                    .flatten(),
+            )
+        }
+    }
+}
+
+// every date the rule produces, before exclusions are applied (exclusions still
+// count toward occurrence_count, matching RFC 5545 EXDATE semantics)
+fn series_candidate_dates(series: &EventSeries) -> Vec<NaiveDate> {
+    let candidates = rule_candidate_dates(
+        series.get_frequency(),
+        series.repeat_interval,
+        series.get_by_weekday(),
+        series.starts_on,
+    );
+
+    let limit = series
+        .occurrence_count
+        .map(|count| count.max(0) as usize)
+        .unwrap_or(MAX_SERIES_OCCURRENCES)
+        .min(MAX_SERIES_OCCURRENCES);
+
+    candidates
+        .take_while(|date| {
+            series
+                .until_date
+                .is_none_or(|until_date| *date <= until_date)
+        })
+        .take(limit)
+        .collect()
+}
+
+pub fn series_occurrence_dates(series: &EventSeries) -> Vec<NaiveDate> {
+    let excluded: HashSet<NaiveDate> = series.get_excluded_dates().into_iter().collect();
+
+    series_candidate_dates(series)
+        .into_iter()
+        .filter(|date| !excluded.contains(date))
+        .collect()
+}
+
+// wall-clock times skipped by a DST gap move forward an hour, repeated times use the first instance
+fn localize(timezone: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    timezone
+        .from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            timezone
+                .from_local_datetime(&(local + TimeDelta::hours(1)))
+                .earliest()
+        })
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+}
+
+// start and end both pinned to local wall-clock time so a 6pm shift stays at 6pm
+// across DST transitions
+pub(crate) fn local_shift_times(
+    timezone: Tz,
+    local_start: NaiveDateTime,
+    duration_minutes: i32,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    let duration = TimeDelta::minutes(duration_minutes as i64);
+
+    let start_time = localize(timezone, local_start);
+    let mut end_time = localize(timezone, local_start + duration);
+
+    if end_time <= start_time {
+        end_time = start_time + duration;
+    }
+
+    (start_time, end_time)
+}
+
+pub fn series_occurrence_times(
+    series: &EventSeries,
+    timezone: Tz,
+    date: NaiveDate,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    local_shift_times(
+        timezone,
+        date.and_time(series.start_time_local),
+        series.duration_minutes,
+    )
+}
+
+pub(crate) async fn has_active_signups(
+    connection: &mut AsyncPgConnection,
+    shift_id: i32,
+) -> Result<bool, AppError> {
+    let count: i64 = event_signups::table
+        .filter(event_signups::event_shift_id.eq(shift_id))
+        .filter(event_signups::status.ne(SignupStatus::Cancelled.as_str()))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(count > 0)
+}
+
+// reconciles the series' upcoming shifts with its rule: matching occurrences are
+// updated in place so signups survive, dropped occurrences are deleted, and new
+// ones are inserted. past shifts are never touched. notifications for signups
+// promoted off a waitlist are returned for the caller to publish once it commits
+async fn sync_series_shifts(
+    connection: &mut AsyncPgConnection,
+    series: &EventSeries,
+) -> Result<(Vec<EventShift>, Vec<Notification>), AppError> {
+    let timezone = validate_timezone(&series.timezone)?;
+    let now = Utc::now();
+    let mut notifications = vec![];
+
+    let mut occurrences: HashMap<NaiveDate, (DateTime<Utc>, DateTime<Utc>)> =
+        series_occurrence_dates(series)
+            .into_iter()
+            .map(|date| (date, series_occurrence_times(series, timezone, date)))
+            .collect();
+
+    let existing: Vec<EventShift> = event_shifts::table
+        .filter(event_shifts::series_id.eq(series.id))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    for shift in existing {
+        let occurrence = shift
+            .series_occurrence_date
+            .and_then(|date| occurrences.remove(&date).map(|times| (date, times)));
+
+        if shift.start_time <= now {
+            continue;
+        }
+
+        match occurrence {
+            Some((_, (start_time, end_time))) => {
+                let update = EventShiftUpdate {
+                    start_time: Some(start_time),
+                    end_time: Some(end_time),
+                    timezone: Some(series.timezone.clone()),
+                    capacity: Some(series.capacity),
+                    notes: Some(series.notes.clone()),
+                    ..Default::default()
+                };
+
+                let updated: EventShift = diesel::update(event_shifts::table.find(shift.id))
+                    .set(&update)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                if updated.capacity != shift.capacity {
+                    let (_, promoted_notifications) =
+                        promote_waitlisted_signups(connection, &updated).await?;
+                    notifications.extend(promoted_notifications);
+                }
+            }
+            // shifts people already signed up for are detached instead of deleted
+            None if has_active_signups(connection, shift.id).await? => {
+                diesel::update(event_shifts::table.find(shift.id))
+                    .set(EventShiftUpdate {
+                        series_id: Some(None),
+                        ..Default::default()
+                    })
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+            }
+            None => {
+                diesel::delete(event_shifts::table.find(shift.id))
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+            }
+        }
+    }
+
+    let new_shifts = occurrences
+        .into_iter()
+        .filter(|(_, (start_time, _))| *start_time > now)
+        .map(|(date, (start_time, end_time))| {
+            let mut new_shift = NewEventShift::new(
+                series.event_id,
+                start_time,
+                end_time,
+                series.timezone.clone(),
+            )?
+            .set_series(series.id, date);
+            if let Some(capacity) = series.capacity {
+                new_shift = new_shift.set_capacity(capacity);
+            }
+            if let Some(ref notes) = series.notes {
+                new_shift = new_shift.set_notes(notes.clone());
+            }
+            Ok(new_shift)
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    if !new_shifts.is_empty() {
+        diesel::insert_into(event_shifts::table)
+            .values(&new_shifts)
+            .execute(connection)
+            .await
+            .map_err(postgres_error)?;
+    }
+
+    let shifts = event_shifts::table
+        .filter(event_shifts::series_id.eq(series.id))
+        .order(event_shifts::start_time.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok((shifts, notifications))
+}
+
+async fn lock_event_series(
+    connection: &mut AsyncPgConnection,
+    series_id: i32,
+) -> Result<EventSeries, AppError> {
+    event_series::table
+        .find(series_id)
+        .for_update()
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event series"))
+}
+
+// removes a single occurrence from the series so syncing never recreates it,
+// used when one shift is edited or deleted on its own
+pub(crate) async fn exclude_series_occurrence(
+    connection: &mut AsyncPgConnection,
+    series_id: i32,
+    occurrence_date: NaiveDate,
+) -> Result<(), AppError> {
+    let series = lock_event_series(connection, series_id).await?;
+
+    let mut excluded_dates = series.get_excluded_dates();
+    if excluded_dates.contains(&occurrence_date) {
+        return Ok(());
+    }
+    excluded_dates.push(occurrence_date);
+    excluded_dates.sort_unstable();
+
+    diesel::update(event_series::table.find(series_id))
+        .set(EventSeriesUpdate {
+            excluded_dates: Some(excluded_dates.into_iter().map(Some).collect()),
+            ..Default::default()
+        })
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(())
+}
+
+// expects the series row to be locked already
+async fn update_and_sync_series(
+    connection: &mut AsyncPgConnection,
+    existing: &EventSeries,
+    update: &EventSeriesUpdate,
+) -> Result<(EventSeries, Vec<EventShift>, Vec<Notification>), AppError> {
+    validate_new_series(&merge_series_update(existing, update))?;
+
+    let series: EventSeries = diesel::update(event_series::table.find(existing.id))
+        .set(update)
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let (shifts, notifications) = sync_series_shifts(connection, &series).await?;
+
+    Ok((series, shifts, notifications))
+}
+
+pub async fn create_event_series(
+    new_series: NewEventSeries,
+) -> Result<(EventSeries, Vec<EventShift>), AppError> {
+    validate_new_series(&new_series)?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (series, shifts) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let series: EventSeries = diesel::insert_into(event_series::table)
+                    .values(&new_series)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                // a new series has no signups yet, so nothing gets promoted
+                let (shifts, _) = sync_series_shifts(connection, &series).await?;
+
+                if shifts.is_empty() {
+                    return Err(AppError::validation(
+                        "starts_on",
+                        "The recurrence rule does not produce any upcoming shifts",
+                    ));
+                }
+
+                Ok((series, shifts))
+            })
+        })
+        .await?;
+
+    invalidate_redis_cached_public_event(series.event_id)
+        .await
+        .ok();
+
+    Ok((series, shifts))
+}
+
+pub async fn get_event_series(series_id: i32) -> Result<EventSeries, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_series::table
+        .find(series_id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event series"))
+}
+
+pub async fn list_event_series(event_id: i32) -> Result<Vec<EventSeries>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_series::table
+        .filter(event_series::event_id.eq(event_id))
+        .order(event_series::starts_on.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn list_event_series_shifts(series_id: i32) -> Result<Vec<EventShift>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_shifts::table
+        .filter(event_shifts::series_id.eq(series_id))
+        .order(event_shifts::start_time.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// "all shifts" edit, re-applies the rule to every upcoming occurrence
+pub async fn update_event_series(
+    series_id: i32,
+    update: EventSeriesUpdate,
+) -> Result<(EventSeries, Vec<EventShift>), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (series, shifts, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let series = lock_event_series(connection, series_id).await?;
+                update_and_sync_series(connection, &series, &update).await
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+    invalidate_redis_cached_public_event(series.event_id)
+        .await
+        .ok();
+
+    Ok((series, shifts))
+}
+
+// "this and following" edit: the original series ends the day before the split
+// date and a new series carries the update from that date onward. shifts on or
+// after the split move to the new series so their signups are kept.
+pub async fn split_event_series(
+    series_id: i32,
+    split_date: NaiveDate,
+    update: EventSeriesUpdate,
+) -> Result<(EventSeries, Vec<EventShift>), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (series, shifts, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let series = lock_event_series(connection, series_id).await?;
+
+                if split_date <= series.starts_on {
+                    return update_and_sync_series(connection, &series, &update).await;
+                }
+
+                let candidates = series_candidate_dates(&series);
+                let elapsed = candidates.iter().filter(|date| **date < split_date).count();
+
+                let mut new_series = NewEventSeries::new(
+                    series.event_id,
+                    series.get_frequency(),
+                    split_date,
+                    series.start_time_local,
+                    series.duration_minutes,
+                    series.timezone.clone(),
+                )
+                .set_repeat_interval(series.repeat_interval)
+                .set_by_weekday(series.get_by_weekday())
+                .set_excluded_dates(
+                    series
+                        .get_excluded_dates()
+                        .into_iter()
+                        .filter(|date| *date >= split_date)
+                        .collect(),
+                );
+                new_series.until_date = series.until_date;
+                new_series.occurrence_count =
+                    series.occurrence_count.map(|count| count - elapsed as i32);
+                new_series.capacity = series.capacity;
+                new_series.notes = series.notes.clone();
+
+                if new_series.occurrence_count.is_some_and(|count| count < 1) {
+                    return Err(AppError::validation(
+                        "series",
+                        "The series has no occurrences on or after this shift",
+                    ));
+                }
+
+                validate_new_series(&new_series)?;
+
+                let tail: EventSeries = diesel::insert_into(event_series::table)
+                    .values(&new_series)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                diesel::update(
+                    event_shifts::table
+                        .filter(event_shifts::series_id.eq(series_id))
+                        .filter(event_shifts::series_occurrence_date.ge(split_date)),
+                )
+                .set(event_shifts::series_id.eq(tail.id))
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                diesel::update(event_series::table.find(series_id))
+                    .set(EventSeriesUpdate {
+                        until_date: Some(split_date.pred_opt()),
+                        occurrence_count: Some(None),
+                        ..Default::default()
+                    })
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                update_and_sync_series(connection, &tail, &update).await
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+    invalidate_redis_cached_public_event(series.event_id)
+        .await
+        .ok();
+
+    Ok((series, shifts))
+}
+
+// "this and following" delete, ends the series the day before the given date
+pub async fn end_event_series(
+    series_id: i32,
+    end_date: NaiveDate,
+) -> Result<(EventSeries, Vec<EventShift>), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let (series, shifts, notifications) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let series = lock_event_series(connection, series_id).await?;
+
+                if end_date <= series.starts_on {
+                    return Err(AppError::validation(
+                        "series",
+                        "Delete the whole series to remove its first shift onward",
+                    ));
+                }
+
+                let update = EventSeriesUpdate {
+                    until_date: Some(end_date.pred_opt()),
+                    occurrence_count: Some(None),
+                    ..Default::default()
+                };
+
+                update_and_sync_series(connection, &series, &update).await
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+    invalidate_redis_cached_public_event(series.event_id)
+        .await
+        .ok();
+
+    Ok((series, shifts))
+}
+
+// "all shifts" delete. past shifts stay on the event as standalone shifts, and
+// upcoming shifts with signups are detached rather than dropped.
+pub async fn delete_event_series(series_id: i32) -> Result<(), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let event_id = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let mut series = lock_event_series(connection, series_id).await?;
+
+                // an empty rule makes the sync drop every upcoming occurrence, so
+                // no shift is updated and nothing gets promoted
+                series.until_date = series.starts_on.pred_opt();
+                series.occurrence_count = None;
+                sync_series_shifts(connection, &series).await?;
+
+                diesel::delete(event_series::table.find(series_id))
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                Ok(series.event_id)
+            })
+        })
+        .await?;
+
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok(())
+}
+
+// builds the shift update for a single-occurrence ("this shift") edit expressed in
+// series terms, keeping the occurrence's local date
+pub fn series_shift_update(
+    shift: &EventShift,
+    start_time_local: Option<NaiveTime>,
+    duration_minutes: Option<i32>,
+    timezone: Option<String>,
+) -> Result<EventShiftUpdate, AppError> {
+    let timezone_name = timezone.unwrap_or_else(|| shift.timezone.clone());
+    let timezone = validate_timezone(&timezone_name)?;
+
+    let local_start = shift.start_time.with_timezone(&timezone);
+    let date = shift
+        .series_occurrence_date
+        .unwrap_or_else(|| local_start.date_naive());
+    let start_time_local = start_time_local.unwrap_or_else(|| local_start.time());
+    let duration_minutes = duration_minutes
+        .unwrap_or_else(|| (shift.end_time - shift.start_time).num_minutes() as i32);
+
+    if !(1..=MAX_SERIES_DURATION_MINUTES).contains(&duration_minutes) {
+        return Err(AppError::validation(
+            "duration_minutes",
+            "Duration must be between 1 minute and 24 hours",
+        ));
+    }
+
+    let (start_time, end_time) =
+        local_shift_times(timezone, date.and_time(start_time_local), duration_minutes);
+
+    Ok(EventShiftUpdate {
+        start_time: Some(start_time),
+        end_time: Some(end_time),
+        timezone: Some(timezone_name),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn new_series(frequency: RecurrenceFrequency, starts_on: NaiveDate) -> NewEventSeries {
+        NewEventSeries::new(
+            1,
+            frequency,
+            starts_on,
+            NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+            120,
+            "America/New_York".to_string(),
+        )
+    }
+
+    fn series(new_series: NewEventSeries) -> EventSeries {
+        EventSeries {
+            id: 1,
+            event_id: new_series.event_id,
+            frequency: new_series.frequency,
+            repeat_interval: new_series.repeat_interval,
+            by_weekday: new_series.by_weekday,
+            starts_on: new_series.starts_on,
+            until_date: new_series.until_date,
+            occurrence_count: new_series.occurrence_count,
+            excluded_dates: new_series.excluded_dates,
+            start_time_local: new_series.start_time_local,
+            duration_minutes: new_series.duration_minutes,
+            timezone: new_series.timezone,
+            capacity: new_series.capacity,
+            notes: new_series.notes,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn weekly_series_covers_every_weekday_from_the_start_date() {
+        // 2026-01-07 is a Wednesday, so the Monday of that week is skipped
+        let series = series(
+            new_series(RecurrenceFrequency::Weekly, date(2026, 1, 7))
+                .set_by_weekday(vec![
+                    RecurrenceWeekday::Friday,
+                    RecurrenceWeekday::Monday,
+                    RecurrenceWeekday::Wednesday,
+                ])
+                .set_occurrence_count(5),
+        );
+
+        assert_eq!(
+            series_candidate_dates(&series),
+            vec![
+                date(2026, 1, 7),
+                date(2026, 1, 9),
+                date(2026, 1, 12),
+                date(2026, 1, 14),
+                date(2026, 1, 16),
+            ]
+        );
+    }
+
+    #[test]
+    fn weekly_series_with_interval_skips_weeks() {
+        let series = series(
+            new_series(RecurrenceFrequency::Weekly, date(2026, 1, 5))
+                .set_by_weekday(vec![RecurrenceWeekday::Monday, RecurrenceWeekday::Thursday])
+                .set_repeat_interval(2)
+                .set_until_date(date(2026, 2, 1)),
+        );
+
+        assert_eq!(
+            series_candidate_dates(&series),
+            vec![
+                date(2026, 1, 5),
+                date(2026, 1, 8),
+                date(2026, 1, 19),
+                date(2026, 1, 22),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_series_on_the_31st_skips_shorter_months() {
+        let series = series(
+            new_series(RecurrenceFrequency::Monthly, date(2026, 1, 31)).set_occurrence_count(4),
+        );
+
+        assert_eq!(
+            series_candidate_dates(&series),
+            vec![
+                date(2026, 1, 31),
+                date(2026, 3, 31),
+                date(2026, 5, 31),
+                date(2026, 7, 31),
+            ]
+        );
+    }
+
+    #[test]
+    fn excluded_dates_count_toward_occurrence_count() {
+        let series = series(
+            new_series(RecurrenceFrequency::Daily, date(2026, 1, 1))
+                .set_occurrence_count(3)
+                .set_excluded_dates(vec![date(2026, 1, 2)]),
+        );
+
+        assert_eq!(
+            series_occurrence_dates(&series),
+            vec![date(2026, 1, 1), date(2026, 1, 3)]
+        );
+    }
+
+    #[test]
+    fn until_date_past_the_occurrence_cap_is_rejected() {
+        let series = new_series(RecurrenceFrequency::Daily, date(2026, 1, 1))
+            .set_until_date(date(2027, 12, 31));
+
+        assert!(validate_new_series(&series).is_err());
+    }
+
+    #[test]
+    fn until_date_within_the_occurrence_cap_is_accepted() {
+        // 2028 is a leap year, so a daily series over it produces exactly the cap
+        let daily = new_series(RecurrenceFrequency::Daily, date(2028, 1, 1))
+            .set_until_date(date(2028, 12, 31));
+        let weekly = new_series(RecurrenceFrequency::Weekly, date(2026, 1, 1))
+            .set_until_date(date(2027, 12, 31));
+
+        assert!(validate_new_series(&daily).is_ok());
+        assert!(validate_new_series(&weekly).is_ok());
+        assert_eq!(
+            series_candidate_dates(&series(daily)).len(),
+            MAX_SERIES_OCCURRENCES
+        );
+    }
+
+    #[test]
+    fn localize_moves_times_in_a_dst_gap_forward_an_hour() {
+        // 2:30am doesn't exist in New York on 2026-03-08, it becomes 3:30am EDT
+        let local = date(2026, 3, 8).and_hms_opt(2, 30, 0).unwrap();
+
+        assert_eq!(
+            localize(chrono_tz::America::New_York, local),
+            utc(2026, 3, 8, 7, 30)
+        );
+    }
+
+    #[test]
+    fn localize_uses_the_first_instance_of_a_repeated_time() {
+        // 1:30am happens twice in New York on 2026-11-01, the EDT one comes first
+        let local = date(2026, 11, 1).and_hms_opt(1, 30, 0).unwrap();
+
+        assert_eq!(
+            localize(chrono_tz::America::New_York, local),
+            utc(2026, 11, 1, 5, 30)
+        );
+    }
+
+    #[test]
+    fn local_shift_times_keep_wall_clock_times_across_dst() {
+        let before = local_shift_times(
+            chrono_tz::America::New_York,
+            date(2026, 3, 7).and_hms_opt(18, 0, 0).unwrap(),
+            120,
+        );
+        let after = local_shift_times(
+            chrono_tz::America::New_York,
+            date(2026, 3, 9).and_hms_opt(18, 0, 0).unwrap(),
+            120,
+        );
+
+        assert_eq!(before, (utc(2026, 3, 7, 23, 0), utc(2026, 3, 8, 1, 0)));
+        assert_eq!(after, (utc(2026, 3, 9, 22, 0), utc(2026, 3, 10, 0, 0)));
+    }
+}
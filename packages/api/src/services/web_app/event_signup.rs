@@ -0,0 +1,502 @@
+use crate::enums::{NotificationChannel, NotificationType, ShiftConflictPolicy, SignupStatus};
+use crate::error::{postgres_error, AppError};
+use crate::models::{
+    Event, EventShift, EventSignup, EventSignupUpdate, NewEventSignup, NewNotification,
+    Notification, Organization, User,
+};
+use crate::postgres::get_postgres_connection;
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{event_shifts, event_signups, events, organizations};
+use crate::services::web_app::{
+    find_shift_conflicts, get_event, get_event_shift, ShiftConflict, MAX_EVENT_TEXT_LENGTH,
+};
+use crate::services::{
+    filter_notification_recipients, get_app_link, get_user_by_id, insert_notifications,
+    publish_notifications, render_email, send_email_in_background, validate_max_length,
+    EmailTemplate,
+};
+use chrono::Utc;
+use chrono_tz::Tz;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+
+// shift times are stored in UTC, volunteers read them in the shift's own timezone
+pub(crate) fn format_shift_start(shift: &EventShift) -> String {
+    let timezone: Tz = shift.timezone.parse().unwrap_or(Tz::UTC);
+
+    shift
+        .start_time
+        .with_timezone(&timezone)
+        .format("%b %-d, %Y at %-I:%M %p %Z")
+        .to_string()
+}
+
+fn seated_statuses() -> Vec<&'static str> {
+    SignupStatus::SEATED
+        .iter()
+        .map(|status| status.as_str())
+        .collect()
+}
+
+// locks the shift row so concurrent signups and cancellations are serialized per shift
+pub(crate) async fn lock_event_shift(
+    connection: &mut AsyncPgConnection,
+    shift_id: i32,
+) -> Result<EventShift, AppError> {
+    event_shifts::table
+        .find(shift_id)
+        .for_update()
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event shift"))
+}
+
+pub(crate) async fn count_seated_signups(
+    connection: &mut AsyncPgConnection,
+    shift_id: i32,
+) -> Result<i64, AppError> {
+    event_signups::table
+        .filter(event_signups::event_shift_id.eq(shift_id))
+        .filter(event_signups::status.eq_any(seated_statuses()))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// a shift already over capacity, e.g. after walk-ins, has no open seats
+fn open_seats(capacity: i32, seated: i64) -> i64 {
+    (i64::from(capacity) - seated).max(0)
+}
+
+// a new or returning signup takes a seat if one is open, otherwise joins the
+// waitlist, None is a shift without a capacity
+fn status_for_open_seats(open_seats: Option<i64>) -> SignupStatus {
+    match open_seats {
+        Some(0) => SignupStatus::Waitlisted,
+        _ => SignupStatus::SignedUp,
+    }
+}
+
+// None when the shift has no capacity, seated signups are only counted when it has one
+async fn count_open_seats(
+    connection: &mut AsyncPgConnection,
+    shift: &EventShift,
+) -> Result<Option<i64>, AppError> {
+    let Some(capacity) = shift.capacity else {
+        return Ok(None);
+    };
+    let seated = count_seated_signups(connection, shift.id).await?;
+
+    Ok(Some(open_seats(capacity, seated)))
+}
+
+// fills any open seats from the waitlist in signup order, must run inside a
+// transaction that already holds the shift row lock, the promoted users'
+// notifications are returned for the caller to publish once it commits
+pub(crate) async fn promote_waitlisted_signups(
+    connection: &mut AsyncPgConnection,
+    shift: &EventShift,
+) -> Result<(Vec<EventSignup>, Vec<Notification>), AppError> {
+    let open_seats = count_open_seats(connection, shift)
+        .await?
+        .unwrap_or(i64::MAX);
+
+    if open_seats == 0 {
+        return Ok((vec![], vec![]));
+    }
+
+    let waitlisted_ids: Vec<i32> = event_signups::table
+        .filter(event_signups::event_shift_id.eq(shift.id))
+        .filter(event_signups::status.eq(SignupStatus::Waitlisted.as_str()))
+        .order((event_signups::signed_up_at.asc(), event_signups::id.asc()))
+        .limit(open_seats)
+        .select(event_signups::id)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    if waitlisted_ids.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+
+    let promoted: Vec<EventSignup> =
+        diesel::update(event_signups::table.filter(event_signups::id.eq_any(&waitlisted_ids)))
+            .set(event_signups::status.eq(SignupStatus::SignedUp.as_str()))
+            .get_results(connection)
+            .await
+            .map_err(postgres_error)?;
+
+    let event: Event = events::table
+        .find(shift.event_id)
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    // guests without an account have no inbox to notify
+    let new_notifications: Vec<NewNotification> = promoted
+        .iter()
+        .filter_map(|signup| signup.user_id)
+        .map(|user_id| {
+            NewNotification::new(
+                user_id,
+                NotificationType::WaitlistPromoted,
+                "You're off the waitlist".to_string(),
+                format!(
+                    "A spot opened up for {} on {}. You're now signed up.",
+                    event.name,
+                    format_shift_start(shift),
+                ),
+            )
+            .set_organization(event.organization_id)
+        })
+        .collect();
+
+    let notifications = insert_notifications(connection, new_notifications).await?;
+
+    Ok((promoted, notifications))
+}
+
+pub async fn get_event_signup(signup_id: i32) -> Result<EventSignup, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_signups::table
+        .find(signup_id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event signup"))
+}
+
+// who a signup belongs to, a guest signup stays with the contact until the contact's
+// email is verified as an account's
+#[derive(Clone, Copy)]
+pub(crate) enum SignupOwner {
+    User(i32),
+    Contact(i32),
+}
+
+pub async fn sign_up_for_shift(
+    shift_id: i32,
+    user_id: i32,
+    notes: Option<String>,
+) -> Result<(EventSignup, Vec<ShiftConflict>), AppError> {
+    let (signup, conflicts) =
+        create_shift_signup(shift_id, SignupOwner::User(user_id), notes).await?;
+
+    // the signup is already saved, a confirmation that can't be sent shouldn't fail it
+    if let Err(error) = send_signup_confirmation(user_id, &signup).await {
+        tracing::warn!(
+            "failed to send signup confirmation for signup {}: {}",
+            signup.id,
+            error
+        );
+    }
+
+    Ok((signup, conflicts))
+}
+
+// account holders manage their signups from the app rather than a signed link, and
+// can turn the email off like any other notification. events with confirmations off
+// send nothing
+async fn send_signup_confirmation(user_id: i32, signup: &EventSignup) -> Result<(), AppError> {
+    let shift = get_event_shift(signup.event_shift_id).await?;
+    let event = get_event(shift.event_id).await?;
+
+    if !event.communication_confirmation {
+        return Ok(());
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let recipients = filter_notification_recipients(
+        connection,
+        &[user_id],
+        Some(event.organization_id),
+        NotificationType::SignupConfirmed,
+        NotificationChannel::Email,
+    )
+    .await?;
+
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let user: User = get_user_by_id(user_id).await?;
+
+    let signup_status = match signup.get_status() {
+        SignupStatus::Waitlisted => "You're on the waitlist for",
+        _ => "You're signed up for",
+    };
+    let shift_date = format_shift_start(&shift);
+    let manage_url = get_app_link("/events")?;
+
+    send_email_in_background(render_email(
+        user.email,
+        EmailTemplate::SignupConfirmation,
+        &[
+            ("first_name", &user.first_name),
+            ("signup_status", signup_status),
+            ("event_name", &event.name),
+            ("shift_date", &shift_date),
+            ("manage_url", &manage_url),
+            ("contact_name", &event.contact_name),
+        ],
+    )?);
+
+    Ok(())
+}
+
+// overlapping signups are returned as a warning, or rejected when the hosting
+// organization blocks conflicts
+pub(crate) async fn create_shift_signup(
+    shift_id: i32,
+    owner: SignupOwner,
+    notes: Option<String>,
+) -> Result<(EventSignup, Vec<ShiftConflict>), AppError> {
+    if let Some(ref notes) = notes {
+        validate_max_length("notes", notes, MAX_EVENT_TEXT_LENGTH)?;
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (signup, conflicts, event_id) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let shift = lock_event_shift(connection, shift_id).await?;
+
+                let event: Event = events::table
+                    .find(shift.event_id)
+                    .first(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                // shared with archive_organization's row lock so no signup lands
+                // after the organization is archived
+                let organization: Organization = organizations::table
+                    .find(event.organization_id)
+                    .for_share()
+                    .first(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                if !event.is_published() || event.is_cancelled() || organization.is_archived() {
+                    return Err(AppError::validation(
+                        "event",
+                        "This event is not accepting signups",
+                    ));
+                }
+                if shift.end_time <= Utc::now() {
+                    return Err(AppError::validation(
+                        "shift",
+                        "This shift has already ended",
+                    ));
+                }
+
+                let status = status_for_open_seats(count_open_seats(connection, &shift).await?);
+
+                let mut existing_query = event_signups::table
+                    .filter(event_signups::event_shift_id.eq(shift_id))
+                    .into_boxed();
+                existing_query = match owner {
+                    SignupOwner::User(user_id) => {
+                        existing_query.filter(event_signups::user_id.eq(user_id))
+                    }
+                    SignupOwner::Contact(contact_id) => {
+                        existing_query.filter(event_signups::contact_id.eq(contact_id))
+                    }
+                };
+
+                let existing: Option<EventSignup> = existing_query
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?;
+
+                let conflicts = find_shift_conflicts(connection, owner, &shift).await?;
+
+                if !conflicts.is_empty()
+                    && ShiftConflictPolicy::from_str(&organization.shift_conflict_policy)
+                        == Some(ShiftConflictPolicy::Block)
+                {
+                    return Err(AppError::validation(
+                        "shift",
+                        "This shift overlaps with another shift you're already signed up for",
+                    ));
+                }
+
+                let signup = match existing {
+                    // once a guest signup has moved to an account only the account holder
+                    // can bring it back
+                    Some(signup)
+                        if matches!(owner, SignupOwner::Contact(_)) && signup.user_id.is_some() =>
+                    {
+                        Err(AppError::already_exists("Event signup"))
+                    }
+                    // a cancelled signup is reactivated at the back of the line
+                    Some(signup) if signup.get_status() == SignupStatus::Cancelled => {
+                        let update = EventSignupUpdate {
+                            status: Some(status.as_str().to_string()),
+                            notes: Some(notes),
+                            signed_up_at: Some(Utc::now()),
+                            checked_in_at: Some(None),
+                            cancelled_at: Some(None),
+                        };
+
+                        diesel::update(event_signups::table.find(signup.id))
+                            .set(&update)
+                            .get_result(connection)
+                            .await
+                            .map_err(postgres_error)
+                    }
+                    Some(_) => Err(AppError::already_exists("Event signup")),
+                    None => {
+                        let mut new_signup = match owner {
+                            SignupOwner::User(user_id) => NewEventSignup::new(shift_id, user_id),
+                            SignupOwner::Contact(contact_id) => {
+                                NewEventSignup::new_for_contact(shift_id, contact_id)
+                            }
+                        }
+                        .set_status(status);
+                        if let Some(notes) = notes {
+                            new_signup = new_signup.set_notes(notes);
+                        }
+
+                        diesel::insert_into(event_signups::table)
+                            .values(&new_signup)
+                            .get_result(connection)
+                            .await
+                            .map_err(postgres_error)
+                    }
+                }?;
+
+                Ok((signup, conflicts, event.id))
+            })
+        })
+        .await?;
+
+    // public pages show the seats left
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok((signup, conflicts))
+}
+
+// returns the cancelled signup along with any signups promoted off the waitlist
+pub async fn cancel_event_signup(
+    signup_id: i32,
+) -> Result<(EventSignup, Vec<EventSignup>), AppError> {
+    let signup = get_event_signup(signup_id).await?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (cancelled, promoted, notifications, event_id) = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let shift = lock_event_shift(connection, signup.event_shift_id).await?;
+
+                // re-read under the shift lock in case the status changed concurrently
+                let signup: EventSignup = event_signups::table
+                    .find(signup.id)
+                    .first(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                match signup.get_status() {
+                    SignupStatus::SignedUp | SignupStatus::Waitlisted => {}
+                    SignupStatus::Cancelled => {
+                        return Err(AppError::validation(
+                            "signup",
+                            "Signup is already cancelled",
+                        ));
+                    }
+                    _ => {
+                        return Err(AppError::validation(
+                            "signup",
+                            "Cannot cancel a signup after check-in",
+                        ));
+                    }
+                }
+
+                let update = EventSignupUpdate {
+                    status: Some(SignupStatus::Cancelled.as_str().to_string()),
+                    cancelled_at: Some(Some(Utc::now())),
+                    ..Default::default()
+                };
+
+                let cancelled: EventSignup = diesel::update(event_signups::table.find(signup.id))
+                    .set(&update)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                let (promoted, notifications) =
+                    promote_waitlisted_signups(connection, &shift).await?;
+
+                Ok((cancelled, promoted, notifications, shift.event_id))
+            })
+        })
+        .await?;
+
+    publish_notifications(&notifications).await;
+    invalidate_redis_cached_public_event(event_id).await.ok();
+
+    Ok((cancelled, promoted))
+}
+
+// seated signups first, then the waitlist in promotion order
+pub async fn list_shift_signups(shift_id: i32) -> Result<Vec<EventSignup>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let signups: Vec<EventSignup> = event_signups::table
+        .filter(event_signups::event_shift_id.eq(shift_id))
+        .filter(event_signups::status.ne(SignupStatus::Cancelled.as_str()))
+        .order((event_signups::signed_up_at.asc(), event_signups::id.asc()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let (mut seated, waitlisted): (Vec<_>, Vec<_>) = signups
+        .into_iter()
+        .partition(|signup| signup.get_status() != SignupStatus::Waitlisted);
+    seated.extend(waitlisted);
+
+    Ok(seated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_seats_are_capacity_minus_seated() {
+        assert_eq!(open_seats(10, 0), 10);
+        assert_eq!(open_seats(10, 7), 3);
+        assert_eq!(open_seats(10, 10), 0);
+    }
+
+    #[test]
+    fn over_capacity_shift_has_no_open_seats() {
+        assert_eq!(open_seats(5, 8), 0);
+    }
+
+    #[test]
+    fn signup_is_seated_while_seats_are_open() {
+        assert!(status_for_open_seats(Some(1)) == SignupStatus::SignedUp);
+        assert!(status_for_open_seats(Some(open_seats(3, 2))) == SignupStatus::SignedUp);
+    }
+
+    #[test]
+    fn signup_is_waitlisted_once_the_shift_is_full() {
+        assert!(status_for_open_seats(Some(0)) == SignupStatus::Waitlisted);
+        assert!(status_for_open_seats(Some(open_seats(3, 3))) == SignupStatus::Waitlisted);
+        assert!(status_for_open_seats(Some(open_seats(3, 4))) == SignupStatus::Waitlisted);
+    }
+
+    #[test]
+    fn shift_without_capacity_never_waitlists() {
+        assert!(status_for_open_seats(None) == SignupStatus::SignedUp);
+    }
+}
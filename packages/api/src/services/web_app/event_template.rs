@@ -0,0 +1,370 @@
+use crate::error::{postgres_error, AppError};
+use crate::interfaces::{EventShiftRequest, EventTemplateShift};
+use crate::models::{
+    Event, EventShift, EventTemplate, EventTemplateUpdate, NewEvent, NewEventTemplate,
+};
+use crate::postgres::get_postgres_connection;
+use crate::schema::event_templates;
+use crate::services::validate_required_string;
+use crate::services::web_app::{
+    batch_get_event_shifts, create_event, local_shift_times, validate_timezone,
+    MAX_SERIES_DURATION_MINUTES,
+};
+use chrono::{Days, NaiveDate};
+use chrono_tz::Tz;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+pub const MAX_EVENT_TEMPLATE_NAME_LENGTH: usize = 255;
+
+// captures each shift's local wall-clock start and length relative to the first
+// shift's day, so the layout can be replayed on any date
+pub fn shift_pattern_from_shifts(shifts: &[EventShift]) -> Vec<EventTemplateShift> {
+    let local_shifts: Vec<_> = shifts
+        .iter()
+        .map(|shift| {
+            let timezone: Tz = shift.timezone.parse().unwrap_or(Tz::UTC);
+            let local_start = shift.start_time.with_timezone(&timezone).naive_local();
+            let local_end = shift.end_time.with_timezone(&timezone).naive_local();
+
+            let mut duration_minutes = (local_end - local_start).num_minutes();
+            if duration_minutes <= 0 {
+                duration_minutes = (shift.end_time - shift.start_time).num_minutes();
+            }
+
+            (shift, local_start, duration_minutes as i32)
+        })
+        .collect();
+
+    let Some(first_date) = local_shifts
+        .iter()
+        .map(|(_, local_start, _)| local_start.date())
+        .min()
+    else {
+        return vec![];
+    };
+
+    let mut pattern: Vec<EventTemplateShift> = local_shifts
+        .into_iter()
+        .map(
+            |(shift, local_start, duration_minutes)| EventTemplateShift {
+                day_offset: (local_start.date() - first_date).num_days() as i32,
+                start_time_local: local_start.time(),
+                duration_minutes,
+                timezone: shift.timezone.clone(),
+                capacity: shift.capacity,
+                notes: shift.notes.clone(),
+            },
+        )
+        .collect();
+
+    pattern.sort_by_key(|shift| (shift.day_offset, shift.start_time_local));
+
+    pattern
+}
+
+pub fn shift_requests_from_pattern(
+    pattern: &[EventTemplateShift],
+    start_date: NaiveDate,
+) -> Result<Vec<EventShiftRequest>, AppError> {
+    pattern
+        .iter()
+        .map(|shift| {
+            let timezone = validate_timezone(&shift.timezone)?;
+
+            if shift.duration_minutes < 1 || shift.duration_minutes > MAX_SERIES_DURATION_MINUTES {
+                return Err(AppError::validation(
+                    "duration_minutes",
+                    format!(
+                        "Shift length must be between 1 and {} minutes",
+                        MAX_SERIES_DURATION_MINUTES
+                    ),
+                ));
+            }
+
+            let date = u64::try_from(shift.day_offset)
+                .ok()
+                .and_then(|day_offset| start_date.checked_add_days(Days::new(day_offset)))
+                .ok_or_else(|| AppError::validation("day_offset", "Invalid shift day"))?;
+
+            let (start_time, end_time) = local_shift_times(
+                timezone,
+                date.and_time(shift.start_time_local),
+                shift.duration_minutes,
+            );
+
+            Ok(EventShiftRequest {
+                start_time,
+                end_time,
+                timezone: shift.timezone.clone(),
+                capacity: shift.capacity,
+                notes: shift.notes.clone(),
+            })
+        })
+        .collect()
+}
+
+fn new_event_from_event(event: &Event, created_by: i32) -> NewEvent {
+    let mut new_event = NewEvent::new(
+        event.organization_id,
+        event.name.clone(),
+        event.get_event_type(),
+        event.contact_name.clone(),
+        created_by,
+    );
+
+    new_event.description = event.description.clone();
+    new_event.attendee_message = event.attendee_message.clone();
+    new_event.image_url = event.image_url.clone();
+    new_event.image_variants = event.image_variants.clone();
+    new_event.location_in_person = event.location_in_person.clone();
+    new_event.location_online = event.location_online.clone();
+    new_event.location_zip_code = event.location_zip_code.clone();
+    new_event.communication_bring_a_friend = event.communication_bring_a_friend;
+    new_event.communication_other_events = event.communication_other_events;
+    new_event.communication_confirmation = event.communication_confirmation;
+    new_event.communication_check_in = event.communication_check_in;
+    new_event.contact_email = event.contact_email.clone();
+    new_event.contact_phone = event.contact_phone.clone();
+    new_event.invite_groups = event.invite_groups.clone();
+
+    new_event
+}
+
+async fn load_shift_pattern(event_id: i32) -> Result<Vec<EventTemplateShift>, AppError> {
+    let mut shifts = batch_get_event_shifts(&[event_id]).await?;
+    let event_shifts = shifts.remove(&event_id).unwrap_or_default();
+
+    Ok(shift_pattern_from_shifts(&event_shifts))
+}
+
+// copies the event as a new draft with its shifts moved to start on the given date,
+// series shifts are copied as one-off shifts
+pub async fn duplicate_event(
+    event: &Event,
+    created_by: i32,
+    start_date: NaiveDate,
+    name: Option<String>,
+) -> Result<(Event, Vec<EventShift>), AppError> {
+    let pattern = load_shift_pattern(event.id).await?;
+    let shifts = shift_requests_from_pattern(&pattern, start_date)?;
+
+    let mut new_event = new_event_from_event(event, created_by);
+    if let Some(name) = name {
+        new_event.name = name;
+    }
+
+    create_event(new_event, shifts).await
+}
+
+async fn require_unique_template_name(
+    organization_id: i32,
+    name: &str,
+    template_id: Option<i32>,
+) -> Result<(), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let existing: Option<i32> = event_templates::table
+        .filter(event_templates::organization_id.eq(organization_id))
+        .filter(event_templates::name.eq(name))
+        .select(event_templates::id)
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?;
+
+    match existing {
+        Some(existing_id) if Some(existing_id) != template_id => {
+            Err(AppError::already_exists("Event template with this name"))
+        }
+        _ => Ok(()),
+    }
+}
+
+// saves the event's details and shift layout under a name for the organization
+pub async fn create_event_template(
+    event: &Event,
+    name: String,
+    created_by: i32,
+) -> Result<EventTemplate, AppError> {
+    let name = name.trim().to_string();
+    validate_required_string("name", &name, MAX_EVENT_TEMPLATE_NAME_LENGTH)?;
+    require_unique_template_name(event.organization_id, &name, None).await?;
+
+    let pattern = load_shift_pattern(event.id).await?;
+    let shifts = serde_json::to_value(&pattern)
+        .map_err(|error| AppError::InternalError(format!("JSON error: {}", error)))?;
+
+    let new_template = NewEventTemplate::from_event(name, event, shifts).set_created_by(created_by);
+
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::insert_into(event_templates::table)
+        .values(&new_template)
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn get_organization_event_template(
+    organization_id: i32,
+    template_id: i32,
+) -> Result<EventTemplate, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_templates::table
+        .find(template_id)
+        .filter(event_templates::organization_id.eq(organization_id))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Event template"))
+}
+
+pub async fn list_event_templates(organization_id: i32) -> Result<Vec<EventTemplate>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_templates::table
+        .filter(event_templates::organization_id.eq(organization_id))
+        .order((event_templates::name.asc(), event_templates::id.asc()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn rename_event_template(
+    template: &EventTemplate,
+    name: String,
+) -> Result<EventTemplate, AppError> {
+    let name = name.trim().to_string();
+    validate_required_string("name", &name, MAX_EVENT_TEMPLATE_NAME_LENGTH)?;
+    require_unique_template_name(template.organization_id, &name, Some(template.id)).await?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::update(event_templates::table.find(template.id))
+        .set(EventTemplateUpdate { name: Some(name) })
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn delete_event_template(template_id: i32) -> Result<(), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::delete(event_templates::table.find(template_id))
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(())
+}
+
+pub async fn create_event_from_template(
+    template: &EventTemplate,
+    created_by: i32,
+    start_date: NaiveDate,
+    name: Option<String>,
+) -> Result<(Event, Vec<EventShift>), AppError> {
+    let pattern: Vec<EventTemplateShift> = serde_json::from_value(template.shifts.clone())
+        .map_err(|error| AppError::InternalError(format!("Invalid template shifts: {}", error)))?;
+    let shifts = shift_requests_from_pattern(&pattern, start_date)?;
+
+    let mut new_event = template.to_new_event(created_by);
+    if let Some(name) = name {
+        new_event.name = name;
+    }
+
+    create_event(new_event, shifts).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+
+    const TIMEZONE: &str = "America/New_York";
+
+    fn utc(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn shift(id: i32, start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> EventShift {
+        EventShift {
+            id,
+            event_id: 1,
+            start_time,
+            end_time,
+            timezone: TIMEZONE.to_string(),
+            capacity: Some(10),
+            notes: Some(format!("Shift {}", id)),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            series_id: None,
+            series_occurrence_date: None,
+            revision: 0,
+        }
+    }
+
+    #[test]
+    fn pattern_keeps_local_start_times_and_day_spacing() {
+        // eastern standard time, UTC-5, given out of order
+        let shifts = [
+            shift(2, utc(3, 7, 13, 0), utc(3, 7, 15, 30)),
+            shift(1, utc(3, 6, 14, 0), utc(3, 6, 17, 0)),
+        ];
+
+        let pattern = shift_pattern_from_shifts(&shifts);
+
+        assert_eq!(pattern.len(), 2);
+        assert_eq!(pattern[0].day_offset, 0);
+        assert_eq!(pattern[0].start_time_local, time(9, 0));
+        assert_eq!(pattern[0].duration_minutes, 180);
+        assert_eq!(pattern[0].notes.as_deref(), Some("Shift 1"));
+        assert_eq!(pattern[1].day_offset, 1);
+        assert_eq!(pattern[1].start_time_local, time(8, 0));
+        assert_eq!(pattern[1].duration_minutes, 150);
+        assert_eq!(pattern[1].capacity, Some(10));
+    }
+
+    #[test]
+    fn pattern_measures_length_on_the_wall_clock() {
+        // midnight to 5am local on the night clocks spring forward, four real hours
+        let shifts = [shift(1, utc(3, 8, 5, 0), utc(3, 8, 9, 0))];
+
+        let pattern = shift_pattern_from_shifts(&shifts);
+
+        assert_eq!(pattern[0].start_time_local, time(0, 0));
+        assert_eq!(pattern[0].duration_minutes, 300);
+    }
+
+    #[test]
+    fn no_shifts_make_an_empty_pattern() {
+        assert!(shift_pattern_from_shifts(&[]).is_empty());
+    }
+
+    #[test]
+    fn pattern_replays_at_the_same_local_times_on_a_new_date() {
+        let shifts = [
+            shift(1, utc(3, 6, 14, 0), utc(3, 6, 17, 0)),
+            shift(2, utc(3, 7, 13, 0), utc(3, 7, 15, 30)),
+        ];
+        let pattern = shift_pattern_from_shifts(&shifts);
+
+        // eastern daylight time by then, UTC-4
+        let start_date = NaiveDate::from_ymd_opt(2026, 4, 10).unwrap();
+        let requests = shift_requests_from_pattern(&pattern, start_date).unwrap();
+
+        assert_eq!(requests[0].start_time, utc(4, 10, 13, 0));
+        assert_eq!(requests[0].end_time, utc(4, 10, 16, 0));
+        assert_eq!(requests[1].start_time, utc(4, 11, 12, 0));
+        assert_eq!(requests[1].end_time, utc(4, 11, 14, 30));
+        assert_eq!(requests[1].timezone, TIMEZONE);
+    }
+}
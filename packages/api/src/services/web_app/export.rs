@@ -0,0 +1,418 @@
+use crate::enums::{ExportFormat, ExportType};
+use crate::error::{postgres_error, AppError};
+use crate::minio::{get_minio_export_url, minio_delete_export, minio_upload_export};
+use crate::models::{Event, EventShift, EventSignup, Export, NewExport};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{contacts, event_shifts, event_signups, events, exports, users};
+use crate::services::web_app::get_organization_event;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel_async::RunQueryDsl;
+use rust_xlsxwriter::{Format, Workbook};
+use slug::slugify;
+use uuid::Uuid;
+
+// exports are built in memory so the size is capped
+pub const MAX_EXPORT_ROWS: i64 = 50_000;
+pub const MAX_EXPORT_RANGE_DAYS: i64 = 366;
+
+const ATTENDEE_EXPORT_HEADERS: [&str; 14] = [
+    "Event",
+    "Shift Start",
+    "Shift End",
+    "Timezone",
+    "Signup ID",
+    "First Name",
+    "Last Name",
+    "Email",
+    "Phone",
+    "Status",
+    "Signed Up At",
+    "Checked In At",
+    "Cancelled At",
+    "Notes",
+];
+
+const EXPORT_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+// the person is either a registered user or a guest contact, users take precedence
+type AttendeeRow = (
+    EventSignup,
+    EventShift,
+    String,
+    Option<(String, String, String, Option<String>)>,
+    Option<(String, String, String, Option<String>)>,
+);
+
+fn format_local(time: DateTime<Utc>, timezone: Tz) -> String {
+    time.with_timezone(&timezone)
+        .format(EXPORT_DATETIME_FORMAT)
+        .to_string()
+}
+
+fn format_optional_local(time: Option<DateTime<Utc>>, timezone: Tz) -> String {
+    time.map(|time| format_local(time, timezone))
+        .unwrap_or_default()
+}
+
+// every timestamp is written in the shift's own timezone, matching what volunteers saw
+fn attendee_record(row: AttendeeRow) -> Vec<String> {
+    let (signup, shift, event_name, user, contact) = row;
+    let timezone: Tz = shift.timezone.parse().unwrap_or(Tz::UTC);
+    let (first_name, last_name, email, phone_number) = user.or(contact).unwrap_or_default();
+
+    vec![
+        event_name,
+        format_local(shift.start_time, timezone),
+        format_local(shift.end_time, timezone),
+        shift.timezone.clone(),
+        signup.id.to_string(),
+        first_name,
+        last_name,
+        email,
+        phone_number.unwrap_or_default(),
+        signup.get_status().display_name().to_string(),
+        format_local(signup.signed_up_at, timezone),
+        format_optional_local(signup.checked_in_at, timezone),
+        format_optional_local(signup.cancelled_at, timezone),
+        signup.notes.unwrap_or_default(),
+    ]
+}
+
+// spreadsheet apps evaluate cells starting with these characters as formulas
+fn escape_csv_cell(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn encode_csv(headers: &[&str], records: &[Vec<String>]) -> Result<Vec<u8>, AppError> {
+    let csv_error = |error: csv::Error| AppError::InternalError(format!("CSV error: {}", error));
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    writer.write_record(headers).map_err(csv_error)?;
+    for record in records {
+        writer
+            .write_record(record.iter().map(|value| escape_csv_cell(value)))
+            .map_err(csv_error)?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|error| AppError::InternalError(format!("CSV error: {}", error)))
+}
+
+fn encode_xlsx(headers: &[&str], records: &[Vec<String>]) -> Result<Vec<u8>, AppError> {
+    let xlsx_error = |error: rust_xlsxwriter::XlsxError| {
+        AppError::InternalError(format!("XLSX error: {}", error))
+    };
+
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Attendees").map_err(xlsx_error)?;
+
+    for (column, header) in headers.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, column as u16, *header, &header_format)
+            .map_err(xlsx_error)?;
+    }
+
+    for (index, record) in records.iter().enumerate() {
+        let row = index as u32 + 1;
+        for (column, value) in record.iter().enumerate() {
+            if value.is_empty() {
+                continue;
+            }
+            worksheet
+                .write_string(row, column as u16, value)
+                .map_err(xlsx_error)?;
+        }
+    }
+
+    worksheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+    worksheet
+        .autofilter(0, 0, records.len() as u32, headers.len() as u16 - 1)
+        .map_err(xlsx_error)?;
+    worksheet.autofit();
+
+    workbook.save_to_buffer().map_err(xlsx_error)
+}
+
+fn encode_export(
+    format: ExportFormat,
+    headers: &[&str],
+    records: &[Vec<String>],
+) -> Result<Vec<u8>, AppError> {
+    match format {
+        ExportFormat::Csv => encode_csv(headers, records),
+        ExportFormat::Xlsx => encode_xlsx(headers, records),
+    }
+}
+
+fn validate_export_range(
+    event_id: Option<i32>,
+    range_start: Option<DateTime<Utc>>,
+    range_end: Option<DateTime<Utc>>,
+) -> Result<(), AppError> {
+    match (range_start, range_end) {
+        (Some(range_start), Some(range_end)) => {
+            if range_end <= range_start {
+                return Err(AppError::validation(
+                    "range_end",
+                    "End date must be after start date",
+                ));
+            }
+            if range_end - range_start > Duration::days(MAX_EXPORT_RANGE_DAYS) {
+                return Err(AppError::validation(
+                    "range_end",
+                    format!(
+                        "Date range cannot be longer than {} days",
+                        MAX_EXPORT_RANGE_DAYS
+                    ),
+                ));
+            }
+            Ok(())
+        }
+        // an event export may be open-ended on either side
+        _ if event_id.is_some() => Ok(()),
+        _ => Err(AppError::validation(
+            "range_start",
+            "Choose an event or a start and end date",
+        )),
+    }
+}
+
+async fn load_attendee_rows(
+    organization_id: i32,
+    event_id: Option<i32>,
+    range_start: Option<DateTime<Utc>>,
+    range_end: Option<DateTime<Utc>>,
+) -> Result<Vec<AttendeeRow>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let mut query = event_signups::table
+        .inner_join(event_shifts::table.inner_join(events::table))
+        .left_join(users::table)
+        .left_join(contacts::table)
+        .filter(events::organization_id.eq(organization_id))
+        .select((
+            EventSignup::as_select(),
+            EventShift::as_select(),
+            events::name,
+            (
+                users::first_name,
+                users::last_name,
+                users::email,
+                users::phone_number,
+            )
+                .nullable(),
+            (
+                contacts::first_name,
+                contacts::last_name,
+                contacts::email,
+                contacts::phone_number,
+            )
+                .nullable(),
+        ))
+        .into_boxed();
+
+    if let Some(event_id) = event_id {
+        query = query.filter(events::id.eq(event_id));
+    }
+    if let Some(range_start) = range_start {
+        query = query.filter(event_shifts::start_time.ge(range_start));
+    }
+    if let Some(range_end) = range_end {
+        query = query.filter(event_shifts::start_time.lt(range_end));
+    }
+
+    let rows: Vec<AttendeeRow> = query
+        .order((
+            event_shifts::start_time.asc(),
+            events::name.asc(),
+            sql::<Text>("COALESCE(users.last_name, contacts.last_name)").asc(),
+            sql::<Text>("COALESCE(users.first_name, contacts.first_name)").asc(),
+            event_signups::id.asc(),
+        ))
+        .limit(MAX_EXPORT_ROWS + 1)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    if rows.len() as i64 > MAX_EXPORT_ROWS {
+        return Err(AppError::validation(
+            "range_start",
+            format!(
+                "Export has more than {} rows, narrow the date range",
+                MAX_EXPORT_ROWS
+            ),
+        ));
+    }
+
+    Ok(rows)
+}
+
+fn attendee_export_filename(
+    event: Option<&Event>,
+    range_start: Option<DateTime<Utc>>,
+    range_end: Option<DateTime<Utc>>,
+    format: ExportFormat,
+) -> String {
+    let mut parts = vec![match event {
+        Some(event) => format!("{}-attendees", slugify(&event.name)),
+        None => "attendees".to_string(),
+    }];
+
+    if let Some(range_start) = range_start {
+        parts.push(range_start.format("%Y-%m-%d").to_string());
+    }
+    if let Some(range_end) = range_end {
+        parts.push(range_end.format("%Y-%m-%d").to_string());
+    }
+    if range_start.is_none() && range_end.is_none() {
+        parts.push(Utc::now().format("%Y-%m-%d").to_string());
+    }
+
+    format!("{}.{}", parts.join("-"), format.extension())
+}
+
+// builds the attendee list for one event and/or a shift date range, uploads it to
+// the exports bucket and records it for the organization
+pub async fn create_attendee_export(
+    organization_id: i32,
+    requested_by: i32,
+    format: ExportFormat,
+    event_id: Option<i32>,
+    range_start: Option<DateTime<Utc>>,
+    range_end: Option<DateTime<Utc>>,
+) -> Result<Export, AppError> {
+    validate_export_range(event_id, range_start, range_end)?;
+
+    let event = match event_id {
+        Some(event_id) => Some(get_organization_event(organization_id, event_id).await?),
+        None => None,
+    };
+
+    let rows = load_attendee_rows(organization_id, event_id, range_start, range_end).await?;
+    let row_count = rows.len() as i32;
+    let records: Vec<Vec<String>> = rows.into_iter().map(attendee_record).collect();
+
+    // building a spreadsheet for a large organization is CPU bound, so it stays off
+    // the async workers
+    let data = tokio::task::spawn_blocking(move || {
+        encode_export(format, &ATTENDEE_EXPORT_HEADERS, &records)
+    })
+    .await
+    .map_err(|error| AppError::InternalError(format!("Export encoding error: {}", error)))??;
+    let file_size_bytes = data.len() as i64;
+
+    let filename = attendee_export_filename(event.as_ref(), range_start, range_end, format);
+    let storage_key = format!("{}/{}/{}", organization_id, Uuid::new_v4(), filename);
+
+    minio_upload_export(&storage_key, data, format.mime_type()).await?;
+
+    let mut new_export = NewExport::new(
+        organization_id,
+        ExportType::EventAttendees,
+        format,
+        filename,
+        file_size_bytes,
+        row_count,
+        storage_key.clone(),
+    )
+    .set_requested_by(requested_by)
+    .set_range(range_start, range_end);
+    if let Some(event_id) = event_id {
+        new_export = new_export.set_event(event_id);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let result = diesel::insert_into(exports::table)
+        .values(&new_export)
+        .get_result(connection)
+        .await
+        .map_err(postgres_error);
+
+    // don't leave an unreferenced file behind if the record couldn't be saved
+    if result.is_err() {
+        if let Err(error) = minio_delete_export(&storage_key).await {
+            tracing::warn!(
+                "Failed to remove orphaned export {}: {}",
+                storage_key,
+                error
+            );
+        }
+    }
+
+    result
+}
+
+pub async fn get_organization_export(
+    organization_id: i32,
+    export_id: i32,
+) -> Result<Export, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    exports::table
+        .find(export_id)
+        .filter(exports::organization_id.eq(organization_id))
+        .first(connection)
+        .await
+        .optional()
+        .map_err(postgres_error)?
+        .ok_or_else(|| AppError::not_found("Export"))
+}
+
+pub async fn list_exports(
+    organization_id: i32,
+    page: i64,
+    per_page: i64,
+) -> Result<(Vec<Export>, i64), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let total: i64 = exports::table
+        .filter(exports::organization_id.eq(organization_id))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let offset = (page - 1) * per_page;
+
+    let exports_list: Vec<Export> = exports::table
+        .filter(exports::organization_id.eq(organization_id))
+        .order((exports::created_at.desc(), exports::id.desc()))
+        .limit(per_page)
+        .offset(offset)
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok((exports_list, total))
+}
+
+pub async fn get_export_download_url(export: &Export) -> Result<String, AppError> {
+    get_minio_export_url(&export.storage_key).await
+}
+
+pub async fn delete_export(export: Export) -> Result<(), AppError> {
+    minio_delete_export(&export.storage_key).await?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    diesel::delete(exports::table.find(export.id))
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(())
+}
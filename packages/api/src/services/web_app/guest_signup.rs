@@ -0,0 +1,145 @@
+use crate::enums::{EventVisibility, SignupStatus};
+use crate::error::{postgres_error, AppError};
+use crate::models::{Contact, Event, EventShift, EventSignup};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{event_shifts, event_signups, events, organizations};
+use crate::services::web_app::{
+    cancel_event_signup, create_shift_signup, format_shift_start, get_event_shift,
+    get_event_signup, SignupOwner,
+};
+use crate::services::{
+    get_app_link, render_email, send_email_in_background, upsert_contact, EmailTemplate,
+};
+use crate::signing::{sign_token, verify_signed_token};
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+const GUEST_SIGNUP_TOKEN_PURPOSE: &str = "guest_signup";
+pub const GUEST_SIGNUP_TOKEN_EXPIRY_DAYS: i64 = 90;
+
+// the manage link carries the contact and an expiry, it is only ever sent to the
+// guest's email so anyone holding it can see and cancel that contact's signups
+pub fn create_guest_signup_token(contact_id: i32) -> Result<String, AppError> {
+    let expires_at = Utc::now() + Duration::days(GUEST_SIGNUP_TOKEN_EXPIRY_DAYS);
+
+    sign_token(
+        GUEST_SIGNUP_TOKEN_PURPOSE,
+        &format!("{}:{}", contact_id, expires_at.timestamp()),
+    )
+}
+
+// returns the contact id for a valid, unexpired manage link
+pub fn verify_guest_signup_token(token: &str) -> Result<i32, AppError> {
+    let invalid = || AppError::validation("token", "Invalid or expired link");
+
+    let payload = verify_signed_token(GUEST_SIGNUP_TOKEN_PURPOSE, token)?.ok_or_else(invalid)?;
+    let (contact_id, expires_at) = payload.split_once(':').ok_or_else(invalid)?;
+
+    let expires_at: i64 = expires_at.parse().map_err(|_| invalid())?;
+    if expires_at <= Utc::now().timestamp() {
+        return Err(invalid());
+    }
+
+    contact_id.parse().map_err(|_| invalid())
+}
+
+// guests can only RSVP to events open to the public while their organization is active
+pub async fn sign_up_guest_for_shift(
+    shift_id: i32,
+    email: String,
+    first_name: String,
+    last_name: String,
+    phone_number: Option<String>,
+    zip_code: Option<String>,
+    notes: Option<String>,
+) -> Result<(Contact, EventSignup, EventShift, Event), AppError> {
+    let shift = get_event_shift(shift_id).await?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let (event, archived_at): (Event, Option<DateTime<Utc>>) = events::table
+        .inner_join(organizations::table)
+        .filter(events::id.eq(shift.event_id))
+        .select((Event::as_select(), organizations::archived_at))
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    if event.get_visibility() != EventVisibility::Public || archived_at.is_some() {
+        return Err(AppError::not_found("Event shift"));
+    }
+
+    let contact = upsert_contact(email, first_name, last_name, phone_number, zip_code).await?;
+
+    let (signup, _conflicts) =
+        create_shift_signup(shift_id, SignupOwner::Contact(contact.id), notes).await?;
+
+    Ok((contact, signup, shift, event))
+}
+
+// the confirmation is the only place the guest gets their manage link, so it's sent
+// whatever the event's communication settings are
+pub fn send_guest_signup_confirmation(
+    contact: &Contact,
+    signup: &EventSignup,
+    shift: &EventShift,
+    event: &Event,
+    token: &str,
+) -> Result<(), AppError> {
+    let signup_status = match signup.get_status() {
+        SignupStatus::Waitlisted => "You're on the waitlist for",
+        _ => "You're signed up for",
+    };
+    let shift_date = format_shift_start(shift);
+    let manage_url = get_app_link(&format!("/rsvp/{}", token))?;
+
+    send_email_in_background(render_email(
+        contact.email.clone(),
+        EmailTemplate::SignupConfirmation,
+        &[
+            ("first_name", &contact.first_name),
+            ("signup_status", signup_status),
+            ("event_name", &event.name),
+            ("shift_date", &shift_date),
+            ("manage_url", &manage_url),
+            ("contact_name", &event.contact_name),
+        ],
+    )?);
+
+    Ok(())
+}
+
+// every signup made with the contact's email, soonest shift first. signups handed
+// over to an account are managed from the app, so the link stops reaching them
+pub async fn list_guest_signups(
+    contact_id: i32,
+) -> Result<Vec<(EventSignup, EventShift, Event)>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    event_signups::table
+        .inner_join(event_shifts::table.inner_join(events::table))
+        .filter(event_signups::contact_id.eq(contact_id))
+        .filter(event_signups::user_id.is_null())
+        .select((
+            EventSignup::as_select(),
+            EventShift::as_select(),
+            Event::as_select(),
+        ))
+        .order((event_shifts::start_time.asc(), event_signups::id.asc()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn cancel_guest_signup(contact_id: i32, signup_id: i32) -> Result<EventSignup, AppError> {
+    let signup = get_event_signup(signup_id).await?;
+
+    if signup.contact_id != Some(contact_id) || signup.user_id.is_some() {
+        return Err(AppError::not_found("Event signup"));
+    }
+
+    let (cancelled, _promoted) = cancel_event_signup(signup.id).await?;
+
+    Ok(cancelled)
+}
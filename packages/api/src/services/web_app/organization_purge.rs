@@ -0,0 +1,183 @@
+use crate::enums::AuditAction;
+use crate::error::{postgres_error, AppError};
+use crate::minio::{minio_delete_event_images, minio_delete_export};
+use crate::models::{NewAuditLog, OrganizationUpdate};
+use crate::postgres::get_postgres_connection;
+use crate::redis::invalidate_redis_cached_public_event;
+use crate::schema::{
+    event_co_hosts, event_templates, events, exports, notifications, organizations,
+    volunteer_hour_adjustments,
+};
+use crate::services::record_audit_log;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde_json::json;
+use std::time::Duration;
+
+const ORGANIZATION_PURGE_POLL_INTERVAL_SECONDS: u64 = 60 * 60;
+
+// deletes the events (their shifts and signups go with them), templates, exports,
+// hour adjustments and notifications of one archived organization whose grace period is over, claimed with SKIP LOCKED so
+// servers don't purge the same organization twice, returns whether one was purged
+pub async fn purge_next_archived_organization() -> Result<bool, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let purged = connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let organization_id: Option<i32> = organizations::table
+                    .filter(organizations::archived_at.is_not_null())
+                    .filter(organizations::purge_scheduled_at.le(Utc::now()))
+                    .filter(organizations::purged_at.is_null())
+                    .order(organizations::purge_scheduled_at.asc())
+                    .select(organizations::id)
+                    .for_update()
+                    .skip_locked()
+                    .first(connection)
+                    .await
+                    .optional()
+                    .map_err(postgres_error)?;
+
+                let Some(organization_id) = organization_id else {
+                    return Ok(None);
+                };
+
+                let deleted_events: Vec<(i32, Option<String>)> = diesel::delete(
+                    events::table.filter(events::organization_id.eq(organization_id)),
+                )
+                .returning((events::id, events::image_storage_prefix))
+                .get_results(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let deleted_co_hosts = diesel::delete(
+                    event_co_hosts::table
+                        .filter(event_co_hosts::organization_id.eq(organization_id)),
+                )
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let deleted_templates = diesel::delete(
+                    event_templates::table
+                        .filter(event_templates::organization_id.eq(organization_id)),
+                )
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let deleted_exports: Vec<(i32, String)> = diesel::delete(
+                    exports::table.filter(exports::organization_id.eq(organization_id)),
+                )
+                .returning((exports::id, exports::storage_key))
+                .get_results(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let deleted_hour_adjustments = diesel::delete(
+                    volunteer_hour_adjustments::table
+                        .filter(volunteer_hour_adjustments::organization_id.eq(organization_id)),
+                )
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                let deleted_notifications = diesel::delete(
+                    notifications::table.filter(notifications::organization_id.eq(organization_id)),
+                )
+                .execute(connection)
+                .await
+                .map_err(postgres_error)?;
+
+                diesel::update(organizations::table.find(organization_id))
+                    .set(OrganizationUpdate {
+                        purged_at: Some(Some(Utc::now())),
+                        ..Default::default()
+                    })
+                    .execute(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                record_audit_log(
+                    connection,
+                    NewAuditLog::new(
+                        AuditAction::OrganizationPurged,
+                        json!({
+                            "events": deleted_events.len(),
+                            "co_hosts": deleted_co_hosts,
+                            "templates": deleted_templates,
+                            "exports": deleted_exports.len(),
+                            "hour_adjustments": deleted_hour_adjustments,
+                            "notifications": deleted_notifications,
+                        }),
+                    )
+                    .set_organization(organization_id),
+                )
+                .await?;
+
+                Ok(Some((organization_id, deleted_events, deleted_exports)))
+            })
+        })
+        .await?;
+
+    let Some((organization_id, deleted_events, deleted_exports)) = purged else {
+        return Ok(false);
+    };
+
+    // stored images, export files and cached pages are cleaned up once the rows are gone, a
+    // failure only leaves an orphaned object behind
+    for (event_id, image_storage_prefix) in deleted_events.iter() {
+        if let Some(prefix) = image_storage_prefix {
+            if let Err(error) = minio_delete_event_images(prefix).await {
+                tracing::warn!(
+                    "failed to delete images of purged event {}: {}",
+                    event_id,
+                    error
+                );
+            }
+        }
+        invalidate_redis_cached_public_event(*event_id).await.ok();
+    }
+    for (export_id, storage_key) in deleted_exports.iter() {
+        if let Err(error) = minio_delete_export(storage_key).await {
+            tracing::warn!(
+                "failed to delete file of purged export {}: {}",
+                export_id,
+                error
+            );
+        }
+    }
+
+    tracing::info!(
+        "purged archived organization {} ({} events)",
+        organization_id,
+        deleted_events.len()
+    );
+
+    Ok(true)
+}
+
+// runs for the life of the server, each tick purges every organization that's due
+pub fn spawn_organization_purge_worker() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            ORGANIZATION_PURGE_POLL_INTERVAL_SECONDS,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            loop {
+                match purge_next_archived_organization().await {
+                    Ok(true) => continue,
+                    Ok(false) => break,
+                    Err(error) => {
+                        tracing::warn!("failed to purge archived organization: {}", error);
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
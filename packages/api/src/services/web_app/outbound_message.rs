@@ -0,0 +1,426 @@
+use crate::enums::{MessageChannel, NotificationType, OutboundMessageStatus, SmsConsentAction};
+use crate::error::{postgres_error, AppError};
+use crate::http::one_click_unsubscribe_path;
+use crate::mailer::send_email;
+use crate::models::{NewOutboundMessage, OutboundMessage, OutboundMessageUpdate};
+use crate::postgres::get_postgres_connection;
+use crate::schema::outbound_messages;
+use crate::services::web_app::{load_sms_consent_statuses, normalize_phone_number};
+use crate::services::{
+    get_app_link, get_current_organization_plan, lock_organization_for_quota,
+    notify_organization_admins, render_plain_email, start_of_current_month,
+};
+use crate::sms::{send_sms, OutgoingSms};
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::{BTreeSet, HashMap};
+
+pub const MAX_OUTBOUND_MESSAGE_ATTEMPTS: i32 = 5;
+const OUTBOUND_MESSAGE_BATCH_SIZE: i64 = 50;
+const OUTBOUND_MESSAGE_POLL_INTERVAL_SECONDS: u64 = 10;
+const OUTBOUND_MESSAGE_CLAIM_LEASE_MINUTES: i64 = 10;
+const OUTBOUND_MESSAGE_RETRY_BASE_SECONDS: i64 = 60;
+
+// messages queued on an organization's behalf this calendar month, whatever became
+// of them. messages failed for being over quota were never attempted and don't count
+pub async fn count_monthly_outbound_messages(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    channel: MessageChannel,
+) -> Result<i64, AppError> {
+    outbound_messages::table
+        .filter(outbound_messages::organization_id.eq(organization_id))
+        .filter(outbound_messages::channel.eq(channel.as_str()))
+        .filter(outbound_messages::created_at.ge(start_of_current_month()))
+        .filter(
+            outbound_messages::status
+                .ne(OutboundMessageStatus::Failed.as_str())
+                .or(outbound_messages::attempts.gt(0)),
+        )
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// None when the organization's plan has no limit for the channel
+async fn get_remaining_message_quota(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    channel: MessageChannel,
+) -> Result<Option<i64>, AppError> {
+    let entitlements = get_current_organization_plan(connection, organization_id)
+        .await?
+        .entitlements();
+    let quota = match channel {
+        MessageChannel::Email => entitlements.monthly_email_quota,
+        MessageChannel::Sms => entitlements.monthly_sms_quota,
+    };
+
+    let Some(quota) = quota else {
+        return Ok(None);
+    };
+
+    let sent = count_monthly_outbound_messages(connection, organization_id, channel).await?;
+
+    Ok(Some((quota - sent).max(0)))
+}
+
+// the organization's admins hear about it the first time a message is held back each
+// month, later ones only show up as failed messages
+async fn warn_message_quota_reached(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    channel: MessageChannel,
+) -> Result<(), AppError> {
+    let already_warned: i64 = outbound_messages::table
+        .filter(outbound_messages::organization_id.eq(organization_id))
+        .filter(outbound_messages::channel.eq(channel.as_str()))
+        .filter(outbound_messages::created_at.ge(start_of_current_month()))
+        .filter(outbound_messages::status.eq(OutboundMessageStatus::Failed.as_str()))
+        .filter(outbound_messages::attempts.eq(0))
+        .count()
+        .get_result(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    if already_warned > 0 {
+        return Ok(());
+    }
+
+    let plan = get_current_organization_plan(connection, organization_id).await?;
+    let entitlements = plan.entitlements();
+    let quota = match channel {
+        MessageChannel::Email => entitlements.monthly_email_quota,
+        MessageChannel::Sms => entitlements.monthly_sms_quota,
+    };
+    let message = match quota {
+        Some(0) => format!(
+            "The {} plan doesn't include {} messages, so they aren't being sent. Upgrade your plan to send them.",
+            plan.display_name(),
+            channel.display_name()
+        ),
+        _ => format!(
+            "Your organization has used the {} {} messages included in the {} plan this month. Further messages won't be sent until next month unless you upgrade.",
+            quota.unwrap_or_default(),
+            channel.display_name(),
+            plan.display_name()
+        ),
+    };
+
+    // admins see it on their next page load, the caller's transaction hasn't committed
+    notify_organization_admins(
+        connection,
+        organization_id,
+        None,
+        NotificationType::Info,
+        format!("{} limit reached", channel.display_name()),
+        message,
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// messages past an organization's monthly quota are stored as failed rather than
+// failing the change that queued them, messages that aren't sent for an organization
+// have no quota. each organization's row stays locked until the caller commits so
+// concurrent enqueues can't both use the last of a quota
+async fn apply_message_quotas(
+    connection: &mut AsyncPgConnection,
+    messages: Vec<NewOutboundMessage>,
+) -> Result<Vec<NewOutboundMessage>, AppError> {
+    // locked in id order so two enqueues spanning the same organizations can't deadlock
+    let organization_ids: BTreeSet<i32> = messages
+        .iter()
+        .filter_map(|message| message.organization_id)
+        .collect();
+    for organization_id in organization_ids {
+        lock_organization_for_quota(connection, organization_id).await?;
+    }
+
+    let mut remaining: HashMap<(i32, &'static str), Option<i64>> = HashMap::new();
+    let mut warned: BTreeSet<(i32, &'static str)> = BTreeSet::new();
+    let mut checked = Vec::with_capacity(messages.len());
+
+    for message in messages {
+        let Some(organization_id) = message.organization_id else {
+            checked.push(message);
+            continue;
+        };
+        let channel = MessageChannel::from_str(&message.channel).unwrap_or(MessageChannel::Email);
+        let key = (organization_id, channel.as_str());
+
+        let quota = match remaining.get(&key) {
+            Some(quota) => *quota,
+            None => get_remaining_message_quota(connection, organization_id, channel).await?,
+        };
+
+        if quota == Some(0) {
+            if warned.insert(key) {
+                warn_message_quota_reached(connection, organization_id, channel).await?;
+            }
+            checked.push(
+                message.set_failed(format!("Monthly {} quota reached", channel.display_name())),
+            );
+        } else {
+            checked.push(message);
+        }
+        remaining.insert(key, quota.map(|left| (left - 1).max(0)));
+    }
+
+    Ok(checked)
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct EnqueuedMessageCounts {
+    pub queued: usize,
+    // stored as failed without being sent
+    pub over_quota: usize,
+}
+
+// queued messages are picked up by the mail and SMS senders, enqueueing inside the
+// caller's transaction means nothing is sent for a change that rolled back
+pub async fn enqueue_outbound_messages(
+    connection: &mut AsyncPgConnection,
+    messages: Vec<NewOutboundMessage>,
+) -> Result<EnqueuedMessageCounts, AppError> {
+    let messages = apply_message_quotas(connection, messages).await?;
+
+    if messages.is_empty() {
+        return Ok(EnqueuedMessageCounts::default());
+    }
+
+    let over_quota = messages
+        .iter()
+        .filter(|message| message.status.is_some())
+        .count();
+
+    diesel::insert_into(outbound_messages::table)
+        .values(&messages)
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(EnqueuedMessageCounts {
+        queued: messages.len() - over_quota,
+        over_quota,
+    })
+}
+
+async fn send_outbound_email(message: &OutboundMessage) -> Result<(), AppError> {
+    let unsubscribe_url = message
+        .unsubscribe_token
+        .as_ref()
+        .map(|token| get_app_link(&format!("/unsubscribe/{}", token)))
+        .transpose()?;
+    let list_unsubscribe_url = message
+        .unsubscribe_token
+        .as_ref()
+        .map(|token| get_app_link(&one_click_unsubscribe_path(token)))
+        .transpose()?;
+
+    let mut email = render_plain_email(
+        message.recipient.clone(),
+        message.subject.clone().unwrap_or_default(),
+        message.body.clone(),
+        unsubscribe_url,
+    )?;
+    email.list_unsubscribe_url = list_unsubscribe_url;
+
+    send_email(email).await
+}
+
+async fn send_outbound_sms(message: &OutboundMessage, to: String) -> Result<(), AppError> {
+    send_sms(OutgoingSms {
+        to,
+        body: message.body.clone(),
+    })
+    .await
+}
+
+// claims a batch for this server by marking it sending, SKIP LOCKED lets every server
+// claim at once without taking the same rows. a claim older than the lease belongs to
+// a server that stopped mid-send, so it's picked up again and may be sent twice
+async fn claim_outbound_messages(
+    channel: MessageChannel,
+) -> Result<Vec<OutboundMessage>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                let now = Utc::now();
+                let lease_expired_at =
+                    now - Duration::minutes(OUTBOUND_MESSAGE_CLAIM_LEASE_MINUTES);
+
+                let message_ids: Vec<i32> = outbound_messages::table
+                    .filter(outbound_messages::channel.eq(channel.as_str()))
+                    .filter(
+                        outbound_messages::status
+                            .eq(OutboundMessageStatus::Pending.as_str())
+                            .and(outbound_messages::next_attempt_at.le(now))
+                            .or(outbound_messages::status
+                                .eq(OutboundMessageStatus::Sending.as_str())
+                                .and(outbound_messages::claimed_at.lt(lease_expired_at))),
+                    )
+                    .order(outbound_messages::id.asc())
+                    .limit(OUTBOUND_MESSAGE_BATCH_SIZE)
+                    .select(outbound_messages::id)
+                    .for_update()
+                    .skip_locked()
+                    .load(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                if message_ids.is_empty() {
+                    return Ok(vec![]);
+                }
+
+                // the attempt is counted at claim time so a send that never reports
+                // back still uses one up
+                diesel::update(
+                    outbound_messages::table.filter(outbound_messages::id.eq_any(&message_ids)),
+                )
+                .set((
+                    outbound_messages::status.eq(OutboundMessageStatus::Sending.as_str()),
+                    outbound_messages::claimed_at.eq(now),
+                    outbound_messages::attempts.eq(outbound_messages::attempts + 1),
+                ))
+                .get_results(connection)
+                .await
+                .map_err(postgres_error)
+            })
+        })
+        .await
+}
+
+// 1, 2, 4, 8 minutes and so on between attempts
+fn next_attempt_at(attempts: i32) -> DateTime<Utc> {
+    let delay = OUTBOUND_MESSAGE_RETRY_BASE_SECONDS << (attempts - 1).clamp(0, 10);
+
+    Utc::now() + Duration::seconds(delay)
+}
+
+// the batch is claimed and committed before anything is sent so no row lock is held
+// while waiting on the mail or SMS provider, each result is then recorded on its own.
+// failures go back to pending with a growing delay until they run out of attempts,
+// returns how many messages were processed
+pub async fn send_pending_outbound_messages(channel: MessageChannel) -> Result<usize, AppError> {
+    let messages = claim_outbound_messages(channel).await?;
+
+    if messages.is_empty() {
+        return Ok(0);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    // consent is checked at send time so an opt-out stops texts already queued
+    let phone_numbers: Vec<String> = match channel {
+        MessageChannel::Sms => messages
+            .iter()
+            .filter_map(|message| normalize_phone_number(&message.recipient))
+            .collect(),
+        MessageChannel::Email => vec![],
+    };
+    let sms_consents = load_sms_consent_statuses(connection, &phone_numbers).await?;
+
+    for message in messages.iter() {
+        // None when the recipient can't be texted
+        let result = match channel {
+            MessageChannel::Email => Some(send_outbound_email(message).await),
+            MessageChannel::Sms => {
+                let phone_number =
+                    normalize_phone_number(&message.recipient).filter(|phone_number| {
+                        sms_consents.get(phone_number) == Some(&SmsConsentAction::OptIn)
+                    });
+                match phone_number {
+                    Some(phone_number) => Some(send_outbound_sms(message, phone_number).await),
+                    None => None,
+                }
+            }
+        };
+
+        let update = match result {
+            Some(Ok(())) => OutboundMessageUpdate {
+                status: Some(OutboundMessageStatus::Sent.as_str().to_string()),
+                last_error: Some(None),
+                sent_at: Some(Some(Utc::now())),
+                claimed_at: Some(None),
+                ..Default::default()
+            },
+            Some(Err(error)) if message.attempts >= MAX_OUTBOUND_MESSAGE_ATTEMPTS => {
+                OutboundMessageUpdate {
+                    status: Some(OutboundMessageStatus::Failed.as_str().to_string()),
+                    last_error: Some(Some(error.to_string())),
+                    claimed_at: Some(None),
+                    ..Default::default()
+                }
+            }
+            Some(Err(error)) => OutboundMessageUpdate {
+                status: Some(OutboundMessageStatus::Pending.as_str().to_string()),
+                last_error: Some(Some(error.to_string())),
+                next_attempt_at: Some(next_attempt_at(message.attempts)),
+                claimed_at: Some(None),
+                ..Default::default()
+            },
+            None => OutboundMessageUpdate {
+                status: Some(OutboundMessageStatus::Blocked.as_str().to_string()),
+                last_error: Some(Some(
+                    "Recipient has not opted in to text messages".to_string(),
+                )),
+                claimed_at: Some(None),
+                ..Default::default()
+            },
+        };
+
+        // only the claim this server holds is settled, a row reclaimed after the
+        // lease ran out is left to whoever claimed it since
+        diesel::update(
+            outbound_messages::table
+                .find(message.id)
+                .filter(outbound_messages::status.eq(OutboundMessageStatus::Sending.as_str()))
+                .filter(outbound_messages::claimed_at.eq(message.claimed_at)),
+        )
+        .set(update)
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+    }
+
+    Ok(messages.len())
+}
+
+// runs for the life of the server, each tick keeps draining until the queue is empty
+pub fn spawn_outbound_message_worker() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            OUTBOUND_MESSAGE_POLL_INTERVAL_SECONDS,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            for channel in [MessageChannel::Email, MessageChannel::Sms] {
+                loop {
+                    match send_pending_outbound_messages(channel).await {
+                        Ok(processed) if processed as i64 == OUTBOUND_MESSAGE_BATCH_SIZE => {
+                            continue
+                        }
+                        Ok(_) => break,
+                        Err(error) => {
+                            tracing::warn!(
+                                "failed to send outbound {} messages: {}",
+                                channel,
+                                error
+                            );
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
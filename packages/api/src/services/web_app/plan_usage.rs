@@ -0,0 +1,36 @@
+use crate::enums::MessageChannel;
+use crate::error::AppError;
+use crate::postgres::get_postgres_connection;
+use crate::services::web_app::count_monthly_outbound_messages;
+use crate::services::{count_used_member_seats, get_used_storage_bytes};
+
+pub struct OrganizationPlanUsage {
+    pub member_seats: i64,
+    pub emails_this_month: i64,
+    pub sms_this_month: i64,
+    pub storage_bytes: i64,
+}
+
+// how much of each plan limit the organization is using right now
+pub async fn get_organization_plan_usage(
+    organization_id: i32,
+) -> Result<OrganizationPlanUsage, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    Ok(OrganizationPlanUsage {
+        member_seats: count_used_member_seats(connection, organization_id).await?,
+        emails_this_month: count_monthly_outbound_messages(
+            connection,
+            organization_id,
+            MessageChannel::Email,
+        )
+        .await?,
+        sms_this_month: count_monthly_outbound_messages(
+            connection,
+            organization_id,
+            MessageChannel::Sms,
+        )
+        .await?,
+        storage_bytes: get_used_storage_bytes(connection, organization_id).await?,
+    })
+}
@@ -0,0 +1,307 @@
+use crate::enums::SignupStatus;
+use crate::error::{postgres_error, AppError};
+use crate::interfaces::{RosterShiftConflictResponse, ShiftConflictResponse};
+use crate::models::{Event, EventShift, EventSignup};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{contacts, event_shifts, event_signups, events, users};
+use crate::services::web_app::SignupOwner;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sql_types::Integer;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::HashMap;
+
+// signups that still hold the volunteer's time
+const ACTIVE_SIGNUP_STATUSES: [SignupStatus; 3] = [
+    SignupStatus::SignedUp,
+    SignupStatus::Waitlisted,
+    SignupStatus::CheckedIn,
+];
+
+// first key of the advisory lock taken on a volunteer, the second is their id
+const USER_SIGNUP_LOCK_KEY: i32 = 1;
+const CONTACT_SIGNUP_LOCK_KEY: i32 = 2;
+
+pub type ShiftConflict = (EventSignup, EventShift, Event);
+
+// the signup and its shift, then the event's id, organization and name
+type ScheduledSignupRow = (EventSignup, EventShift, i32, i32, String);
+
+// the signup, its shift, the event's id and name, then the user's or the guest
+// contact's name
+type RosterSignupRow = (
+    EventSignup,
+    EventShift,
+    i32,
+    String,
+    Option<(String, String)>,
+    Option<(String, String)>,
+);
+
+fn active_statuses() -> Vec<&'static str> {
+    ACTIVE_SIGNUP_STATUSES
+        .iter()
+        .map(|status| status.as_str())
+        .collect()
+}
+
+fn overlaps(a: &EventShift, b: &EventShift) -> bool {
+    a.start_time < b.end_time && b.start_time < a.end_time
+}
+
+// details of another organization's event stay private
+fn scheduled_conflict_response(
+    row: &ScheduledSignupRow,
+    organization_id: i32,
+) -> ShiftConflictResponse {
+    let (signup, shift, event_id, event_organization_id, event_name) = row;
+    let same_organization = *event_organization_id == organization_id;
+
+    ShiftConflictResponse {
+        event_shift_id: shift.id,
+        start_time: shift.start_time,
+        end_time: shift.end_time,
+        timezone: shift.timezone.clone(),
+        status: signup.get_status(),
+        event_id: same_organization.then_some(*event_id),
+        event_name: same_organization.then(|| event_name.clone()),
+    }
+}
+
+// a signup is tied to a person by account first, guests without one by contact
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+enum Attendee {
+    User(i32),
+    Contact(i32),
+}
+
+impl Attendee {
+    fn from_signup(signup: &EventSignup) -> Option<Self> {
+        signup
+            .user_id
+            .map(Attendee::User)
+            .or(signup.contact_id.map(Attendee::Contact))
+    }
+}
+
+// the owner's other active signups, across every organization, whose shifts overlap
+// this one. the volunteer is locked until the caller's transaction ends, so two
+// signups for overlapping shifts at once can't both miss each other
+pub(crate) async fn find_shift_conflicts(
+    connection: &mut AsyncPgConnection,
+    owner: SignupOwner,
+    shift: &EventShift,
+) -> Result<Vec<ShiftConflict>, AppError> {
+    let (lock_key, owner_id) = match owner {
+        SignupOwner::User(user_id) => (USER_SIGNUP_LOCK_KEY, user_id),
+        SignupOwner::Contact(contact_id) => (CONTACT_SIGNUP_LOCK_KEY, contact_id),
+    };
+    diesel::sql_query("SELECT pg_advisory_xact_lock($1, $2)")
+        .bind::<Integer, _>(lock_key)
+        .bind::<Integer, _>(owner_id)
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let mut query = event_signups::table
+        .inner_join(event_shifts::table.inner_join(events::table))
+        .filter(event_signups::event_shift_id.ne(shift.id))
+        .filter(event_signups::status.eq_any(active_statuses()))
+        .filter(event_shifts::start_time.lt(shift.end_time))
+        .filter(event_shifts::end_time.gt(shift.start_time))
+        .filter(events::cancelled_at.is_null())
+        .select((
+            EventSignup::as_select(),
+            EventShift::as_select(),
+            Event::as_select(),
+        ))
+        .order(event_shifts::start_time.asc())
+        .into_boxed();
+
+    query = match owner {
+        SignupOwner::User(user_id) => query.filter(event_signups::user_id.eq(user_id)),
+        SignupOwner::Contact(contact_id) => query.filter(event_signups::contact_id.eq(contact_id)),
+    };
+
+    query.load(connection).await.map_err(postgres_error)
+}
+
+// every upcoming signup on the organization's roster that overlaps another of the
+// same volunteer's signups, shifts at other organizations are shown without details
+pub async fn list_roster_shift_conflicts(
+    organization_id: i32,
+) -> Result<Vec<RosterShiftConflictResponse>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let now = Utc::now();
+
+    let roster: Vec<RosterSignupRow> = event_signups::table
+        .inner_join(event_shifts::table.inner_join(events::table))
+        .left_join(users::table)
+        .left_join(contacts::table)
+        .filter(events::organization_id.eq(organization_id))
+        .filter(events::cancelled_at.is_null())
+        .filter(event_shifts::end_time.gt(now))
+        .filter(event_signups::status.eq_any(active_statuses()))
+        .select((
+            EventSignup::as_select(),
+            EventShift::as_select(),
+            events::id,
+            events::name,
+            (users::first_name, users::last_name).nullable(),
+            (contacts::first_name, contacts::last_name).nullable(),
+        ))
+        .order((event_shifts::start_time.asc(), event_signups::id.asc()))
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let mut user_ids: Vec<i32> = vec![];
+    let mut contact_ids: Vec<i32> = vec![];
+    for (signup, ..) in roster.iter() {
+        match Attendee::from_signup(signup) {
+            Some(Attendee::User(user_id)) => user_ids.push(user_id),
+            Some(Attendee::Contact(contact_id)) => contact_ids.push(contact_id),
+            None => {}
+        }
+    }
+
+    if user_ids.is_empty() && contact_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let scheduled: Vec<ScheduledSignupRow> = event_signups::table
+        .inner_join(event_shifts::table.inner_join(events::table))
+        .filter(
+            event_signups::user_id
+                .eq_any(&user_ids)
+                .or(event_signups::user_id
+                    .is_null()
+                    .and(event_signups::contact_id.eq_any(&contact_ids))),
+        )
+        .filter(events::cancelled_at.is_null())
+        .filter(event_shifts::end_time.gt(now))
+        .filter(event_signups::status.eq_any(active_statuses()))
+        .select((
+            EventSignup::as_select(),
+            EventShift::as_select(),
+            events::id,
+            events::organization_id,
+            events::name,
+        ))
+        .order(event_shifts::start_time.asc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let mut schedules: HashMap<Attendee, Vec<&ScheduledSignupRow>> = HashMap::new();
+    for row in scheduled.iter() {
+        if let Some(attendee) = Attendee::from_signup(&row.0) {
+            schedules.entry(attendee).or_default().push(row);
+        }
+    }
+
+    let mut report = vec![];
+
+    for (signup, shift, event_id, event_name, user, contact) in roster {
+        let Some(attendee) = Attendee::from_signup(&signup) else {
+            continue;
+        };
+
+        let conflicts: Vec<ShiftConflictResponse> = schedules
+            .get(&attendee)
+            .into_iter()
+            .flatten()
+            .filter(|(other_signup, other_shift, ..)| {
+                other_signup.id != signup.id && overlaps(&shift, other_shift)
+            })
+            .map(|row| scheduled_conflict_response(row, organization_id))
+            .collect();
+
+        if conflicts.is_empty() {
+            continue;
+        }
+
+        let (first_name, last_name) = user.or(contact).unwrap_or_default();
+
+        report.push(RosterShiftConflictResponse {
+            signup_id: signup.id,
+            user_id: signup.user_id,
+            contact_id: signup.contact_id,
+            first_name,
+            last_name,
+            event_id,
+            event_name,
+            event_shift_id: shift.id,
+            start_time: shift.start_time,
+            end_time: shift.end_time,
+            conflicts,
+        });
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone};
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 2, hour, minute, 0).unwrap()
+    }
+
+    fn shift(start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> EventShift {
+        EventShift {
+            id: 1,
+            event_id: 1,
+            start_time,
+            end_time,
+            timezone: "UTC".to_string(),
+            capacity: None,
+            notes: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            series_id: None,
+            series_occurrence_date: None,
+            revision: 0,
+        }
+    }
+
+    #[test]
+    fn partially_overlapping_shifts_conflict_either_way_round() {
+        let morning = shift(at(9, 0), at(12, 0));
+        let midday = shift(at(11, 30), at(14, 0));
+
+        assert!(overlaps(&morning, &midday));
+        assert!(overlaps(&midday, &morning));
+    }
+
+    #[test]
+    fn shift_inside_another_conflicts() {
+        let day = shift(at(8, 0), at(18, 0));
+        let lunch = shift(at(12, 0), at(13, 0));
+
+        assert!(overlaps(&day, &lunch));
+        assert!(overlaps(&lunch, &day));
+        assert!(overlaps(&day, &day));
+    }
+
+    #[test]
+    fn back_to_back_shifts_do_not_conflict() {
+        let morning = shift(at(9, 0), at(12, 0));
+        let afternoon = shift(at(12, 0), at(15, 0));
+
+        assert!(!overlaps(&morning, &afternoon));
+        assert!(!overlaps(&afternoon, &morning));
+    }
+
+    #[test]
+    fn separate_shifts_do_not_conflict() {
+        let morning = shift(at(9, 0), at(11, 0));
+        let evening = shift(at(18, 0), at(20, 0));
+
+        assert!(!overlaps(&morning, &evening));
+        assert!(!overlaps(&evening, &morning));
+    }
+}
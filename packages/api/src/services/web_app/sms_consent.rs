@@ -0,0 +1,420 @@
+use crate::enums::{SmsConsentAction, SmsConsentSource, SMS_CONSENT_DISCLOSURE};
+use crate::error::{postgres_error, AppError};
+use crate::models::{Contact, NewSmsConsent, SmsConsent, User, UserUpdate};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{contacts, sms_consents, users};
+use crate::services::get_app_link;
+use crate::sms::get_sms_program_name;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use ipnetwork::IpNetwork;
+use std::collections::HashMap;
+
+// the industry standard keywords, matched against the whole message
+const SMS_STOP_KEYWORDS: [&str; 8] = [
+    "STOP",
+    "STOPALL",
+    "UNSUBSCRIBE",
+    "CANCEL",
+    "END",
+    "QUIT",
+    "REVOKE",
+    "OPTOUT",
+];
+const SMS_START_KEYWORDS: [&str; 4] = ["START", "UNSTOP", "YES", "OPTIN"];
+const SMS_HELP_KEYWORDS: [&str; 2] = ["HELP", "INFO"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SmsKeyword {
+    Stop,
+    Start,
+    Help,
+}
+
+// surrounding whitespace and punctuation are ignored so "Stop." still counts
+fn parse_sms_keyword(body: &str) -> Option<SmsKeyword> {
+    let keyword = body
+        .trim()
+        .trim_matches(|character: char| !character.is_alphanumeric())
+        .to_uppercase();
+
+    if SMS_STOP_KEYWORDS.contains(&keyword.as_str()) {
+        Some(SmsKeyword::Stop)
+    } else if SMS_START_KEYWORDS.contains(&keyword.as_str()) {
+        Some(SmsKeyword::Start)
+    } else if SMS_HELP_KEYWORDS.contains(&keyword.as_str()) {
+        Some(SmsKeyword::Help)
+    } else {
+        None
+    }
+}
+
+// numbers are stored in E.164 so the same phone typed two ways is one ledger entry,
+// ten digit numbers are assumed to be US numbers
+pub fn normalize_phone_number(phone_number: &str) -> Option<String> {
+    let digits: String = phone_number
+        .chars()
+        .filter(|character| character.is_ascii_digit())
+        .collect();
+
+    if phone_number.trim_start().starts_with('+') {
+        return (8..=15)
+            .contains(&digits.len())
+            .then(|| format!("+{}", digits));
+    }
+
+    match digits.len() {
+        10 => Some(format!("+1{}", digits)),
+        11 if digits.starts_with('1') => Some(format!("+{}", digits)),
+        _ => None,
+    }
+}
+
+pub fn parse_sms_phone_number(phone_number: &str) -> Result<String, AppError> {
+    normalize_phone_number(phone_number)
+        .ok_or_else(|| AppError::validation("phone_number", "Enter a valid mobile number"))
+}
+
+// silently ignore invalid IPs
+fn parse_ip_address(ip_address: Option<String>) -> Option<IpNetwork> {
+    ip_address
+        .and_then(|ip_address| ip_address.parse::<std::net::IpAddr>().ok())
+        .map(IpNetwork::from)
+}
+
+async fn record_sms_consent(
+    connection: &mut AsyncPgConnection,
+    consent: NewSmsConsent,
+) -> Result<SmsConsent, AppError> {
+    diesel::insert_into(sms_consents::table)
+        .values(&consent)
+        .get_result::<SmsConsent>(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// once a number has opted out only the number itself can opt back in by texting a
+// START keyword, opt-ins typed into a form after that stay in the ledger but don't
+// count, so nobody can resubscribe someone else's phone
+async fn load_latest_sms_consents(
+    connection: &mut AsyncPgConnection,
+    phone_numbers: &[String],
+) -> Result<Vec<SmsConsent>, AppError> {
+    let opted_out: Vec<String> = sms_consents::table
+        .filter(sms_consents::phone_number.eq_any(phone_numbers))
+        .filter(sms_consents::action.eq(SmsConsentAction::OptOut.as_str()))
+        .select(sms_consents::phone_number)
+        .distinct()
+        .load(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    sms_consents::table
+        .filter(sms_consents::phone_number.eq_any(phone_numbers))
+        .filter(
+            sms_consents::phone_number
+                .ne_all(opted_out)
+                .or(sms_consents::action.eq(SmsConsentAction::OptOut.as_str()))
+                .or(sms_consents::source.eq(SmsConsentSource::Keyword.as_str())),
+        )
+        .distinct_on(sms_consents::phone_number)
+        .order((
+            sms_consents::phone_number,
+            sms_consents::created_at.desc(),
+            sms_consents::id.desc(),
+        ))
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// where each number's consent stands, a number without an entry has never opted in
+pub async fn load_sms_consent_statuses(
+    connection: &mut AsyncPgConnection,
+    phone_numbers: &[String],
+) -> Result<HashMap<String, SmsConsentAction>, AppError> {
+    if phone_numbers.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let latest = load_latest_sms_consents(connection, phone_numbers).await?;
+
+    Ok(latest
+        .into_iter()
+        .map(|consent| {
+            let action = consent.get_action();
+            (consent.phone_number, action)
+        })
+        .collect())
+}
+
+async fn get_latest_sms_consent(
+    connection: &mut AsyncPgConnection,
+    phone_number: &str,
+) -> Result<Option<SmsConsent>, AppError> {
+    let latest = load_latest_sms_consents(connection, &[phone_number.to_string()]).await?;
+
+    Ok(latest.into_iter().next())
+}
+
+// the number on the account and where its consent currently stands
+pub async fn get_user_sms_consent(
+    user_id: i32,
+) -> Result<(Option<String>, Option<SmsConsent>), AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let phone_number: Option<String> = users::table
+        .find(user_id)
+        .select(users::phone_number)
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let consent = match phone_number.as_deref().and_then(normalize_phone_number) {
+        Some(normalized) => get_latest_sms_consent(connection, &normalized).await?,
+        None => None,
+    };
+
+    Ok((phone_number, consent))
+}
+
+// opting in from account settings also makes the number the account's phone number,
+// a number that texted STOP has to text START instead
+pub async fn opt_in_user_to_sms(
+    user_id: i32,
+    phone_number: String,
+    ip_address: Option<String>,
+) -> Result<SmsConsent, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let latest = get_latest_sms_consent(connection, &phone_number).await?;
+    if latest.is_some_and(|consent| consent.get_action() == SmsConsentAction::OptOut) {
+        return Err(AppError::validation(
+            "phone_number",
+            "This number unsubscribed from text messages, text START to resubscribe",
+        ));
+    }
+
+    let mut consent = NewSmsConsent::new(
+        phone_number.clone(),
+        SmsConsentAction::OptIn,
+        SmsConsentSource::Account,
+    )
+    .set_user(user_id)
+    .set_consent_text(SMS_CONSENT_DISCLOSURE.to_string());
+    if let Some(ip_address) = parse_ip_address(ip_address) {
+        consent = consent.set_ip_address(ip_address);
+    }
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                diesel::update(users::table.find(user_id))
+                    .set(UserUpdate {
+                        phone_number: Some(Some(phone_number)),
+                        ..Default::default()
+                    })
+                    .get_result::<User>(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                record_sms_consent(connection, consent).await
+            })
+        })
+        .await
+}
+
+pub async fn opt_out_user_from_sms(
+    user_id: i32,
+    ip_address: Option<String>,
+) -> Result<SmsConsent, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let phone_number: Option<String> = users::table
+        .find(user_id)
+        .select(users::phone_number)
+        .first(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    let phone_number = phone_number
+        .as_deref()
+        .and_then(normalize_phone_number)
+        .ok_or_else(|| {
+            AppError::validation("phone_number", "There is no mobile number on your account")
+        })?;
+
+    let mut consent = NewSmsConsent::new(
+        phone_number,
+        SmsConsentAction::OptOut,
+        SmsConsentSource::Account,
+    )
+    .set_user(user_id);
+    if let Some(ip_address) = parse_ip_address(ip_address) {
+        consent = consent.set_ip_address(ip_address);
+    }
+
+    record_sms_consent(connection, consent).await
+}
+// ticking the box on a public RSVP form, recorded against the organization asking,
+// it doesn't undo a STOP from the number. guests are texted at the contact's number,
+// so the number they agreed to replaces whatever the contact had on file
+pub async fn opt_in_contact_to_sms(
+    contact_id: i32,
+    organization_id: i32,
+    phone_number: String,
+    ip_address: Option<String>,
+) -> Result<SmsConsent, AppError> {
+    let mut consent = NewSmsConsent::new(
+        phone_number.clone(),
+        SmsConsentAction::OptIn,
+        SmsConsentSource::GuestSignup,
+    )
+    .set_contact(contact_id)
+    .set_organization(organization_id)
+    .set_consent_text(SMS_CONSENT_DISCLOSURE.to_string());
+    if let Some(ip_address) = parse_ip_address(ip_address) {
+        consent = consent.set_ip_address(ip_address);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                diesel::update(contacts::table.find(contact_id))
+                    .set(contacts::phone_number.eq(Some(phone_number)))
+                    .get_result::<Contact>(connection)
+                    .await
+                    .map_err(postgres_error)?;
+
+                record_sms_consent(connection, consent).await
+            })
+        })
+        .await
+}
+
+// STOP and START change the number's consent, HELP only gets a reply, anything else
+// is left alone, returns the reply to text back if there is one
+pub async fn handle_inbound_sms(
+    from: &str,
+    body: &str,
+    provider_message_id: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let Some(phone_number) = normalize_phone_number(from) else {
+        tracing::warn!("ignoring text from unrecognized number {}", from);
+        return Ok(None);
+    };
+
+    let Some(keyword) = parse_sms_keyword(body) else {
+        tracing::info!("received text from {} with no keyword", phone_number);
+        return Ok(None);
+    };
+    let program_name = get_sms_program_name()?;
+
+    let (action, reply) = match keyword {
+        SmsKeyword::Stop => (
+            SmsConsentAction::OptOut,
+            format!(
+                "{}: You are unsubscribed and will not receive any more messages. Reply START to resubscribe.",
+                program_name
+            ),
+        ),
+        SmsKeyword::Start => (
+            SmsConsentAction::OptIn,
+            format!(
+                "{}: You are resubscribed to text messages. Msg frequency varies. Msg & data rates may apply. Reply HELP for help, STOP to cancel.",
+                program_name
+            ),
+        ),
+        SmsKeyword::Help => {
+            let preferences_url = get_app_link("/account/notifications")?;
+            return Ok(Some(format!(
+                "{}: Manage your text preferences at {}. Msg frequency varies. Msg & data rates may apply. Reply STOP to cancel.",
+                program_name, preferences_url
+            )));
+        }
+    };
+
+    let mut consent = NewSmsConsent::new(phone_number, action, SmsConsentSource::Keyword)
+        .set_consent_text(body.to_string());
+    if let Some(provider_message_id) = provider_message_id {
+        consent = consent.set_provider_message_id(provider_message_id);
+    }
+
+    let connection = &mut get_postgres_connection().await?;
+
+    record_sms_consent(connection, consent).await?;
+
+    Ok(Some(reply))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_keywords_match_regardless_of_case_and_punctuation() {
+        for body in [
+            "STOP",
+            "stop",
+            " Stop. ",
+            "unsubscribe",
+            "OptOut!",
+            "stopall",
+        ] {
+            assert_eq!(parse_sms_keyword(body), Some(SmsKeyword::Stop), "{}", body);
+        }
+    }
+
+    #[test]
+    fn start_and_help_keywords_match() {
+        for body in ["START", "unstop", "Yes", "optin"] {
+            assert_eq!(parse_sms_keyword(body), Some(SmsKeyword::Start), "{}", body);
+        }
+        for body in ["HELP", "help?", "Info"] {
+            assert_eq!(parse_sms_keyword(body), Some(SmsKeyword::Help), "{}", body);
+        }
+    }
+
+    #[test]
+    fn keywords_must_be_the_whole_message() {
+        for body in ["please stop", "STOP texting me", "stopping", "", "?!"] {
+            assert_eq!(parse_sms_keyword(body), None, "{}", body);
+        }
+    }
+
+    #[test]
+    fn us_numbers_are_normalized_to_e164() {
+        assert_eq!(
+            normalize_phone_number("(555) 123-4567"),
+            Some("+15551234567".to_string())
+        );
+        assert_eq!(
+            normalize_phone_number("1 555 123 4567"),
+            Some("+15551234567".to_string())
+        );
+        assert_eq!(
+            normalize_phone_number("+1 (555) 123-4567"),
+            Some("+15551234567".to_string())
+        );
+    }
+
+    #[test]
+    fn international_numbers_keep_their_country_code() {
+        assert_eq!(
+            normalize_phone_number("+44 20 7946 0958"),
+            Some("+442079460958".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_numbers_are_rejected() {
+        assert_eq!(normalize_phone_number("123-4567"), None);
+        assert_eq!(normalize_phone_number("2 555 123 4567"), None);
+        assert_eq!(normalize_phone_number("+1234567"), None);
+        assert_eq!(normalize_phone_number("+1234567890123456"), None);
+        assert_eq!(normalize_phone_number(""), None);
+        assert!(parse_sms_phone_number("not a number").is_err());
+    }
+}
@@ -0,0 +1,364 @@
+use crate::enums::SignupStatus;
+use crate::error::{postgres_error, AppError};
+use crate::interfaces::{
+    OrganizationVolunteerHoursResponse, UserVolunteerHoursResponse, VolunteerHoursResponse,
+};
+use crate::models::{NewVolunteerHourAdjustment, Organization, User, VolunteerHourAdjustment};
+use crate::postgres::get_postgres_connection;
+use crate::schema::{
+    event_shifts, event_signups, events, organization_members, organizations, users,
+    volunteer_hour_adjustments,
+};
+use crate::services::validate_required_string;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer, Nullable, Text, Timestamptz};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::HashMap;
+
+pub const MAX_VOLUNTEER_HOUR_REASON_LENGTH: usize = 1_000;
+// a single adjustment larger than this is almost certainly a typo
+pub const MAX_VOLUNTEER_HOUR_ADJUSTMENT_MINUTES: i32 = 1_000 * 60;
+pub const DEFAULT_VOLUNTEER_HOURS_LEADERBOARD_SIZE: i64 = 25;
+pub const MAX_VOLUNTEER_HOURS_LEADERBOARD_SIZE: i64 = 100;
+
+#[derive(QueryableByName)]
+struct HoursTally {
+    #[diesel(sql_type = Integer)]
+    organization_id: i32,
+    #[diesel(sql_type = Integer)]
+    user_id: i32,
+    #[diesel(sql_type = BigInt)]
+    shift_count: i64,
+    #[diesel(sql_type = BigInt)]
+    checked_in_minutes: i64,
+    #[diesel(sql_type = BigInt)]
+    adjustment_minutes: i64,
+}
+
+impl HoursTally {
+    fn total_minutes(&self) -> i64 {
+        self.checked_in_minutes + self.adjustment_minutes
+    }
+}
+
+// checked in shifts and adjustments are summed per organization and volunteer in the
+// database, a leaderboard limit also drops anyone without hours and keeps only the top
+// of the board, most hours first
+const TALLY_VOLUNTEER_HOURS_QUERY: &str = "
+    WITH checked_in AS (
+        SELECT events.organization_id, event_signups.user_id,
+            COUNT(*) AS shift_count,
+            SUM(GREATEST(FLOOR(EXTRACT(EPOCH FROM event_shifts.end_time - event_shifts.start_time) / 60), 0))::BIGINT AS minutes
+        FROM event_signups
+        INNER JOIN event_shifts ON event_shifts.id = event_signups.event_shift_id
+        INNER JOIN events ON events.id = event_shifts.event_id
+        WHERE event_signups.status = $1
+            AND event_signups.user_id IS NOT NULL
+            AND ($2::INTEGER IS NULL OR events.organization_id = $2)
+            AND ($3::INTEGER IS NULL OR event_signups.user_id = $3)
+            AND ($4::TIMESTAMPTZ IS NULL OR event_shifts.start_time >= $4)
+            AND ($5::TIMESTAMPTZ IS NULL OR event_shifts.start_time < $5)
+        GROUP BY events.organization_id, event_signups.user_id
+    ), adjusted AS (
+        SELECT organization_id, user_id, SUM(minutes)::BIGINT AS minutes
+        FROM volunteer_hour_adjustments
+        WHERE ($2::INTEGER IS NULL OR organization_id = $2)
+            AND ($3::INTEGER IS NULL OR user_id = $3)
+            AND ($4::TIMESTAMPTZ IS NULL OR created_at >= $4)
+            AND ($5::TIMESTAMPTZ IS NULL OR created_at < $5)
+        GROUP BY organization_id, user_id
+    )
+    SELECT COALESCE(checked_in.organization_id, adjusted.organization_id) AS organization_id,
+        COALESCE(checked_in.user_id, adjusted.user_id) AS user_id,
+        COALESCE(checked_in.shift_count, 0) AS shift_count,
+        COALESCE(checked_in.minutes, 0) AS checked_in_minutes,
+        COALESCE(adjusted.minutes, 0) AS adjustment_minutes
+    FROM checked_in
+    FULL OUTER JOIN adjusted ON adjusted.organization_id = checked_in.organization_id
+        AND adjusted.user_id = checked_in.user_id
+    INNER JOIN users ON users.id = COALESCE(checked_in.user_id, adjusted.user_id)
+    WHERE $6::BIGINT IS NULL
+        OR COALESCE(checked_in.minutes, 0) + COALESCE(adjusted.minutes, 0) > 0
+    ORDER BY COALESCE(checked_in.minutes, 0) + COALESCE(adjusted.minutes, 0) DESC,
+        users.last_name ASC, users.first_name ASC, users.id ASC
+    LIMIT $6
+";
+
+// hours only come from registered users, guests are counted once they create an
+// account and their signups are attached
+async fn tally_volunteer_hours(
+    connection: &mut AsyncPgConnection,
+    organization_id: Option<i32>,
+    user_id: Option<i32>,
+    range_start: Option<DateTime<Utc>>,
+    range_end: Option<DateTime<Utc>>,
+    leaderboard_limit: Option<i64>,
+) -> Result<Vec<HoursTally>, AppError> {
+    diesel::sql_query(TALLY_VOLUNTEER_HOURS_QUERY)
+        .bind::<Text, _>(SignupStatus::CheckedIn.as_str())
+        .bind::<Nullable<Integer>, _>(organization_id)
+        .bind::<Nullable<Integer>, _>(user_id)
+        .bind::<Nullable<Timestamptz>, _>(range_start)
+        .bind::<Nullable<Timestamptz>, _>(range_end)
+        .bind::<Nullable<BigInt>, _>(leaderboard_limit)
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+pub async fn get_volunteer_hours_leaderboard(
+    organization_id: i32,
+    range_start: Option<DateTime<Utc>>,
+    range_end: Option<DateTime<Utc>>,
+    limit: Option<i64>,
+) -> Result<Vec<VolunteerHoursResponse>, AppError> {
+    if let (Some(range_start), Some(range_end)) = (range_start, range_end) {
+        if range_end <= range_start {
+            return Err(AppError::validation(
+                "range_end",
+                "End date must be after start date",
+            ));
+        }
+    }
+
+    let limit = limit
+        .unwrap_or(DEFAULT_VOLUNTEER_HOURS_LEADERBOARD_SIZE)
+        .clamp(1, MAX_VOLUNTEER_HOURS_LEADERBOARD_SIZE);
+
+    let connection = &mut get_postgres_connection().await?;
+
+    let tallies = tally_volunteer_hours(
+        connection,
+        Some(organization_id),
+        None,
+        range_start,
+        range_end,
+        Some(limit),
+    )
+    .await?;
+
+    let user_ids: Vec<i32> = tallies.iter().map(|tally| tally.user_id).collect();
+
+    let mut volunteers: HashMap<i32, User> = if user_ids.is_empty() {
+        HashMap::new()
+    } else {
+        users::table
+            .filter(users::id.eq_any(&user_ids))
+            .load::<User>(connection)
+            .await
+            .map_err(postgres_error)?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect()
+    };
+
+    Ok(tallies
+        .into_iter()
+        .filter_map(|tally| {
+            let user = volunteers.remove(&tally.user_id)?;
+            Some(VolunteerHoursResponse {
+                user_id: user.id,
+                first_name: user.first_name,
+                last_name: user.last_name,
+                shift_count: tally.shift_count,
+                checked_in_minutes: tally.checked_in_minutes,
+                adjustment_minutes: tally.adjustment_minutes,
+                total_minutes: tally.total_minutes(),
+            })
+        })
+        .collect())
+}
+
+pub async fn get_user_volunteer_hours(
+    user_id: i32,
+) -> Result<UserVolunteerHoursResponse, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    let tallies: HashMap<i32, HoursTally> =
+        tally_volunteer_hours(connection, None, Some(user_id), None, None, None)
+            .await?
+            .into_iter()
+            .map(|tally| (tally.organization_id, tally))
+            .collect();
+
+    let organization_ids: Vec<i32> = tallies.keys().copied().collect();
+
+    let hosts: Vec<Organization> = if organization_ids.is_empty() {
+        vec![]
+    } else {
+        organizations::table
+            .filter(organizations::id.eq_any(&organization_ids))
+            .order(organizations::name.asc())
+            .load(connection)
+            .await
+            .map_err(postgres_error)?
+    };
+
+    let organizations: Vec<OrganizationVolunteerHoursResponse> = hosts
+        .into_iter()
+        .filter_map(|organization| {
+            let tally = tallies.get(&organization.id)?;
+            Some(OrganizationVolunteerHoursResponse {
+                organization_id: organization.id,
+                organization_name: organization.name,
+                organization_slug: organization.slug,
+                shift_count: tally.shift_count,
+                checked_in_minutes: tally.checked_in_minutes,
+                adjustment_minutes: tally.adjustment_minutes,
+                total_minutes: tally.total_minutes(),
+            })
+        })
+        .collect();
+
+    let total_minutes = organizations
+        .iter()
+        .map(|organization| organization.total_minutes)
+        .sum();
+
+    Ok(UserVolunteerHoursResponse {
+        organizations,
+        total_minutes,
+    })
+}
+
+pub async fn list_volunteer_hour_adjustments(
+    organization_id: i32,
+    user_id: i32,
+) -> Result<Vec<VolunteerHourAdjustment>, AppError> {
+    let connection = &mut get_postgres_connection().await?;
+
+    volunteer_hour_adjustments::table
+        .filter(volunteer_hour_adjustments::organization_id.eq(organization_id))
+        .filter(volunteer_hour_adjustments::user_id.eq(user_id))
+        .order(volunteer_hour_adjustments::created_at.desc())
+        .load(connection)
+        .await
+        .map_err(postgres_error)
+}
+
+// a volunteer's adjustments in one organization are serialized on a single bigint
+// advisory key, postgres keeps that key space apart from the two-key locks
+async fn lock_volunteer_hours(
+    connection: &mut AsyncPgConnection,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<(), AppError> {
+    let lock_key = (i64::from(organization_id) << 32) | i64::from(user_id as u32);
+
+    diesel::sql_query("SELECT pg_advisory_xact_lock($1)")
+        .bind::<BigInt, _>(lock_key)
+        .execute(connection)
+        .await
+        .map_err(postgres_error)?;
+
+    Ok(())
+}
+
+// only volunteers who have signed up for one of the organization's events or are on
+// its team can be adjusted, and a total can never be pushed below zero
+pub async fn create_volunteer_hour_adjustment(
+    organization_id: i32,
+    created_by: i32,
+    user_id: i32,
+    event_signup_id: Option<i32>,
+    minutes: i32,
+    reason: String,
+) -> Result<VolunteerHourAdjustment, AppError> {
+    if minutes == 0 {
+        return Err(AppError::validation("minutes", "Adjustment cannot be zero"));
+    }
+    if minutes.abs() > MAX_VOLUNTEER_HOUR_ADJUSTMENT_MINUTES {
+        return Err(AppError::validation(
+            "minutes",
+            format!(
+                "Adjustment cannot be more than {} hours",
+                MAX_VOLUNTEER_HOUR_ADJUSTMENT_MINUTES / 60
+            ),
+        ));
+    }
+    validate_required_string("reason", &reason, MAX_VOLUNTEER_HOUR_REASON_LENGTH)?;
+
+    let connection = &mut get_postgres_connection().await?;
+
+    connection
+        .transaction::<_, AppError, _>(|connection| {
+            Box::pin(async move {
+                // held until commit so two negative adjustments can't both pass the
+                // check against the same total
+                lock_volunteer_hours(connection, organization_id, user_id).await?;
+
+                let organization_signups = event_signups::table
+                    .inner_join(event_shifts::table.inner_join(events::table))
+                    .filter(events::organization_id.eq(organization_id))
+                    .filter(event_signups::user_id.eq(user_id));
+
+                if let Some(event_signup_id) = event_signup_id {
+                    let found: Option<i32> = organization_signups
+                        .filter(event_signups::id.eq(event_signup_id))
+                        .select(event_signups::id)
+                        .first(connection)
+                        .await
+                        .optional()
+                        .map_err(postgres_error)?;
+
+                    if found.is_none() {
+                        return Err(AppError::not_found("Signup"));
+                    }
+                } else {
+                    let has_signup: Option<i32> = organization_signups
+                        .select(event_signups::id)
+                        .first(connection)
+                        .await
+                        .optional()
+                        .map_err(postgres_error)?;
+                    let is_member: Option<i32> = organization_members::table
+                        .filter(organization_members::organization_id.eq(organization_id))
+                        .filter(organization_members::user_id.eq(user_id))
+                        .select(organization_members::id)
+                        .first(connection)
+                        .await
+                        .optional()
+                        .map_err(postgres_error)?;
+
+                    if has_signup.is_none() && is_member.is_none() {
+                        return Err(AppError::not_found("Volunteer"));
+                    }
+                }
+
+                if minutes < 0 {
+                    let tallies = tally_volunteer_hours(
+                        connection,
+                        Some(organization_id),
+                        Some(user_id),
+                        None,
+                        None,
+                        None,
+                    )
+                    .await?;
+                    let current_minutes: i64 = tallies.iter().map(HoursTally::total_minutes).sum();
+
+                    if current_minutes + i64::from(minutes) < 0 {
+                        return Err(AppError::validation(
+                            "minutes",
+                            "Adjustment would bring the volunteer's hours below zero",
+                        ));
+                    }
+                }
+
+                let mut new_adjustment =
+                    NewVolunteerHourAdjustment::new(organization_id, user_id, minutes, reason)
+                        .set_created_by(created_by);
+                if let Some(event_signup_id) = event_signup_id {
+                    new_adjustment = new_adjustment.set_event_signup(event_signup_id);
+                }
+
+                diesel::insert_into(volunteer_hour_adjustments::table)
+                    .values(&new_adjustment)
+                    .get_result(connection)
+                    .await
+                    .map_err(postgres_error)
+            })
+        })
+        .await
+}
@@ -0,0 +1,166 @@
+use crate::error::AppError;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{env, sync::OnceLock};
+
+const MIN_TOKEN_SIGNING_SECRET_LENGTH: usize = 32;
+
+static TOKEN_SIGNING_SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+
+pub fn is_token_signing_initialized() -> bool {
+    TOKEN_SIGNING_SECRET.get().is_some()
+}
+
+pub fn initialize_token_signing() -> Result<(), AppError> {
+    let secret = env::var("TOKEN_SIGNING_SECRET")
+        .map_err(|_| AppError::ConfigError("TOKEN_SIGNING_SECRET not set".to_string()))?;
+
+    if secret.len() < MIN_TOKEN_SIGNING_SECRET_LENGTH {
+        return Err(AppError::ConfigError(format!(
+            "TOKEN_SIGNING_SECRET must be at least {} characters",
+            MIN_TOKEN_SIGNING_SECRET_LENGTH
+        )));
+    }
+
+    TOKEN_SIGNING_SECRET
+        .set(secret.into_bytes())
+        .map_err(|_| AppError::ConfigError("Token signing already initialized".to_string()))?;
+
+    tracing::info!("Token signing initialized");
+
+    Ok(())
+}
+
+fn token_mac(purpose: &str, payload: &str) -> Result<Hmac<Sha256>, AppError> {
+    let secret = TOKEN_SIGNING_SECRET
+        .get()
+        .ok_or_else(|| AppError::ConfigError("Token signing not initialized".to_string()))?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+        .map_err(|error| AppError::InternalError(format!("Token signing failed: {}", error)))?;
+
+    // the purpose is mixed in so a token issued for one feature is never valid for another
+    mac.update(purpose.as_bytes());
+    mac.update(b":");
+    mac.update(payload.as_bytes());
+
+    Ok(mac)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_hex(string: &str) -> Option<Vec<u8>> {
+    if !string.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..string.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(string.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+// tokens are "<payload>.<hex hmac>", the payload itself is not secret
+pub fn sign_token(purpose: &str, payload: &str) -> Result<String, AppError> {
+    let signature = token_mac(purpose, payload)?.finalize().into_bytes();
+
+    Ok(format!("{}.{}", payload, encode_hex(&signature)))
+}
+
+// returns the payload when the signature matches, compared in constant time
+pub fn verify_signed_token(purpose: &str, token: &str) -> Result<Option<String>, AppError> {
+    let Some((payload, signature)) = token.trim().rsplit_once('.') else {
+        return Ok(None);
+    };
+    let Some(signature) = decode_hex(signature) else {
+        return Ok(None);
+    };
+
+    let verified = token_mac(purpose, payload)?
+        .verify_slice(&signature)
+        .is_ok();
+
+    Ok(verified.then(|| payload.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PURPOSE: &str = "test";
+
+    fn initialize() {
+        TOKEN_SIGNING_SECRET.get_or_init(|| vec![b'k'; MIN_TOKEN_SIGNING_SECRET_LENGTH]);
+    }
+
+    #[test]
+    fn signed_token_verifies_to_its_payload() {
+        initialize();
+        let token = sign_token(PURPOSE, "42").unwrap();
+
+        assert!(token.starts_with("42."));
+        assert_eq!(
+            verify_signed_token(PURPOSE, &token).unwrap().as_deref(),
+            Some("42")
+        );
+        assert_eq!(
+            verify_signed_token(PURPOSE, &format!(" {}\n", token))
+                .unwrap()
+                .as_deref(),
+            Some("42")
+        );
+    }
+
+    #[test]
+    fn payload_may_contain_dots() {
+        initialize();
+        let token = sign_token(PURPOSE, "a.b.c").unwrap();
+
+        assert_eq!(
+            verify_signed_token(PURPOSE, &token).unwrap().as_deref(),
+            Some("a.b.c")
+        );
+    }
+
+    #[test]
+    fn token_for_another_purpose_is_rejected() {
+        initialize();
+        let token = sign_token("event_check_in", "42").unwrap();
+
+        assert_eq!(verify_signed_token("unsubscribe", &token).unwrap(), None);
+    }
+
+    #[test]
+    fn tampered_token_is_rejected() {
+        initialize();
+        let token = sign_token(PURPOSE, "42").unwrap();
+        let (_, signature) = token.split_once('.').unwrap();
+
+        assert_eq!(
+            verify_signed_token(PURPOSE, &format!("43.{}", signature)).unwrap(),
+            None
+        );
+
+        let mut flipped = token.clone().into_bytes();
+        let last = flipped.len() - 1;
+        flipped[last] = if flipped[last] == b'0' { b'1' } else { b'0' };
+        let flipped = String::from_utf8(flipped).unwrap();
+        assert_eq!(verify_signed_token(PURPOSE, &flipped).unwrap(), None);
+    }
+
+    #[test]
+    fn malformed_token_is_rejected() {
+        initialize();
+
+        for token in ["", "42", "42.", "42.abc", "42.zz", "42.0"] {
+            assert_eq!(
+                verify_signed_token(PURPOSE, token).unwrap(),
+                None,
+                "{}",
+                token
+            );
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::error::{sms_error, AppError};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha1::Sha1;
+use std::{env, sync::OnceLock, time::Duration};
+
+// log is the default so local development works without a provider account
+const DEFAULT_SMS_BACKEND: &str = "log";
+const DEFAULT_SMS_API_URL: &str = "https://api.twilio.com";
+const SMS_REQUEST_TIMEOUT_SECONDS: u64 = 15;
+
+enum SmsTransport {
+    // any provider speaking the Twilio messages API, Twilio itself or a compatible one
+    // pointed at with SMS_API_URL
+    Twilio {
+        client: reqwest::Client,
+        api_url: String,
+        account_sid: String,
+        auth_token: String,
+        from: String,
+    },
+    // the local fake, texts are only logged and inbound webhooks are rejected since
+    // there's no token to check their signature with
+    Log,
+}
+
+static SMS_TRANSPORT: OnceLock<SmsTransport> = OnceLock::new();
+static SMS_PROGRAM_NAME: OnceLock<String> = OnceLock::new();
+
+pub struct OutgoingSms {
+    pub to: String,
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+struct TwilioMessage {
+    sid: String,
+}
+
+#[derive(Deserialize)]
+struct TwilioError {
+    code: Option<i64>,
+    message: String,
+}
+
+pub fn is_sms_initialized() -> bool {
+    SMS_TRANSPORT.get().is_some()
+}
+
+pub fn initialize_sms() -> Result<(), AppError> {
+    let backend = env::var("SMS_BACKEND").unwrap_or_else(|_| DEFAULT_SMS_BACKEND.to_string());
+
+    // carriers require the program to identify itself in HELP and STOP replies
+    let program_name = env::var("SMS_PROGRAM_NAME")
+        .map_err(|_| AppError::ConfigError("SMS_PROGRAM_NAME not set".to_string()))?;
+
+    let transport = match backend.as_str() {
+        "twilio" => {
+            let api_url = env::var("SMS_API_URL")
+                .unwrap_or_else(|_| DEFAULT_SMS_API_URL.to_string())
+                .trim_end_matches('/')
+                .to_string();
+            let account_sid = env::var("SMS_ACCOUNT_SID")
+                .map_err(|_| AppError::ConfigError("SMS_ACCOUNT_SID not set".to_string()))?;
+            let auth_token = env::var("SMS_AUTH_TOKEN")
+                .map_err(|_| AppError::ConfigError("SMS_AUTH_TOKEN not set".to_string()))?;
+            // a sending number in E.164 format or a messaging service SID (MG...)
+            let from = env::var("SMS_FROM")
+                .map_err(|_| AppError::ConfigError("SMS_FROM not set".to_string()))?;
+
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(SMS_REQUEST_TIMEOUT_SECONDS))
+                .build()
+                .map_err(|error| {
+                    AppError::ConfigError(format!("Failed to build SMS client: {}", error))
+                })?;
+
+            SmsTransport::Twilio {
+                client,
+                api_url,
+                account_sid,
+                auth_token,
+                from,
+            }
+        }
+        "log" => SmsTransport::Log,
+        other => {
+            return Err(AppError::ConfigError(format!(
+                "SMS_BACKEND must be one of twilio or log, got {}",
+                other
+            )))
+        }
+    };
+
+    SMS_TRANSPORT
+        .set(transport)
+        .map_err(|_| AppError::ConfigError("SMS already initialized".to_string()))?;
+
+    SMS_PROGRAM_NAME
+        .set(program_name)
+        .map_err(|_| AppError::ConfigError("SMS program name already set".to_string()))?;
+
+    tracing::info!("SMS initialized ({} backend)", backend);
+
+    Ok(())
+}
+
+fn get_sms_transport() -> Result<&'static SmsTransport, AppError> {
+    SMS_TRANSPORT
+        .get()
+        .ok_or_else(|| AppError::ConfigError("SMS not initialized".to_string()))
+}
+
+pub fn get_sms_program_name() -> Result<&'static str, AppError> {
+    SMS_PROGRAM_NAME
+        .get()
+        .map(|string| string.as_str())
+        .ok_or_else(|| AppError::ConfigError("SMS program name not initialized".to_string()))
+}
+
+// callers are responsible for checking consent, this only talks to the provider
+pub async fn send_sms(sms: OutgoingSms) -> Result<(), AppError> {
+    let (client, api_url, account_sid, auth_token, from) = match get_sms_transport()? {
+        SmsTransport::Twilio {
+            client,
+            api_url,
+            account_sid,
+            auth_token,
+            from,
+        } => (client, api_url, account_sid, auth_token, from),
+        SmsTransport::Log => {
+            tracing::info!("text to {}: {}", sms.to, sms.body);
+            return Ok(());
+        }
+    };
+
+    let from_field = if from.starts_with("MG") {
+        "MessagingServiceSid"
+    } else {
+        "From"
+    };
+
+    let response = client
+        .post(format!(
+            "{}/2010-04-01/Accounts/{}/Messages.json",
+            api_url, account_sid
+        ))
+        .basic_auth(account_sid, Some(auth_token))
+        .form(&[
+            ("To", sms.to.as_str()),
+            (from_field, from.as_str()),
+            ("Body", sms.body.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(sms_error)?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let message = match response.json::<TwilioError>().await {
+            Ok(TwilioError {
+                code: Some(code),
+                message,
+            }) => format!("{} (code {})", message, code),
+            Ok(TwilioError { message, .. }) => message,
+            Err(_) => format!("provider responded with {}", status),
+        };
+        return Err(sms_error(message));
+    }
+
+    let message = response.json::<TwilioMessage>().await.map_err(sms_error)?;
+    tracing::debug!("text to {} accepted as {}", sms.to, message.sid);
+
+    Ok(())
+}
+
+// twilio signs the full webhook url followed by every posted parameter sorted by
+// name with the auth token, the log backend has no token so nothing is accepted,
+// otherwise anyone could text STOP or START for any number
+pub fn verify_sms_webhook_signature(
+    url: &str,
+    params: &[(String, String)],
+    signature: Option<&str>,
+) -> Result<bool, AppError> {
+    let auth_token = match get_sms_transport()? {
+        SmsTransport::Twilio { auth_token, .. } => auth_token,
+        SmsTransport::Log => {
+            tracing::warn!("rejecting inbound text, the log SMS backend can't verify webhooks");
+            return Ok(false);
+        }
+    };
+
+    is_valid_sms_webhook_signature(auth_token, url, params, signature)
+}
+
+fn is_valid_sms_webhook_signature(
+    auth_token: &str,
+    url: &str,
+    params: &[(String, String)],
+    signature: Option<&str>,
+) -> Result<bool, AppError> {
+    let Some(signature) = signature.and_then(|signature| BASE64.decode(signature).ok()) else {
+        return Ok(false);
+    };
+
+    let mut sorted: Vec<&(String, String)> = params.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut mac = Hmac::<Sha1>::new_from_slice(auth_token.as_bytes()).map_err(|error| {
+        AppError::InternalError(format!("SMS signature check failed: {}", error))
+    })?;
+    mac.update(url.as_bytes());
+    for (name, value) in sorted {
+        mac.update(name.as_bytes());
+        mac.update(value.as_bytes());
+    }
+
+    Ok(mac.verify_slice(&signature).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the example from twilio's webhook security documentation
+    const AUTH_TOKEN: &str = "12345";
+    const URL: &str = "https://mycompany.com/myapp.php?foo=1&bar=2";
+    const SIGNATURE: &str = "0/KCTR6DLpKmkAf8muzZqo1nDgQ=";
+
+    fn params() -> Vec<(String, String)> {
+        [
+            ("From", "+12349013030"),
+            ("CallSid", "CA1234567890ABCDE"),
+            ("To", "+18005551212"),
+            ("Caller", "+12349013030"),
+            ("Digits", "1234"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn valid_signature_is_accepted_whatever_the_parameter_order() {
+        assert!(
+            is_valid_sms_webhook_signature(AUTH_TOKEN, URL, &params(), Some(SIGNATURE)).unwrap()
+        );
+    }
+
+    #[test]
+    fn tampered_request_is_rejected() {
+        let mut tampered = params();
+        tampered[0].1 = "+15550000000".to_string();
+
+        assert!(
+            !is_valid_sms_webhook_signature(AUTH_TOKEN, URL, &tampered, Some(SIGNATURE)).unwrap()
+        );
+        assert!(!is_valid_sms_webhook_signature(
+            AUTH_TOKEN,
+            "https://mycompany.com/myapp.php?foo=1",
+            &params(),
+            Some(SIGNATURE)
+        )
+        .unwrap());
+        assert!(!is_valid_sms_webhook_signature("54321", URL, &params(), Some(SIGNATURE)).unwrap());
+    }
+
+    #[test]
+    fn missing_or_malformed_signature_is_rejected() {
+        assert!(!is_valid_sms_webhook_signature(AUTH_TOKEN, URL, &params(), None).unwrap());
+        assert!(
+            !is_valid_sms_webhook_signature(AUTH_TOKEN, URL, &params(), Some("not base64!"))
+                .unwrap()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::enums::{MemberRole, SubscriptionType};
+use crate::enums::{Capability, MemberRole, SubscriptionType};
 use dioxus::prelude::*;
 use std::collections::HashMap;
 
@@ -30,6 +30,7 @@ pub struct OrganizationMembership {
     pub organization: Organization,
     pub user_role: MemberRole,
     pub permissions: Permissions,
+    pub capabilities: Vec<Capability>,
 }
 
 impl OrganizationMembership {
@@ -48,8 +49,14 @@ impl OrganizationMembership {
             organization: Organization::new(organization_id, name, avatar_url, member_count),
             user_role,
             permissions,
+            capabilities: Vec::new(),
         }
     }
+
+    pub fn set_capabilities(mut self, capabilities: Vec<Capability>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
 }
 
 pub type OrganizationMemberships = HashMap<i32, OrganizationMembership>;
@@ -4,7 +4,7 @@ pub mod notification_badge;
 pub mod sidebar;
 pub mod toast;
 
-use api::enums::SubscriptionType;
+use api::enums::{Capability, SubscriptionType};
 use api::state::{UserAccount, UserAccountStoreExt};
 pub use avatar::*;
 pub use confirmation_modal::*;
@@ -90,4 +90,19 @@ impl UserAccountContext {
             return false;
         }
     }
+
+    // the same capabilities the server checks, so menus and endpoints agree
+    pub fn has_capability(&self, capability: Capability) -> bool {
+        let Some(active_organization_membership_id) = self.get_active_organization_membership_id()
+        else {
+            // no active organization ID set
+            return false;
+        };
+
+        self.user_account
+            .organization_memberships()
+            .read()
+            .get(&active_organization_membership_id)
+            .is_some_and(|membership| membership.capabilities.contains(&capability))
+    }
 }
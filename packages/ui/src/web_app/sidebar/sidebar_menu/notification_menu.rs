@@ -1,8 +1,9 @@
 use crate::shared::{Button, ButtonSize, ButtonType, ButtonVariant, Icon, IconSize, IconVariant};
 use crate::web_app::{notification_badge::NotificationBadge, UserAccountContext};
-use api::state::UserAccountStoreExt;
+use api::providers::{mark_all_notifications_read, mark_notification_read};
+use api::state::{Notification, UserAccountStoreExt};
 use dioxus::prelude::*;
-use lucide_dioxus::{Bell, X};
+use lucide_dioxus::{Bell, Check, X};
 
 #[derive(Clone, PartialEq, Props)]
 pub struct NotificationMenuProps {
@@ -12,12 +13,39 @@ pub struct NotificationMenuProps {
 #[component]
 pub fn NotificationMenu(mut props: NotificationMenuProps) -> Element {
     let user_account = use_context::<UserAccountContext>().user_account;
-    let unread_count = user_account
+    let mut unread_notifications: Vec<Notification> = user_account
         .notifications()
         .read()
         .values()
         .filter(|notification| !notification.read)
-        .count() as i32;
+        .cloned()
+        .collect();
+    unread_notifications.sort_by_key(|notification| std::cmp::Reverse(notification.id));
+    let unread_count = unread_notifications.len() as i32;
+
+    let mark_read = move |notification_id: i32| {
+        spawn(async move {
+            if mark_notification_read(notification_id).await.is_ok() {
+                if let Some(notification) = user_account
+                    .notifications()
+                    .write()
+                    .get_mut(&notification_id)
+                {
+                    notification.read = true;
+                }
+            }
+        });
+    };
+
+    let mark_all_read = move |_| {
+        spawn(async move {
+            if mark_all_notifications_read().await.is_ok() {
+                for notification in user_account.notifications().write().values_mut() {
+                    notification.read = true;
+                }
+            }
+        });
+    };
 
     rsx! {
         Button {
@@ -50,6 +78,17 @@ pub fn NotificationMenu(mut props: NotificationMenuProps) -> Element {
                     span { class: "text-sm font-medium text-foreground cursor-default",
                         "Notifications"
                     }
+                    div { class: "flex flex-1 justify-end",
+                        if unread_count > 0 {
+                            Button {
+                                r#type: ButtonType::Button,
+                                onclick: mark_all_read,
+                                size: ButtonSize::Fit,
+                                variant: ButtonVariant::Sidebar,
+                                span { class: "text-xs", "Mark all read" }
+                            }
+                        }
+                    }
                     Button {
                         r#type: ButtonType::Button,
                         onclick: move |_| props.show_menu.set(false),
@@ -63,8 +102,47 @@ pub fn NotificationMenu(mut props: NotificationMenuProps) -> Element {
                     }
                 
                 }
-                div { class: "px-2 h-80 overflow-y-auto flex flex-col align-center",
-                    span { class: "text-sm text-foreground cursor-default", "No new notifications." }
+                div { class: "px-2 h-80 overflow-y-auto flex flex-col align-center gap-1",
+                    if unread_notifications.is_empty() {
+                        span { class: "text-sm text-foreground cursor-default", "No new notifications." }
+                    }
+                    for notification in unread_notifications {
+                        div {
+                            key: "{notification.id}",
+                            class: "flex flex-row gap-2 items-start rounded-md px-2 py-1.5 hover:bg-accent",
+                            div { class: "flex flex-1 flex-col cursor-default",
+                                // links created before paths were validated might point off-site
+                                if let Some(link) = notification
+                                    .link
+                                    .clone()
+                                    .filter(|link| link.starts_with('/') && !link.starts_with("//") && !link.starts_with("/\\"))
+                                {
+                                    Link {
+                                        to: link,
+                                        onclick: move |_| props.show_menu.set(false),
+                                        class: "text-sm font-medium text-foreground hover:underline",
+                                        {notification.title.clone()}
+                                    }
+                                } else {
+                                    span { class: "text-sm font-medium text-foreground",
+                                        {notification.title.clone()}
+                                    }
+                                }
+                                span { class: "text-xs text-muted-foreground", {notification.message.clone()} }
+                            }
+                            Button {
+                                r#type: ButtonType::Button,
+                                onclick: move |_| mark_read(notification.id),
+                                size: ButtonSize::Icon,
+                                variant: ButtonVariant::Sidebar,
+                                Icon {
+                                    size: IconSize::Small,
+                                    variant: IconVariant::Button,
+                                    Check {}
+                                }
+                            }
+                        }
+                    }
                 }
             }
         }
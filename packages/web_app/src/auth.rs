@@ -1,10 +1,15 @@
-use api::interfaces::UserAccountResponse;
-use api::providers::get_current_user;
+use api::interfaces::{NotificationResponse, UserAccountResponse};
+use api::providers::{get_current_user, stream_notifications};
 use api::state::{
-    Organization, OrganizationMembership, OrganizationMemberships, Permissions, UserAccount,
+    Notification, Notifications, Organization, OrganizationMembership, OrganizationMemberships,
+    Permissions, UserAccount, UserAccountStoreExt,
 };
+use dioxus::core::Task;
 use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use gloo::timers::future::TimeoutFuture;
 use std::collections::HashMap;
+use ui::web_app::UserAccountContext;
 
 #[derive(Clone, PartialEq)]
 pub enum AuthState {
@@ -71,6 +76,20 @@ impl AuthContext {
     }
 }
 
+/// Convert NotificationResponse to the Notification kept in UserAccountContext
+pub fn notification_response_to_state(response: &NotificationResponse) -> Notification {
+    let mut notification = Notification::new(
+        response.id,
+        response.notification_type,
+        response.title.clone(),
+        response.message.clone(),
+        response.read,
+    );
+    notification.organization_id = response.organization_id;
+    notification.link = response.link.clone();
+    notification
+}
+
 /// Convert UserAccountResponse to UserAccount for UserAccountContext
 pub fn user_response_to_account(response: &UserAccountResponse) -> UserAccount {
     let mut organization_memberships: OrganizationMemberships = HashMap::new();
@@ -92,11 +111,23 @@ pub fn user_response_to_account(response: &UserAccountResponse) -> UserAccount {
             organization,
             user_role: membership_info.user_role.clone(),
             permissions,
+            capabilities: membership_info.capabilities.clone(),
         };
 
         organization_memberships.insert(*id, membership);
     }
 
+    let notifications: Notifications = response
+        .notifications
+        .iter()
+        .map(|notification| {
+            (
+                notification.id,
+                notification_response_to_state(notification),
+            )
+        })
+        .collect();
+
     UserAccount {
         id: response.id,
         first_name: response.first_name.clone(),
@@ -104,7 +135,7 @@ pub fn user_response_to_account(response: &UserAccountResponse) -> UserAccount {
         avatar_url: response.avatar_url.clone(),
         active_organization_membership_id: response.active_organization_membership_id,
         organization_memberships,
-        notifications: HashMap::new(),
+        notifications,
     }
 }
 
@@ -135,3 +166,73 @@ pub fn use_auth_init() -> AuthContext {
 
     auth_context
 }
+
+const NOTIFICATION_STREAM_MIN_RETRY_MS: u32 = 1_000;
+const NOTIFICATION_STREAM_MAX_RETRY_MS: u32 = 60_000;
+
+/// Hook to push new notifications into UserAccountContext while signed in
+pub fn use_notification_stream() {
+    let auth_context = use_context::<AuthContext>();
+    let user_account_context = use_context::<UserAccountContext>();
+    let mut stream_task = use_signal(|| None::<Task>);
+
+    use_effect(move || {
+        // restart the stream whenever the signed in user changes
+        let user_id = auth_context
+            .user_account
+            .read()
+            .as_ref()
+            .map(|user| user.id);
+
+        if let Some(task) = stream_task.write().take() {
+            task.cancel();
+        }
+
+        if user_id.is_none() {
+            return;
+        }
+
+        let task = spawn(async move {
+            let mut retry_delay = NOTIFICATION_STREAM_MIN_RETRY_MS;
+
+            // the stream ends whenever the server restarts or the connection drops,
+            // so keep reconnecting while the user is signed in
+            loop {
+                if let Ok(mut stream) = stream_notifications().await {
+                    retry_delay = NOTIFICATION_STREAM_MIN_RETRY_MS;
+
+                    while let Some(Ok(notification)) = stream.recv().await {
+                        user_account_context
+                            .user_account
+                            .notifications()
+                            .write()
+                            .insert(
+                                notification.id,
+                                notification_response_to_state(&notification),
+                            );
+                    }
+                }
+
+                if !wait_to_reconnect(retry_delay).await {
+                    return;
+                }
+                retry_delay = (retry_delay * 2).min(NOTIFICATION_STREAM_MAX_RETRY_MS);
+            }
+        });
+        stream_task.set(Some(task));
+    });
+}
+
+// returns false when there's no timer to back off with
+async fn wait_to_reconnect(delay_ms: u32) -> bool {
+    #[cfg(feature = "web")]
+    {
+        TimeoutFuture::new(delay_ms).await;
+        true
+    }
+    #[cfg(not(feature = "web"))]
+    {
+        let _ = delay_ms;
+        false
+    }
+}
@@ -1,6 +1,6 @@
 use crate::auth::AuthContext;
 use crate::routes::Routes;
-use api::enums::SubscriptionType;
+use api::enums::{Capability, SubscriptionType};
 use api::state::UserAccountStoreExt;
 use dioxus::prelude::*;
 use ui::web_app::{
@@ -11,6 +11,7 @@ use ui::web_app::{
 #[derive(Clone, PartialEq, Props)]
 pub struct GateProps {
     required_permission: Option<SubscriptionType>,
+    required_capability: Option<Capability>,
     permission_fallback_route: Option<String>,
     children: Element,
 }
@@ -87,6 +88,23 @@ pub fn Gate(props: GateProps) -> Element {
         }
     }
 
+    // if a required capability is specified, check the user's role in the organization grants it
+    if let Some(required_capability) = props.required_capability {
+        if !user_account_context.has_capability(required_capability) {
+            toast_context.create(
+                "Access Denied".to_string(),
+                "Your role doesn't allow you to view that page for this organization.".to_string(),
+                ToastVariant::Error,
+            );
+            if let Some(route) = &props.permission_fallback_route {
+                router().push(route.clone());
+            } else {
+                router().push(Routes::Dashboard {}.to_string());
+            }
+            return rsx! {};
+        }
+    }
+
     // render the route if not redirected
     props.children
 }
@@ -3,9 +3,17 @@ pub mod gate;
 pub mod routes;
 
 #[cfg(feature = "server")]
-use api::{http::session_middleware, initialize_services};
+use api::{
+    http::{
+        inbound_sms_webhook, one_click_unsubscribe, session_middleware, INBOUND_SMS_WEBHOOK_PATH,
+        ONE_CLICK_UNSUBSCRIBE_PATH,
+    },
+    initialize_services,
+    services::assign_missing_organization_plans,
+    services::web_app::{spawn_organization_purge_worker, spawn_outbound_message_worker},
+};
 #[cfg(feature = "server")]
-use axum::middleware;
+use axum::{middleware, routing::post};
 use dioxus::prelude::*;
 #[cfg(feature = "server")]
 use dioxus::server::router;
@@ -21,8 +29,16 @@ fn main() {
     dioxus::serve(|| async {
         dotenv().ok();
         initialize_services().map_err(|error| ServerFnError::new(error.to_string()))?;
+        assign_missing_organization_plans()
+            .await
+            .map_err(|error| ServerFnError::new(error.to_string()))?;
+        spawn_outbound_message_worker();
+        spawn_organization_purge_worker();
 
-        Ok(router(App).layer(middleware::from_fn(session_middleware)))
+        Ok(router(App)
+            .route(INBOUND_SMS_WEBHOOK_PATH, post(inbound_sms_webhook))
+            .route(ONE_CLICK_UNSUBSCRIBE_PATH, post(one_click_unsubscribe))
+            .layer(middleware::from_fn(session_middleware)))
     });
 }
 
@@ -6,31 +6,39 @@ mod dashboard;
 mod events;
 mod exports;
 mod groups;
+mod invitation;
 mod login;
+mod reset_password;
+mod rsvp;
 mod settings;
 mod team;
+mod unsubscribe;
+mod verify_email;
 
-use crate::auth::{user_response_to_account, AuthContext, AuthState};
+use crate::auth::{use_notification_stream, user_response_to_account, AuthContext, AuthState};
 use account::{
     devices::DeviceSessions, notifications::NotificationPreferences,
     organizations::OrganizationManagement, Account,
 };
 use actions::Actions;
 use analytics::Analytics;
-use api::enums::SubscriptionType;
+use api::enums::{Capability, SubscriptionType};
 use api::providers::get_current_user;
 use api::state::{UserAccount, UserAccountStoreExt};
-use create::{CreateAccount, CreateOrganization};
+use create::{CreateAccount, CreateOrganization, InvitationCreateAccount};
 use dashboard::Dashboard;
 use dioxus::prelude::*;
 use events::Events;
 use exports::Exports;
 use groups::Groups;
+use invitation::Invitation;
 use login::Login;
 use lucide_dioxus::{
     Bell, Building, Calendar, ChartColumn, ContactRound, FileOutput, LayoutGrid, Megaphone,
     MonitorSmartphone, Settings as Settings1, Settings2, User, UsersRound,
 };
+use reset_password::ResetPassword;
+use rsvp::Rsvp;
 use settings::Settings;
 use std::collections::HashMap;
 use team::Team;
@@ -39,6 +47,8 @@ use ui::web_app::{
     toast::ToastProvider,
     UserAccountContext,
 };
+use unsubscribe::Unsubscribe;
+use verify_email::VerifyEmail;
 
 #[component]
 fn Layout() -> Element {
@@ -90,6 +100,9 @@ fn Layout() -> Element {
 
     let user_account_context = use_context::<UserAccountContext>();
 
+    // deliver new notifications without waiting for a reload
+    use_notification_stream();
+
     // sync UserAccountContext when AuthContext changes (e.g., after org creation)
     use_effect(move || {
         let updated_account = auth_context
@@ -165,7 +178,9 @@ fn Layout() -> Element {
             label: "Dashboard".to_string(),
         });
 
-        if user_account_context.has_permission(SubscriptionType::Events) {
+        if user_account_context.has_permission(SubscriptionType::Events)
+            && user_account_context.has_capability(Capability::EventsView)
+        {
             main_menu_routes.extend([
                 NavRoute {
                     route: Routes::Events {}.to_string(),
@@ -199,28 +214,38 @@ fn Layout() -> Element {
                 },
                 label: "Analytics".to_string(),
             },
-            NavRoute {
+        ]);
+
+        // tools the member's role doesn't grant are hidden, the endpoints check the same capabilities
+        if user_account_context.has_capability(Capability::ExportsDownload) {
+            tools_routes.push(NavRoute {
                 route: Routes::Exports {}.to_string(),
                 icon: rsx! {
                     FileOutput {}
                 },
                 label: "Exports".to_string(),
-            },
-            NavRoute {
+            });
+        }
+
+        if user_account_context.has_capability(Capability::TeamView) {
+            tools_routes.push(NavRoute {
                 route: Routes::Team {}.to_string(),
                 icon: rsx! {
                     UsersRound {}
                 },
                 label: "Team".to_string(),
-            },
-            NavRoute {
+            });
+        }
+
+        if user_account_context.has_capability(Capability::OrganizationManage) {
+            tools_routes.push(NavRoute {
                 route: Routes::Settings {}.to_string(),
                 icon: rsx! {
                     Settings1 {}
                 },
                 label: "Settings".to_string(),
-            },
-        ]);
+            });
+        }
 
         support_link = "https://support.campaigncenter.com".to_string();
 
@@ -373,4 +398,22 @@ pub enum Routes {
 
     #[route("/account/organizations")]
     OrganizationManagement {},
+
+    #[route("/invitations/:token")]
+    Invitation { token: String },
+
+    #[route("/invitations/:token/create-account")]
+    InvitationCreateAccount { token: String },
+
+    #[route("/reset-password/:token")]
+    ResetPassword { token: String },
+
+    #[route("/rsvp/:token")]
+    Rsvp { token: String },
+
+    #[route("/unsubscribe/:token")]
+    Unsubscribe { token: String },
+
+    #[route("/verify-email/:token")]
+    VerifyEmail { token: String },
 }
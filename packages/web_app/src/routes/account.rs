@@ -3,16 +3,64 @@ pub mod notifications;
 pub mod organizations;
 
 use crate::gate::Gate;
+use api::interfaces::OrganizationVolunteerHoursResponse;
+use api::providers::get_my_volunteer_hours;
 use dioxus::prelude::*;
 
 #[component]
 pub fn Account() -> Element {
+    let volunteer_hours = use_resource(|| async move { get_my_volunteer_hours().await.ok() });
+
     rsx! {
         Gate {
-            div { class: "w-full",
+            div { class: "w-full space-y-4",
                 h1 { class: "text-primary font-bold text-xl", "Account" }
-                p { "Welcome to the account page!" }
+                h2 { class: "font-semibold", "Volunteer Hours" }
+                match &*volunteer_hours.read() {
+                    Some(Some(response)) if !response.organizations.is_empty() => rsx! {
+                        p { class: "text-muted-foreground",
+                            "{format_hours(response.total_minutes)} hours in total"
+                        }
+                        table { class: "w-full text-sm",
+                            thead {
+                                tr { class: "text-left text-muted-foreground border-b border-border",
+                                    th { class: "py-2", "Organization" }
+                                    th { class: "py-2", "Shifts" }
+                                    th { class: "py-2", "Hours" }
+                                }
+                            }
+                            tbody {
+                                for organization in response.organizations.iter() {
+                                    {organization_hours_row(organization)}
+                                }
+                            }
+                        }
+                    },
+                    Some(Some(_)) => rsx! {
+                        p { class: "text-muted-foreground", "No volunteer hours yet." }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "Failed to load volunteer hours." }
+                    },
+                    None => rsx! {
+                        div { class: "h-32 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
             }
         }
     }
 }
+
+pub(crate) fn format_hours(minutes: i64) -> String {
+    format!("{:.1}", minutes as f64 / 60.0)
+}
+
+fn organization_hours_row(organization: &OrganizationVolunteerHoursResponse) -> Element {
+    rsx! {
+        tr { key: "{organization.organization_id}", class: "border-b border-border",
+            td { class: "py-2 font-medium", "{organization.organization_name}" }
+            td { class: "py-2", "{organization.shift_count}" }
+            td { class: "py-2", "{format_hours(organization.total_minutes)}" }
+        }
+    }
+}
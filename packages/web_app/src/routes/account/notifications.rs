@@ -1,13 +1,330 @@
 use crate::gate::Gate;
+use api::enums::{NotificationChannel, NotificationType, SmsConsentAction, SMS_CONSENT_DISCLOSURE};
+use api::interfaces::{
+    NotificationPreferenceResponse, NotificationPreferenceSetting,
+    UpdateNotificationPreferencesRequest, UpdateSmsConsentRequest,
+};
+use api::providers::{
+    get_sms_consent, list_notification_preferences, update_notification_preferences,
+    update_sms_consent,
+};
+use api::state::UserAccountStoreExt;
 use dioxus::prelude::*;
+use ui::web_app::UserAccountContext;
+
+const NOTIFICATION_TYPES: [NotificationType; 9] = [
+    NotificationType::Info,
+    NotificationType::EventReminder,
+    NotificationType::TeamInvite,
+    NotificationType::MemberJoined,
+    NotificationType::SignupConfirmed,
+    NotificationType::WaitlistPromoted,
+    NotificationType::CoHostInvite,
+    NotificationType::EventCancelled,
+    NotificationType::OwnershipTransfer,
+];
+
+const NOTIFICATION_CHANNELS: [NotificationChannel; 4] = [
+    NotificationChannel::InApp,
+    NotificationChannel::Email,
+    NotificationChannel::Sms,
+    NotificationChannel::Push,
+];
+
+// an organization override wins over the account default, anything unset is delivered
+fn is_enabled(
+    preferences: &[NotificationPreferenceResponse],
+    organization_id: Option<i32>,
+    notification_type: NotificationType,
+    channel: NotificationChannel,
+) -> bool {
+    let find = |organization_id: Option<i32>| {
+        preferences
+            .iter()
+            .find(|preference| {
+                preference.organization_id == organization_id
+                    && preference.notification_type == notification_type
+                    && preference.channel == channel
+            })
+            .map(|preference| preference.enabled)
+    };
+
+    organization_id
+        .and_then(|organization_id| find(Some(organization_id)))
+        .or_else(|| find(None))
+        .unwrap_or(true)
+}
+
+// text messages need their own recorded consent on top of the SMS preferences below
+#[component]
+fn SmsConsentSettings() -> Element {
+    let mut phone_number = use_signal(String::new);
+    let mut agreed = use_signal(|| false);
+    let mut update_error = use_signal(|| None::<String>);
+
+    let mut consent = use_resource(move || async move {
+        let consent = get_sms_consent().await.ok();
+        if let Some(ref consent) = consent {
+            phone_number.set(consent.phone_number.clone().unwrap_or_default());
+        }
+        consent
+    });
+
+    let save = move |opt_in: bool| {
+        spawn(async move {
+            update_error.set(None);
+
+            let request = UpdateSmsConsentRequest {
+                phone_number: Some(phone_number.cloned()),
+                opt_in,
+            };
+
+            match update_sms_consent(request).await {
+                Ok(_) => {
+                    agreed.set(false);
+                    consent.restart();
+                }
+                Err(error) => update_error.set(Some(error.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "space-y-3 border border-border rounded-md p-4",
+            h2 { class: "font-semibold", "Text Messages" }
+
+            if let Some(error) = update_error.read().as_ref() {
+                div { class: "bg-destructive/10 text-destructive p-3 rounded-md text-sm",
+                    "{error}"
+                }
+            }
+
+            match &*consent.read() {
+                Some(Some(consent)) if consent.action == Some(SmsConsentAction::OptIn) => {
+                    let number = consent.phone_number.clone().unwrap_or_default();
+                    let agreed_on = consent
+                        .recorded_at
+                        .map(|recorded_at| recorded_at.format("%b %-d, %Y").to_string())
+                        .unwrap_or_default();
+                    rsx! {
+                        p { class: "text-sm",
+                            "Texts go to "
+                            span { class: "font-medium", "{number}" }
+                            ", you agreed on {agreed_on}."
+                        }
+                        button {
+                            class: "px-3 py-2 text-sm border border-input rounded-md hover:bg-muted",
+                            onclick: move |_| save(false),
+                            "Stop text messages"
+                        }
+                    }
+                }
+                Some(Some(_)) => rsx! {
+                    div { class: "flex items-end gap-4",
+                        div {
+                            label {
+                                class: "block text-sm font-medium mb-1",
+                                r#for: "sms-consent-phone-number",
+                                "Mobile number"
+                            }
+                            input {
+                                id: "sms-consent-phone-number",
+                                r#type: "tel",
+                                class: "px-3 py-2 border border-input rounded-md bg-background",
+                                value: "{phone_number}",
+                                oninput: move |evt| phone_number.set(evt.value()),
+                            }
+                        }
+                        button {
+                            class: "px-3 py-2 text-sm border border-input rounded-md hover:bg-muted disabled:opacity-50",
+                            disabled: !agreed() || phone_number.read().trim().is_empty(),
+                            onclick: move |_| save(true),
+                            "Receive text messages"
+                        }
+                    }
+                    label { class: "flex items-start gap-2 text-sm text-muted-foreground",
+                        input {
+                            r#type: "checkbox",
+                            class: "h-4 w-4 mt-0.5 cursor-pointer",
+                            checked: agreed(),
+                            onchange: move |evt| agreed.set(evt.checked()),
+                        }
+                        "{SMS_CONSENT_DISCLOSURE}"
+                    }
+                },
+                Some(None) => rsx! {
+                    p { class: "text-destructive", "Failed to load text message settings." }
+                },
+                None => rsx! {
+                    div { class: "h-16 w-full bg-muted rounded animate-pulse" }
+                },
+            }
+        }
+    }
+}
 
 #[component]
 pub fn NotificationPreferences() -> Element {
+    let user_account_context = use_context::<UserAccountContext>();
+    let mut organization_id = use_signal(|| None::<i32>);
+    let mut update_error = use_signal(|| None::<String>);
+
+    let mut preferences = use_resource(|| async move {
+        list_notification_preferences()
+            .await
+            .ok()
+            .map(|response| response.preferences)
+    });
+
+    let mut organizations: Vec<(i32, String)> = user_account_context
+        .user_account
+        .organization_memberships()
+        .read()
+        .values()
+        .map(|membership| {
+            (
+                membership.organization_id,
+                membership.organization.name.clone(),
+            )
+        })
+        .collect();
+    organizations.sort_by(|a, b| a.1.cmp(&b.1));
+
+    let save = move |settings: Vec<NotificationPreferenceSetting>| {
+        spawn(async move {
+            update_error.set(None);
+
+            let request = UpdateNotificationPreferencesRequest {
+                organization_id: *organization_id.read(),
+                preferences: settings,
+            };
+
+            match update_notification_preferences(request).await {
+                Ok(_) => preferences.restart(),
+                Err(error) => update_error.set(Some(error.to_string())),
+            }
+        });
+    };
+
+    let handle_reset = move |_| {
+        let Some(selected_organization_id) = *organization_id.read() else {
+            return;
+        };
+        let overrides: Vec<NotificationPreferenceSetting> = match &*preferences.read() {
+            Some(Some(preferences)) => preferences
+                .iter()
+                .filter(|preference| preference.organization_id == Some(selected_organization_id))
+                .map(|preference| NotificationPreferenceSetting {
+                    notification_type: preference.notification_type,
+                    channel: preference.channel,
+                    enabled: None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+        if !overrides.is_empty() {
+            save(overrides);
+        }
+    };
+
     rsx! {
         Gate {
-            div { class: "w-full",
+            div { class: "w-full space-y-4",
                 h1 { class: "text-primary font-bold text-xl", "Notification Preferences" }
-                p { "Welcome to the notification preferences page!" }
+                p { class: "text-muted-foreground",
+                    "Choose how you hear about each kind of notification. Organization settings override your account defaults."
+                }
+
+                if let Some(error) = update_error.read().as_ref() {
+                    div { class: "bg-destructive/10 text-destructive p-3 rounded-md text-sm",
+                        "{error}"
+                    }
+                }
+
+                div { class: "flex items-end gap-4",
+                    div {
+                        label {
+                            class: "block text-sm font-medium mb-1",
+                            r#for: "notification-preferences-scope",
+                            "Applies to"
+                        }
+                        select {
+                            id: "notification-preferences-scope",
+                            class: "px-3 py-2 border border-input rounded-md bg-background",
+                            onchange: move |evt| organization_id.set(evt.value().parse().ok()),
+                            option { value: "", "All organizations (account default)" }
+                            for (id, name) in organizations.into_iter() {
+                                option { key: "{id}", value: "{id}", "{name}" }
+                            }
+                        }
+                    }
+                    if organization_id.read().is_some() {
+                        button {
+                            class: "px-3 py-2 text-sm border border-input rounded-md hover:bg-muted",
+                            onclick: handle_reset,
+                            "Use account defaults"
+                        }
+                    }
+                }
+
+                SmsConsentSettings {}
+
+                match &*preferences.read() {
+                    Some(Some(preferences)) => rsx! {
+                        table { class: "w-full text-sm",
+                            thead {
+                                tr { class: "text-left text-muted-foreground border-b border-border",
+                                    th { class: "py-2", "Notification" }
+                                    for channel in NOTIFICATION_CHANNELS {
+                                        th { key: "{channel.as_str()}", class: "py-2 text-center",
+                                            "{channel}"
+                                        }
+                                    }
+                                }
+                            }
+                            tbody {
+                                for notification_type in NOTIFICATION_TYPES {
+                                    tr {
+                                        key: "{notification_type.as_str()}",
+                                        class: "border-b border-border",
+                                        td { class: "py-2 font-medium", "{notification_type}" }
+                                        for channel in NOTIFICATION_CHANNELS {
+                                            td {
+                                                key: "{channel.as_str()}",
+                                                class: "py-2 text-center",
+                                                input {
+                                                    r#type: "checkbox",
+                                                    class: "h-4 w-4 cursor-pointer",
+                                                    checked: is_enabled(
+                                                        preferences,
+                                                        *organization_id.read(),
+                                                        notification_type,
+                                                        channel,
+                                                    ),
+                                                    onchange: move |evt| {
+                                                        save(vec![
+                                                            NotificationPreferenceSetting {
+                                                                notification_type,
+                                                                channel,
+                                                                enabled: Some(evt.checked()),
+                                                            },
+                                                        ]);
+                                                    },
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "Failed to load notification preferences." }
+                    },
+                    None => rsx! {
+                        div { class: "h-32 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
             }
         }
     }
@@ -1,5 +1,5 @@
 use crate::{gate::Gate, routes::Routes};
-use api::enums::SubscriptionType;
+use api::enums::{Capability, SubscriptionType};
 use dioxus::prelude::*;
 
 #[component]
@@ -7,6 +7,7 @@ pub fn Actions() -> Element {
     rsx! {
         Gate {
             required_permission: SubscriptionType::Events,
+            required_capability: Capability::EventsView,
             permission_fallback_route: Routes::Dashboard {}.to_string(),
             div { class: "w-full",
                 h1 { class: "text-primary font-bold text-xl", "Actions" }
@@ -1,14 +1,75 @@
 use crate::gate::Gate;
+use crate::routes::account::format_hours;
+use api::interfaces::{VolunteerHoursLeaderboardRequest, VolunteerHoursResponse};
+use api::providers::get_volunteer_hours_leaderboard;
 use dioxus::prelude::*;
+use ui::web_app::UserAccountContext;
 
 #[component]
 pub fn Analytics() -> Element {
+    let user_account_context = use_context::<UserAccountContext>();
+
+    // re-fetch whenever the active organization changes
+    let leaderboard = use_resource(move || {
+        let active_organization_membership_id =
+            user_account_context.get_active_organization_membership_id();
+        async move {
+            active_organization_membership_id?;
+            get_volunteer_hours_leaderboard(VolunteerHoursLeaderboardRequest {
+                range_start: None,
+                range_end: None,
+                limit: None,
+            })
+            .await
+            .ok()
+        }
+    });
+
     rsx! {
         Gate {
-            div { class: "w-full",
+            div { class: "w-full space-y-4",
                 h1 { class: "text-primary font-bold text-xl", "Analytics" }
-                p { "Welcome to the analytics page!" }
+                h2 { class: "font-semibold", "Top Volunteers" }
+                match &*leaderboard.read() {
+                    Some(Some(response)) if !response.volunteers.is_empty() => rsx! {
+                        table { class: "w-full text-sm",
+                            thead {
+                                tr { class: "text-left text-muted-foreground border-b border-border",
+                                    th { class: "py-2", "#" }
+                                    th { class: "py-2", "Volunteer" }
+                                    th { class: "py-2", "Shifts" }
+                                    th { class: "py-2", "Hours" }
+                                }
+                            }
+                            tbody {
+                                for (index, volunteer) in response.volunteers.iter().enumerate() {
+                                    {volunteer_row(index + 1, volunteer)}
+                                }
+                            }
+                        }
+                    },
+                    Some(Some(_)) => rsx! {
+                        p { class: "text-muted-foreground", "No volunteer hours recorded yet." }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "Failed to load volunteer hours." }
+                    },
+                    None => rsx! {
+                        div { class: "h-32 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
             }
         }
     }
 }
+
+fn volunteer_row(rank: usize, volunteer: &VolunteerHoursResponse) -> Element {
+    rsx! {
+        tr { key: "{volunteer.user_id}", class: "border-b border-border",
+            td { class: "py-2 text-muted-foreground", "{rank}" }
+            td { class: "py-2 font-medium", "{volunteer.first_name} {volunteer.last_name}" }
+            td { class: "py-2", "{volunteer.shift_count}" }
+            td { class: "py-2", "{format_hours(volunteer.total_minutes)}" }
+        }
+    }
+}
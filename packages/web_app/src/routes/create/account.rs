@@ -8,6 +8,22 @@ use dioxus::prelude::*;
 
 #[component]
 pub fn CreateAccount() -> Element {
+    rsx! {
+        CreateAccountForm { invitation_token: None }
+    }
+}
+
+// opened from an invitation, registering through the link verifies the email and
+// joins the organization straight away
+#[component]
+pub fn InvitationCreateAccount(token: String) -> Element {
+    rsx! {
+        CreateAccountForm { invitation_token: Some(token) }
+    }
+}
+
+#[component]
+fn CreateAccountForm(invitation_token: Option<String>) -> Element {
     let mut email = use_signal(String::new);
     let mut password = use_signal(String::new);
     let mut first_name = use_signal(String::new);
@@ -19,6 +35,7 @@ pub fn CreateAccount() -> Element {
     let handle_submit = move |evt: FormEvent| {
         evt.prevent_default();
         let mut auth_context_spawn = auth_context.clone();
+        let invitation_token = invitation_token.clone();
 
         spawn(async move {
             is_loading.set(true);
@@ -29,6 +46,7 @@ pub fn CreateAccount() -> Element {
                 first_name: first_name.read().clone(),
                 last_name: last_name.read().clone(),
                 platform: Platform::Web,
+                invitation_token,
             };
 
             match register(request).await {
@@ -36,9 +54,14 @@ pub fn CreateAccount() -> Element {
                     // fetch the full user account after registration
                     match get_current_user().await {
                         Ok(Some(user)) => {
+                            // a user who joined through an invitation already has an organization
+                            let next_route = if user.active_organization_membership_id.is_some() {
+                                Routes::Dashboard {}
+                            } else {
+                                Routes::CreateOrganization {}
+                            };
                             auth_context_spawn.set_authenticated(user);
-                            // redirect to create organization (new users have no orgs)
-                            router().push(Routes::CreateOrganization {}.to_string());
+                            router().push(next_route.to_string());
                         }
                         Ok(None) => {
                             create_error.set(Some("Failed to fetch user account".to_string()));
@@ -1,17 +1,93 @@
 use crate::{gate::Gate, routes::Routes};
-use api::enums::SubscriptionType;
+use api::enums::{Capability, SubscriptionType};
+use api::interfaces::{EventResponse, ListEventsRequest};
+use api::providers::list_events;
 use dioxus::prelude::*;
+use ui::web_app::UserAccountContext;
 
 #[component]
 pub fn Events() -> Element {
+    let user_account_context = use_context::<UserAccountContext>();
+
+    // re-fetch whenever the active organization changes
+    let events = use_resource(move || {
+        let active_organization_membership_id =
+            user_account_context.get_active_organization_membership_id();
+        async move {
+            active_organization_membership_id?;
+            list_events(ListEventsRequest {
+                event_type: None,
+                visibility: None,
+                include_cancelled: Some(true),
+                page: None,
+                per_page: None,
+            })
+            .await
+            .ok()
+        }
+    });
+
     rsx! {
         Gate {
             required_permission: SubscriptionType::Events,
+            required_capability: Capability::EventsView,
             permission_fallback_route: Routes::Dashboard {}.to_string(),
-            div { class: "w-full",
+            div { class: "w-full space-y-4",
                 h1 { class: "text-primary font-bold text-xl", "Events" }
-                p { "Welcome to the events page!" }
+                match &*events.read() {
+                    Some(Some(response)) if !response.events.is_empty() => rsx! {
+                        table { class: "w-full text-sm",
+                            thead {
+                                tr { class: "text-left text-muted-foreground border-b border-border",
+                                    th { class: "py-2", "Name" }
+                                    th { class: "py-2", "Type" }
+                                    th { class: "py-2", "Status" }
+                                    th { class: "py-2", "Next Shift" }
+                                }
+                            }
+                            tbody {
+                                for event in response.events.iter() {
+                                    {event_row(event)}
+                                }
+                            }
+                        }
+                    },
+                    Some(Some(_)) => rsx! {
+                        p { class: "text-muted-foreground", "No events yet." }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "Failed to load events." }
+                    },
+                    None => rsx! {
+                        div { class: "h-32 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
             }
         }
     }
 }
+
+fn event_row(event: &EventResponse) -> Element {
+    let status = if event.cancelled_at.is_some() {
+        "Cancelled".to_string()
+    } else {
+        event.visibility.display_name().to_string()
+    };
+
+    let next_shift = event
+        .shifts
+        .iter()
+        .map(|shift| shift.start_time)
+        .find(|start_time| *start_time > chrono::Utc::now())
+        .map(|start_time| start_time.format("%b %-d, %Y %-I:%M %p UTC").to_string())
+        .unwrap_or_else(|| "—".to_string());
+
+    rsx! {
+        tr { key: "{event.id}", class: "border-b border-border",
+            td { class: "py-2 font-medium", "{event.name}" }
+            td { class: "py-2", "{event.event_type}" }
+            td { class: "py-2", "{status}" }
+            td { class: "py-2", "{next_shift}" }
+        }
+    }
+}
@@ -1,13 +1,252 @@
 use crate::gate::Gate;
+use api::enums::{Capability, ExportFormat};
+use api::interfaces::{
+    CreateAttendeeExportRequest, EventResponse, ExportResponse, ListEventsRequest,
+    ListExportsRequest,
+};
+use api::providers::{create_attendee_export, get_export_download_url, list_events, list_exports};
+use chrono::{Days, NaiveDate, NaiveTime};
 use dioxus::prelude::*;
+use ui::web_app::UserAccountContext;
 
 #[component]
 pub fn Exports() -> Element {
+    let user_account_context = use_context::<UserAccountContext>();
+    let mut format = use_signal(|| ExportFormat::Csv);
+    let mut event_id = use_signal(|| None::<i32>);
+    let mut range_start = use_signal(String::new);
+    let mut range_end = use_signal(String::new);
+    let mut export_error = use_signal(|| None::<String>);
+    let mut is_loading = use_signal(|| false);
+
+    // re-fetch whenever the active organization changes
+    let exports_account_context = user_account_context.clone();
+    let mut exports = use_resource(move || {
+        let active_organization_membership_id =
+            exports_account_context.get_active_organization_membership_id();
+        async move {
+            active_organization_membership_id?;
+            list_exports(ListExportsRequest {
+                page: None,
+                per_page: None,
+            })
+            .await
+            .ok()
+        }
+    });
+
+    let events = use_resource(move || {
+        let active_organization_membership_id =
+            user_account_context.get_active_organization_membership_id();
+        async move {
+            active_organization_membership_id?;
+            list_events(ListEventsRequest {
+                event_type: None,
+                visibility: None,
+                include_cancelled: Some(true),
+                page: None,
+                per_page: Some(100),
+            })
+            .await
+            .ok()
+            .map(|response| response.events)
+        }
+    });
+
+    let handle_submit = move |evt: FormEvent| {
+        evt.prevent_default();
+
+        spawn(async move {
+            is_loading.set(true);
+            export_error.set(None);
+
+            // dates are whole days in utc, the end date is inclusive
+            let parse_date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+            let request = CreateAttendeeExportRequest {
+                format: *format.read(),
+                event_id: *event_id.read(),
+                range_start: parse_date(&range_start.read())
+                    .map(|date| date.and_time(NaiveTime::MIN).and_utc()),
+                range_end: parse_date(&range_end.read())
+                    .and_then(|date| date.checked_add_days(Days::new(1)))
+                    .map(|date| date.and_time(NaiveTime::MIN).and_utc()),
+            };
+
+            match create_attendee_export(request).await {
+                Ok(response) => {
+                    exports.restart();
+                    navigator().push(NavigationTarget::<String>::External(response.download_url));
+                }
+                Err(error) => {
+                    export_error.set(Some(error.to_string()));
+                }
+            }
+            is_loading.set(false);
+        });
+    };
+
     rsx! {
         Gate {
-            div { class: "w-full",
+            required_capability: Capability::ExportsDownload,
+            div { class: "w-full space-y-6",
                 h1 { class: "text-primary font-bold text-xl", "Exports" }
-                p { "Welcome to the exports page!" }
+
+                if let Some(error) = export_error.read().as_ref() {
+                    div { class: "bg-destructive/10 text-destructive p-3 rounded-md text-sm",
+                        "{error}"
+                    }
+                }
+
+                form {
+                    class: "grid gap-4 md:grid-cols-5 items-end",
+                    onsubmit: handle_submit,
+                    div {
+                        label {
+                            class: "block text-sm font-medium mb-1",
+                            r#for: "export-event",
+                            "Event"
+                        }
+                        select {
+                            id: "export-event",
+                            class: "w-full px-3 py-2 border border-input rounded-md bg-background",
+                            onchange: move |evt| event_id.set(evt.value().parse().ok()),
+                            option { value: "", "All events" }
+                            if let Some(Some(events)) = &*events.read() {
+                                for event in events.iter() {
+                                    {event_option(event)}
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        label {
+                            class: "block text-sm font-medium mb-1",
+                            r#for: "export-start",
+                            "From"
+                        }
+                        input {
+                            id: "export-start",
+                            r#type: "date",
+                            class: "w-full px-3 py-2 border border-input rounded-md bg-background",
+                            value: "{range_start}",
+                            oninput: move |evt| range_start.set(evt.value()),
+                        }
+                    }
+                    div {
+                        label {
+                            class: "block text-sm font-medium mb-1",
+                            r#for: "export-end",
+                            "To"
+                        }
+                        input {
+                            id: "export-end",
+                            r#type: "date",
+                            class: "w-full px-3 py-2 border border-input rounded-md bg-background",
+                            value: "{range_end}",
+                            oninput: move |evt| range_end.set(evt.value()),
+                        }
+                    }
+                    div {
+                        label {
+                            class: "block text-sm font-medium mb-1",
+                            r#for: "export-format",
+                            "Format"
+                        }
+                        select {
+                            id: "export-format",
+                            class: "w-full px-3 py-2 border border-input rounded-md bg-background",
+                            onchange: move |evt| {
+                                if let Some(export_format) = ExportFormat::from_str(&evt.value()) {
+                                    format.set(export_format);
+                                }
+                            },
+                            option { value: ExportFormat::Csv.as_str(), "{ExportFormat::Csv}" }
+                            option { value: ExportFormat::Xlsx.as_str(), "{ExportFormat::Xlsx}" }
+                        }
+                    }
+                    button {
+                        r#type: "submit",
+                        class: "px-4 py-2 bg-primary text-primary-foreground rounded-md font-medium disabled:opacity-50",
+                        disabled: *is_loading.read(),
+                        if *is_loading.read() {
+                            "Exporting..."
+                        } else {
+                            "Export Attendees"
+                        }
+                    }
+                }
+
+                match &*exports.read() {
+                    Some(Some(response)) if !response.exports.is_empty() => rsx! {
+                        table { class: "w-full text-sm",
+                            thead {
+                                tr { class: "text-left text-muted-foreground border-b border-border",
+                                    th { class: "py-2", "File" }
+                                    th { class: "py-2", "Rows" }
+                                    th { class: "py-2", "Created" }
+                                    th { class: "py-2" }
+                                }
+                            }
+                            tbody {
+                                for export in response.exports.iter() {
+                                    {export_row(export, export_error)}
+                                }
+                            }
+                        }
+                    },
+                    Some(Some(_)) => rsx! {
+                        p { class: "text-muted-foreground", "No exports yet." }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "Failed to load exports." }
+                    },
+                    None => rsx! {
+                        div { class: "h-32 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
+            }
+        }
+    }
+}
+
+fn event_option(event: &EventResponse) -> Element {
+    rsx! {
+        option { key: "{event.id}", value: "{event.id}", "{event.name}" }
+    }
+}
+
+fn export_row(export: &ExportResponse, mut export_error: Signal<Option<String>>) -> Element {
+    let export_id = export.id;
+    let created_at = export
+        .created_at
+        .format("%b %-d, %Y %-I:%M %p UTC")
+        .to_string();
+
+    // download links expire quickly so a fresh one is requested on each click
+    let handle_download = move |_| {
+        spawn(async move {
+            match get_export_download_url(export_id).await {
+                Ok(response) => {
+                    navigator().push(NavigationTarget::<String>::External(response.download_url));
+                }
+                Err(error) => {
+                    export_error.set(Some(error.to_string()));
+                }
+            }
+        });
+    };
+
+    rsx! {
+        tr { key: "{export.id}", class: "border-b border-border",
+            td { class: "py-2 font-medium", "{export.filename}" }
+            td { class: "py-2", "{export.row_count}" }
+            td { class: "py-2", "{created_at}" }
+            td { class: "py-2 text-right",
+                button {
+                    class: "text-primary hover:underline",
+                    onclick: handle_download,
+                    "Download"
+                }
             }
         }
     }
@@ -0,0 +1,107 @@
+use crate::auth::AuthContext;
+use crate::routes::Routes;
+use api::enums::InvitationStatus;
+use api::providers::{accept_invitation, get_current_user, get_invitation};
+use dioxus::prelude::*;
+
+// opened from the invitation email, anyone can see it but accepting needs the
+// account the invitation was sent to
+#[component]
+pub fn Invitation(token: String) -> Element {
+    let auth_context = use_context::<AuthContext>();
+    let mut accept_error = use_signal(|| None::<String>);
+    let mut is_accepting = use_signal(|| false);
+    let is_authenticated = auth_context.is_authenticated();
+
+    let details_token = token.clone();
+    let invitation = use_resource(move || {
+        let token = details_token.clone();
+        async move { get_invitation(token).await.ok() }
+    });
+
+    let register_token = token.clone();
+    let handle_accept = move |_| {
+        let token = token.clone();
+        let mut auth_context = auth_context.clone();
+        spawn(async move {
+            is_accepting.set(true);
+            accept_error.set(None);
+
+            match accept_invitation(token).await {
+                Ok(_) => {
+                    if let Ok(Some(user)) = get_current_user().await {
+                        auth_context.update_user(user);
+                    }
+                    router().push(Routes::Dashboard {}.to_string());
+                }
+                Err(error) => accept_error.set(Some(error.to_string())),
+            }
+            is_accepting.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "flex min-h-screen items-center justify-center",
+            div { class: "w-full max-w-md space-y-4 p-8 text-center",
+                h1 { class: "text-2xl font-bold text-primary", "Invitation" }
+
+                if let Some(error) = accept_error.read().as_ref() {
+                    div { class: "bg-destructive/10 text-destructive p-3 rounded-md text-sm",
+                        "{error}"
+                    }
+                }
+
+                match &*invitation.read() {
+                    Some(Some(invitation)) if invitation.status == InvitationStatus::Pending => rsx! {
+                        p {
+                            "{invitation.inviter_name} invited {invitation.email} to join "
+                            span { class: "font-medium", "{invitation.organization_name}" }
+                            " with the {invitation.role} role."
+                        }
+                        if is_authenticated {
+                            button {
+                                class: "w-full px-4 py-2 bg-primary text-primary-foreground rounded-md hover:bg-primary/90 disabled:opacity-50",
+                                disabled: is_accepting(),
+                                onclick: handle_accept,
+                                if is_accepting() {
+                                    "Joining..."
+                                } else {
+                                    "Accept invitation"
+                                }
+                            }
+                        } else {
+                            p { class: "text-muted-foreground text-sm",
+                                a {
+                                    class: "text-primary underline",
+                                    href: Routes::Login {}.to_string(),
+                                    "Log in"
+                                }
+                                " to accept, or "
+                                a {
+                                    class: "text-primary underline",
+                                    href: Routes::InvitationCreateAccount {
+                                        token: register_token.clone(),
+                                    }
+                                        .to_string(),
+                                    "create an account"
+                                }
+                                " with {invitation.email} and you'll join automatically."
+                            }
+                        }
+                    },
+                    Some(Some(invitation)) => rsx! {
+                        p { class: "text-muted-foreground",
+                            "This invitation to {invitation.organization_name} is {invitation.status.display_name().to_lowercase()}."
+                        }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "This invitation link is invalid." }
+                    },
+                    None => rsx! {
+                        div { class: "h-16 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
+            }
+        }
+    }
+}
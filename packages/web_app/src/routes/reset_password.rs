@@ -0,0 +1,134 @@
+use crate::routes::Routes;
+use api::interfaces::{ResetPasswordRequest, ValidateResetTokenRequest};
+use api::providers::{reset_password, validate_reset_token};
+use dioxus::prelude::*;
+
+// opened from the password reset email, works whether or not someone is signed in
+#[component]
+pub fn ResetPassword(token: String) -> Element {
+    let mut new_password = use_signal(String::new);
+    let mut confirm_password = use_signal(String::new);
+    let mut reset_error = use_signal(|| None::<String>);
+    let mut is_loading = use_signal(|| false);
+    let mut is_reset = use_signal(|| false);
+
+    let validate_token = token.clone();
+    let is_valid = use_resource(move || {
+        let token = validate_token.clone();
+        async move {
+            validate_reset_token(ValidateResetTokenRequest { token })
+                .await
+                .map(|response| response.valid)
+                .unwrap_or(false)
+        }
+    });
+
+    let handle_submit = move |evt: FormEvent| {
+        evt.prevent_default();
+        let token = token.clone();
+
+        if *new_password.read() != *confirm_password.read() {
+            reset_error.set(Some("Passwords don't match".to_string()));
+            return;
+        }
+
+        spawn(async move {
+            is_loading.set(true);
+            reset_error.set(None);
+
+            let request = ResetPasswordRequest {
+                token,
+                new_password: new_password.read().clone(),
+            };
+
+            match reset_password(request).await {
+                Ok(_) => is_reset.set(true),
+                Err(error) => reset_error.set(Some(error.to_string())),
+            }
+            is_loading.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "flex min-h-screen items-center justify-center",
+            div { class: "w-full max-w-md space-y-6 p-8",
+                h1 { class: "text-2xl font-bold text-primary text-center", "Reset Password" }
+
+                if *is_reset.read() {
+                    p { class: "text-center",
+                        "Your password has been reset. "
+                        a {
+                            class: "text-primary underline",
+                            href: Routes::Login {}.to_string(),
+                            "Log in"
+                        }
+                        " with your new password."
+                    }
+                } else {
+                    match *is_valid.read() {
+                        Some(true) => rsx! {
+                            if let Some(error) = reset_error.read().as_ref() {
+                                div { class: "bg-destructive/10 text-destructive p-3 rounded-md text-sm",
+                                    "{error}"
+                                }
+                            }
+
+                            form { class: "space-y-4", onsubmit: handle_submit,
+                                div {
+                                    label {
+                                        class: "block text-sm font-medium mb-1",
+                                        r#for: "new-password",
+                                        "New password"
+                                    }
+                                    input {
+                                        id: "new-password",
+                                        r#type: "password",
+                                        required: true,
+                                        class: "w-full px-3 py-2 border border-input rounded-md bg-background",
+                                        value: "{new_password}",
+                                        oninput: move |evt| new_password.set(evt.value()),
+                                    }
+                                }
+
+                                div {
+                                    label {
+                                        class: "block text-sm font-medium mb-1",
+                                        r#for: "confirm-password",
+                                        "Confirm new password"
+                                    }
+                                    input {
+                                        id: "confirm-password",
+                                        r#type: "password",
+                                        required: true,
+                                        class: "w-full px-3 py-2 border border-input rounded-md bg-background",
+                                        value: "{confirm_password}",
+                                        oninput: move |evt| confirm_password.set(evt.value()),
+                                    }
+                                }
+
+                                button {
+                                    r#type: "submit",
+                                    disabled: *is_loading.read(),
+                                    class: "w-full py-2 px-4 bg-primary text-primary-foreground rounded-md font-medium hover:bg-primary/90 disabled:opacity-50",
+                                    if *is_loading.read() {
+                                        "Resetting..."
+                                    } else {
+                                        "Reset password"
+                                    }
+                                }
+                            }
+                        },
+                        Some(false) => rsx! {
+                            p { class: "text-destructive text-center",
+                                "This reset link is invalid or has expired. Request a new one to try again."
+                            }
+                        },
+                        None => rsx! {
+                            div { class: "h-32 w-full bg-muted rounded animate-pulse" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
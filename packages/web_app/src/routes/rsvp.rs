@@ -0,0 +1,101 @@
+use api::enums::SignupStatus;
+use api::interfaces::GuestSignupResponse;
+use api::providers::{cancel_guest_signup, list_guest_signups};
+use dioxus::prelude::*;
+
+// opened from the guest signup confirmation email, the token is the only credential
+#[component]
+pub fn Rsvp(token: String) -> Element {
+    let mut cancel_error = use_signal(|| None::<String>);
+
+    let list_token = token.clone();
+    let mut signups = use_resource(move || {
+        let token = list_token.clone();
+        async move { list_guest_signups(token).await.ok() }
+    });
+
+    let handle_cancel = move |signup_id: i32| {
+        let token = token.clone();
+        spawn(async move {
+            cancel_error.set(None);
+
+            match cancel_guest_signup(token, signup_id).await {
+                Ok(_) => signups.restart(),
+                Err(error) => cancel_error.set(Some(error.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "flex min-h-screen justify-center",
+            div { class: "w-full max-w-2xl space-y-6 p-8",
+                h1 { class: "text-2xl font-bold text-primary", "My RSVPs" }
+
+                if let Some(error) = cancel_error.read().as_ref() {
+                    div { class: "bg-destructive/10 text-destructive p-3 rounded-md text-sm",
+                        "{error}"
+                    }
+                }
+
+                match &*signups.read() {
+                    Some(Some(response)) if !response.signups.is_empty() => rsx! {
+                        p { class: "text-muted-foreground",
+                            "Signups for {response.first_name} {response.last_name}"
+                        }
+                        table { class: "w-full text-sm",
+                            thead {
+                                tr { class: "text-left text-muted-foreground border-b border-border",
+                                    th { class: "py-2", "Event" }
+                                    th { class: "py-2", "Shift" }
+                                    th { class: "py-2", "Status" }
+                                    th { class: "py-2" }
+                                }
+                            }
+                            tbody {
+                                for signup in response.signups.iter() {
+                                    {signup_row(signup, handle_cancel.clone())}
+                                }
+                            }
+                        }
+                    },
+                    Some(Some(_)) => rsx! {
+                        p { class: "text-muted-foreground", "No signups yet." }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "This link is invalid or has expired." }
+                    },
+                    None => rsx! {
+                        div { class: "h-32 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
+            }
+        }
+    }
+}
+
+fn signup_row(signup: &GuestSignupResponse, mut on_cancel: impl FnMut(i32) + 'static) -> Element {
+    let signup_id = signup.id;
+    let is_cancellable =
+        signup.status != SignupStatus::Cancelled && signup.start_time > chrono::Utc::now();
+    let start_time = signup
+        .start_time
+        .format("%b %-d, %Y %-I:%M %p UTC")
+        .to_string();
+
+    rsx! {
+        tr { key: "{signup.id}", class: "border-b border-border",
+            td { class: "py-2 font-medium", "{signup.event_name}" }
+            td { class: "py-2", "{start_time}" }
+            td { class: "py-2", "{signup.status}" }
+            td { class: "py-2 text-right",
+                if is_cancellable {
+                    button {
+                        class: "px-3 py-1 text-sm border border-input rounded-md hover:bg-muted",
+                        onclick: move |_| on_cancel(signup_id),
+                        "Cancel"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use crate::gate::Gate;
+use api::enums::Capability;
 use dioxus::prelude::*;
 
 #[component]
 pub fn Settings() -> Element {
     rsx! {
         Gate {
+            required_capability: Capability::OrganizationManage,
             div { class: "w-full",
                 h1 { class: "text-primary font-bold text-xl", "Settings" }
                 p { "Welcome to the settings page!" }
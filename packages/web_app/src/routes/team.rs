@@ -1,10 +1,12 @@
 use crate::gate::Gate;
+use api::enums::Capability;
 use dioxus::prelude::*;
 
 #[component]
 pub fn Team() -> Element {
     rsx! {
         Gate {
+            required_capability: Capability::TeamView,
             div { class: "w-full",
                 h1 { class: "text-primary font-bold text-xl", "Team" }
                 p { "Welcome to the team page!" }
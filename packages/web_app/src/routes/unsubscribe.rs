@@ -0,0 +1,68 @@
+use crate::routes::Routes;
+use api::enums::NotificationType;
+use api::providers::unsubscribe_notifications;
+use dioxus::prelude::*;
+
+// opened from the footer of notification emails, the token is the only credential.
+// nothing changes until the button is pressed, link scanners and prefetchers open
+// the page too
+#[component]
+pub fn Unsubscribe(token: String) -> Element {
+    let mut unsubscribed = use_signal(|| None::<NotificationType>);
+    let mut unsubscribe_error = use_signal(|| None::<String>);
+    let mut is_unsubscribing = use_signal(|| false);
+
+    let handle_unsubscribe = move |_| {
+        let token = token.clone();
+        spawn(async move {
+            is_unsubscribing.set(true);
+            unsubscribe_error.set(None);
+
+            match unsubscribe_notifications(token).await {
+                Ok(response) => unsubscribed.set(Some(response.notification_type)),
+                Err(_) => {
+                    unsubscribe_error.set(Some("This unsubscribe link is invalid.".to_string()))
+                }
+            }
+            is_unsubscribing.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "flex min-h-screen items-center justify-center",
+            div { class: "w-full max-w-md space-y-4 p-8 text-center",
+                h1 { class: "text-2xl font-bold text-primary", "Unsubscribe" }
+                if let Some(error) = unsubscribe_error.read().as_ref() {
+                    p { class: "text-destructive", "{error}" }
+                }
+                match *unsubscribed.read() {
+                    Some(notification_type) => rsx! {
+                        p { "You won't get {notification_type} emails anymore." }
+                        p { class: "text-muted-foreground text-sm",
+                            "Changed your mind? Turn them back on in your "
+                            a {
+                                class: "text-primary underline",
+                                href: Routes::NotificationPreferences {}.to_string(),
+                                "notification preferences"
+                            }
+                            "."
+                        }
+                    },
+                    None => rsx! {
+                        p { "Stop getting emails like the one that linked you here?" }
+                        button {
+                            class: "w-full px-4 py-2 bg-primary text-primary-foreground rounded-md hover:bg-primary/90 disabled:opacity-50",
+                            disabled: is_unsubscribing(),
+                            onclick: handle_unsubscribe,
+                            if is_unsubscribing() {
+                                "Unsubscribing..."
+                            } else {
+                                "Unsubscribe"
+                            }
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::auth::AuthContext;
+use crate::routes::Routes;
+use api::interfaces::VerifyEmailRequest;
+use api::providers::{get_current_user, verify_email};
+use dioxus::prelude::*;
+
+// opened from the verification email, the token is the only credential
+#[component]
+pub fn VerifyEmail(token: String) -> Element {
+    let auth_context = use_context::<AuthContext>();
+
+    let verified = use_resource(move || {
+        let token = token.clone();
+        let mut auth_context = auth_context.clone();
+        async move {
+            let response = verify_email(VerifyEmailRequest { token }).await.ok()?;
+
+            // organizations joined on verification show up straight away
+            if auth_context.is_authenticated() {
+                if let Ok(Some(user)) = get_current_user().await {
+                    auth_context.update_user(user);
+                }
+            }
+
+            Some(response)
+        }
+    });
+
+    rsx! {
+        div { class: "flex min-h-screen items-center justify-center",
+            div { class: "w-full max-w-md space-y-4 p-8 text-center",
+                h1 { class: "text-2xl font-bold text-primary", "Verify Email" }
+                match &*verified.read() {
+                    Some(Some(response)) => rsx! {
+                        p { "{response.email} is verified." }
+                        if response.organizations_joined > 0 {
+                            p { class: "text-muted-foreground text-sm",
+                                "You joined {response.organizations_joined} organization(s) you were invited to."
+                            }
+                        }
+                        a {
+                            class: "text-primary underline",
+                            href: Routes::Dashboard {}.to_string(),
+                            "Continue"
+                        }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-destructive", "This verification link is invalid or has expired." }
+                    },
+                    None => rsx! {
+                        div { class: "h-16 w-full bg-muted rounded animate-pulse" }
+                    },
+                }
+            }
+        }
+    }
+}